@@ -131,6 +131,23 @@ impl<'a> NamespaceName<'a> {
 
         Ok(Self::new(format!("{org}_{bucket}"))?)
     }
+
+    /// Map an InfluxDB 1.X database & retention policy into an IOx NamespaceName.
+    ///
+    /// The default retention policy - unspecified, `autogen` or `default` - maps
+    /// to the `database` name. Any other retention policy is appended to the
+    /// `database`, separated by a `/`, which is the namespace the V1 write API
+    /// writes to.
+    pub fn from_database_and_retention_policy<D: AsRef<str>>(
+        database: D,
+        retention_policy: Option<&str>,
+    ) -> Result<Self, NamespaceNameError> {
+        let database = database.as_ref();
+        match retention_policy.map(str::to_lowercase).as_deref() {
+            None | Some("" | "autogen" | "default") => Self::new(database.to_owned()),
+            Some(rp) => Self::new(format!("{database}/{rp}")),
+        }
+    }
 }
 
 impl<'a> From<NamespaceName<'a>> for String {
@@ -206,6 +223,31 @@ mod tests {
         assert_eq!(Into::<String>::into(got), "org_bucket");
     }
 
+    #[test]
+    fn test_database_retention_policy_map() {
+        let got = NamespaceName::from_database_and_retention_policy("telegraf", None).unwrap();
+        assert_eq!(got.as_str(), "telegraf");
+
+        for rp in ["", "autogen", "AUTOGEN", "default"] {
+            let got =
+                NamespaceName::from_database_and_retention_policy("telegraf", Some(rp)).unwrap();
+            assert_eq!(got.as_str(), "telegraf");
+        }
+
+        let got =
+            NamespaceName::from_database_and_retention_policy("telegraf", Some("Ageless")).unwrap();
+        assert_eq!(got.as_str(), "telegraf/ageless");
+
+        let err = NamespaceName::from_database_and_retention_policy("", None);
+        assert!(matches!(
+            err,
+            Err(NamespaceNameError::LengthConstraint { .. })
+        ));
+
+        let err = NamespaceName::from_database_and_retention_policy("telegraf", Some("a.b"));
+        assert!(matches!(err, Err(NamespaceNameError::BadChars { .. })));
+    }
+
     #[test]
     fn test_into_string() {
         let name = NamespaceName::new("bananas").unwrap();
@@ -13,7 +13,8 @@ use arrow_flight::sql::{
     ActionClosePreparedStatementRequest, ActionCreatePreparedStatementRequest, Any,
     CommandGetCatalogs, CommandGetCrossReference, CommandGetDbSchemas, CommandGetExportedKeys,
     CommandGetImportedKeys, CommandGetPrimaryKeys, CommandGetSqlInfo, CommandGetTableTypes,
-    CommandGetTables, CommandGetXdbcTypeInfo, CommandPreparedStatementQuery, CommandStatementQuery,
+    CommandGetTables, CommandGetXdbcTypeInfo, CommandPreparedStatementQuery,
+    CommandStatementIngest, CommandStatementQuery, CommandStatementUpdate,
 };
 use arrow_util::display::pretty_format_batches;
 use bytes::Bytes;
@@ -203,6 +204,11 @@ pub enum FlightSQLCommand {
     CommandGetXdbcTypeInfo(CommandGetXdbcTypeInfo),
     /// Get a list of the available table types
     CommandGetTableTypes(CommandGetTableTypes),
+    /// Append the record batches sent with `DoPut` to a table. See
+    /// [`CommandStatementIngest`] for details.
+    CommandStatementIngest(CommandStatementIngest),
+    /// Run a SQL statement that modifies data, which IOx does not support.
+    CommandStatementUpdate(CommandStatementUpdate),
     /// Create a prepared statement
     ActionCreatePreparedStatementRequest(ActionCreatePreparedStatementRequest),
     /// Close a prepared statement
@@ -331,6 +337,23 @@ impl Display for FlightSQLCommand {
             Self::CommandGetTableTypes(CommandGetTableTypes {}) => {
                 write!(f, "CommandGetTableTypes")
             }
+            Self::CommandStatementIngest(CommandStatementIngest {
+                catalog,
+                schema,
+                table,
+                ..
+            }) => {
+                write!(
+                    f,
+                    "CommandStatementIngest(catalog={}, db_schema={}, table={})",
+                    catalog.as_ref().map(|c| c.as_str()).unwrap_or("<NONE>"),
+                    schema.as_ref().map(|c| c.as_str()).unwrap_or("<NONE>"),
+                    table
+                )
+            }
+            Self::CommandStatementUpdate(CommandStatementUpdate { query, .. }) => {
+                write!(f, "CommandStatementUpdate{query}")
+            }
             Self::CommandGetXdbcTypeInfo(CommandGetXdbcTypeInfo { data_type }) => {
                 write!(
                     f,
@@ -386,6 +409,10 @@ impl FlightSQLCommand {
             Ok(Self::CommandGetTableTypes(decoded_cmd))
         } else if let Some(decoded_cmd) = Any::unpack::<CommandGetXdbcTypeInfo>(&msg)? {
             Ok(Self::CommandGetXdbcTypeInfo(decoded_cmd))
+        } else if let Some(decoded_cmd) = Any::unpack::<CommandStatementIngest>(&msg)? {
+            Ok(Self::CommandStatementIngest(decoded_cmd))
+        } else if let Some(decoded_cmd) = Any::unpack::<CommandStatementUpdate>(&msg)? {
+            Ok(Self::CommandStatementUpdate(decoded_cmd))
         } else if let Some(decoded_cmd) = Any::unpack::<ActionCreatePreparedStatementRequest>(&msg)?
         {
             Ok(Self::ActionCreatePreparedStatementRequest(decoded_cmd))
@@ -426,6 +453,8 @@ impl FlightSQLCommand {
             Self::CommandGetTables(cmd) => Any::pack(&cmd),
             Self::CommandGetTableTypes(cmd) => Any::pack(&cmd),
             Self::CommandGetXdbcTypeInfo(cmd) => Any::pack(&cmd),
+            Self::CommandStatementIngest(cmd) => Any::pack(&cmd),
+            Self::CommandStatementUpdate(cmd) => Any::pack(&cmd),
             Self::ActionCreatePreparedStatementRequest(cmd) => Any::pack(&cmd),
             Self::ActionClosePreparedStatementRequest(handle) => {
                 let prepared_statement_handle = handle.encode()?;
@@ -440,9 +469,10 @@ impl FlightSQLCommand {
 
 #[cfg(test)]
 mod tests {
+    use arrow_flight::sql::{CommandStatementIngest, CommandStatementUpdate};
     use bytes::Bytes;
 
-    use crate::PreparedStatementHandle;
+    use crate::{FlightSQLCommand, PreparedStatementHandle};
 
     /// Tests that the older UTF-8 encoded format for the FlightSQL prepared
     /// statement handle can be decoded by the current implementation.
@@ -453,4 +483,22 @@ mod tests {
         assert_eq!(handle.query, "SELECT 1");
         assert_eq!(handle.params, None);
     }
+
+    #[test]
+    fn write_command_roundtrip() {
+        let cmds = [
+            FlightSQLCommand::CommandStatementIngest(CommandStatementIngest {
+                table: "cpu".to_string(),
+                ..Default::default()
+            }),
+            FlightSQLCommand::CommandStatementUpdate(CommandStatementUpdate {
+                query: "DELETE FROM cpu".to_string(),
+                transaction_id: None,
+            }),
+        ];
+        for cmd in cmds {
+            let encoded = cmd.clone().try_encode().unwrap();
+            assert_eq!(FlightSQLCommand::try_decode(encoded).unwrap(), cmd);
+        }
+    }
 }
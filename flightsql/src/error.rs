@@ -65,6 +65,18 @@ pub enum Error {
         parameter_name
     ))]
     UnknownParameterType { parameter_name: String },
+
+    #[snafu(display("Unsupported FlightSQL operation: {}", description))]
+    Unsupported { description: String },
+
+    #[snafu(display("CommandStatementIngest table name must not be empty"))]
+    EmptyIngestTable,
+
+    #[snafu(display("Table '{}' already exists", table))]
+    IngestTableExists { table: String },
+
+    #[snafu(display("Table '{}' not found", table))]
+    IngestTableNotFound { table: String },
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -15,8 +15,8 @@ use arrow_flight::{
         ActionCreatePreparedStatementRequest, ActionCreatePreparedStatementResult, Any,
         CommandGetCatalogs, CommandGetCrossReference, CommandGetDbSchemas, CommandGetExportedKeys,
         CommandGetImportedKeys, CommandGetPrimaryKeys, CommandGetSqlInfo, CommandGetTableTypes,
-        CommandGetTables, CommandGetXdbcTypeInfo, CommandStatementQuery,
-        DoPutPreparedStatementResult,
+        CommandGetTables, CommandGetXdbcTypeInfo, CommandStatementIngest, CommandStatementQuery,
+        DoPutPreparedStatementResult, TableExistsOption, TableNotExistOption,
     },
 };
 use arrow_util::flight::prepare_schema_for_flight;
@@ -91,7 +91,9 @@ impl FlightSQLPlanner {
                 Ok(Arc::clone(&GET_XDBC_TYPE_INFO_SCHEMA))
             }
             FlightSQLCommand::ActionCreatePreparedStatementRequest(_)
-            | FlightSQLCommand::ActionClosePreparedStatementRequest(_) => ProtocolSnafu {
+            | FlightSQLCommand::ActionClosePreparedStatementRequest(_)
+            | FlightSQLCommand::CommandStatementIngest(_)
+            | FlightSQLCommand::CommandStatementUpdate(_) => ProtocolSnafu {
                 cmd: format!("{cmd:?}"),
                 method: "GetFlightInfo",
             }
@@ -233,7 +235,9 @@ impl FlightSQLPlanner {
                 Ok(ctx.create_physical_plan(&plan).await?)
             }
             FlightSQLCommand::ActionClosePreparedStatementRequest(_)
-            | FlightSQLCommand::ActionCreatePreparedStatementRequest(_) => ProtocolSnafu {
+            | FlightSQLCommand::ActionCreatePreparedStatementRequest(_)
+            | FlightSQLCommand::CommandStatementIngest(_)
+            | FlightSQLCommand::CommandStatementUpdate(_) => ProtocolSnafu {
                 cmd: format!("{cmd:?}"),
                 method: "DoGet",
             }
@@ -290,6 +294,9 @@ impl FlightSQLPlanner {
     /// Handles receiving the given `data` from the client based on the action
     /// specified by `cmd` and returns bytes for the [`arrow_flight::Result`]
     /// (not the same as a rust [`Result`]!)
+    ///
+    /// [`FlightSQLCommand::CommandStatementIngest`] writes data, so is handled
+    /// by the caller, using [`FlightSQLPlanner::get_ingest_table_schema`].
     pub async fn do_put(
         namespace_name: impl Into<String> + Send,
         _database: Arc<dyn QueryNamespace>,
@@ -299,6 +306,8 @@ impl FlightSQLPlanner {
     ) -> Result<Bytes> {
         let namespace_name = namespace_name.into();
         debug!(%namespace_name, %cmd, "Handling flightsql do_put");
+        Self::check_supported(&cmd)?;
+
         match cmd {
             FlightSQLCommand::CommandPreparedStatementQuery(mut prepared_statement_handle) => {
                 debug!(
@@ -333,6 +342,114 @@ impl FlightSQLPlanner {
         }
     }
 
+    /// Returns an error if `cmd` is not supported by IOx at all, whatever
+    /// the method it is sent to, so that it can be rejected before the
+    /// request is authorized.
+    pub fn check_supported(cmd: &FlightSQLCommand) -> Result<()> {
+        match cmd {
+            FlightSQLCommand::CommandStatementUpdate(_) => UnsupportedSnafu {
+                description: "CommandStatementUpdate: IOx does not support SQL statements \
+                    that modify data, use CommandStatementIngest to write data",
+            }
+            .fail(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Validates the table options of the ingest command `cmd`, returning
+    /// the schema of the target table, or `None` if it does not exist yet.
+    ///
+    /// IOx only appends to tables in the namespace, creating them on the
+    /// first write, so:
+    ///
+    /// * `cmd` may only name the default catalog and schema of `ctx`.
+    /// * An unspecified [`TableExistsOption`] appends, as does
+    ///   [`TableExistsOption::Append`]. Replacing a table is not supported.
+    /// * An unspecified [`TableNotExistOption`] creates the table, as does
+    ///   [`TableNotExistOption::Create`].
+    /// * Temporary tables and transactions are not supported.
+    pub async fn get_ingest_table_schema(
+        cmd: &CommandStatementIngest,
+        ctx: &IOxSessionContext,
+    ) -> Result<Option<SchemaRef>> {
+        let CommandStatementIngest {
+            table_definition_options,
+            table,
+            schema,
+            catalog,
+            temporary,
+            transaction_id,
+            options: _,
+        } = cmd;
+
+        if *temporary {
+            return UnsupportedSnafu {
+                description: "CommandStatementIngest into a temporary table",
+            }
+            .fail();
+        }
+        if transaction_id.is_some() {
+            return UnsupportedSnafu {
+                description: "CommandStatementIngest within a transaction",
+            }
+            .fail();
+        }
+        if table.is_empty() {
+            return EmptyIngestTableSnafu.fail();
+        }
+
+        let state = ctx.inner().state();
+        let defaults = &state.config_options().catalog;
+        if let Some(catalog) = catalog
+            && catalog != &defaults.default_catalog
+        {
+            return UnsupportedSnafu {
+                description: format!("CommandStatementIngest into catalog '{catalog}'"),
+            }
+            .fail();
+        }
+        if let Some(schema) = schema
+            && schema != &defaults.default_schema
+        {
+            return UnsupportedSnafu {
+                description: format!("CommandStatementIngest into schema '{schema}'"),
+            }
+            .fail();
+        }
+
+        let table_ref = TableReference::bare(table.as_str());
+        let table_schema = if ctx.inner().table_exist(table_ref.clone())? {
+            Some(ctx.inner().table_provider(table_ref).await?.schema())
+        } else {
+            None
+        };
+
+        let (if_exists, if_not_exist) = table_definition_options.as_ref().map_or(
+            (
+                TableExistsOption::Unspecified,
+                TableNotExistOption::Unspecified,
+            ),
+            |options| (options.if_exists(), options.if_not_exist()),
+        );
+        match (if_exists, &table_schema) {
+            (TableExistsOption::Unspecified | TableExistsOption::Append, _) | (_, None) => {}
+            (TableExistsOption::Fail, Some(_)) => {
+                return IngestTableExistsSnafu { table }.fail();
+            }
+            (TableExistsOption::Replace, Some(_)) => {
+                return UnsupportedSnafu {
+                    description: "CommandStatementIngest replacing an existing table",
+                }
+                .fail();
+            }
+        }
+        if if_not_exist == TableNotExistOption::Fail && table_schema.is_none() {
+            return IngestTableNotFoundSnafu { table }.fail();
+        }
+
+        Ok(table_schema)
+    }
+
     /// Return the schema for the specified logical plan
     pub fn get_schema_for_plan(logical_plan: &LogicalPlan) -> SchemaRef {
         // gather real schema, but only
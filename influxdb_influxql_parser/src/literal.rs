@@ -273,8 +273,8 @@ impl Display for Duration {
         match v {
             0 => f.write_str("0s")?,
             mut i => {
-                // only return the divisors that are > self
-                for (div, unit) in DIVISORS.iter().filter(|(div, _)| v > *div) {
+                // only return the divisors that are <= self
+                for (div, unit) in DIVISORS.iter().filter(|(div, _)| v >= *div) {
                     let units = i / div;
                     if units > 0 {
                         write!(f, "{units}{unit}")?;
@@ -550,6 +550,31 @@ mod test {
                 + 500,
         );
         assert_eq!(d.to_string(), "20w6d13h11m10s9ms8u500ns");
+
+        // Durations that are an exact multiple of a single unit
+        let d = Duration(1);
+        assert_eq!(d.to_string(), "1ns");
+
+        let (_, d) = duration("1u").unwrap();
+        assert_eq!(d.to_string(), "1u");
+
+        let (_, d) = duration("10µ").unwrap();
+        assert_eq!(d.to_string(), "10u");
+
+        let (_, d) = duration("1ms").unwrap();
+        assert_eq!(d.to_string(), "1ms");
+
+        let (_, d) = duration("1s").unwrap();
+        assert_eq!(d.to_string(), "1s");
+
+        let (_, d) = duration("1h").unwrap();
+        assert_eq!(d.to_string(), "1h");
+
+        let (_, d) = duration("1w").unwrap();
+        assert_eq!(d.to_string(), "1w");
+
+        let d = Duration(-1);
+        assert_eq!(d.to_string(), "-1ns");
     }
 
     #[test]
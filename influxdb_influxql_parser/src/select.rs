@@ -25,7 +25,7 @@ use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::char;
 use nom::combinator::{map, opt, value};
-use nom::sequence::{delimited, pair, preceded};
+use nom::sequence::{delimited, pair, preceded, terminated};
 use nom::{Offset, Parser as _};
 use std::fmt;
 use std::fmt::{Display, Formatter, Write};
@@ -37,6 +37,9 @@ pub struct SelectStatement {
     /// Expressions returned by the selection.
     pub fields: FieldList,
 
+    /// The measurement the results of the selection are written to.
+    pub into: Option<IntoClause>,
+
     /// A list of measurements or subqueries used as the source data for the selection.
     pub from: FromMeasurementClause,
 
@@ -84,7 +87,13 @@ impl SelectStatement {
 
 impl Display for SelectStatement {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "SELECT {} {}", self.fields, self.from)?;
+        write!(f, "SELECT {}", self.fields)?;
+
+        if let Some(into) = &self.into {
+            write!(f, " {into}")?;
+        }
+
+        write!(f, " {}", self.from)?;
 
         if let Some(where_clause) = &self.condition {
             write!(f, " {where_clause}")?;
@@ -133,6 +142,7 @@ pub(crate) fn select_statement(i: &str) -> ParseResult<&str, SelectStatement> {
             _, // SELECT
             _, // whitespace
             fields,
+            into,
             from,
             condition,
             group_by,
@@ -148,6 +158,7 @@ pub(crate) fn select_statement(i: &str) -> ParseResult<&str, SelectStatement> {
         keyword("SELECT"),
         ws0,
         field_list,
+        opt(preceded(ws0, into_clause)),
         preceded(ws0, from_clause),
         opt(preceded(ws0, where_clause)),
         opt(preceded(ws0, group_by_clause)),
@@ -165,6 +176,7 @@ pub(crate) fn select_statement(i: &str) -> ParseResult<&str, SelectStatement> {
         remaining,
         SelectStatement {
             fields,
+            into,
             from,
             condition,
             group_by,
@@ -179,6 +191,104 @@ pub(crate) fn select_statement(i: &str) -> ParseResult<&str, SelectStatement> {
     ))
 }
 
+/// Represents the measurement name of an `INTO` clause.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IntoMeasurementName {
+    /// The results are written to the named measurement.
+    Name(Identifier),
+
+    /// The `:MEASUREMENT` backreference, which writes the results of each
+    /// source measurement to a measurement of the same name.
+    Backreference,
+}
+
+impl Display for IntoMeasurementName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Name(ident) => write!(f, "{ident}"),
+            Self::Backreference => f.write_str(":MEASUREMENT"),
+        }
+    }
+}
+
+/// Represents an `INTO` clause, which identifies the measurement, and
+/// optionally the database and retention policy, that the results of
+/// a `SELECT` statement are written to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntoClause {
+    /// An optional database name.
+    pub database: Option<Identifier>,
+
+    /// An optional retention policy.
+    pub retention_policy: Option<Identifier>,
+
+    /// The target measurement name.
+    pub name: IntoMeasurementName,
+}
+
+impl Display for IntoClause {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("INTO ")?;
+        match (&self.database, &self.retention_policy) {
+            (None, None) => write!(f, "{}", self.name),
+            (Some(db), None) => write!(f, "{db}..{}", self.name),
+            (None, Some(rp)) => write!(f, "{rp}.{}", self.name),
+            (Some(db), Some(rp)) => write!(f, "{db}.{rp}.{}", self.name),
+        }
+    }
+}
+
+/// Parse an `INTO` clause.
+///
+/// ```text
+/// into_clause         ::= "INTO" ( into_measurement_name |
+///                                  ( policy_name "." into_measurement_name ) |
+///                                  ( db_name "." policy_name? "." into_measurement_name ) )
+///
+/// into_measurement_name ::= identifier | ":MEASUREMENT"
+/// ```
+fn into_clause(i: &str) -> ParseResult<&str, IntoClause> {
+    let (remaining_input, (opt_db_rp, name)) = preceded(
+        pair(keyword("INTO"), ws1),
+        expect(
+            "invalid INTO clause, expected measurement",
+            pair(
+                opt(alt((
+                    // database "." retention_policy "."
+                    map(
+                        pair(
+                            terminated(identifier, tag(".")),
+                            terminated(identifier, tag(".")),
+                        ),
+                        |(db, rp)| (Some(db), Some(rp)),
+                    ),
+                    // database ".."
+                    map(terminated(identifier, tag("..")), |db| (Some(db), None)),
+                    // retention_policy "."
+                    map(terminated(identifier, tag(".")), |rp| (None, Some(rp))),
+                ))),
+                alt((
+                    value(IntoMeasurementName::Backreference, tag(":MEASUREMENT")),
+                    map(identifier, IntoMeasurementName::Name),
+                )),
+            ),
+        ),
+    )
+    .parse(i)?;
+
+    // Extract possible `database` and / or `retention_policy`
+    let (database, retention_policy) = opt_db_rp.unwrap_or_default();
+
+    Ok((
+        remaining_input,
+        IntoClause {
+            database,
+            retention_policy,
+            name,
+        },
+    ))
+}
+
 /// Represents a single measurement selection for a `FROM` clause.
 #[derive(Clone, Debug, PartialEq)]
 pub enum MeasurementSelection {
@@ -850,6 +960,15 @@ mod test {
             r#"SELECT f1, /f2/, f3 AS "a field" FROM foo WHERE host =~ /c1/"#
         );
 
+        let (_, got) = select_statement(
+            r#"SELECT mean(value) INTO "db"."rp".:MEASUREMENT FROM /.*/ GROUP BY time(1h), *"#,
+        )
+        .unwrap();
+        assert_eq!(
+            got.to_string(),
+            "SELECT mean(value) INTO db.rp.:MEASUREMENT FROM /.*/ GROUP BY TIME(1h), *"
+        );
+
         let (_, got) =
             select_statement("SELECT sum(value) FROM foo GROUP BY time(5m), host").unwrap();
         assert_eq!(
@@ -857,6 +976,27 @@ mod test {
             r#"SELECT sum(value) FROM foo GROUP BY TIME(5m), host"#
         );
 
+        // Sub-millisecond intervals are formatted in their own unit, and
+        // parse to the same statement
+        for (interval, want) in [
+            ("1ns", "1ns"),
+            ("10u", "10u"),
+            ("10µ", "10u"),
+            ("1000ns", "1u"),
+        ] {
+            let (_, got) = select_statement(&format!(
+                "SELECT count(value) FROM foo GROUP BY time({interval}) FILL(null)"
+            ))
+            .unwrap();
+            let stmt = got.to_string();
+            assert_eq!(
+                stmt,
+                format!("SELECT count(value) FROM foo GROUP BY TIME({want}) FILL(NULL)")
+            );
+            let (_, reparsed) = select_statement(&stmt).unwrap();
+            assert_eq!(reparsed, got);
+        }
+
         // Parses TIME() with expressions
         let (_, got) =
             select_statement("SELECT sum(value) FROM foo GROUP BY time(5m * 10), host").unwrap();
@@ -957,6 +1097,39 @@ mod test {
         assert_eq!(rem, "");
     }
 
+    #[test]
+    fn test_into_clause() {
+        let (_, got) = into_clause("INTO foo").unwrap();
+        assert_eq!(got.to_string(), "INTO foo");
+        assert_matches!(got.name, IntoMeasurementName::Name(ref name) if name.as_str() == "foo");
+
+        let (_, got) = into_clause(r#"INTO "db"."rp"."foo bar""#).unwrap();
+        assert_eq!(got.to_string(), r#"INTO db.rp."foo bar""#);
+
+        let (_, got) = into_clause("INTO db..foo").unwrap();
+        assert_eq!(got.to_string(), "INTO db..foo");
+        assert_eq!(got.database, Some("db".into()));
+        assert_eq!(got.retention_policy, None);
+
+        let (_, got) = into_clause("INTO rp.foo").unwrap();
+        assert_eq!(got.to_string(), "INTO rp.foo");
+        assert_eq!(got.database, None);
+        assert_eq!(got.retention_policy, Some("rp".into()));
+
+        let (_, got) = into_clause("INTO db.rp.:MEASUREMENT").unwrap();
+        assert_eq!(got.to_string(), "INTO db.rp.:MEASUREMENT");
+        assert_eq!(got.name, IntoMeasurementName::Backreference);
+
+        let (_, got) = into_clause("INTO :MEASUREMENT").unwrap();
+        assert_eq!(got.to_string(), "INTO :MEASUREMENT");
+
+        // Fallible cases
+        assert_expect_error!(
+            into_clause("INTO /foo/"),
+            "invalid INTO clause, expected measurement"
+        );
+    }
+
     #[test]
     fn test_field() {
         // Parse a VarRef
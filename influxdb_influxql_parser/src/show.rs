@@ -8,10 +8,11 @@ use crate::impl_tuple_clause;
 use crate::internal::{ParseResult, expect};
 use crate::keywords::keyword;
 use crate::show_field_keys::show_field_keys;
-use crate::show_measurements::show_measurements;
+use crate::show_measurements::{show_measurement_cardinality, show_measurements};
 use crate::show_retention_policies::show_retention_policies;
+use crate::show_series::show_series;
 use crate::show_tag_keys::show_tag_keys;
-use crate::show_tag_values::show_tag_values;
+use crate::show_tag_values::{show_tag_values, show_tag_values_cardinality};
 use crate::statement::Statement;
 use nom::Parser;
 use nom::branch::alt;
@@ -20,7 +21,8 @@ use nom::sequence::{pair, preceded};
 use std::fmt::{Display, Formatter};
 
 const INVALID_SHOW_STATEMENT_ERROR: &str = "invalid SHOW statement, expected DATABASES, \
-    FIELD KEYS, MEASUREMENTS, TAG KEYS, TAG VALUES, or RETENTION POLICIES following SHOW";
+    FIELD KEYS, MEASUREMENTS, MEASUREMENT CARDINALITY, SERIES, TAG KEYS, TAG VALUES, \
+    or RETENTION POLICIES following SHOW";
 
 /// Parse a SHOW statement.
 pub(crate) fn show_statement(i: &str) -> ParseResult<&str, Statement> {
@@ -37,10 +39,16 @@ pub(crate) fn show_statement(i: &str) -> ParseResult<&str, Statement> {
                 map(show_measurements, |s| {
                     Statement::ShowMeasurements(Box::new(s))
                 }),
+                // SHOW MEASUREMENT CARDINALITY
+                map(show_measurement_cardinality, |s| {
+                    Statement::ShowMeasurementCardinality(Box::new(s))
+                }),
                 // SHOW RETENTION POLICIES
                 map(show_retention_policies, |s| {
                     Statement::ShowRetentionPolicies(Box::new(s))
                 }),
+                // SHOW SERIES
+                show_series,
                 // SHOW TAG
                 show_tag,
             )),
@@ -100,6 +108,25 @@ pub(crate) fn on_clause(i: &str) -> ParseResult<&str, OnClause> {
     .parse(i)
 }
 
+/// Parse the `[EXACT] CARDINALITY` clause of statements such as
+/// `SHOW SERIES CARDINALITY`, returning `true` if `EXACT` was specified.
+pub(crate) fn cardinality(i: &str) -> ParseResult<&str, bool> {
+    alt((
+        value(
+            true,
+            preceded(
+                keyword("EXACT"),
+                expect(
+                    "invalid EXACT CARDINALITY clause, expected CARDINALITY",
+                    preceded(ws1, keyword("CARDINALITY")),
+                ),
+            ),
+        ),
+        value(false, keyword("CARDINALITY")),
+    ))
+    .parse(i)
+}
+
 /// Parse a `SHOW TAG (KEYS|VALUES)` statement.
 fn show_tag(i: &str) -> ParseResult<&str, Statement> {
     preceded(
@@ -108,6 +135,9 @@ fn show_tag(i: &str) -> ParseResult<&str, Statement> {
             "invalid SHOW TAG statement, expected KEYS or VALUES",
             alt((
                 map(show_tag_keys, |s| Statement::ShowTagKeys(Box::new(s))),
+                map(show_tag_values_cardinality, |s| {
+                    Statement::ShowTagValuesCardinality(Box::new(s))
+                }),
                 map(show_tag_values, |s| Statement::ShowTagValues(Box::new(s))),
             )),
         ),
@@ -133,15 +163,30 @@ mod test {
         let (_, got) = show_statement("SHOW MEASUREMENTS").unwrap();
         assert_eq!(got.to_string(), "SHOW MEASUREMENTS");
 
+        let (_, got) = show_statement("SHOW MEASUREMENT CARDINALITY").unwrap();
+        assert_eq!(got.to_string(), "SHOW MEASUREMENT CARDINALITY");
+
         let (_, got) = show_statement("SHOW RETENTION POLICIES ON \"foo\"").unwrap();
         assert_eq!(got.to_string(), "SHOW RETENTION POLICIES ON foo");
 
+        let (_, got) = show_statement("SHOW SERIES").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES");
+
+        let (_, got) = show_statement("SHOW SERIES EXACT CARDINALITY").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES EXACT CARDINALITY");
+
         let (_, got) = show_statement("SHOW TAG KEYS").unwrap();
         assert_eq!(got.to_string(), "SHOW TAG KEYS");
 
         let (_, got) = show_statement("SHOW TAG VALUES WITH KEY = some_key").unwrap();
         assert_eq!(got.to_string(), "SHOW TAG VALUES WITH KEY = some_key");
 
+        let (_, got) = show_statement("SHOW TAG VALUES CARDINALITY WITH KEY = some_key").unwrap();
+        assert_eq!(
+            got.to_string(),
+            "SHOW TAG VALUES CARDINALITY WITH KEY = some_key"
+        );
+
         // Fallible cases
 
         assert_expect_error!(
@@ -160,6 +205,23 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_cardinality() {
+        let (_, got) = cardinality("CARDINALITY").unwrap();
+        assert!(!got);
+
+        let (_, got) = cardinality("EXACT  CARDINALITY").unwrap();
+        assert!(got);
+
+        // Fallible cases
+
+        assert_expect_error!(
+            cardinality("EXACT COUNT"),
+            "invalid EXACT CARDINALITY clause, expected CARDINALITY"
+        );
+        cardinality("COUNT").unwrap_err();
+    }
+
     #[test]
     fn on_clause() {
         let Ok((_, Statement::ShowRetentionPolicies(got))) =
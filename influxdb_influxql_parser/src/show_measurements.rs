@@ -1,6 +1,8 @@
-//! Types and parsers for the [`SHOW MEASUREMENTS`][sql] statement.
+//! Types and parsers for the [`SHOW MEASUREMENTS`][sql] and
+//! [`SHOW MEASUREMENT CARDINALITY`][card] statements.
 //!
 //! [sql]: https://docs.influxdata.com/influxdb/v1.8/query_language/explore-schema/#show-measurements
+//! [card]: https://docs.influxdata.com/influxdb/v1.8/query_language/spec/#show-measurement-cardinality
 
 use crate::common::{
     LimitClause, OffsetClause, QualifiedMeasurementName, WhereClause, limit_clause, offset_clause,
@@ -9,6 +11,8 @@ use crate::common::{
 use crate::identifier::{Identifier, identifier};
 use crate::internal::{ParseResult, expect};
 use crate::keywords::keyword;
+use crate::show::{OnClause, cardinality, on_clause};
+use crate::simple_from_clause::{ShowFromClause, show_from_clause};
 use nom::Parser;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
@@ -207,6 +211,90 @@ pub(crate) fn show_measurements(i: &str) -> ParseResult<&str, ShowMeasurementsSt
     ))
 }
 
+/// Represents a `SHOW MEASUREMENT CARDINALITY` statement.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ShowMeasurementCardinalityStatement {
+    /// `true` if the statement specified `EXACT CARDINALITY`.
+    pub exact: bool,
+
+    /// The name of the database to query. If `None`, a default
+    /// database will be used.
+    pub database: Option<OnClause>,
+
+    /// The measurement or measurements to restrict which measurements
+    /// are counted.
+    pub from: Option<ShowFromClause>,
+
+    /// Represents the `WHERE` clause, which holds a conditional
+    /// expression to filter the measurements.
+    pub condition: Option<WhereClause>,
+}
+
+impl fmt::Display for ShowMeasurementCardinalityStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("SHOW MEASUREMENT")?;
+
+        if self.exact {
+            f.write_str(" EXACT")?;
+        }
+
+        f.write_str(" CARDINALITY")?;
+
+        if let Some(ref on_clause) = self.database {
+            write!(f, " {on_clause}")?;
+        }
+
+        if let Some(ref from_clause) = self.from {
+            write!(f, " {from_clause}")?;
+        }
+
+        if let Some(ref where_clause) = self.condition {
+            write!(f, " {where_clause}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse a `SHOW MEASUREMENT CARDINALITY` statement after `SHOW` and any whitespace
+/// has been consumed.
+pub(crate) fn show_measurement_cardinality(
+    i: &str,
+) -> ParseResult<&str, ShowMeasurementCardinalityStatement> {
+    let (
+        remaining_input,
+        (
+            _, // "MEASUREMENT"
+            _, // whitespace
+            exact,
+            database,
+            from,
+            condition,
+        ),
+    ) = (
+        keyword("MEASUREMENT"),
+        ws1,
+        expect(
+            "invalid SHOW MEASUREMENT CARDINALITY statement, expected CARDINALITY",
+            cardinality,
+        ),
+        opt(preceded(ws1, on_clause)),
+        opt(preceded(ws1, show_from_clause)),
+        opt(preceded(ws1, where_clause)),
+    )
+        .parse(i)?;
+
+    Ok((
+        remaining_input,
+        ShowMeasurementCardinalityStatement {
+            exact,
+            database,
+            from,
+            condition,
+        },
+    ))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -279,6 +367,29 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_show_measurement_cardinality() {
+        let (_, got) = show_measurement_cardinality("MEASUREMENT CARDINALITY").unwrap();
+        assert_eq!(got, ShowMeasurementCardinalityStatement::default());
+        assert_eq!(got.to_string(), "SHOW MEASUREMENT CARDINALITY");
+
+        let (_, got) = show_measurement_cardinality(
+            "MEASUREMENT EXACT CARDINALITY ON foo FROM /cpu/ WHERE host = 'a'",
+        )
+        .unwrap();
+        assert!(got.exact);
+        assert_eq!(
+            got.to_string(),
+            "SHOW MEASUREMENT EXACT CARDINALITY ON foo FROM /cpu/ WHERE host = 'a'"
+        );
+
+        // Fallible cases
+        assert_expect_error!(
+            show_measurement_cardinality("MEASUREMENT COUNT"),
+            "invalid SHOW MEASUREMENT CARDINALITY statement, expected CARDINALITY"
+        );
+    }
+
     #[test]
     fn test_display() {
         let got = format!(
@@ -0,0 +1,242 @@
+//! Types and parsers for the [`SHOW SERIES`][sql] and [`SHOW SERIES CARDINALITY`][card]
+//! statements.
+//!
+//! [sql]: https://docs.influxdata.com/influxdb/v1.8/query_language/explore-schema/#show-series
+//! [card]: https://docs.influxdata.com/influxdb/v1.8/query_language/spec/#show-series-cardinality
+
+use crate::common::{
+    LimitClause, OffsetClause, WhereClause, limit_clause, offset_clause, where_clause, ws1,
+};
+use crate::internal::ParseResult;
+use crate::keywords::keyword;
+use crate::show::{OnClause, cardinality, on_clause};
+use crate::simple_from_clause::{ShowFromClause, show_from_clause};
+use crate::statement::Statement;
+use nom::Parser;
+use nom::branch::alt;
+use nom::combinator::{map, opt};
+use nom::sequence::preceded;
+use std::fmt;
+use std::fmt::Formatter;
+
+/// Represents a `SHOW SERIES` InfluxQL statement.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ShowSeriesStatement {
+    /// The name of the database to query. If `None`, a default
+    /// database will be used.
+    pub database: Option<OnClause>,
+
+    /// The measurement or measurements to restrict which series
+    /// are retrieved.
+    pub from: Option<ShowFromClause>,
+
+    /// A conditional expression to filter the series.
+    pub condition: Option<WhereClause>,
+
+    /// A value to restrict the number of series returned.
+    pub limit: Option<LimitClause>,
+
+    /// A value to specify an offset to start retrieving series.
+    pub offset: Option<OffsetClause>,
+}
+
+impl fmt::Display for ShowSeriesStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("SHOW SERIES")?;
+
+        if let Some(ref on_clause) = self.database {
+            write!(f, " {on_clause}")?;
+        }
+
+        if let Some(ref from_clause) = self.from {
+            write!(f, " {from_clause}")?;
+        }
+
+        if let Some(ref where_clause) = self.condition {
+            write!(f, " {where_clause}")?;
+        }
+
+        if let Some(ref limit) = self.limit {
+            write!(f, " {limit}")?;
+        }
+
+        if let Some(ref offset) = self.offset {
+            write!(f, " {offset}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Represents a `SHOW SERIES CARDINALITY` InfluxQL statement.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ShowSeriesCardinalityStatement {
+    /// `true` if the statement specified `EXACT CARDINALITY`.
+    pub exact: bool,
+
+    /// The name of the database to query. If `None`, a default
+    /// database will be used.
+    pub database: Option<OnClause>,
+
+    /// The measurement or measurements to restrict which series
+    /// are counted.
+    pub from: Option<ShowFromClause>,
+
+    /// A conditional expression to filter the series.
+    pub condition: Option<WhereClause>,
+}
+
+impl fmt::Display for ShowSeriesCardinalityStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("SHOW SERIES")?;
+
+        if self.exact {
+            f.write_str(" EXACT")?;
+        }
+
+        f.write_str(" CARDINALITY")?;
+
+        if let Some(ref on_clause) = self.database {
+            write!(f, " {on_clause}")?;
+        }
+
+        if let Some(ref from_clause) = self.from {
+            write!(f, " {from_clause}")?;
+        }
+
+        if let Some(ref where_clause) = self.condition {
+            write!(f, " {where_clause}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse a `SHOW SERIES` or `SHOW SERIES CARDINALITY` statement, starting from
+/// the `SERIES` token.
+pub(crate) fn show_series(i: &str) -> ParseResult<&str, Statement> {
+    preceded(
+        keyword("SERIES"),
+        alt((
+            map(show_series_cardinality, |s| {
+                Statement::ShowSeriesCardinality(Box::new(s))
+            }),
+            map(show_series_statement, |s| {
+                Statement::ShowSeries(Box::new(s))
+            }),
+        )),
+    )
+    .parse(i)
+}
+
+/// Parse the clauses of a `SHOW SERIES` statement, following the `SERIES` token.
+fn show_series_statement(i: &str) -> ParseResult<&str, ShowSeriesStatement> {
+    let (remaining_input, (database, from, condition, limit, offset)) = (
+        opt(preceded(ws1, on_clause)),
+        opt(preceded(ws1, show_from_clause)),
+        opt(preceded(ws1, where_clause)),
+        opt(preceded(ws1, limit_clause)),
+        opt(preceded(ws1, offset_clause)),
+    )
+        .parse(i)?;
+
+    Ok((
+        remaining_input,
+        ShowSeriesStatement {
+            database,
+            from,
+            condition,
+            limit,
+            offset,
+        },
+    ))
+}
+
+/// Parse the clauses of a `SHOW SERIES CARDINALITY` statement, following the
+/// `SERIES` token.
+fn show_series_cardinality(i: &str) -> ParseResult<&str, ShowSeriesCardinalityStatement> {
+    let (remaining_input, (exact, database, from, condition)) = (
+        preceded(ws1, cardinality),
+        opt(preceded(ws1, on_clause)),
+        opt(preceded(ws1, show_from_clause)),
+        opt(preceded(ws1, where_clause)),
+    )
+        .parse(i)?;
+
+    Ok((
+        remaining_input,
+        ShowSeriesCardinalityStatement {
+            exact,
+            database,
+            from,
+            condition,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::assert_expect_error;
+
+    #[test]
+    fn test_show_series() {
+        // No optional clauses
+        let (_, got) = show_series("SERIES").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES");
+
+        let (_, got) = show_series("SERIES ON db").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES ON db");
+
+        let (_, got) = show_series("SERIES FROM /cpu/, disk").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES FROM /cpu/, disk");
+
+        let (_, got) = show_series("SERIES WHERE host = 'a'").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES WHERE host = 'a'");
+
+        // all optional clauses
+        let (_, got) =
+            show_series("SERIES ON db FROM cpu WHERE host = 'a' LIMIT 1 OFFSET 2").unwrap();
+        assert_eq!(
+            got.to_string(),
+            "SHOW SERIES ON db FROM cpu WHERE host = 'a' LIMIT 1 OFFSET 2"
+        );
+        let Statement::ShowSeries(got) = got else {
+            panic!("expected SHOW SERIES statement")
+        };
+        assert_eq!(got.database, Some(OnClause("db".into())));
+        assert_eq!(got.limit, Some(1.into()));
+        assert_eq!(got.offset, Some(2.into()));
+    }
+
+    #[test]
+    fn test_show_series_cardinality() {
+        let (_, got) = show_series("SERIES CARDINALITY").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES CARDINALITY");
+        assert!(matches!(
+            got,
+            Statement::ShowSeriesCardinality(s) if !s.exact
+        ));
+
+        let (_, got) = show_series("SERIES EXACT CARDINALITY").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES EXACT CARDINALITY");
+        assert!(matches!(
+            got,
+            Statement::ShowSeriesCardinality(s) if s.exact
+        ));
+
+        // all optional clauses
+        let (_, got) =
+            show_series("SERIES EXACT CARDINALITY ON db FROM cpu WHERE host = 'a'").unwrap();
+        assert_eq!(
+            got.to_string(),
+            "SHOW SERIES EXACT CARDINALITY ON db FROM cpu WHERE host = 'a'"
+        );
+
+        // Fallible cases
+        assert_expect_error!(
+            show_series("SERIES EXACT"),
+            "invalid EXACT CARDINALITY clause, expected CARDINALITY"
+        );
+    }
+}
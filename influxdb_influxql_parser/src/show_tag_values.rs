@@ -1,6 +1,8 @@
-//! Types and parsers for the [`SHOW TAG VALUES`][sql] statement.
+//! Types and parsers for the [`SHOW TAG VALUES`][sql] and
+//! [`SHOW TAG VALUES CARDINALITY`][card] statements.
 //!
 //! [sql]: https://docs.influxdata.com/influxdb/v1.8/query_language/explore-schema/#show-tag-values
+//! [card]: https://docs.influxdata.com/influxdb/v1.8/query_language/spec/#show-tag-values-cardinality
 
 use crate::common::{
     LimitClause, OffsetClause, OneOrMore, WhereClause, limit_clause, offset_clause, where_clause,
@@ -9,7 +11,7 @@ use crate::common::{
 use crate::identifier::{Identifier, identifier};
 use crate::internal::{ParseResult, expect};
 use crate::keywords::keyword;
-use crate::show::{OnClause, on_clause};
+use crate::show::{OnClause, cardinality, on_clause};
 use crate::simple_from_clause::{ShowFromClause, show_from_clause};
 use crate::string::{Regex, regex};
 use nom::Parser;
@@ -116,6 +118,95 @@ pub(crate) fn show_tag_values(i: &str) -> ParseResult<&str, ShowTagValuesStateme
     ))
 }
 
+/// Represents a `SHOW TAG VALUES CARDINALITY` InfluxQL statement.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShowTagValuesCardinalityStatement {
+    /// `true` if the statement specified `EXACT CARDINALITY`.
+    pub exact: bool,
+
+    /// The name of the database to query. If `None`, a default
+    /// database will be used.
+    pub database: Option<OnClause>,
+
+    /// The measurement or measurements to restrict which tag values
+    /// are counted.
+    pub from: Option<ShowFromClause>,
+
+    /// Represents the `WITH KEY` clause, to restrict the tag values to
+    /// the matching tag keys.
+    pub with_key: WithKeyClause,
+
+    /// A conditional expression to filter the tag values.
+    pub condition: Option<WhereClause>,
+}
+
+impl Display for ShowTagValuesCardinalityStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("SHOW TAG VALUES")?;
+
+        if self.exact {
+            f.write_str(" EXACT")?;
+        }
+
+        f.write_str(" CARDINALITY")?;
+
+        if let Some(ref on_clause) = self.database {
+            write!(f, " {on_clause}")?;
+        }
+
+        if let Some(ref from_clause) = self.from {
+            write!(f, " {from_clause}")?;
+        }
+
+        write!(f, " {}", self.with_key)?;
+
+        if let Some(ref where_clause) = self.condition {
+            write!(f, " {where_clause}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse a `SHOW TAG VALUES CARDINALITY` statement, starting from the `VALUES` token.
+pub(crate) fn show_tag_values_cardinality(
+    i: &str,
+) -> ParseResult<&str, ShowTagValuesCardinalityStatement> {
+    let (
+        remaining_input,
+        (
+            _, // "VALUES"
+            exact,
+            database,
+            from,
+            with_key,
+            condition,
+        ),
+    ) = (
+        keyword("VALUES"),
+        preceded(ws1, cardinality),
+        opt(preceded(ws1, on_clause)),
+        opt(preceded(ws1, show_from_clause)),
+        expect(
+            "invalid SHOW TAG VALUES CARDINALITY statement, expected WITH KEY clause",
+            preceded(ws1, with_key_clause),
+        ),
+        opt(preceded(ws1, where_clause)),
+    )
+        .parse(i)?;
+
+    Ok((
+        remaining_input,
+        ShowTagValuesCardinalityStatement {
+            exact,
+            database,
+            from,
+            with_key,
+            condition,
+        },
+    ))
+}
+
 /// Represents a list of identifiers when the `WITH KEY` clause
 /// specifies the `IN` operator.
 pub type InList = OneOrMore<Identifier>;
@@ -308,6 +399,37 @@ mod test {
         // Fallible cases are tested by the various combinator functions
     }
 
+    #[test]
+    fn test_show_tag_values_cardinality() {
+        let (_, got) =
+            show_tag_values_cardinality("VALUES CARDINALITY WITH KEY = some_key").unwrap();
+        assert!(!got.exact);
+        assert_eq!(
+            got.to_string(),
+            "SHOW TAG VALUES CARDINALITY WITH KEY = some_key"
+        );
+
+        // all optional clauses
+        let (_, got) = show_tag_values_cardinality(
+            "VALUES EXACT CARDINALITY ON db FROM /foo/ WITH KEY =~ /host/ WHERE foo = 'bar'",
+        )
+        .unwrap();
+        assert!(got.exact);
+        assert_eq!(
+            got.to_string(),
+            "SHOW TAG VALUES EXACT CARDINALITY ON db FROM /foo/ WITH KEY =~ /host/ WHERE foo = 'bar'"
+        );
+
+        // Not a cardinality statement
+        show_tag_values_cardinality("VALUES WITH KEY = some_key").unwrap_err();
+
+        // Fallible cases
+        assert_expect_error!(
+            show_tag_values_cardinality("VALUES CARDINALITY ON db"),
+            "invalid SHOW TAG VALUES CARDINALITY statement, expected WITH KEY clause"
+        );
+    }
+
     #[test]
     fn test_with_key_clause() {
         let (_, got) = with_key_clause("WITH KEY = foo").unwrap();
@@ -8,10 +8,11 @@ use crate::internal::ParseResult;
 use crate::select::{SelectStatement, select_statement};
 use crate::show::{ShowDatabasesStatement, show_statement};
 use crate::show_field_keys::ShowFieldKeysStatement;
-use crate::show_measurements::ShowMeasurementsStatement;
+use crate::show_measurements::{ShowMeasurementCardinalityStatement, ShowMeasurementsStatement};
 use crate::show_retention_policies::ShowRetentionPoliciesStatement;
+use crate::show_series::{ShowSeriesCardinalityStatement, ShowSeriesStatement};
 use crate::show_tag_keys::ShowTagKeysStatement;
-use crate::show_tag_values::ShowTagValuesStatement;
+use crate::show_tag_values::{ShowTagValuesCardinalityStatement, ShowTagValuesStatement};
 use nom::Parser;
 use nom::branch::alt;
 use nom::combinator::map;
@@ -34,12 +35,20 @@ pub enum Statement {
     ShowDatabases(Box<ShowDatabasesStatement>),
     /// Represents a `SHOW MEASUREMENTS` statement.
     ShowMeasurements(Box<ShowMeasurementsStatement>),
+    /// Represents a `SHOW MEASUREMENT CARDINALITY` statement.
+    ShowMeasurementCardinality(Box<ShowMeasurementCardinalityStatement>),
     /// Represents a `SHOW RETENTION POLICIES` statement.
     ShowRetentionPolicies(Box<ShowRetentionPoliciesStatement>),
+    /// Represents a `SHOW SERIES` statement.
+    ShowSeries(Box<ShowSeriesStatement>),
+    /// Represents a `SHOW SERIES CARDINALITY` statement.
+    ShowSeriesCardinality(Box<ShowSeriesCardinalityStatement>),
     /// Represents a `SHOW TAG KEYS` statement.
     ShowTagKeys(Box<ShowTagKeysStatement>),
     /// Represents a `SHOW TAG VALUES` statement.
     ShowTagValues(Box<ShowTagValuesStatement>),
+    /// Represents a `SHOW TAG VALUES CARDINALITY` statement.
+    ShowTagValuesCardinality(Box<ShowTagValuesCardinalityStatement>),
     /// Represents a `SHOW FIELD KEYS` statement.
     ShowFieldKeys(Box<ShowFieldKeysStatement>),
 }
@@ -66,9 +75,13 @@ impl Display for Statement {
             Self::Select(s) => Display::fmt(s, f),
             Self::ShowDatabases(s) => Display::fmt(s, f),
             Self::ShowMeasurements(s) => Display::fmt(s, f),
+            Self::ShowMeasurementCardinality(s) => Display::fmt(s, f),
             Self::ShowRetentionPolicies(s) => Display::fmt(s, f),
+            Self::ShowSeries(s) => Display::fmt(s, f),
+            Self::ShowSeriesCardinality(s) => Display::fmt(s, f),
             Self::ShowTagKeys(s) => Display::fmt(s, f),
             Self::ShowTagValues(s) => Display::fmt(s, f),
+            Self::ShowTagValuesCardinality(s) => Display::fmt(s, f),
             Self::ShowFieldKeys(s) => Display::fmt(s, f),
         }
     }
@@ -994,8 +994,8 @@ mod test {
 
         assert_error!(process_expr("'2004-04-09' + false"), Expression(ref s) if s == "invalid expression \"'2004-04-09' + false\": found literal 'false', expected duration, float, integer, or timestamp string");
 
-        assert_error!(process_expr("1s * 1s"), Expression(ref s) if s == "invalid expression \"1000ms * 1000ms\": found operator '*', expected +, -");
-        assert_error!(process_expr("1s + 0.5"), Expression(ref s) if s == "invalid expression \"1000ms + 0.5\": found operator '+', expected *, /");
+        assert_error!(process_expr("1s * 1s"), Expression(ref s) if s == "invalid expression \"1s * 1s\": found operator '*', expected +, -");
+        assert_error!(process_expr("1s + 0.5"), Expression(ref s) if s == "invalid expression \"1s + 0.5\": found operator '+', expected *, /");
 
         assert_error!(process_expr("'2004-04-09T'"), Expression(ref s) if s == "invalid expression \"'2004-04-09T'\": '2004-04-09T' is not a valid timestamp");
         assert_error!(process_expr("now() * 1"), Expression(ref s) if s == "invalid expression \"now() * 1\": invalid operator '*' for timestamp and duration: expected +, -");
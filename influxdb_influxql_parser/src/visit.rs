@@ -37,18 +37,22 @@ use crate::expression::conditional::ConditionalExpression;
 use crate::expression::{Binary, Call, ConditionalBinary, VarRef};
 use crate::literal::Literal;
 use crate::select::{
-    Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause,
+    Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause, IntoClause,
     MeasurementSelection, SLimitClause, SOffsetClause, SelectStatement, TimeDimension,
     TimeZoneClause,
 };
 use crate::show::{OnClause, ShowDatabasesStatement};
 use crate::show_field_keys::ShowFieldKeysStatement;
 use crate::show_measurements::{
-    ExtendedOnClause, ShowMeasurementsStatement, WithMeasurementClause,
+    ExtendedOnClause, ShowMeasurementCardinalityStatement, ShowMeasurementsStatement,
+    WithMeasurementClause,
 };
 use crate::show_retention_policies::ShowRetentionPoliciesStatement;
+use crate::show_series::{ShowSeriesCardinalityStatement, ShowSeriesStatement};
 use crate::show_tag_keys::ShowTagKeysStatement;
-use crate::show_tag_values::{ShowTagValuesStatement, WithKeyClause};
+use crate::show_tag_values::{
+    ShowTagValuesCardinalityStatement, ShowTagValuesStatement, WithKeyClause,
+};
 use crate::simple_from_clause::{DeleteFromClause, ShowFromClause};
 use crate::statement::Statement;
 
@@ -257,6 +261,70 @@ pub trait Visitor: Sized {
         Ok(self)
     }
 
+    /// Invoked before any children of the `SHOW MEASUREMENT CARDINALITY` statement are visited.
+    fn pre_visit_show_measurement_cardinality_statement(
+        self,
+        _n: &ShowMeasurementCardinalityStatement,
+    ) -> Result<Recursion<Self>, Self::Error> {
+        Ok(Continue(self))
+    }
+
+    /// Invoked after all children of the `SHOW MEASUREMENT CARDINALITY` statement are visited.
+    fn post_visit_show_measurement_cardinality_statement(
+        self,
+        _n: &ShowMeasurementCardinalityStatement,
+    ) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
+    /// Invoked before any children of the `SHOW SERIES` statement are visited.
+    fn pre_visit_show_series_statement(
+        self,
+        _n: &ShowSeriesStatement,
+    ) -> Result<Recursion<Self>, Self::Error> {
+        Ok(Continue(self))
+    }
+
+    /// Invoked after all children of the `SHOW SERIES` statement are visited.
+    fn post_visit_show_series_statement(
+        self,
+        _n: &ShowSeriesStatement,
+    ) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
+    /// Invoked before any children of the `SHOW SERIES CARDINALITY` statement are visited.
+    fn pre_visit_show_series_cardinality_statement(
+        self,
+        _n: &ShowSeriesCardinalityStatement,
+    ) -> Result<Recursion<Self>, Self::Error> {
+        Ok(Continue(self))
+    }
+
+    /// Invoked after all children of the `SHOW SERIES CARDINALITY` statement are visited.
+    fn post_visit_show_series_cardinality_statement(
+        self,
+        _n: &ShowSeriesCardinalityStatement,
+    ) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
+    /// Invoked before any children of the `SHOW TAG VALUES CARDINALITY` statement are visited.
+    fn pre_visit_show_tag_values_cardinality_statement(
+        self,
+        _n: &ShowTagValuesCardinalityStatement,
+    ) -> Result<Recursion<Self>, Self::Error> {
+        Ok(Continue(self))
+    }
+
+    /// Invoked after all children of the `SHOW TAG VALUES CARDINALITY` statement are visited.
+    fn post_visit_show_tag_values_cardinality_statement(
+        self,
+        _n: &ShowTagValuesCardinalityStatement,
+    ) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
     /// Invoked before any children of the `SHOW FIELD KEYS` statement are visited.
     fn pre_visit_show_field_keys_statement(
         self,
@@ -483,6 +551,16 @@ pub trait Visitor: Sized {
         Ok(self)
     }
 
+    /// Invoked before any children of an `INTO` clause are visited.
+    fn pre_visit_into_clause(self, _n: &IntoClause) -> Result<Recursion<Self>, Self::Error> {
+        Ok(Continue(self))
+    }
+
+    /// Invoked after all children of an `INTO` clause are visited.
+    fn post_visit_into_clause(self, _n: &IntoClause) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
     /// Invoked before any children of a `TZ` clause are visited.
     fn pre_visit_timezone_clause(
         self,
@@ -620,9 +698,13 @@ impl Visitable for Statement {
             Self::Select(s) => s.accept(visitor),
             Self::ShowDatabases(s) => s.accept(visitor),
             Self::ShowMeasurements(s) => s.accept(visitor),
+            Self::ShowMeasurementCardinality(s) => s.accept(visitor),
             Self::ShowRetentionPolicies(s) => s.accept(visitor),
+            Self::ShowSeries(s) => s.accept(visitor),
+            Self::ShowSeriesCardinality(s) => s.accept(visitor),
             Self::ShowTagKeys(s) => s.accept(visitor),
             Self::ShowTagValues(s) => s.accept(visitor),
+            Self::ShowTagValuesCardinality(s) => s.accept(visitor),
             Self::ShowFieldKeys(s) => s.accept(visitor),
         }?;
 
@@ -735,6 +817,12 @@ impl Visitable for SelectStatement {
 
         let visitor = self.fields.accept(visitor)?;
 
+        let visitor = if let Some(into) = &self.into {
+            into.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
         let visitor = self.from.accept(visitor)?;
 
         let visitor = if let Some(condition) = &self.condition {
@@ -795,6 +883,17 @@ impl Visitable for SelectStatement {
     }
 }
 
+impl Visitable for IntoClause {
+    fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
+        let visitor = match visitor.pre_visit_into_clause(self)? {
+            Continue(visitor) => visitor,
+            Stop(visitor) => return Ok(visitor),
+        };
+
+        visitor.post_visit_into_clause(self)
+    }
+}
+
 impl Visitable for TimeZoneClause {
     fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
         let visitor = match visitor.pre_visit_timezone_clause(self)? {
@@ -1080,6 +1179,136 @@ impl Visitable for ShowTagValuesStatement {
     }
 }
 
+impl Visitable for ShowMeasurementCardinalityStatement {
+    fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
+        let visitor = match visitor.pre_visit_show_measurement_cardinality_statement(self)? {
+            Continue(visitor) => visitor,
+            Stop(visitor) => return Ok(visitor),
+        };
+
+        let visitor = if let Some(on_clause) = &self.database {
+            on_clause.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(from) = &self.from {
+            from.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(condition) = &self.condition {
+            condition.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        visitor.post_visit_show_measurement_cardinality_statement(self)
+    }
+}
+
+impl Visitable for ShowSeriesStatement {
+    fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
+        let visitor = match visitor.pre_visit_show_series_statement(self)? {
+            Continue(visitor) => visitor,
+            Stop(visitor) => return Ok(visitor),
+        };
+
+        let visitor = if let Some(on_clause) = &self.database {
+            on_clause.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(from) = &self.from {
+            from.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(condition) = &self.condition {
+            condition.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(limit) = &self.limit {
+            limit.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(offset) = &self.offset {
+            offset.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        visitor.post_visit_show_series_statement(self)
+    }
+}
+
+impl Visitable for ShowSeriesCardinalityStatement {
+    fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
+        let visitor = match visitor.pre_visit_show_series_cardinality_statement(self)? {
+            Continue(visitor) => visitor,
+            Stop(visitor) => return Ok(visitor),
+        };
+
+        let visitor = if let Some(on_clause) = &self.database {
+            on_clause.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(from) = &self.from {
+            from.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(condition) = &self.condition {
+            condition.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        visitor.post_visit_show_series_cardinality_statement(self)
+    }
+}
+
+impl Visitable for ShowTagValuesCardinalityStatement {
+    fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
+        let visitor = match visitor.pre_visit_show_tag_values_cardinality_statement(self)? {
+            Continue(visitor) => visitor,
+            Stop(visitor) => return Ok(visitor),
+        };
+
+        let visitor = if let Some(on_clause) = &self.database {
+            on_clause.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(from) = &self.from {
+            from.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = self.with_key.accept(visitor)?;
+
+        let visitor = if let Some(condition) = &self.condition {
+            condition.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        visitor.post_visit_show_tag_values_cardinality_statement(self)
+    }
+}
+
 impl Visitable for ShowFieldKeysStatement {
     fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
         let visitor = match visitor.pre_visit_show_field_keys_statement(self)? {
@@ -1362,11 +1591,15 @@ mod test {
     use crate::show::{OnClause, ShowDatabasesStatement};
     use crate::show_field_keys::ShowFieldKeysStatement;
     use crate::show_measurements::{
-        ExtendedOnClause, ShowMeasurementsStatement, WithMeasurementClause,
+        ExtendedOnClause, ShowMeasurementCardinalityStatement, ShowMeasurementsStatement,
+        WithMeasurementClause,
     };
     use crate::show_retention_policies::ShowRetentionPoliciesStatement;
+    use crate::show_series::{ShowSeriesCardinalityStatement, ShowSeriesStatement};
     use crate::show_tag_keys::ShowTagKeysStatement;
-    use crate::show_tag_values::{ShowTagValuesStatement, WithKeyClause};
+    use crate::show_tag_values::{
+        ShowTagValuesCardinalityStatement, ShowTagValuesStatement, WithKeyClause,
+    };
     use crate::simple_from_clause::{DeleteFromClause, ShowFromClause};
     use crate::statement::{Statement, statement};
 
@@ -1416,12 +1649,25 @@ mod test {
         trace_visit!(select_statement, SelectStatement);
         trace_visit!(show_databases_statement, ShowDatabasesStatement);
         trace_visit!(show_measurements_statement, ShowMeasurementsStatement);
+        trace_visit!(
+            show_measurement_cardinality_statement,
+            ShowMeasurementCardinalityStatement
+        );
         trace_visit!(
             show_retention_policies_statement,
             ShowRetentionPoliciesStatement
         );
+        trace_visit!(show_series_statement, ShowSeriesStatement);
+        trace_visit!(
+            show_series_cardinality_statement,
+            ShowSeriesCardinalityStatement
+        );
         trace_visit!(show_tag_keys_statement, ShowTagKeysStatement);
         trace_visit!(show_tag_values_statement, ShowTagValuesStatement);
+        trace_visit!(
+            show_tag_values_cardinality_statement,
+            ShowTagValuesCardinalityStatement
+        );
         trace_visit!(show_field_keys_statement, ShowFieldKeysStatement);
         trace_visit!(conditional_expression, ConditionalExpression);
         trace_visit!(expr, Expr);
@@ -1441,6 +1687,7 @@ mod test {
         trace_visit!(offset_clause, OffsetClause);
         trace_visit!(slimit_clause, SLimitClause);
         trace_visit!(soffset_clause, SOffsetClause);
+        trace_visit!(into_clause, IntoClause);
         trace_visit!(timezone_clause, TimeZoneClause);
         trace_visit!(extended_on_clause, ExtendedOnClause);
         trace_visit!(on_clause, OnClause);
@@ -1507,6 +1754,9 @@ mod test {
             TZ('Australia/Hobart')
         "#
         ));
+        insta::assert_yaml_snapshot!(visit_statement!(
+            r#"SELECT value INTO db.rp.:MEASUREMENT FROM temp"#
+        ));
     }
 
     #[test]
@@ -1558,6 +1808,35 @@ mod test {
         ));
     }
 
+    #[test]
+    fn test_show_measurement_cardinality_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!(
+            "SHOW MEASUREMENT EXACT CARDINALITY ON telegraf FROM cpu WHERE host = \"west\""
+        ));
+    }
+
+    #[test]
+    fn test_show_series_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!("SHOW SERIES"));
+        insta::assert_yaml_snapshot!(visit_statement!(
+            "SHOW SERIES ON telegraf FROM cpu WHERE host = \"west\" LIMIT 5 OFFSET 10"
+        ));
+    }
+
+    #[test]
+    fn test_show_series_cardinality_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!(
+            "SHOW SERIES CARDINALITY ON telegraf FROM cpu WHERE host = \"west\""
+        ));
+    }
+
+    #[test]
+    fn test_show_tag_values_cardinality_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!(
+            "SHOW TAG VALUES CARDINALITY ON telegraf FROM cpu WITH KEY = host WHERE host = \"west\""
+        ));
+    }
+
     #[test]
     fn test_show_field_keys_statement() {
         insta::assert_yaml_snapshot!(visit_statement!("SHOW FIELD KEYS"));
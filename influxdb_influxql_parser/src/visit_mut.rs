@@ -37,18 +37,22 @@ use crate::expression::conditional::ConditionalExpression;
 use crate::expression::{Binary, Call, ConditionalBinary, VarRef};
 use crate::literal::Literal;
 use crate::select::{
-    Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause,
+    Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause, IntoClause,
     MeasurementSelection, SLimitClause, SOffsetClause, SelectStatement, TimeDimension,
     TimeZoneClause,
 };
 use crate::show::{OnClause, ShowDatabasesStatement};
 use crate::show_field_keys::ShowFieldKeysStatement;
 use crate::show_measurements::{
-    ExtendedOnClause, ShowMeasurementsStatement, WithMeasurementClause,
+    ExtendedOnClause, ShowMeasurementCardinalityStatement, ShowMeasurementsStatement,
+    WithMeasurementClause,
 };
 use crate::show_retention_policies::ShowRetentionPoliciesStatement;
+use crate::show_series::{ShowSeriesCardinalityStatement, ShowSeriesStatement};
 use crate::show_tag_keys::ShowTagKeysStatement;
-use crate::show_tag_values::{ShowTagValuesStatement, WithKeyClause};
+use crate::show_tag_values::{
+    ShowTagValuesCardinalityStatement, ShowTagValuesStatement, WithKeyClause,
+};
 use crate::simple_from_clause::{DeleteFromClause, ShowFromClause};
 use crate::statement::Statement;
 
@@ -263,6 +267,70 @@ pub trait VisitorMut: Sized {
         Ok(())
     }
 
+    /// Invoked before any children of the `SHOW MEASUREMENT CARDINALITY` statement are visited.
+    fn pre_visit_show_measurement_cardinality_statement(
+        &mut self,
+        _n: &mut ShowMeasurementCardinalityStatement,
+    ) -> Result<Recursion, Self::Error> {
+        Ok(Continue)
+    }
+
+    /// Invoked after all children of the `SHOW MEASUREMENT CARDINALITY` statement are visited.
+    fn post_visit_show_measurement_cardinality_statement(
+        &mut self,
+        _n: &mut ShowMeasurementCardinalityStatement,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Invoked before any children of the `SHOW SERIES` statement are visited.
+    fn pre_visit_show_series_statement(
+        &mut self,
+        _n: &mut ShowSeriesStatement,
+    ) -> Result<Recursion, Self::Error> {
+        Ok(Continue)
+    }
+
+    /// Invoked after all children of the `SHOW SERIES` statement are visited.
+    fn post_visit_show_series_statement(
+        &mut self,
+        _n: &mut ShowSeriesStatement,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Invoked before any children of the `SHOW SERIES CARDINALITY` statement are visited.
+    fn pre_visit_show_series_cardinality_statement(
+        &mut self,
+        _n: &mut ShowSeriesCardinalityStatement,
+    ) -> Result<Recursion, Self::Error> {
+        Ok(Continue)
+    }
+
+    /// Invoked after all children of the `SHOW SERIES CARDINALITY` statement are visited.
+    fn post_visit_show_series_cardinality_statement(
+        &mut self,
+        _n: &mut ShowSeriesCardinalityStatement,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Invoked before any children of the `SHOW TAG VALUES CARDINALITY` statement are visited.
+    fn pre_visit_show_tag_values_cardinality_statement(
+        &mut self,
+        _n: &mut ShowTagValuesCardinalityStatement,
+    ) -> Result<Recursion, Self::Error> {
+        Ok(Continue)
+    }
+
+    /// Invoked after all children of the `SHOW TAG VALUES CARDINALITY` statement are visited.
+    fn post_visit_show_tag_values_cardinality_statement(
+        &mut self,
+        _n: &mut ShowTagValuesCardinalityStatement,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Invoked before any children of the `SHOW FIELD KEYS` statement are visited.
     fn pre_visit_show_field_keys_statement(
         &mut self,
@@ -504,6 +572,16 @@ pub trait VisitorMut: Sized {
         Ok(())
     }
 
+    /// Invoked before any children of an `INTO` clause are visited.
+    fn pre_visit_into_clause(&mut self, _n: &mut IntoClause) -> Result<Recursion, Self::Error> {
+        Ok(Continue)
+    }
+
+    /// Invoked after all children of an `INTO` clause are visited.
+    fn post_visit_into_clause(&mut self, _n: &mut IntoClause) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Invoked before any children of a `TZ` clause are visited.
     fn pre_visit_timezone_clause(
         &mut self,
@@ -649,9 +727,13 @@ impl VisitableMut for Statement {
             Self::Select(s) => s.accept(visitor),
             Self::ShowDatabases(s) => s.accept(visitor),
             Self::ShowMeasurements(s) => s.accept(visitor),
+            Self::ShowMeasurementCardinality(s) => s.accept(visitor),
             Self::ShowRetentionPolicies(s) => s.accept(visitor),
+            Self::ShowSeries(s) => s.accept(visitor),
+            Self::ShowSeriesCardinality(s) => s.accept(visitor),
             Self::ShowTagKeys(s) => s.accept(visitor),
             Self::ShowTagValues(s) => s.accept(visitor),
+            Self::ShowTagValuesCardinality(s) => s.accept(visitor),
             Self::ShowFieldKeys(s) => s.accept(visitor),
         }?;
 
@@ -756,6 +838,10 @@ impl VisitableMut for SelectStatement {
 
         self.fields.accept(visitor)?;
 
+        if let Some(into) = &mut self.into {
+            into.accept(visitor)?;
+        }
+
         self.from.accept(visitor)?;
 
         if let Some(condition) = &mut self.condition {
@@ -798,6 +884,16 @@ impl VisitableMut for SelectStatement {
     }
 }
 
+impl VisitableMut for IntoClause {
+    fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
+        if let Stop = visitor.pre_visit_into_clause(self)? {
+            return Ok(());
+        };
+
+        visitor.post_visit_into_clause(self)
+    }
+}
+
 impl VisitableMut for TimeZoneClause {
     fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
         if let Stop = visitor.pre_visit_timezone_clause(self)? {
@@ -1039,6 +1135,104 @@ impl VisitableMut for ShowTagValuesStatement {
     }
 }
 
+impl VisitableMut for ShowMeasurementCardinalityStatement {
+    fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
+        if let Stop = visitor.pre_visit_show_measurement_cardinality_statement(self)? {
+            return Ok(());
+        };
+
+        if let Some(on_clause) = &mut self.database {
+            on_clause.accept(visitor)?;
+        }
+
+        if let Some(from) = &mut self.from {
+            from.accept(visitor)?;
+        }
+
+        if let Some(condition) = &mut self.condition {
+            condition.accept(visitor)?;
+        }
+
+        visitor.post_visit_show_measurement_cardinality_statement(self)
+    }
+}
+
+impl VisitableMut for ShowSeriesStatement {
+    fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
+        if let Stop = visitor.pre_visit_show_series_statement(self)? {
+            return Ok(());
+        };
+
+        if let Some(on_clause) = &mut self.database {
+            on_clause.accept(visitor)?;
+        }
+
+        if let Some(from) = &mut self.from {
+            from.accept(visitor)?;
+        }
+
+        if let Some(condition) = &mut self.condition {
+            condition.accept(visitor)?;
+        }
+
+        if let Some(limit) = &mut self.limit {
+            limit.accept(visitor)?;
+        }
+
+        if let Some(offset) = &mut self.offset {
+            offset.accept(visitor)?;
+        }
+
+        visitor.post_visit_show_series_statement(self)
+    }
+}
+
+impl VisitableMut for ShowSeriesCardinalityStatement {
+    fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
+        if let Stop = visitor.pre_visit_show_series_cardinality_statement(self)? {
+            return Ok(());
+        };
+
+        if let Some(on_clause) = &mut self.database {
+            on_clause.accept(visitor)?;
+        }
+
+        if let Some(from) = &mut self.from {
+            from.accept(visitor)?;
+        }
+
+        if let Some(condition) = &mut self.condition {
+            condition.accept(visitor)?;
+        }
+
+        visitor.post_visit_show_series_cardinality_statement(self)
+    }
+}
+
+impl VisitableMut for ShowTagValuesCardinalityStatement {
+    fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
+        if let Stop = visitor.pre_visit_show_tag_values_cardinality_statement(self)? {
+            return Ok(());
+        };
+
+        if let Some(on_clause) = &mut self.database {
+            on_clause.accept(visitor)?;
+        }
+
+        if let Some(from) = &mut self.from {
+            from.accept(visitor)?;
+        }
+
+        self.with_key.accept(visitor)?;
+
+        if let Some(condition) = &mut self.condition {
+            condition.accept(visitor)?;
+        }
+
+        visitor.post_visit_show_tag_values_cardinality_statement(self)
+    }
+}
+
 impl VisitableMut for ShowFieldKeysStatement {
     fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
         if let Stop = visitor.pre_visit_show_field_keys_statement(self)? {
@@ -1299,11 +1493,15 @@ mod test {
     use crate::show::{OnClause, ShowDatabasesStatement};
     use crate::show_field_keys::ShowFieldKeysStatement;
     use crate::show_measurements::{
-        ExtendedOnClause, ShowMeasurementsStatement, WithMeasurementClause,
+        ExtendedOnClause, ShowMeasurementCardinalityStatement, ShowMeasurementsStatement,
+        WithMeasurementClause,
     };
     use crate::show_retention_policies::ShowRetentionPoliciesStatement;
+    use crate::show_series::{ShowSeriesCardinalityStatement, ShowSeriesStatement};
     use crate::show_tag_keys::ShowTagKeysStatement;
-    use crate::show_tag_values::{ShowTagValuesStatement, WithKeyClause};
+    use crate::show_tag_values::{
+        ShowTagValuesCardinalityStatement, ShowTagValuesStatement, WithKeyClause,
+    };
     use crate::simple_from_clause::{DeleteFromClause, ShowFromClause};
     use crate::statement::{Statement, statement};
 
@@ -1351,12 +1549,25 @@ mod test {
         trace_visit!(select_statement, SelectStatement);
         trace_visit!(show_databases_statement, ShowDatabasesStatement);
         trace_visit!(show_measurements_statement, ShowMeasurementsStatement);
+        trace_visit!(
+            show_measurement_cardinality_statement,
+            ShowMeasurementCardinalityStatement
+        );
         trace_visit!(
             show_retention_policies_statement,
             ShowRetentionPoliciesStatement
         );
+        trace_visit!(show_series_statement, ShowSeriesStatement);
+        trace_visit!(
+            show_series_cardinality_statement,
+            ShowSeriesCardinalityStatement
+        );
         trace_visit!(show_tag_keys_statement, ShowTagKeysStatement);
         trace_visit!(show_tag_values_statement, ShowTagValuesStatement);
+        trace_visit!(
+            show_tag_values_cardinality_statement,
+            ShowTagValuesCardinalityStatement
+        );
         trace_visit!(show_field_keys_statement, ShowFieldKeysStatement);
         trace_visit!(conditional_expression, ConditionalExpression);
         trace_visit!(expr, Expr);
@@ -1376,6 +1587,7 @@ mod test {
         trace_visit!(offset_clause, OffsetClause);
         trace_visit!(slimit_clause, SLimitClause);
         trace_visit!(soffset_clause, SOffsetClause);
+        trace_visit!(into_clause, IntoClause);
         trace_visit!(timezone_clause, TimeZoneClause);
         trace_visit!(extended_on_clause, ExtendedOnClause);
         trace_visit!(on_clause, OnClause);
@@ -1444,6 +1656,9 @@ mod test {
             TZ('Australia/Hobart')
         "#
         ));
+        insta::assert_yaml_snapshot!(visit_statement!(
+            r#"SELECT value INTO db.rp.:MEASUREMENT FROM temp"#
+        ));
     }
 
     #[test]
@@ -1495,6 +1710,35 @@ mod test {
         ));
     }
 
+    #[test]
+    fn test_show_measurement_cardinality_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!(
+            "SHOW MEASUREMENT EXACT CARDINALITY ON telegraf FROM cpu WHERE host = \"west\""
+        ));
+    }
+
+    #[test]
+    fn test_show_series_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!("SHOW SERIES"));
+        insta::assert_yaml_snapshot!(visit_statement!(
+            "SHOW SERIES ON telegraf FROM cpu WHERE host = \"west\" LIMIT 5 OFFSET 10"
+        ));
+    }
+
+    #[test]
+    fn test_show_series_cardinality_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!(
+            "SHOW SERIES CARDINALITY ON telegraf FROM cpu WHERE host = \"west\""
+        ));
+    }
+
+    #[test]
+    fn test_show_tag_values_cardinality_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!(
+            "SHOW TAG VALUES CARDINALITY ON telegraf FROM cpu WITH KEY = host WHERE host = \"west\""
+        ));
+    }
+
     #[test]
     fn test_show_field_keys_statement() {
         insta::assert_yaml_snapshot!(visit_statement!("SHOW FIELD KEYS"));
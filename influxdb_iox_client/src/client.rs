@@ -1,3 +1,6 @@
+/// Client for the bulk ingest API
+pub mod bulk_ingest;
+
 /// Client for interacting with a remote catalog
 pub mod catalog;
 
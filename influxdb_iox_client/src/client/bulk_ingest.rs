@@ -0,0 +1,384 @@
+use std::collections::HashMap;
+
+use ::generated_types::{google::protobuf::Timestamp, influxdata::iox::Target};
+use client_util::connection::GrpcConnection;
+
+use self::generated_types::{bulk_ingest_service_client::BulkIngestServiceClient, *};
+use crate::connection::Connection;
+use crate::error::Error;
+
+#[cfg(feature = "bulk_ingest")]
+pub use ingest::*;
+
+/// Re-export generated_types
+pub mod generated_types {
+    pub use generated_types::influxdata::iox::bulk_ingest::v1::*;
+}
+
+/// A basic client for bulk ingesting Parquet files, bypassing the write path.
+///
+/// With the `bulk_ingest` feature enabled, `BulkIngester` produces, uploads
+/// and finalizes compliant Parquet files using this client.
+#[derive(Debug, Clone)]
+pub struct Client {
+    inner: BulkIngestServiceClient<GrpcConnection>,
+}
+
+impl Client {
+    /// Creates a new client with the provided connection
+    pub fn new(connection: Connection) -> Self {
+        Self {
+            inner: BulkIngestServiceClient::new(connection.into_grpc_connection()),
+        }
+    }
+
+    /// Generate the Parquet metadata and upload URL for a file of data for
+    /// `partition_key` of `table`, sorted by `file_sort_key`.
+    ///
+    /// `file_sort_key` lists the columns of the file ordered by the
+    /// cardinality of the full data set, as returned by
+    /// [`Client::upsert_sort_key`]. `data_created_at` orders data sets
+    /// imported into the same partition.
+    pub async fn new_parquet_metadata(
+        &mut self,
+        namespace: impl Into<Target> + Send,
+        table: impl Into<Target> + Send,
+        partition_key: impl Into<String> + Send,
+        file_sort_key: Vec<String>,
+        data_created_at: Option<Timestamp>,
+    ) -> Result<NewParquetMetadataResponse, Error> {
+        let response = self
+            .inner
+            .new_parquet_metadata(NewParquetMetadataRequest {
+                namespace_target: namespace.into().into(),
+                table_target: table.into().into(),
+                partition_key: partition_key.into(),
+                file_sort_key,
+                data_created_at,
+            })
+            .await?;
+
+        Ok(response.into_inner())
+    }
+
+    /// Merge `columns`, ordered by cardinality from low to high, into the
+    /// catalog sort key of `partition_key` of `table`, returning the full
+    /// sort key that imported files must be sorted by.
+    pub async fn upsert_sort_key(
+        &mut self,
+        namespace: impl Into<Target> + Send,
+        table: impl Into<Target> + Send,
+        partition_key: impl Into<String> + Send,
+        columns: Vec<String>,
+    ) -> Result<Vec<String>, Error> {
+        let response = self
+            .inner
+            .upsert_sort_key(UpsertSortKeyRequest {
+                namespace_target: namespace.into().into(),
+                table_target: table.into().into(),
+                partition_key: partition_key.into(),
+                columns,
+            })
+            .await?;
+
+        Ok(response.into_inner().sort_key)
+    }
+
+    /// Validate and add an uploaded Parquet file, carrying `metadata` as
+    /// returned by [`Client::new_parquet_metadata`], to the catalog.
+    pub async fn finalize_parquet(
+        &mut self,
+        metadata: HashMap<String, String>,
+    ) -> Result<(), Error> {
+        self.inner
+            .finalize_parquet(FinalizeParquetRequest { metadata })
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "bulk_ingest")]
+mod ingest {
+    use std::{collections::HashMap, sync::Arc};
+
+    use ::generated_types::{google::protobuf::Timestamp, influxdata::iox::Target};
+    use arrow::{
+        compute::{SortColumn, concat_batches, lexsort_to_indices, take_record_batch},
+        datatypes::SchemaRef,
+        error::ArrowError,
+        record_batch::RecordBatch,
+    };
+    use datafusion::execution::memory_pool::UnboundedMemoryPool;
+    use parquet_file::serialize::{CodecError, to_parquet};
+    use schema::sort::sort_key_from_cardinalities;
+    use thiserror::Error;
+
+    use super::Client;
+
+    /// Errors bulk ingesting a partition with [`BulkIngester`].
+    #[derive(Debug, Error)]
+    pub enum BulkIngestError {
+        /// A bulk ingest API request failed.
+        #[error(transparent)]
+        Client(#[from] crate::error::Error),
+
+        /// The record batches could not be combined or sorted.
+        #[error("error sorting record batches: {0}")]
+        Sort(#[from] ArrowError),
+
+        /// The sorted data could not be encoded as Parquet.
+        #[error("error encoding parquet file: {0}")]
+        Encode(#[from] CodecError),
+
+        /// The Parquet file could not be uploaded to object storage.
+        #[error("error uploading parquet file: {0}")]
+        Upload(#[from] reqwest::Error),
+    }
+
+    /// Returns the columns of `cardinalities` ordered from low to high
+    /// cardinality, followed by the time column, for use as the `columns` of
+    /// [`Client::upsert_sort_key`].
+    ///
+    /// `cardinalities` maps the primary key (tag) columns of a table to the
+    /// number of distinct values observed in all the data to be imported for
+    /// a partition, not just the data of a single file.
+    pub fn sort_key_columns(cardinalities: &HashMap<String, usize>) -> Vec<String> {
+        (&sort_key_from_cardinalities(cardinalities)).into()
+    }
+
+    /// Bulk ingests partitions of data as Parquet files, bypassing the write
+    /// path.
+    ///
+    /// For each partition, [`BulkIngester::ingest_partition`]:
+    ///
+    /// 1. Upserts the catalog sort key of the partition with the
+    ///    cardinality-ordered [`sort_key_columns`].
+    /// 2. Requests the signed Parquet metadata and upload URL for a file
+    ///    sorted by the catalog sort key.
+    /// 3. Sorts the data and encodes it as Parquet carrying the metadata.
+    /// 4. Uploads the file, and finalizes it to add it to the catalog.
+    #[derive(Debug)]
+    pub struct BulkIngester {
+        client: Client,
+        http: reqwest::Client,
+        data_created_at: Option<Timestamp>,
+    }
+
+    impl BulkIngester {
+        /// Create a [`BulkIngester`] making API requests with `client`.
+        pub fn new(client: Client) -> Self {
+            Self {
+                client,
+                http: reqwest::Client::new(),
+                data_created_at: None,
+            }
+        }
+
+        /// Upload files using `http` rather than a default HTTP client.
+        pub fn with_http_client(self, http: reqwest::Client) -> Self {
+            Self { http, ..self }
+        }
+
+        /// Record the imported data set as created at `data_created_at`,
+        /// ordering it relative to other data in the same partitions.
+        pub fn with_data_created_at(self, data_created_at: Timestamp) -> Self {
+            Self {
+                data_created_at: Some(data_created_at),
+                ..self
+            }
+        }
+
+        /// Ingest `batches` into `partition_key` of `table` as a single
+        /// Parquet file.
+        ///
+        /// `cardinalities` maps the tag columns of the table to their
+        /// number of distinct values in the full data set being imported,
+        /// as described by [`sort_key_columns`]. The `batches` must share a
+        /// schema, and contain at least one row.
+        pub async fn ingest_partition(
+            &mut self,
+            namespace: impl Into<Target> + Send,
+            table: impl Into<Target> + Send,
+            partition_key: &str,
+            cardinalities: &HashMap<String, usize>,
+            batches: Vec<RecordBatch>,
+        ) -> Result<(), BulkIngestError> {
+            let (namespace, table) = (namespace.into(), table.into());
+            let schema = batches_schema(&batches)?;
+
+            let sort_key = self
+                .client
+                .upsert_sort_key(
+                    namespace.clone(),
+                    table.clone(),
+                    partition_key,
+                    sort_key_columns(cardinalities),
+                )
+                .await?;
+            let file_sort_key = file_sort_key(&sort_key, &schema);
+
+            let response = self
+                .client
+                .new_parquet_metadata(
+                    namespace,
+                    table,
+                    partition_key,
+                    file_sort_key.clone(),
+                    self.data_created_at,
+                )
+                .await?;
+
+            let batch = sort_batches(&schema, &batches, &file_sort_key)?;
+            let file = encode_parquet(batch, &response.metadata).await?;
+
+            self.http
+                .put(&response.upload_url)
+                .body(file)
+                .send()
+                .await?
+                .error_for_status()?;
+
+            self.client.finalize_parquet(response.metadata).await?;
+            Ok(())
+        }
+    }
+
+    /// Returns the schema of `batches`, or an error if they contain no rows,
+    /// before any request is made for the partition.
+    fn batches_schema(batches: &[RecordBatch]) -> Result<SchemaRef, BulkIngestError> {
+        match batches.first() {
+            Some(batch) if batches.iter().any(|b| b.num_rows() > 0) => Ok(batch.schema()),
+            _ => Err(CodecError::NoRows.into()),
+        }
+    }
+
+    /// Returns the columns of the catalog `sort_key` present in `schema`.
+    fn file_sort_key(sort_key: &[String], schema: &SchemaRef) -> Vec<String> {
+        sort_key
+            .iter()
+            .filter(|col| schema.index_of(col).is_ok())
+            .cloned()
+            .collect()
+    }
+
+    /// Combine `batches` into a single batch sorted by `sort_key`.
+    fn sort_batches(
+        schema: &SchemaRef,
+        batches: &[RecordBatch],
+        sort_key: &[String],
+    ) -> Result<RecordBatch, ArrowError> {
+        let batch = concat_batches(schema, batches)?;
+        let columns = sort_key
+            .iter()
+            .map(|col| {
+                Ok(SortColumn {
+                    values: Arc::clone(batch.column(schema.index_of(col)?)),
+                    options: None,
+                })
+            })
+            .collect::<Result<Vec<_>, ArrowError>>()?;
+
+        if columns.is_empty() {
+            return Ok(batch);
+        }
+        let indices = lexsort_to_indices(&columns, None)?;
+        take_record_batch(&batch, &indices)
+    }
+
+    /// Encode `batch` as a Parquet file carrying the key/value `metadata`.
+    async fn encode_parquet(
+        batch: RecordBatch,
+        metadata: &HashMap<String, String>,
+    ) -> Result<Vec<u8>, CodecError> {
+        let metadata = metadata
+            .iter()
+            .map(|(k, v)| parquet::file::metadata::KeyValue::new(k.clone(), v.clone()))
+            .collect();
+        let stream = datafusion_util::stream_from_batch(batch.schema(), batch);
+
+        let mut file = vec![];
+        to_parquet(
+            stream,
+            metadata,
+            Arc::new(UnboundedMemoryPool::default()),
+            &mut file,
+        )
+        .await?;
+        Ok(file)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use arrow::array::{ArrayRef, Int64Array, StringArray};
+        use arrow_util::assert_batches_eq;
+
+        use super::*;
+
+        #[test]
+        fn test_sort_key_columns() {
+            let cardinalities = HashMap::from([
+                ("host".to_string(), 100),
+                ("region".to_string(), 3),
+                ("env".to_string(), 3),
+            ]);
+            assert_eq!(
+                sort_key_columns(&cardinalities),
+                ["env", "region", "host", "time"]
+            );
+        }
+
+        #[test]
+        fn test_batches_schema() {
+            let batch = RecordBatch::try_from_iter([(
+                "time",
+                Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef,
+            )])
+            .unwrap();
+
+            assert_eq!(
+                batches_schema(&[batch.slice(0, 0), batch.clone()]).unwrap(),
+                batch.schema()
+            );
+            assert!(matches!(
+                batches_schema(&[]),
+                Err(BulkIngestError::Encode(CodecError::NoRows))
+            ));
+            assert!(matches!(
+                batches_schema(&[batch.slice(0, 0), batch.slice(1, 0)]),
+                Err(BulkIngestError::Encode(CodecError::NoRows))
+            ));
+        }
+
+        #[test]
+        fn test_sort_batches() {
+            let batch = |host: Vec<&str>, time: Vec<i64>| {
+                RecordBatch::try_from_iter([
+                    ("host", Arc::new(StringArray::from(host)) as ArrayRef),
+                    ("time", Arc::new(Int64Array::from(time))),
+                ])
+                .unwrap()
+            };
+            let batches = vec![batch(vec!["b", "a"], vec![2, 3]), batch(vec!["a"], vec![1])];
+            let schema = batches[0].schema();
+
+            let sort_key = ["region", "host", "time"].map(String::from);
+            let file_sort_key = file_sort_key(&sort_key, &schema);
+            assert_eq!(file_sort_key, ["host", "time"]);
+
+            let sorted = sort_batches(&schema, &batches, &file_sort_key).unwrap();
+            assert_batches_eq!(
+                [
+                    "+------+------+",
+                    "| host | time |",
+                    "+------+------+",
+                    "| a    | 1    |",
+                    "| a    | 3    |",
+                    "| b    | 2    |",
+                    "+------+------+",
+                ],
+                &[sorted]
+            );
+        }
+    }
+}
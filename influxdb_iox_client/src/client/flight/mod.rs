@@ -258,6 +258,7 @@ impl Client {
             flightsql_command: vec![],
             params: vec![],
             is_debug: false,
+            time_range: None,
         };
 
         self.do_get_with_read_info(request).await
@@ -277,6 +278,7 @@ impl Client {
             flightsql_command: vec![],
             params: vec![],
             is_debug: false,
+            time_range: None,
         };
 
         self.do_get_with_read_info(request).await
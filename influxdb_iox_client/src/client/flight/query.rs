@@ -142,6 +142,7 @@ impl QueryBuilder<'_, Query> {
                 })
                 .collect(),
             is_debug: false,
+            time_range: None,
         };
         self.client.do_get_with_read_info(request).await
     }
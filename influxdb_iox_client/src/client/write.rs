@@ -1,4 +1,4 @@
-use std::{fmt::Debug, num::NonZeroUsize, sync::Arc};
+use std::{fmt::Debug, io::Write, num::NonZeroUsize, sync::Arc};
 
 use client_util::{connection::HttpConnection, namespace_translation::split_namespace};
 use futures_util::{FutureExt, Stream, StreamExt, TryStreamExt, future::BoxFuture};
@@ -7,11 +7,45 @@ use crate::{
     connection::Connection,
     error::{Error, translate_response},
 };
-use reqwest::{Body, Method};
+use reqwest::{Body, Method, header::CONTENT_ENCODING};
 
 /// The default value for the maximum size of each request, in bytes
 pub const DEFAULT_MAX_REQUEST_PAYLOAD_SIZE_BYTES: Option<usize> = Some(1024 * 1024);
 
+/// The compression applied to the body of each write request, sent with the
+/// matching `Content-Encoding` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// Compress request bodies using gzip.
+    Gzip,
+    /// Compress request bodies using zstd.
+    Zstd,
+}
+
+impl Compression {
+    /// The value of the `Content-Encoding` header for this compression.
+    pub fn content_encoding(&self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+        }
+    }
+
+    /// Compress `data`.
+    fn compress(&self, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            // Level 0 is the zstd-provided default
+            Self::Zstd => zstd::encode_all(data, 0),
+        }
+    }
+}
+
 /// Name of a database.
 #[derive(Debug, Clone)]
 pub struct DatabaseName {
@@ -114,6 +148,9 @@ pub struct Client {
 
     /// Makes this many concurrent requests at a time. Defaults to 1
     max_concurrent_uploads: NonZeroUsize,
+
+    /// If `Some`, compresses the body of each request. Defaults to `None`
+    compression: Option<Compression>,
 }
 
 impl Client {
@@ -128,6 +165,7 @@ impl Client {
             inner,
             max_request_payload_size_bytes: DEFAULT_MAX_REQUEST_PAYLOAD_SIZE_BYTES,
             max_concurrent_uploads: NonZeroUsize::new(1).unwrap(),
+            compression: None,
         }
     }
 
@@ -154,10 +192,23 @@ impl Client {
         }
     }
 
+    /// Compress the body of each request using `compression`, or send
+    /// uncompressed line protocol if `None`. Defaults to `None`.
+    ///
+    /// The maximum request payload size applies to the line protocol before
+    /// compression.
+    pub fn with_compression(self, compression: Option<Compression>) -> Self {
+        Self {
+            compression,
+            ..self
+        }
+    }
+
     /// Write the [LineProtocol] formatted string in `lp_data` to
     /// namespace `database`.
     ///
-    /// Returns the number of bytes which were written to the namespace.
+    /// Returns the number of bytes which were written to the namespace,
+    /// before any compression.
     ///
     /// [LineProtocol]: https://docs.influxdata.com/influxdb/v2.0/reference/syntax/line-protocol/#data-types-and-format
     pub async fn write_lp(
@@ -175,7 +226,7 @@ impl Client {
     /// individual lines (points) do not cross these strings.
     ///
     /// Returns the number of bytes, in total, which were written to
-    /// the namespace.
+    /// the namespace, before any compression.
     ///
     /// [LineProtocol]: https://docs.influxdata.com/influxdb/v2.0/reference/syntax/line-protocol/#data-types-and-format
     pub async fn write_lp_stream(
@@ -185,6 +236,7 @@ impl Client {
     ) -> Result<usize, Error> {
         let max_concurrent_uploads: usize = self.max_concurrent_uploads.into();
         let max_request_payload_size_bytes = self.max_request_payload_size_bytes;
+        let compression = self.compression;
 
         let database = database.into();
         let (org_id, bucket_id) = database.get_org_bucket();
@@ -205,9 +257,11 @@ impl Client {
                 let bucket_id = bucket_id.to_string();
                 let inner = Arc::clone(&self.inner);
 
-                tokio::task::spawn(
-                    async move { inner.write_source(org_id, bucket_id, source).await },
-                )
+                tokio::task::spawn(async move {
+                    inner
+                        .write_source(org_id, bucket_id, source, compression)
+                        .await
+                })
             })
             // Do the uploads in parallel
             .buffered(max_concurrent_uploads)
@@ -226,8 +280,9 @@ impl Client {
 /// Something that knows how to send http data. Exists so it can be
 /// mocked out for testing
 trait RequestMaker: Debug + Send + Sync {
-    /// Write the body data to the specified org, bucket, and
-    /// returning the number of bytes written
+    /// Write the body data to the specified org, bucket, compressed
+    /// with `compression` if specified, and returning the number of
+    /// (uncompressed) bytes written
     ///
     /// (this is implemented manually to avoid `async_trait`)
     fn write_source(
@@ -235,6 +290,7 @@ trait RequestMaker: Debug + Send + Sync {
         org_id: String,
         bucket_id: String,
         body: String,
+        compression: Option<Compression>,
     ) -> BoxFuture<'_, Result<usize, Error>>;
 }
 
@@ -244,22 +300,30 @@ impl RequestMaker for HttpConnection {
         org_id: String,
         bucket_id: String,
         body: String,
+        compression: Option<Compression>,
     ) -> BoxFuture<'_, Result<usize, Error>> {
         let write_url = format!("{}api/v2/write", self.uri());
 
         async move {
-            let body: Body = body.into();
-
-            let data_len = body.as_bytes().map(|b| b.len()).unwrap_or(0);
+            let data_len = body.len();
 
-            let response = self
+            let mut request = self
                 .client()
                 .request(Method::POST, &write_url)
-                .query(&[("bucket", bucket_id), ("org", org_id)])
-                .body(body)
-                .send()
-                .await
-                .map_err(Error::client)?;
+                .query(&[("bucket", bucket_id), ("org", org_id)]);
+
+            let body: Body = match compression {
+                Some(compression) => {
+                    request = request.header(CONTENT_ENCODING, compression.content_encoding());
+                    compression
+                        .compress(body.as_bytes())
+                        .map_err(Error::client)?
+                        .into()
+                }
+                None => body.into(),
+            };
+
+            let response = request.body(body).send().await.map_err(Error::client)?;
 
             translate_response(response).await?;
 
@@ -483,6 +547,47 @@ mod tests {
         assert_eq!(num_bytes, 22);
     }
 
+    #[tokio::test]
+    async fn test_compression() {
+        let mock = Arc::new(MockRequestMaker::new());
+
+        let data = "m,t=foo f=4\nm,t=bar f=3";
+
+        let num_bytes = Client::new_with_maker(Arc::clone(&mock) as _)
+            .with_max_request_payload_size_bytes(Some(12))
+            .with_compression(Some(Compression::Zstd))
+            .write_lp("bucket_name", data)
+            .await
+            .unwrap();
+
+        // the line protocol is split before compression
+        assert_eq!(
+            mock.compression(),
+            vec![Some(Compression::Zstd), Some(Compression::Zstd)]
+        );
+        assert_eq!(mock.requests().len(), 2);
+        assert_eq!(num_bytes, 22);
+    }
+
+    #[test]
+    fn test_compress() {
+        let data = "m,t=foo f=4\n".repeat(100);
+
+        let gzip = Compression::Gzip.compress(data.as_bytes()).unwrap();
+        assert!(gzip.len() < data.len());
+        let mut decoded = String::new();
+        std::io::Read::read_to_string(&mut flate2::read::GzDecoder::new(&gzip[..]), &mut decoded)
+            .unwrap();
+        assert_eq!(decoded, data);
+
+        let zstd = Compression::Zstd.compress(data.as_bytes()).unwrap();
+        assert!(zstd.len() < data.len());
+        assert_eq!(zstd::decode_all(&zstd[..]).unwrap(), data.as_bytes());
+
+        assert_eq!(Compression::Gzip.content_encoding(), "gzip");
+        assert_eq!(Compression::Zstd.content_encoding(), "zstd");
+    }
+
     #[derive(Debug, Clone, PartialEq)]
     struct MockRequest {
         org_id: String,
@@ -493,12 +598,14 @@ mod tests {
     #[derive(Debug)]
     struct MockRequestMaker {
         requests: Mutex<Vec<MockRequest>>,
+        compression: Mutex<Vec<Option<Compression>>>,
     }
 
     impl MockRequestMaker {
         fn new() -> Self {
             Self {
                 requests: Mutex::new(vec![]),
+                compression: Mutex::new(vec![]),
             }
         }
 
@@ -506,6 +613,11 @@ mod tests {
         fn requests(&self) -> Vec<MockRequest> {
             self.requests.lock().unwrap().clone()
         }
+
+        /// get the compression of each request made using this mock
+        fn compression(&self) -> Vec<Option<Compression>> {
+            self.compression.lock().unwrap().clone()
+        }
     }
 
     impl RequestMaker for MockRequestMaker {
@@ -514,9 +626,11 @@ mod tests {
             org_id: String,
             bucket_id: String,
             body: String,
+            compression: Option<Compression>,
         ) -> BoxFuture<'_, Result<usize, Error>> {
             let sz = body.len();
 
+            self.compression.lock().unwrap().push(compression);
             self.requests.lock().unwrap().push(MockRequest {
                 org_id,
                 bucket_id,
@@ -1,21 +1,34 @@
 //! Output formatting utilities for Arrow record batches
 
-use std::{fmt::Display, str::FromStr};
+use std::{fmt::Display, io::Write, str::FromStr};
 
 use thiserror::Error;
 
 use arrow::{
-    self, csv::WriterBuilder, error::ArrowError, json::ArrayWriter, record_batch::RecordBatch,
+    self,
+    csv::{self, WriterBuilder},
+    error::ArrowError,
+    json::{ArrayWriter, LineDelimitedWriter},
+    record_batch::RecordBatch,
 };
+use parquet::{arrow::ArrowWriter, errors::ParquetError};
 
 /// Output formatting for InfluxQL.
 pub mod influxql;
+mod line_protocol;
+mod series;
+
+use series::SeriesWriter;
 
 /// Error type for results formatting
 #[derive(Debug, Error)]
 pub enum Error {
     /// Unknown formatting type
-    #[error("Unknown format type: {}. Expected one of 'pretty', 'csv' or 'json'", .0)]
+    #[error(
+        "Unknown format type: {}. Expected one of 'pretty', 'csv', 'json', 'ndjson', \
+         'json_series', 'line_protocol' or 'parquet'",
+        .0
+    )]
     Invalid(String),
 
     /// Error pretty printing
@@ -37,6 +50,31 @@ pub enum Error {
     /// Error converting JSON output to utf-8
     #[error("Error converting JSON output to UTF-8: {}", .0)]
     JsonUtf8(std::string::FromUtf8Error),
+
+    /// Error during newline delimited JSON conversion
+    #[error("Arrow ndjson printing error: {}", .0)]
+    NdjsonArrow(ArrowError),
+
+    /// Error during Parquet encoding
+    #[error("Parquet writing error: {}", .0)]
+    Parquet(ParquetError),
+
+    /// Error converting InfluxQL results to the 1.x JSON series format or
+    /// to line protocol
+    #[error("InfluxQL formatting error: {}", .0)]
+    InfluxQl(#[from] influxql::Error),
+
+    /// Error writing formatted output
+    #[error("Error writing output: {}", .0)]
+    Write(#[from] std::io::Error),
+
+    /// Error converting output to utf-8
+    #[error("Error converting output to UTF-8: {}", .0)]
+    Utf8(std::string::FromUtf8Error),
+
+    /// A binary format cannot be formatted as a string
+    #[error("Output format {} is binary and cannot be formatted as a string", .0)]
+    Binary(QueryOutputFormat),
 }
 type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -49,6 +87,14 @@ pub enum QueryOutputFormat {
     Csv,
     /// Arrow JSON format
     Json,
+    /// Newline delimited JSON, with one object per row
+    Ndjson,
+    /// InfluxDB 1.x JSON `series` format, for InfluxQL query results
+    JsonSeries,
+    /// Line protocol, re-creating the points of InfluxQL query results
+    LineProtocol,
+    /// Apache Parquet
+    Parquet,
 }
 
 impl Display for QueryOutputFormat {
@@ -57,6 +103,10 @@ impl Display for QueryOutputFormat {
             Self::Pretty => write!(f, "pretty"),
             Self::Csv => write!(f, "csv"),
             Self::Json => write!(f, "json"),
+            Self::Ndjson => write!(f, "ndjson"),
+            Self::JsonSeries => write!(f, "json_series"),
+            Self::LineProtocol => write!(f, "line_protocol"),
+            Self::Parquet => write!(f, "parquet"),
         }
     }
 }
@@ -75,6 +125,10 @@ impl FromStr for QueryOutputFormat {
             "pretty" => Ok(Self::Pretty),
             "csv" => Ok(Self::Csv),
             "json" => Ok(Self::Json),
+            "ndjson" => Ok(Self::Ndjson),
+            "json_series" => Ok(Self::JsonSeries),
+            "line_protocol" | "lp" => Ok(Self::LineProtocol),
+            "parquet" => Ok(Self::Parquet),
             _ => Err(Error::Invalid(s.to_string())),
         }
     }
@@ -87,8 +141,130 @@ impl QueryOutputFormat {
             Self::Pretty => "text/plain",
             Self::Csv => "text/csv",
             Self::Json => "application/json",
+            Self::Ndjson => "application/x-ndjson",
+            Self::JsonSeries => "application/json",
+            Self::LineProtocol => "text/plain; charset=utf-8",
+            Self::Parquet => "application/vnd.apache.parquet",
         }
     }
+
+    /// Returns a [`QueryOutputWriter`] writing batches to `w` in this format
+    /// as they are received, so large results need not be held in memory.
+    ///
+    /// The [`JsonSeries`](Self::JsonSeries) and
+    /// [`LineProtocol`](Self::LineProtocol) formats require the batches to be
+    /// InfluxQL query results, carrying the InfluxQL metadata in their
+    /// schema.
+    ///
+    /// The [`Pretty`](Self::Pretty) format must size its columns to fit all
+    /// the data, so buffers all batches until
+    /// [`QueryOutputWriter::finish`].
+    pub fn writer<W: Write + Send>(&self, w: W) -> QueryOutputWriter<W> {
+        let inner = match self {
+            Self::Pretty => Inner::Pretty(w, vec![]),
+            Self::Csv => Inner::Csv(WriterBuilder::new().with_header(true).build(w)),
+            Self::Json => Inner::Json(ArrayWriter::new(w)),
+            Self::Ndjson => Inner::Ndjson(LineDelimitedWriter::new(w)),
+            Self::JsonSeries => Inner::JsonSeries(SeriesWriter::new(w)),
+            Self::LineProtocol => Inner::LineProtocol(w),
+            Self::Parquet => Inner::Parquet(ParquetOutput::Pending(w)),
+        };
+        QueryOutputWriter { inner }
+    }
+}
+
+/// Writes [`RecordBatch`]es in a [`QueryOutputFormat`] as they are received.
+///
+/// Created by [`QueryOutputFormat::writer`]. The output is only complete once
+/// [`QueryOutputWriter::finish`] has been called.
+#[derive(Debug)]
+pub struct QueryOutputWriter<W: Write + Send> {
+    inner: Inner<W>,
+}
+
+#[derive(Debug)]
+enum Inner<W: Write + Send> {
+    Pretty(W, Vec<RecordBatch>),
+    Csv(csv::Writer<W>),
+    Json(ArrayWriter<W>),
+    Ndjson(LineDelimitedWriter<W>),
+    JsonSeries(SeriesWriter<W>),
+    LineProtocol(W),
+    Parquet(ParquetOutput<W>),
+}
+
+/// The Parquet writer needs the schema of the data, so is created when the
+/// first batch is written.
+#[derive(Debug)]
+enum ParquetOutput<W: Write + Send> {
+    Pending(W),
+    Writing(ArrowWriter<W>),
+    /// Creating the [`ArrowWriter`] failed.
+    Failed,
+}
+
+impl<W: Write + Send> ParquetOutput<W> {
+    fn write(&mut self, batch: &RecordBatch) -> Result<(), ParquetError> {
+        *self = match std::mem::replace(self, Self::Failed) {
+            Self::Pending(w) => Self::Writing(ArrowWriter::try_new(w, batch.schema(), None)?),
+            output => output,
+        };
+        match self {
+            Self::Writing(writer) => writer.write(batch),
+            Self::Pending(_) | Self::Failed => Err(Self::failed()),
+        }
+    }
+
+    fn failed() -> ParquetError {
+        ParquetError::General("error creating parquet writer".to_owned())
+    }
+}
+
+impl<W: Write + Send> QueryOutputWriter<W> {
+    /// Write the rows of `batch`.
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        match &mut self.inner {
+            Inner::Pretty(_, batches) => batches.push(batch.clone()),
+            Inner::Csv(writer) => writer.write(batch).map_err(Error::CsvArrow)?,
+            Inner::Json(writer) => writer.write(batch).map_err(Error::JsonArrow)?,
+            Inner::Ndjson(writer) => writer.write(batch).map_err(Error::NdjsonArrow)?,
+            Inner::JsonSeries(writer) => writer.write(batch)?,
+            Inner::LineProtocol(w) => line_protocol::write_line_protocol(w, batch)?,
+            Inner::Parquet(output) => output.write(batch).map_err(Error::Parquet)?,
+        }
+        Ok(())
+    }
+
+    /// Complete the output, returning the underlying writer.
+    ///
+    /// A Parquet output to which no batches were written is left empty, as
+    /// there is no schema to describe.
+    pub fn finish(self) -> Result<W> {
+        Ok(match self.inner {
+            Inner::Pretty(mut w, batches) => {
+                w.write_all(batches_to_pretty(&batches)?.as_bytes())?;
+                w
+            }
+            Inner::Csv(writer) => writer.into_inner(),
+            Inner::Json(mut writer) => {
+                writer.finish().map_err(Error::JsonArrow)?;
+                writer.into_inner()
+            }
+            Inner::Ndjson(mut writer) => {
+                writer.finish().map_err(Error::NdjsonArrow)?;
+                writer.into_inner()
+            }
+            Inner::JsonSeries(writer) => writer.finish()?,
+            Inner::LineProtocol(w) => w,
+            Inner::Parquet(ParquetOutput::Pending(w)) => w,
+            Inner::Parquet(ParquetOutput::Writing(writer)) => {
+                writer.into_inner().map_err(Error::Parquet)?
+            }
+            Inner::Parquet(ParquetOutput::Failed) => {
+                return Err(Error::Parquet(ParquetOutput::<W>::failed()));
+            }
+        })
+    }
 }
 
 impl QueryOutputFormat {
@@ -119,11 +295,23 @@ impl QueryOutputFormat {
     ///  {"location":"Boston","state":"MA","surface_degrees":50.2,"time":1568756160}
     /// ]
     /// ```
+    ///
+    /// The Ndjson, JsonSeries and LineProtocol formats are produced by
+    /// [`QueryOutputFormat::writer`]. Parquet is a binary format, and is
+    /// only available from [`QueryOutputFormat::writer`].
     pub fn format(&self, batches: &[RecordBatch]) -> Result<String> {
         match self {
             Self::Pretty => batches_to_pretty(batches),
             Self::Csv => batches_to_csv(batches),
             Self::Json => batches_to_json(batches),
+            Self::Ndjson | Self::JsonSeries | Self::LineProtocol => {
+                let mut writer = self.writer(vec![]);
+                for batch in batches {
+                    writer.write(batch)?;
+                }
+                String::from_utf8(writer.finish()?).map_err(Error::Utf8)
+            }
+            Self::Parquet => Err(Error::Binary(*self)),
         }
     }
 }
@@ -165,6 +353,11 @@ fn batches_to_json(batches: &[RecordBatch]) -> Result<String> {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{ArrayRef, Int64Array};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
     use super::*;
 
     #[test]
@@ -196,9 +389,27 @@ mod tests {
             QueryOutputFormat::Json
         );
 
+        assert_eq!(
+            QueryOutputFormat::from_str("ndjson").unwrap(),
+            QueryOutputFormat::Ndjson
+        );
+        assert_eq!(
+            QueryOutputFormat::from_str("json_series").unwrap(),
+            QueryOutputFormat::JsonSeries
+        );
+        assert_eq!(
+            QueryOutputFormat::from_str("LP").unwrap(),
+            QueryOutputFormat::LineProtocol
+        );
+        assert_eq!(
+            QueryOutputFormat::from_str("parquet").unwrap(),
+            QueryOutputFormat::Parquet
+        );
+
         assert_eq!(
             QueryOutputFormat::from_str("un").unwrap_err().to_string(),
-            "Unknown format type: un. Expected one of 'pretty', 'csv' or 'json'"
+            "Unknown format type: un. Expected one of 'pretty', 'csv', 'json', 'ndjson', \
+             'json_series', 'line_protocol' or 'parquet'"
         );
     }
 
@@ -218,5 +429,75 @@ mod tests {
             QueryOutputFormat::from_str(&QueryOutputFormat::Json.to_string()).unwrap(),
             QueryOutputFormat::Json
         );
+
+        for format in [
+            QueryOutputFormat::Ndjson,
+            QueryOutputFormat::JsonSeries,
+            QueryOutputFormat::LineProtocol,
+            QueryOutputFormat::Parquet,
+        ] {
+            assert_eq!(
+                QueryOutputFormat::from_str(&format.to_string()).unwrap(),
+                format
+            );
+        }
+    }
+
+    fn batches() -> Vec<RecordBatch> {
+        let batch = |vals: Vec<i64>| {
+            RecordBatch::try_from_iter([("val", Arc::new(Int64Array::from(vals)) as ArrayRef)])
+                .unwrap()
+        };
+        vec![batch(vec![1, 2]), batch(vec![3])]
+    }
+
+    #[test]
+    fn test_writer() {
+        let batches = batches();
+        for (format, want) in [
+            (QueryOutputFormat::Csv, "val\n1\n2\n3\n"),
+            (
+                QueryOutputFormat::Json,
+                r#"[{"val":1},{"val":2},{"val":3}]"#,
+            ),
+            (
+                QueryOutputFormat::Ndjson,
+                "{\"val\":1}\n{\"val\":2}\n{\"val\":3}\n",
+            ),
+        ] {
+            let mut writer = format.writer(vec![]);
+            for batch in &batches {
+                writer.write(batch).unwrap();
+            }
+            let got = String::from_utf8(writer.finish().unwrap()).unwrap();
+            assert_eq!(got, want, "{format}");
+            assert_eq!(format.format(&batches).unwrap(), want, "{format}");
+        }
+    }
+
+    #[test]
+    fn test_writer_parquet() {
+        let batches = batches();
+        let mut writer = QueryOutputFormat::Parquet.writer(vec![]);
+        for batch in &batches {
+            writer.write(batch).unwrap();
+        }
+        let file = bytes::Bytes::from(writer.finish().unwrap());
+
+        let got = ParquetRecordBatchReaderBuilder::try_new(file)
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            arrow::compute::concat_batches(&batches[0].schema(), &got).unwrap(),
+            arrow::compute::concat_batches(&batches[0].schema(), &batches).unwrap()
+        );
+
+        assert!(matches!(
+            QueryOutputFormat::Parquet.format(&batches),
+            Err(Error::Binary(QueryOutputFormat::Parquet))
+        ));
     }
 }
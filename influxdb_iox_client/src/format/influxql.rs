@@ -1,5 +1,6 @@
-use arrow::array::{Array, ArrayData, StringArray};
-use arrow::datatypes::DataType;
+use arrow::array::{Array, ArrayData, ArrayRef, AsArray, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Schema};
 use arrow::error::ArrowError;
 use arrow::record_batch::RecordBatch;
 use arrow::util::display::ArrayFormatter;
@@ -59,6 +60,22 @@ impl Options {
     }
 }
 
+/// Returns the [`InfluxQlMetadata`] of an InfluxQL query result `schema`.
+pub(crate) fn influxql_metadata(schema: &Schema) -> Result<InfluxQlMetadata> {
+    let md = schema
+        .metadata()
+        .get(schema::INFLUXQL_METADATA_KEY)
+        .ok_or(Error::MissingMetadata)?;
+
+    Ok(serde_json::from_str(md)?)
+}
+
+/// Cast the column `column` of an InfluxQL query result to a [`StringArray`].
+pub(crate) fn utf8(column: &ArrayRef) -> Result<StringArray> {
+    let column = cast(column, &DataType::Utf8).map_err(Error::Arrow)?;
+    Ok(column.as_string::<i32>().clone())
+}
+
 /// Write the record batches in a columnar format.
 pub fn write_columnar(mut w: impl Write, batches: &[RecordBatch], options: Options) -> Result<()> {
     let arrow_opts = arrow::util::display::FormatOptions::default().with_display_error(true);
@@ -66,12 +83,7 @@ pub fn write_columnar(mut w: impl Write, batches: &[RecordBatch], options: Optio
     let Some(schema) = batches.first().map(|b| b.schema()) else {
         return Ok(());
     };
-    let md = schema
-        .metadata()
-        .get(schema::INFLUXQL_METADATA_KEY)
-        .ok_or(Error::MissingMetadata)?;
-
-    let v: InfluxQlMetadata = serde_json::from_str(md)?;
+    let v = influxql_metadata(&schema)?;
 
     let measurement_idx = v.measurement_column_index as usize;
     let (tag_keys, tag_key_indexes): (Vec<_>, Vec<_>) = v
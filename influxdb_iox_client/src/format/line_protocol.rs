@@ -0,0 +1,285 @@
+//! Conversion of InfluxQL query results back to line protocol.
+
+use std::{io::Write, sync::Arc};
+
+use arrow::{
+    array::{Array, ArrayRef, AsArray, StringArray},
+    compute::cast,
+    datatypes::{DataType, Float64Type, Int64Type, TimeUnit, TimestampNanosecondType, UInt64Type},
+    record_batch::RecordBatch,
+};
+use influxdb_line_protocol::LineProtocolBuilder;
+use schema::{InfluxColumnType, TIME_COLUMN_NAME, get_influx_type};
+
+use super::influxql::{Error, influxql_metadata, utf8};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// A field value of a single row.
+enum Value<'a> {
+    F64(f64),
+    I64(i64),
+    U64(u64),
+    Bool(bool),
+    String(&'a str),
+}
+
+/// Add a field to a [`LineProtocolBuilder`] in any state accepting fields.
+macro_rules! add_field {
+    ($builder:expr, $name:expr, $value:expr) => {
+        match $value {
+            Value::F64(v) => $builder.field($name, v),
+            Value::I64(v) => $builder.field($name, v),
+            Value::U64(v) => $builder.field($name, v),
+            Value::Bool(v) => $builder.field($name, v),
+            Value::String(v) => $builder.field($name, v),
+        }
+    };
+}
+
+/// The columns of an InfluxQL query result, classified for conversion to line
+/// protocol.
+struct Columns {
+    measurement: StringArray,
+    tags: Vec<(String, StringArray)>,
+    fields: Vec<(String, ArrayRef)>,
+    time: Option<ArrayRef>,
+}
+
+impl Columns {
+    /// Classify the columns of `batch`.
+    ///
+    /// The measurement and group key tag columns are identified by the
+    /// InfluxQL metadata of the schema, other tag columns by their
+    /// [`InfluxColumnType`] metadata. All remaining columns other than the
+    /// time column are fields.
+    fn try_new(batch: &RecordBatch) -> Result<Self> {
+        let schema = batch.schema();
+        let md = influxql_metadata(&schema)?;
+        let measurement_idx = md.measurement_column_index as usize;
+
+        let measurement = utf8(batch.column(measurement_idx))?;
+        let mut tags = vec![];
+        let mut fields = vec![];
+        let mut time = None;
+
+        for (idx, field) in schema.fields().iter().enumerate() {
+            let column = batch.column(idx);
+            let tag_key = md
+                .tag_key_columns
+                .iter()
+                .find(|tk| tk.column_index as usize == idx)
+                .map(|tk| tk.tag_key.as_str());
+
+            if idx == measurement_idx {
+                continue;
+            } else if let Some(tag_key) = tag_key {
+                tags.push((tag_key.to_owned(), utf8(column)?));
+            } else if field.name() == TIME_COLUMN_NAME {
+                time = Some(
+                    cast(column, &DataType::Timestamp(TimeUnit::Nanosecond, None))
+                        .map_err(Error::Arrow)?,
+                );
+            } else if matches!(get_influx_type(field), Ok(InfluxColumnType::Tag)) {
+                tags.push((field.name().to_owned(), utf8(column)?));
+            } else {
+                let column = match column.data_type() {
+                    DataType::Float64
+                    | DataType::Int64
+                    | DataType::UInt64
+                    | DataType::Boolean
+                    | DataType::Utf8 => Arc::clone(column),
+                    _ => cast(column, &DataType::Utf8).map_err(Error::Arrow)?,
+                };
+                fields.push((field.name().to_owned(), column));
+            }
+        }
+
+        Ok(Self {
+            measurement,
+            tags,
+            fields,
+            time,
+        })
+    }
+}
+
+/// Returns the value of the field `column` at `row`, or [`None`] if it is
+/// null.
+fn field_value(column: &ArrayRef, row: usize) -> Option<Value<'_>> {
+    if column.is_null(row) {
+        return None;
+    }
+    Some(match column.data_type() {
+        DataType::Float64 => Value::F64(column.as_primitive::<Float64Type>().value(row)),
+        DataType::Int64 => Value::I64(column.as_primitive::<Int64Type>().value(row)),
+        DataType::UInt64 => Value::U64(column.as_primitive::<UInt64Type>().value(row)),
+        DataType::Boolean => Value::Bool(column.as_boolean().value(row)),
+        _ => Value::String(column.as_string::<i32>().value(row)),
+    })
+}
+
+/// Write the rows of the InfluxQL query result `batch` as line protocol.
+///
+/// Each row is written as a point of the measurement named by its
+/// `iox::measurement` column, with its non-null, non-empty tag values and
+/// non-null field values. Rows without a measurement or any field values are
+/// skipped, as they cannot be represented in line protocol.
+pub(crate) fn write_line_protocol(mut w: impl Write, batch: &RecordBatch) -> Result<()> {
+    let columns = Columns::try_new(batch)?;
+    let time = columns
+        .time
+        .as_ref()
+        .map(|t| t.as_primitive::<TimestampNanosecondType>());
+
+    let mut buf = vec![];
+    for row in 0..batch.num_rows() {
+        if columns.measurement.is_null(row) {
+            continue;
+        }
+
+        let mut fields = columns
+            .fields
+            .iter()
+            .filter_map(|(name, column)| Some((name, field_value(column, row)?)));
+        let Some((name, value)) = fields.next() else {
+            continue;
+        };
+
+        let mut builder =
+            LineProtocolBuilder::new_with(&mut buf).measurement(columns.measurement.value(row));
+        for (key, values) in &columns.tags {
+            if values.is_valid(row) && !values.value(row).is_empty() {
+                builder = builder.tag(key, values.value(row));
+            }
+        }
+
+        let mut builder = add_field!(builder, name, value);
+        for (name, value) in fields {
+            builder = add_field!(builder, name, value);
+        }
+
+        match time {
+            Some(time) if time.is_valid(row) => builder.timestamp(time.value(row)).close_line(),
+            _ => builder.close_line(),
+        };
+    }
+
+    w.write_all(&buf)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use arrow::{
+        array::{BooleanArray, Float64Array, Int64Array, TimestampNanosecondArray},
+        datatypes::{Field, Schema},
+    };
+    use generated_types::influxdata::iox::querier::v1::{
+        InfluxQlMetadata, influx_ql_metadata::TagKeyColumn,
+    };
+    use schema::{INFLUXQL_METADATA_KEY, TIME_DATA_TIMEZONE, TIME_DATA_TYPE};
+
+    use super::*;
+
+    #[test]
+    fn test_write_line_protocol() {
+        let md = InfluxQlMetadata {
+            measurement_column_index: 0,
+            tag_key_columns: vec![TagKeyColumn {
+                tag_key: "cpu".to_owned(),
+                column_index: 2,
+                is_projected: false,
+            }],
+        };
+        let schema = Arc::new(Schema::new_with_metadata(
+            vec![
+                Field::new("iox::measurement", DataType::Utf8, false),
+                Field::new("time", TIME_DATA_TYPE(), false),
+                Field::new("cpu", DataType::Utf8, true),
+                Field::new("host", DataType::Utf8, true).with_metadata(HashMap::from([(
+                    "iox::column::type".to_owned(),
+                    "iox::column_type::tag".to_owned(),
+                )])),
+                Field::new("usage idle", DataType::Float64, true),
+                Field::new("free", DataType::Int64, true),
+                Field::new("up", DataType::Boolean, true),
+                Field::new("msg", DataType::Utf8, true),
+            ],
+            HashMap::from([(
+                INFLUXQL_METADATA_KEY.to_owned(),
+                serde_json::to_string(&md).unwrap(),
+            )]),
+        ));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                // The last row without a measurement is skipped
+                Arc::new(StringArray::from(vec![
+                    Some("cpu"),
+                    Some("cpu"),
+                    Some("disk"),
+                    Some("disk"),
+                    None,
+                ])),
+                Arc::new(
+                    TimestampNanosecondArray::from(vec![1, 2, 3, 4, 5])
+                        .with_timezone_opt(TIME_DATA_TIMEZONE()),
+                ),
+                Arc::new(StringArray::from(vec![
+                    Some("cpu0"),
+                    Some("cpu1"),
+                    None,
+                    None,
+                    None,
+                ])),
+                Arc::new(StringArray::from(vec![
+                    Some("a"),
+                    Some(""),
+                    Some("b"),
+                    None,
+                    None,
+                ])),
+                Arc::new(Float64Array::from(vec![
+                    Some(99.5),
+                    Some(1.0),
+                    None,
+                    None,
+                    Some(5.0),
+                ])),
+                Arc::new(Int64Array::from(vec![None, None, Some(2133), None, None])),
+                Arc::new(BooleanArray::from(vec![Some(true), None, None, None, None])),
+                Arc::new(StringArray::from(vec![
+                    Some("ok, \"fine\""),
+                    None,
+                    None,
+                    None,
+                    None,
+                ])),
+            ],
+        )
+        .unwrap();
+
+        let mut lp = vec![];
+        write_line_protocol(&mut lp, &batch).unwrap();
+        assert_eq!(
+            String::from_utf8(lp).unwrap(),
+            "cpu,cpu=cpu0,host=a usage\\ idle=99.5,up=true,msg=\"ok, \\\"fine\\\"\" 1\n\
+             cpu,cpu=cpu1 usage\\ idle=1 2\n\
+             disk,host=b free=2133i 3\n"
+        );
+    }
+
+    #[test]
+    fn test_missing_metadata() {
+        let batch = RecordBatch::try_from_iter([(
+            "val",
+            Arc::new(Float64Array::from(vec![1.0])) as ArrayRef,
+        )])
+        .unwrap();
+        let err = write_line_protocol(vec![], &batch).unwrap_err();
+        assert!(matches!(err, Error::MissingMetadata));
+    }
+}
@@ -0,0 +1,386 @@
+//! Streaming output of InfluxQL query results in the InfluxDB 1.x JSON
+//! `series` format.
+
+use std::{io::Write, sync::Arc};
+
+use arrow::{
+    array::{Array, ArrayRef, AsArray, StringArray},
+    compute::cast,
+    datatypes::{DataType, Float64Type, Int64Type, TimeUnit, TimestampNanosecondType, UInt64Type},
+    record_batch::RecordBatch,
+};
+use schema::TIME_COLUMN_NAME;
+use serde_json::{Number, Value};
+
+use super::influxql::{Error, influxql_metadata, utf8};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// The `strftime` format of the RFC 3339 timestamps of the `time` column.
+const TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.fZ";
+
+/// The layout of the InfluxQL query result, derived from the schema of the
+/// first batch written.
+#[derive(Debug)]
+struct Layout {
+    measurement_idx: usize,
+    /// The group key tags, as `(tag_key, column_index)`.
+    tags: Vec<(String, usize)>,
+    /// The indexes of the columns output as series values, excluding the
+    /// measurement column and any tag columns that only appear in the
+    /// `GROUP BY` clause.
+    col_indexes: Vec<usize>,
+    /// The names of the `col_indexes` columns.
+    col_names: Vec<String>,
+}
+
+/// Writes InfluxQL query results in the InfluxDB 1.x JSON `series` format:
+///
+/// ```text
+/// {"results":[{"statement_id":0,"series":[
+///   {"name":"cpu","tags":{"host":"a"},"columns":["time","usage"],"values":[["2019-01-01T00:00:00Z",99.5]]}
+/// ]}]}
+/// ```
+///
+/// A new series is started whenever the measurement or the group key tag
+/// values change between rows, including across batches, so rows are written
+/// as they are received.
+#[derive(Debug)]
+pub(crate) struct SeriesWriter<W> {
+    w: W,
+    layout: Option<Layout>,
+    /// The measurement and group key tag values of the series being written,
+    /// if any.
+    series: Option<(String, Vec<String>)>,
+}
+
+impl<W: Write> SeriesWriter<W> {
+    pub(crate) fn new(w: W) -> Self {
+        Self {
+            w,
+            layout: None,
+            series: None,
+        }
+    }
+
+    /// Write the rows of `batch`.
+    pub(crate) fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        let layout = match &mut self.layout {
+            Some(layout) => layout,
+            layout @ None => layout.insert(Layout::try_new(batch)?),
+        };
+
+        let measurement = utf8(batch.column(layout.measurement_idx))?;
+        let tag_values = layout
+            .tags
+            .iter()
+            .map(|(_, idx)| utf8(batch.column(*idx)))
+            .collect::<Result<Vec<_>>>()?;
+        let columns = layout
+            .col_indexes
+            .iter()
+            .zip(&layout.col_names)
+            .map(|(idx, name)| Column::try_new(batch.column(*idx), name))
+            .collect::<Result<Vec<_>>>()?;
+
+        for row in 0..batch.num_rows() {
+            // Rows without a measurement are not part of any series.
+            if measurement.is_null(row) {
+                continue;
+            }
+            let m = measurement.value(row);
+            let tags = tag_values.iter().map(|col| str_value(col, row));
+
+            match &self.series {
+                Some((curr_m, curr_tags))
+                    if curr_m == m && tags.clone().eq(curr_tags.iter().map(String::as_str)) =>
+                {
+                    self.w.write_all(b",")?;
+                }
+                series => {
+                    let tags = tags.map(str::to_owned).collect::<Vec<_>>();
+                    write_series_header(&mut self.w, series.is_some(), layout, m, &tags)?;
+                    self.series = Some((m.to_owned(), tags));
+                }
+            }
+
+            let values = columns.iter().map(|c| c.value(row)).collect::<Vec<_>>();
+            serde_json::to_writer(&mut self.w, &values).map_err(std::io::Error::from)?;
+        }
+
+        Ok(())
+    }
+
+    /// Complete the output, returning the underlying writer.
+    pub(crate) fn finish(mut self) -> Result<W> {
+        if self.series.is_some() {
+            self.w.write_all(b"]}]}]}")?;
+        } else {
+            self.w.write_all(br#"{"results":[{"statement_id":0}]}"#)?;
+        }
+        Ok(self.w)
+    }
+}
+
+/// Close the current series, if `in_series`, and write the header of a new
+/// series of `measurement` with the group key `tags` values.
+fn write_series_header(
+    mut w: impl Write,
+    in_series: bool,
+    layout: &Layout,
+    measurement: &str,
+    tags: &[String],
+) -> Result<()> {
+    if in_series {
+        w.write_all(b"]},")?;
+    } else {
+        w.write_all(br#"{"results":[{"statement_id":0,"series":["#)?;
+    }
+
+    let mut header = serde_json::Map::new();
+    header.insert("name".to_owned(), measurement.into());
+    if !layout.tags.is_empty() {
+        let tags = layout
+            .tags
+            .iter()
+            .map(|(key, _)| key.clone())
+            .zip(tags.iter().map(|v| Value::from(v.as_str())))
+            .collect();
+        header.insert("tags".to_owned(), Value::Object(tags));
+    }
+    header.insert("columns".to_owned(), layout.col_names.clone().into());
+
+    // Write the header object without its closing brace, to be followed by
+    // the streamed values.
+    let header = Value::Object(header).to_string();
+    w.write_all(&header.as_bytes()[..header.len() - 1])?;
+    w.write_all(br#","values":["#)?;
+
+    Ok(())
+}
+
+impl Layout {
+    fn try_new(batch: &RecordBatch) -> Result<Self> {
+        let schema = batch.schema();
+        let md = influxql_metadata(&schema)?;
+        let measurement_idx = md.measurement_column_index as usize;
+
+        let tags = md
+            .tag_key_columns
+            .iter()
+            .map(|tk| (tk.tag_key.clone(), tk.column_index as usize))
+            .collect();
+        let col_indexes = (0..schema.fields().len())
+            .filter(|i| {
+                !md.tag_key_columns
+                    .iter()
+                    .any(|tk| tk.column_index as usize == *i && !tk.is_projected)
+                    && measurement_idx != *i
+            })
+            .collect::<Vec<_>>();
+        let col_names = col_indexes
+            .iter()
+            .map(|idx| schema.field(*idx).name().clone())
+            .collect();
+
+        Ok(Self {
+            measurement_idx,
+            tags,
+            col_indexes,
+            col_names,
+        })
+    }
+}
+
+/// A column of series values.
+enum Column {
+    Float(ArrayRef),
+    Integer(ArrayRef),
+    Unsigned(ArrayRef),
+    Boolean(ArrayRef),
+    /// String values, including any other types cast to strings.
+    String(ArrayRef),
+    /// The `time` column, cast to nanosecond timestamps.
+    Time(ArrayRef),
+}
+
+impl Column {
+    fn try_new(column: &ArrayRef, name: &str) -> Result<Self> {
+        let column = Arc::clone(column);
+        Ok(match column.data_type() {
+            DataType::Timestamp(_, _) if name == TIME_COLUMN_NAME => Self::Time(
+                cast(&column, &DataType::Timestamp(TimeUnit::Nanosecond, None))
+                    .map_err(Error::Arrow)?,
+            ),
+            DataType::Float64 => Self::Float(column),
+            DataType::Int64 => Self::Integer(column),
+            DataType::UInt64 => Self::Unsigned(column),
+            DataType::Boolean => Self::Boolean(column),
+            DataType::Utf8 => Self::String(column),
+            _ => Self::String(cast(&column, &DataType::Utf8).map_err(Error::Arrow)?),
+        })
+    }
+
+    /// Returns the JSON value at `row`, which is null for null values and
+    /// for non-finite floats, which cannot be represented in JSON.
+    fn value(&self, row: usize) -> Value {
+        let (Self::Float(column)
+        | Self::Integer(column)
+        | Self::Unsigned(column)
+        | Self::Boolean(column)
+        | Self::String(column)
+        | Self::Time(column)) = self;
+        if column.is_null(row) {
+            return Value::Null;
+        }
+
+        match self {
+            Self::Float(c) => Number::from_f64(c.as_primitive::<Float64Type>().value(row))
+                .map_or(Value::Null, Value::Number),
+            Self::Integer(c) => c.as_primitive::<Int64Type>().value(row).into(),
+            Self::Unsigned(c) => c.as_primitive::<UInt64Type>().value(row).into(),
+            Self::Boolean(c) => c.as_boolean().value(row).into(),
+            Self::String(c) => c.as_string::<i32>().value(row).into(),
+            // Output times in UTC in RFC 3339 format, as InfluxDB 1.x does.
+            Self::Time(c) => c
+                .as_primitive::<TimestampNanosecondType>()
+                .value_as_datetime(row)
+                .map_or(Value::Null, |t| t.format(TIME_FORMAT).to_string().into()),
+        }
+    }
+}
+
+/// Returns the value of `column` at `row`, or the empty string if it is null.
+fn str_value(column: &StringArray, row: usize) -> &str {
+    if column.is_null(row) {
+        ""
+    } else {
+        column.value(row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use arrow::{
+        array::{Float64Array, Int64Array, TimestampNanosecondArray},
+        datatypes::{Field, Schema},
+    };
+    use generated_types::influxdata::iox::querier::v1::{
+        InfluxQlMetadata, influx_ql_metadata::TagKeyColumn,
+    };
+    use schema::{INFLUXQL_METADATA_KEY, TIME_DATA_TIMEZONE, TIME_DATA_TYPE};
+    use serde_json::json;
+
+    use super::*;
+
+    fn batch(
+        measurement: &[Option<&str>],
+        time: &[i64],
+        cpu: &[Option<&str>],
+        usage: &[f64],
+        free: &[Option<i64>],
+    ) -> RecordBatch {
+        let md = InfluxQlMetadata {
+            measurement_column_index: 0,
+            tag_key_columns: vec![TagKeyColumn {
+                tag_key: "cpu".to_owned(),
+                column_index: 2,
+                is_projected: false,
+            }],
+        };
+        let schema = Arc::new(Schema::new_with_metadata(
+            vec![
+                Field::new("iox::measurement", DataType::Utf8, true),
+                Field::new("time", TIME_DATA_TYPE(), false),
+                Field::new("cpu", DataType::Utf8, true),
+                Field::new("usage_idle", DataType::Float64, true),
+                Field::new("free", DataType::Int64, true),
+            ],
+            HashMap::from([(
+                INFLUXQL_METADATA_KEY.to_owned(),
+                serde_json::to_string(&md).unwrap(),
+            )]),
+        ));
+        RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(measurement.to_vec())),
+                Arc::new(
+                    TimestampNanosecondArray::from(time.to_vec())
+                        .with_timezone_opt(TIME_DATA_TIMEZONE()),
+                ),
+                Arc::new(StringArray::from(cpu.to_vec())),
+                Arc::new(Float64Array::from(usage.to_vec())),
+                Arc::new(Int64Array::from(free.to_vec())),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_series_writer() {
+        let mut writer = SeriesWriter::new(vec![]);
+        writer
+            .write(&batch(
+                &[Some("cpu"), Some("cpu")],
+                &[1157082300000000000, 1157082310500000000],
+                &[Some("cpu0"), Some("cpu0")],
+                &[99.1, f64::NAN],
+                &[None, None],
+            ))
+            .unwrap();
+        // The series of cpu0 continues across batches, and the row without a
+        // measurement is skipped.
+        writer
+            .write(&batch(
+                &[Some("cpu"), Some("cpu"), None, Some("disk")],
+                &[1157082320000000000, 1157082300000000000, 2, 1],
+                &[Some("cpu0"), Some("cpu1"), Some("cpu2"), None],
+                &[99.8, 99.2, 1.0, 0.0],
+                &[None, None, None, Some(2133)],
+            ))
+            .unwrap();
+        let out = writer.finish().unwrap();
+
+        let got: Value = serde_json::from_slice(&out).unwrap();
+        let columns = json!(["time", "usage_idle", "free"]);
+        assert_eq!(
+            got,
+            json!({"results": [{"statement_id": 0, "series": [
+                {
+                    "name": "cpu",
+                    "tags": {"cpu": "cpu0"},
+                    "columns": columns,
+                    "values": [
+                        ["2006-09-01T03:45:00Z", 99.1, null],
+                        ["2006-09-01T03:45:10.500Z", null, null],
+                        ["2006-09-01T03:45:20Z", 99.8, null],
+                    ],
+                },
+                {
+                    "name": "cpu",
+                    "tags": {"cpu": "cpu1"},
+                    "columns": columns,
+                    "values": [["2006-09-01T03:45:00Z", 99.2, null]],
+                },
+                {
+                    "name": "disk",
+                    "tags": {"cpu": ""},
+                    "columns": columns,
+                    "values": [["1970-01-01T00:00:00.000000001Z", 0.0, 2133]],
+                },
+            ]}]})
+        );
+    }
+
+    #[test]
+    fn test_series_writer_empty() {
+        let out = SeriesWriter::new(vec![]).finish().unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            r#"{"results":[{"statement_id":0}]}"#
+        );
+    }
+}
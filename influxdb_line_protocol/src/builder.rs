@@ -2,6 +2,7 @@
 //!
 //! [line protocol]: https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol
 //! [special characters]: https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#special-characters
+use crate::v3::FIELD_FAMILY_DELIMITER;
 use bytes::BufMut;
 use std::{
     fmt::{self},
@@ -51,6 +52,10 @@ pub struct AfterTimestamp;
 /// This builder does not check for semantic errors. In particular, it does not check for duplicate tag and field
 /// names, nor it does enforce [naming restrictions] on keys.
 ///
+/// The one exception is [`LineProtocolBuilder::qualified_field`], which panics
+/// rather than write a field family containing the `::` delimiter, as that would
+/// be read back as a different field family and key.
+///
 /// Attempts to consume the line protocol before closing a line yield
 /// compile-time errors:
 ///
@@ -172,7 +177,38 @@ where
     where
         F: FieldValue,
     {
-        self.write(format_args!(" {}", format_field(field_key, &field_value)))
+        self.write(format_args!(
+            " {}",
+            format_field(None, field_key, &field_value)
+        ))
+    }
+
+    /// Add a [line protocol v3] field qualified with a field family, written
+    /// as `family::key=value`.
+    ///
+    /// Field families and keys are escaped like [`Self::field`] keys.
+    ///
+    /// This function is called for the first field only. It returns a new builder whose type no longer allows adding tags.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `field_family` contains the `::` delimiter, which cannot be
+    /// escaped.
+    ///
+    /// [line protocol v3]: crate::v3
+    pub fn qualified_field<F>(
+        self,
+        field_family: &str,
+        field_key: &str,
+        field_value: F,
+    ) -> LineProtocolBuilder<B, AfterField>
+    where
+        F: FieldValue,
+    {
+        self.write(format_args!(
+            " {}",
+            format_field(Some(field_family), field_key, &field_value)
+        ))
     }
 }
 
@@ -184,7 +220,32 @@ where
     ///
     /// This function is called for the second and subsequent fields.
     pub fn field<F: FieldValue>(self, field_key: &str, field_value: F) -> Self {
-        self.write(format_args!(",{}", format_field(field_key, &field_value)))
+        self.write(format_args!(
+            ",{}",
+            format_field(None, field_key, &field_value)
+        ))
+    }
+
+    /// Add a [line protocol v3] field qualified with a field family.
+    ///
+    /// This function is called for the second and subsequent fields.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `field_family` contains the `::` delimiter, which cannot be
+    /// escaped.
+    ///
+    /// [line protocol v3]: crate::v3
+    pub fn qualified_field<F: FieldValue>(
+        self,
+        field_family: &str,
+        field_key: &str,
+        field_value: F,
+    ) -> Self {
+        self.write(format_args!(
+            ",{}",
+            format_field(Some(field_family), field_key, &field_value)
+        ))
     }
 
     /// Provide a timestamp.
@@ -260,13 +321,24 @@ impl<const N: usize> fmt::Display for Escaped<'_, N> {
     }
 }
 
-// This method is used by the two [`LineProtocolBuilder::field`] variants in order to render the
-// `key=value` encoding of a field.
-fn format_field<'a, F>(field_key: &'a str, field_value: &'a F) -> impl fmt::Display + 'a
+// This method is used by the [`LineProtocolBuilder::field`] and
+// [`LineProtocolBuilder::qualified_field`] variants in order to render the
+// `key=value` or `family::key=value` encoding of a field.
+fn format_field<'a, F>(
+    field_family: Option<&'a str>,
+    field_key: &'a str,
+    field_value: &'a F,
+) -> impl fmt::Display + 'a
 where
     F: FieldValue,
 {
+    assert!(
+        field_family.is_none_or(|family| !family.contains(FIELD_FAMILY_DELIMITER)),
+        "field family {field_family:?} contains the {FIELD_FAMILY_DELIMITER} delimiter"
+    );
+
     FormattedField {
+        field_family,
         field_key,
         field_value,
     }
@@ -276,6 +348,7 @@ struct FormattedField<'a, F>
 where
     F: FieldValue,
 {
+    field_family: Option<&'a str>,
     field_key: &'a str,
     field_value: &'a F,
 }
@@ -285,6 +358,13 @@ where
     F: FieldValue,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(field_family) = self.field_family {
+            write!(
+                f,
+                "{}{FIELD_FAMILY_DELIMITER}",
+                escape(field_family, COMMA_EQ_SPACE)
+            )?;
+        }
         write!(f, "{}=", escape(self.field_key, COMMA_EQ_SPACE))?;
         self.field_value.fmt(f)
     }
@@ -534,4 +614,47 @@ mod tests {
 
         assert_eq!(parsed_line.field_set, expected_fields)
     }
+
+    #[test]
+    #[should_panic(expected = "contains the :: delimiter")]
+    fn test_qualified_field_delimiter() {
+        LineProtocolBuilder::new()
+            .measurement("cpu")
+            .qualified_field("sensor::a", "temp", 21.5);
+    }
+
+    #[test]
+    fn test_qualified_field() {
+        let builder = LineProtocolBuilder::new()
+            .measurement("cpu")
+            .tag("host", "a")
+            .qualified_field("sensor", "temp", 21.5)
+            .field("load", 2_i64)
+            .qualified_field("my sensor", "hum,idity", 3_u64)
+            .close_line();
+
+        let lp = String::from_utf8(builder.build()).unwrap();
+        assert_eq!(
+            lp,
+            "cpu,host=a sensor::temp=21.5,load=2i,my\\ sensor::hum\\,idity=3u\n"
+        );
+
+        let parsed_lines = crate::v3::parse_lines(&lp)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(parsed_lines.len(), 1);
+        let names = parsed_lines[0]
+            .field_set
+            .iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                &crate::v3::FieldName::Qualified("sensor".into(), "temp".into()),
+                &crate::v3::FieldName::Unqualified("load".into()),
+                &crate::v3::FieldName::Qualified("my sensor".into(), "hum,idity".into()),
+            ]
+        );
+    }
 }
@@ -0,0 +1,133 @@
+//! Incremental splitting of line protocol received as a sequence of
+//! [`Bytes`] chunks, such as the frames of an HTTP request body.
+use bytes::{Bytes, BytesMut};
+
+use crate::LineScanner;
+
+/// Splits line protocol received in [`Bytes`] chunks into individual lines,
+/// following the same rules as [`split_lines`](crate::split_lines).
+///
+/// Lines contained within a single chunk are returned as zero-copy slices of
+/// that chunk. Only a line split across a chunk boundary is copied, into an
+/// internal buffer, until its end is received.
+///
+/// Lines are not validated as UTF-8, which is left to the caller so that an
+/// invalid line does not reject the rest of the input.
+///
+/// ```
+/// use bytes::Bytes;
+/// use influxdb_line_protocol::LineSplitter;
+///
+/// let mut splitter = LineSplitter::default();
+///
+/// let lines = splitter.push(Bytes::from_static(b"cpu val=1 1\ncpu va"));
+/// assert_eq!(lines, [Bytes::from_static(b"cpu val=1 1")]);
+///
+/// let lines = splitter.push(Bytes::from_static(b"l=2 2\ncpu val=3 3"));
+/// assert_eq!(lines, [Bytes::from_static(b"cpu val=2 2")]);
+///
+/// assert_eq!(splitter.finish(), Some(Bytes::from_static(b"cpu val=3 3")));
+/// ```
+#[derive(Debug, Default)]
+pub struct LineSplitter {
+    scanner: LineScanner,
+    /// The start of a line split across chunks, if any.
+    partial: BytesMut,
+}
+
+impl LineSplitter {
+    /// Split `chunk`, returning the lines it completes.
+    ///
+    /// Any trailing incomplete line is retained, and completed by a
+    /// subsequent call to [`LineSplitter::push`] or returned by
+    /// [`LineSplitter::finish`].
+    pub fn push(&mut self, chunk: Bytes) -> Vec<Bytes> {
+        let mut lines = Vec::new();
+        let mut start = 0;
+
+        for (idx, b) in chunk.iter().enumerate() {
+            // All line protocol delimiters are ASCII, which never occurs
+            // within a multi-byte UTF-8 sequence, so scanning bytes rather
+            // than chars finds the same line boundaries.
+            if !self.scanner.is_line_end(char::from(*b)) {
+                continue;
+            }
+
+            if self.partial.is_empty() {
+                lines.push(chunk.slice(start..idx));
+            } else {
+                self.partial.extend_from_slice(&chunk[start..idx]);
+                lines.push(self.partial.split().freeze());
+            }
+            start = idx + 1;
+        }
+
+        self.partial.extend_from_slice(&chunk[start..]);
+        lines
+    }
+
+    /// Returns the final line of the input, which is not terminated by a
+    /// newline, if any, and resets the splitter.
+    pub fn finish(&mut self) -> Option<Bytes> {
+        self.scanner = LineScanner::default();
+        let line = self.partial.split().freeze();
+        (!line.is_empty()).then_some(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::split_lines;
+
+    /// Split `input` into chunks at every possible pair of positions, and
+    /// assert the lines match [`split_lines`].
+    fn assert_split_matches(input: &str) {
+        let want = split_lines(input)
+            .filter(|l| !l.is_empty())
+            .map(|l| l.as_bytes().to_vec())
+            .collect::<Vec<_>>();
+
+        let input = Bytes::copy_from_slice(input.as_bytes());
+        for a in 0..=input.len() {
+            for b in a..=input.len() {
+                let mut splitter = LineSplitter::default();
+                let mut lines = [input.slice(..a), input.slice(a..b), input.slice(b..)]
+                    .into_iter()
+                    .flat_map(|chunk| splitter.push(chunk))
+                    .collect::<Vec<_>>();
+                lines.extend(splitter.finish());
+
+                let got = lines
+                    .into_iter()
+                    .filter(|l| !l.is_empty())
+                    .map(|l| l.to_vec())
+                    .collect::<Vec<_>>();
+                assert_eq!(got, want, "split at {a} and {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_matches_split_lines() {
+        assert_split_matches("cpu val=1 1\ncpu val=2 2\n");
+        assert_split_matches("cpu val=1 1\n\ncpu val=2 2");
+        assert_split_matches("cpu str=\"with\nnewline\" 1\ncpu val=2 2");
+        assert_split_matches("cpu,tag=a\\ b str=\"esc\\\"aped\n\",val=1 1\ncpu val=2 2");
+        assert_split_matches("# comment\ncpu,ünï=cödé,t=\\ü str=\"🦀\" 1\n");
+    }
+
+    #[test]
+    fn test_zero_copy() {
+        let chunk = Bytes::from_static(b"cpu val=1 1\ncpu val=2 2\ncpu");
+        let mut splitter = LineSplitter::default();
+        let lines = splitter.push(chunk.clone());
+        assert_eq!(lines.len(), 2);
+        for line in lines {
+            let range = chunk.as_ptr_range();
+            assert!(range.contains(&line.as_ptr()));
+        }
+        assert_eq!(splitter.finish(), Some(Bytes::from_static(b"cpu")));
+        assert_eq!(splitter.finish(), None);
+    }
+}
@@ -73,6 +73,8 @@
 
 pub mod builder;
 pub use builder::LineProtocolBuilder;
+pub mod chunked;
+pub use chunked::LineSplitter;
 pub mod v3;
 
 use fmt::Display;
@@ -507,60 +509,73 @@ pub fn parse_lines(input: &str) -> impl Iterator<Item = Result<ParsedLine<'_>>>
 /// we can be more sure of the compatibility of the Rust parser and
 /// the canonical Go parser.
 pub fn split_lines(input: &str) -> impl Iterator<Item = &str> {
-    // NB: This is ported as closely as possible from the original Go code:
-    let mut quoted = false;
-    let mut fields = false;
+    let mut scanner = LineScanner::default();
+    input.split(move |c| scanner.is_line_end(c))
+}
+
+/// The state of the [`split_lines`] scanner, which may be carried across
+/// chunks of input by [`LineSplitter`].
+///
+/// NB: This is ported as closely as possible from the original Go code.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct LineScanner {
+    quoted: bool,
+    fields: bool,
 
     // tracks how many '=' and commas we've seen
     // this duplicates some of the functionality in scanFields
-    let mut equals = 0;
-    let mut commas = 0;
+    equals: usize,
+    commas: usize,
 
-    let mut in_escape = false;
-    input.split(move |c| {
+    in_escape: bool,
+}
+
+impl LineScanner {
+    /// Returns true if `c` ends the line being scanned.
+    pub(crate) fn is_line_end(&mut self, c: char) -> bool {
         // skip past escaped characters
-        if in_escape {
-            in_escape = false;
+        if self.in_escape {
+            self.in_escape = false;
             return false;
         }
 
         if c == '\\' {
-            in_escape = true;
+            self.in_escape = true;
             return false;
         }
 
         if c == ' ' {
-            fields = true;
+            self.fields = true;
             return false;
         }
 
         // If we see a double quote, makes sure it is not escaped
-        if fields {
-            if !quoted && c == '=' {
-                equals += 1;
+        if self.fields {
+            if !self.quoted && c == '=' {
+                self.equals += 1;
                 return false;
-            } else if !quoted && c == ',' {
-                commas += 1;
+            } else if !self.quoted && c == ',' {
+                self.commas += 1;
                 return false;
-            } else if c == '"' && equals > commas {
-                quoted = !quoted;
+            } else if c == '"' && self.equals > self.commas {
+                self.quoted = !self.quoted;
                 return false;
             }
         }
 
-        if c == '\n' && !quoted {
+        if c == '\n' && !self.quoted {
             // reset all the state -- we found a line
-            quoted = false;
-            fields = false;
-            equals = 0;
-            commas = 0;
-            assert!(!in_escape);
-            in_escape = false;
+            self.quoted = false;
+            self.fields = false;
+            self.equals = 0;
+            self.commas = 0;
+            assert!(!self.in_escape);
+            self.in_escape = false;
             return true;
         }
 
         false
-    })
+    }
 }
 
 fn parse_line(i: &str) -> IResult<&str, ParsedLine<'_>> {
@@ -15,7 +15,9 @@ use snafu::whatever;
 use std::fmt;
 use std::fmt::{Display, Write};
 
-const FIELD_FAMILY_DELIMITER: &str = "::";
+/// The delimiter between the field family and field name of a
+/// [`FieldName::Qualified`] field.
+pub const FIELD_FAMILY_DELIMITER: &str = "::";
 
 #[derive(Debug, PartialOrd, PartialEq)]
 pub enum FieldName<'a> {
@@ -7,9 +7,15 @@ pub mod v2;
 pub mod multi_tenant;
 pub mod single_tenant;
 
+mod body;
+pub use body::*;
+
 mod params;
 pub use params::*;
 
+mod partial;
+pub use partial::*;
+
 pub mod mock {
     use async_trait::async_trait;
     use iox_http_util::Request;
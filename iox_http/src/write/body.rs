@@ -0,0 +1,502 @@
+//! Reading of (optionally compressed) write request bodies.
+//!
+//! Request bodies with a `Content-Encoding` of `gzip` or `zstd` are
+//! decompressed as they are received, so that a small compressed payload
+//! cannot expand beyond the configured size limit in memory.
+
+use std::io::Write;
+
+use http_body_util::BodyExt;
+use hyper::{
+    HeaderMap, StatusCode,
+    body::Bytes,
+    header::{CONTENT_ENCODING, ToStrError},
+};
+use iox_http_util::{BoxError, RequestBody};
+use thiserror::Error;
+use zstd::stream::raw::{InBuffer, Operation, OutBuffer};
+
+/// The compression applied to a write request body, as specified by the
+/// `Content-Encoding` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContentEncoding {
+    /// The body is not compressed.
+    #[default]
+    Identity,
+    /// The body is compressed using gzip.
+    Gzip,
+    /// The body is compressed using zstd.
+    Zstd,
+}
+
+impl ContentEncoding {
+    /// Returns the [`ContentEncoding`] specified by the `Content-Encoding`
+    /// header in `headers`, defaulting to [`ContentEncoding::Identity`].
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self, RequestBodyError> {
+        let Some(value) = headers.get(CONTENT_ENCODING) else {
+            return Ok(Self::Identity);
+        };
+
+        let value = value
+            .to_str()
+            .map_err(RequestBodyError::InvalidContentEncodingHeader)?
+            .trim();
+        match value.to_ascii_lowercase().as_str() {
+            "" | "identity" => Ok(Self::Identity),
+            "gzip" | "x-gzip" => Ok(Self::Gzip),
+            "zstd" => Ok(Self::Zstd),
+            _ => Err(RequestBodyError::UnsupportedContentEncoding(
+                value.to_string(),
+            )),
+        }
+    }
+
+    /// The value of the `Content-Encoding` header for this encoding.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Identity => "identity",
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+        }
+    }
+}
+
+impl std::fmt::Display for ContentEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Errors reading a write request body.
+#[derive(Debug, Error)]
+pub enum RequestBodyError {
+    /// The `Content-Encoding` header is not valid ASCII.
+    #[error("invalid content-encoding header: {0}")]
+    InvalidContentEncodingHeader(ToStrError),
+
+    /// The `Content-Encoding` is not supported.
+    #[error("unsupported content-encoding: {0}")]
+    UnsupportedContentEncoding(String),
+
+    /// An error occurred receiving the request body.
+    #[error("error reading request body: {0}")]
+    ReadBody(BoxError),
+
+    /// The request body exceeds the configured limit before decompression.
+    #[error("the request body exceeds the maximum size of {max_bytes} bytes")]
+    RequestSizeExceeded {
+        /// The configured limit.
+        max_bytes: usize,
+    },
+
+    /// The request body decompresses to more than the configured limit.
+    #[error(
+        "the decompressed {encoding} request body exceeds the maximum size of {max_bytes} bytes"
+    )]
+    DecompressedSizeExceeded {
+        /// The encoding of the request body.
+        encoding: ContentEncoding,
+        /// The configured limit.
+        max_bytes: usize,
+    },
+
+    /// The request body is not valid for its `Content-Encoding`.
+    #[error("error decompressing {encoding} request body: {source}")]
+    Decompress {
+        /// The encoding of the request body.
+        encoding: ContentEncoding,
+        /// The underlying decoder error.
+        source: std::io::Error,
+    },
+}
+
+impl From<&RequestBodyError> for StatusCode {
+    fn from(value: &RequestBodyError) -> Self {
+        match value {
+            RequestBodyError::InvalidContentEncodingHeader(_)
+            | RequestBodyError::ReadBody(_)
+            | RequestBodyError::Decompress { .. } => Self::BAD_REQUEST,
+            RequestBodyError::UnsupportedContentEncoding(_) => Self::UNSUPPORTED_MEDIA_TYPE,
+            RequestBodyError::RequestSizeExceeded { .. }
+            | RequestBodyError::DecompressedSizeExceeded { .. } => Self::PAYLOAD_TOO_LARGE,
+        }
+    }
+}
+
+/// Read the write request `body`, decompressing it according to `encoding`.
+///
+/// Both the request body as received and the decompressed body are limited
+/// to `max_bytes`. Decompression stops as soon as the limit is exceeded,
+/// returning [`RequestBodyError::DecompressedSizeExceeded`].
+pub async fn read_body(
+    mut body: RequestBody,
+    encoding: ContentEncoding,
+    max_bytes: usize,
+) -> Result<Bytes, RequestBodyError> {
+    let mut decoder = Decoder::new(encoding, max_bytes)?;
+    let mut received = 0;
+
+    while let Some(frame) = body.frame().await {
+        let frame = frame.map_err(RequestBodyError::ReadBody)?;
+        let Ok(data) = frame.into_data() else {
+            // Ignore trailers
+            continue;
+        };
+
+        received += data.len();
+        if received > max_bytes {
+            return Err(RequestBodyError::RequestSizeExceeded { max_bytes });
+        }
+
+        decoder.write(&data)?;
+    }
+
+    decoder.finish()
+}
+
+/// A [`Write`] sink that refuses to grow beyond `max_bytes`.
+#[derive(Debug)]
+struct LimitedBuffer {
+    buf: Vec<u8>,
+    max_bytes: usize,
+    exceeded: bool,
+}
+
+impl LimitedBuffer {
+    fn new(max_bytes: usize) -> Self {
+        Self {
+            buf: Vec::new(),
+            max_bytes,
+            exceeded: false,
+        }
+    }
+}
+
+impl Write for LimitedBuffer {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        if self.buf.len() + data.len() > self.max_bytes {
+            self.exceeded = true;
+            return Err(std::io::Error::other("decompressed size limit exceeded"));
+        }
+        self.buf.extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A streaming zstd decoder, which unlike [`zstd::stream::write::Decoder`]
+/// detects input that ends part way through a frame.
+struct ZstdDecoder {
+    decoder: zstd::stream::raw::Decoder<'static>,
+    buf: LimitedBuffer,
+    /// `true` if the input written so far ends at the end of a frame.
+    frame_complete: bool,
+}
+
+impl ZstdDecoder {
+    fn new(buf: LimitedBuffer) -> std::io::Result<Self> {
+        Ok(Self {
+            decoder: zstd::stream::raw::Decoder::new()?,
+            buf,
+            frame_complete: true,
+        })
+    }
+
+    fn write_all(&mut self, data: &[u8]) -> std::io::Result<()> {
+        let mut input = InBuffer::around(data);
+        let mut out = [0; 32 * 1024];
+
+        loop {
+            // start the next of multiple concatenated frames
+            if self.frame_complete && input.pos() < data.len() {
+                self.decoder.reinit()?;
+            }
+
+            let pos = input.pos();
+            let mut output = OutBuffer::around(&mut out[..]);
+            let hint = self.decoder.run(&mut input, &mut output)?;
+            let written = output.pos();
+            self.buf.write_all(&out[..written])?;
+
+            // a run without progress, such as for an empty chunk after the
+            // end of a frame, hints at the header of a frame that may never
+            // arrive
+            if input.pos() > pos || written > 0 {
+                self.frame_complete = hint == 0;
+            }
+
+            // the decoder may hold more output than fits in `out`
+            if input.pos() == data.len() && written < out.len() {
+                return Ok(());
+            }
+        }
+    }
+
+    fn finish(&mut self) -> std::io::Result<()> {
+        match self.frame_complete {
+            true => Ok(()),
+            false => Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "incomplete zstd frame",
+            )),
+        }
+    }
+}
+
+/// A streaming decoder of request body chunks.
+enum Decoder {
+    Identity(Vec<u8>),
+    Gzip(flate2::write::MultiGzDecoder<LimitedBuffer>),
+    Zstd(ZstdDecoder),
+}
+
+impl std::fmt::Debug for Decoder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Decoder").field(&self.encoding()).finish()
+    }
+}
+
+impl Decoder {
+    fn new(encoding: ContentEncoding, max_bytes: usize) -> Result<Self, RequestBodyError> {
+        let buf = LimitedBuffer::new(max_bytes);
+        Ok(match encoding {
+            ContentEncoding::Identity => Self::Identity(Vec::new()),
+            ContentEncoding::Gzip => Self::Gzip(flate2::write::MultiGzDecoder::new(buf)),
+            ContentEncoding::Zstd => Self::Zstd(
+                ZstdDecoder::new(buf)
+                    .map_err(|source| RequestBodyError::Decompress { encoding, source })?,
+            ),
+        })
+    }
+
+    fn encoding(&self) -> ContentEncoding {
+        match self {
+            Self::Identity(_) => ContentEncoding::Identity,
+            Self::Gzip(_) => ContentEncoding::Gzip,
+            Self::Zstd(_) => ContentEncoding::Zstd,
+        }
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<(), RequestBodyError> {
+        let res = match self {
+            Self::Identity(buf) => {
+                buf.extend_from_slice(data);
+                Ok(())
+            }
+            Self::Gzip(d) => d.write_all(data),
+            Self::Zstd(d) => d.write_all(data),
+        };
+        res.map_err(|source| self.error(source))
+    }
+
+    fn finish(mut self) -> Result<Bytes, RequestBodyError> {
+        let res = match &mut self {
+            Self::Identity(buf) => return Ok(std::mem::take(buf).into()),
+            Self::Gzip(d) => d.try_finish(),
+            Self::Zstd(d) => d.finish(),
+        };
+        res.map_err(|source| self.error(source))?;
+
+        let buf = match &mut self {
+            Self::Identity(buf) => buf,
+            Self::Gzip(d) => &mut d.get_mut().buf,
+            Self::Zstd(d) => &mut d.buf,
+        };
+        Ok(std::mem::take(buf).into())
+    }
+
+    /// Map a decoder `source` error, distinguishing the size limit from an
+    /// invalid payload.
+    fn error(&self, source: std::io::Error) -> RequestBodyError {
+        let encoding = self.encoding();
+        let limit = match self {
+            Self::Identity(_) => None,
+            Self::Gzip(d) => Some(d.get_ref()),
+            Self::Zstd(d) => Some(&d.buf),
+        };
+
+        match limit {
+            Some(buf) if buf.exceeded => RequestBodyError::DecompressedSizeExceeded {
+                encoding,
+                max_bytes: buf.max_bytes,
+            },
+            _ => RequestBodyError::Decompress { encoding, source },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use futures::stream;
+    use http_body_util::StreamBody;
+    use hyper::{body::Frame, header::HeaderValue};
+
+    use super::*;
+
+    const LP: &str = "cpu,host=a usage=1.0 1\ncpu,host=b usage=2.0 2\n";
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut e = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        e.write_all(data).unwrap();
+        e.finish().unwrap()
+    }
+
+    fn zstd(data: &[u8]) -> Vec<u8> {
+        zstd::encode_all(data, 0).unwrap()
+    }
+
+    /// Returns a body that yields `data` in chunks of `chunk_size` bytes.
+    fn chunked_body(data: Vec<u8>, chunk_size: usize) -> RequestBody {
+        let chunks = data
+            .chunks(chunk_size)
+            .map(|c| Ok::<_, BoxError>(Frame::data(Bytes::copy_from_slice(c))))
+            .collect::<Vec<_>>();
+        StreamBody::new(stream::iter(chunks)).boxed()
+    }
+
+    fn headers(value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_ENCODING, HeaderValue::from_static(value));
+        headers
+    }
+
+    #[test]
+    fn test_content_encoding_from_headers() {
+        assert_matches!(
+            ContentEncoding::from_headers(&HeaderMap::new()),
+            Ok(ContentEncoding::Identity)
+        );
+        assert_matches!(
+            ContentEncoding::from_headers(&headers("identity")),
+            Ok(ContentEncoding::Identity)
+        );
+        assert_matches!(
+            ContentEncoding::from_headers(&headers("GZIP")),
+            Ok(ContentEncoding::Gzip)
+        );
+        assert_matches!(
+            ContentEncoding::from_headers(&headers("x-gzip")),
+            Ok(ContentEncoding::Gzip)
+        );
+        assert_matches!(
+            ContentEncoding::from_headers(&headers("zstd")),
+            Ok(ContentEncoding::Zstd)
+        );
+
+        let err = ContentEncoding::from_headers(&headers("br")).unwrap_err();
+        assert_matches!(&err, RequestBodyError::UnsupportedContentEncoding(e) if e == "br");
+        assert_eq!(StatusCode::from(&err), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[tokio::test]
+    async fn test_read_body() {
+        for (encoding, data) in [
+            (ContentEncoding::Identity, LP.as_bytes().to_vec()),
+            (ContentEncoding::Gzip, gzip(LP.as_bytes())),
+            (ContentEncoding::Zstd, zstd(LP.as_bytes())),
+        ] {
+            let got = read_body(chunked_body(data, 7), encoding, 1024)
+                .await
+                .unwrap();
+            assert_eq!(got, LP, "{encoding}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_body_decompressed_size_exceeded() {
+        // Highly compressible, so the compressed body is well within the limit
+        let lp = LP.repeat(1_000);
+
+        for (encoding, data) in [
+            (ContentEncoding::Gzip, gzip(lp.as_bytes())),
+            (ContentEncoding::Zstd, zstd(lp.as_bytes())),
+        ] {
+            assert!(data.len() < 4096);
+
+            let err = read_body(chunked_body(data, 128), encoding, 4096)
+                .await
+                .unwrap_err();
+            assert_matches!(
+                &err,
+                RequestBodyError::DecompressedSizeExceeded { encoding: e, max_bytes: 4096 }
+                    if *e == encoding
+            );
+            assert_eq!(StatusCode::from(&err), StatusCode::PAYLOAD_TOO_LARGE);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_body_request_size_exceeded() {
+        let err = read_body(
+            chunked_body(LP.as_bytes().to_vec(), 7),
+            ContentEncoding::Identity,
+            10,
+        )
+        .await
+        .unwrap_err();
+        assert_matches!(err, RequestBodyError::RequestSizeExceeded { max_bytes: 10 });
+    }
+
+    #[tokio::test]
+    async fn test_read_body_invalid_payload() {
+        for encoding in [ContentEncoding::Gzip, ContentEncoding::Zstd] {
+            let err = read_body(chunked_body(LP.as_bytes().to_vec(), 7), encoding, 1024)
+                .await
+                .unwrap_err();
+            assert_matches!(
+                &err,
+                RequestBodyError::Decompress { encoding: e, .. } if *e == encoding
+            );
+            assert_eq!(StatusCode::from(&err), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_body_truncated() {
+        for (encoding, data) in [
+            (ContentEncoding::Gzip, gzip(LP.as_bytes())),
+            (ContentEncoding::Zstd, zstd(LP.as_bytes())),
+        ] {
+            // A truncated body is rejected, rather than yielding a prefix of the
+            // payload
+            for len in [1, data.len() / 2, data.len() - 1] {
+                let err = read_body(chunked_body(data[..len].to_vec(), 7), encoding, 1024)
+                    .await
+                    .unwrap_err();
+                assert_matches!(
+                    &err,
+                    RequestBodyError::Decompress { encoding: e, .. } if *e == encoding,
+                    "{encoding} truncated to {len} bytes"
+                );
+            }
+
+            // Concatenated frames or members are decoded in full
+            let data = [data.clone(), data].concat();
+            let got = read_body(chunked_body(data, 7), encoding, 1024)
+                .await
+                .unwrap();
+            assert_eq!(got, LP.repeat(2), "{encoding}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_body_empty_chunk_after_frame() {
+        for (encoding, data) in [
+            (ContentEncoding::Gzip, gzip(LP.as_bytes())),
+            (ContentEncoding::Zstd, zstd(LP.as_bytes())),
+        ] {
+            let chunks = [data, vec![], vec![]]
+                .into_iter()
+                .map(|c| Ok::<_, BoxError>(Frame::data(Bytes::from(c))))
+                .collect::<Vec<_>>();
+            let body = StreamBody::new(stream::iter(chunks)).boxed();
+
+            let got = read_body(body, encoding, 1024).await.unwrap();
+            assert_eq!(got, LP, "{encoding}");
+        }
+    }
+}
@@ -71,6 +71,8 @@ fn parse_v2(req: &Request) -> Result<WriteParams, MultiTenantExtractError> {
     Ok(WriteParams {
         namespace,
         precision: write_params.precision,
+        accept_partial: write_params.accept_partial,
+        field_families: write_params.field_families,
     })
 }
 
@@ -226,10 +228,29 @@ mod tests {
         query_string = "?org=banana&bucket=cool&precision=ms",
         want = Ok(WriteParams {
             namespace,
-            precision
+            precision,
+            ..
         }) => {
             assert_eq!(namespace.as_str(), "banana_cool");
             assert_matches!(precision, Precision::Millisecond);
         }
     );
+
+    test_parse_v2!(
+        with_accept_partial,
+        query_string = "?org=banana&bucket=cool&accept_partial=true",
+        want = Ok(WriteParams {
+            accept_partial: true,
+            ..
+        })
+    );
+
+    test_parse_v2!(
+        with_field_families,
+        query_string = "?org=banana&bucket=cool&field_families=true",
+        want = Ok(WriteParams {
+            field_families: true,
+            ..
+        })
+    );
 }
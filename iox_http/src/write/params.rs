@@ -38,6 +38,13 @@ impl Precision {
 pub struct WriteParams {
     pub namespace: NamespaceName<'static>,
     pub precision: Precision,
+    /// When true, valid lines of a write are accepted even if other lines in
+    /// the same request are rejected, responding with a
+    /// [`PartialWriteResponse`](super::PartialWriteResponse).
+    pub accept_partial: bool,
+    /// When true, the request body is parsed as line protocol v3, accepting
+    /// fields qualified with a field family such as `family::name=1i`.
+    pub field_families: bool,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -0,0 +1,197 @@
+//! InfluxDB compatible responses for writes that were only partially
+//! accepted.
+
+use hyper::StatusCode;
+use mutable_batch_lp::PartialWrite;
+use serde::Serialize;
+
+/// A rejected line of a partially accepted write.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RejectedLine {
+    /// The line number (1-based) within the request body.
+    pub line_number: usize,
+    /// A stable, machine readable classification of the error.
+    pub error_code: &'static str,
+    /// A human readable description of the error.
+    pub error_message: String,
+}
+
+/// The outcome of a write request with `accept_partial` enabled, which can be
+/// rendered as a response body for either the [V1 Write API] or
+/// [V2 Write API].
+///
+/// [V1 Write API]:
+///     https://docs.influxdata.com/influxdb/v1.8/tools/api/#write-http-endpoint
+/// [V2 Write API]:
+///     https://docs.influxdata.com/influxdb/v2.6/api/#operation/PostWrite
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialWriteResponse {
+    /// The number of lines accepted.
+    pub accepted: usize,
+    /// The total number of lines rejected, which may be more than the number
+    /// of `errors`.
+    pub rejected: usize,
+    /// The rejected lines.
+    pub errors: Vec<RejectedLine>,
+}
+
+impl From<&PartialWrite> for PartialWriteResponse {
+    fn from(value: &PartialWrite) -> Self {
+        Self {
+            accepted: value.accepted_lines,
+            rejected: value.rejected_lines,
+            errors: value
+                .errors
+                .iter()
+                .map(|e| RejectedLine {
+                    line_number: e.line(),
+                    error_code: e.code().as_str(),
+                    error_message: e.to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct V1Body<'a> {
+    error: String,
+    accepted: usize,
+    rejected: usize,
+    lines: &'a [RejectedLine],
+}
+
+#[derive(Debug, Serialize)]
+struct V2Body<'a> {
+    code: &'static str,
+    message: String,
+    accepted: usize,
+    rejected: usize,
+    lines: &'a [RejectedLine],
+}
+
+impl PartialWriteResponse {
+    /// Returns true if any line of the write was rejected.
+    pub fn is_partial(&self) -> bool {
+        self.rejected > 0
+    }
+
+    /// The HTTP status code of the response.
+    ///
+    /// As with InfluxDB, a write that rejects any line is a
+    /// [`StatusCode::BAD_REQUEST`], even though the accepted lines were
+    /// written and must not be retried by the client.
+    pub fn status_code(&self) -> StatusCode {
+        if self.is_partial() {
+            StatusCode::BAD_REQUEST
+        } else {
+            StatusCode::NO_CONTENT
+        }
+    }
+
+    /// Render the JSON response body for the V1 Write API, or [`None`] if
+    /// every line was accepted.
+    pub fn v1_body(&self) -> Option<String> {
+        self.is_partial().then(|| {
+            serde_json::to_string(&V1Body {
+                error: format!(
+                    "partial write: {} dropped={}",
+                    self.summary(),
+                    self.rejected
+                ),
+                accepted: self.accepted,
+                rejected: self.rejected,
+                lines: &self.errors,
+            })
+            .expect("partial write response must serialise")
+        })
+    }
+
+    /// Render the JSON response body for the V2 Write API, or [`None`] if
+    /// every line was accepted.
+    pub fn v2_body(&self) -> Option<String> {
+        self.is_partial().then(|| {
+            serde_json::to_string(&V2Body {
+                code: "invalid",
+                message: format!(
+                    "partial write error ({} written): {}",
+                    self.accepted,
+                    self.summary()
+                ),
+                accepted: self.accepted,
+                rejected: self.rejected,
+                lines: &self.errors,
+            })
+            .expect("partial write response must serialise")
+        })
+    }
+
+    /// A single line summary of the first rejected line.
+    fn summary(&self) -> String {
+        match self.errors.first() {
+            Some(e) => e.error_message.clone(),
+            None => "lines rejected".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mutable_batch_lp::LinesConverter;
+
+    use super::*;
+
+    fn partial_write(lp: &str) -> PartialWriteResponse {
+        let mut converter = LinesConverter::new(0);
+        PartialWriteResponse::from(&converter.write_lp_partial(lp))
+    }
+
+    #[test]
+    fn test_all_accepted() {
+        let resp = partial_write("cpu val=1i 1\ncpu val=2i 2");
+        assert!(!resp.is_partial());
+        assert_eq!(resp.accepted, 2);
+        assert_eq!(resp.status_code(), StatusCode::NO_CONTENT);
+        assert_eq!(resp.v1_body(), None);
+        assert_eq!(resp.v2_body(), None);
+    }
+
+    #[test]
+    fn test_partial() {
+        let resp = partial_write("cpu val=1i 1\ncpu val=2u 2\ncpu val=3i 3");
+        assert!(resp.is_partial());
+        assert_eq!(resp.accepted, 2);
+        assert_eq!(resp.rejected, 1);
+        assert_eq!(resp.status_code(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            resp.errors,
+            [RejectedLine {
+                line_number: 2,
+                error_code: "schema_conflict",
+                error_message: resp.errors[0].error_message.clone(),
+            }]
+        );
+
+        let v1: serde_json::Value = serde_json::from_str(&resp.v1_body().unwrap()).unwrap();
+        assert!(
+            v1["error"]
+                .as_str()
+                .unwrap()
+                .starts_with("partial write: error writing line 2 (1-based)")
+        );
+        assert!(v1["error"].as_str().unwrap().ends_with("dropped=1"));
+        assert_eq!(v1["accepted"], 2);
+        assert_eq!(v1["lines"][0]["line_number"], 2);
+        assert_eq!(v1["lines"][0]["error_code"], "schema_conflict");
+
+        let v2: serde_json::Value = serde_json::from_str(&resp.v2_body().unwrap()).unwrap();
+        assert_eq!(v2["code"], "invalid");
+        assert!(
+            v2["message"]
+                .as_str()
+                .unwrap()
+                .starts_with("partial write error (2 written): error writing line 2")
+        );
+        assert_eq!(v2["rejected"], 1);
+    }
+}
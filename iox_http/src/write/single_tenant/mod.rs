@@ -139,6 +139,8 @@ async fn parse_v1(
     Ok(WriteParams {
         namespace,
         precision: write_params.precision,
+        accept_partial: write_params.accept_partial,
+        field_families: write_params.field_families,
     })
 }
 
@@ -166,6 +168,8 @@ async fn parse_v2(
     Ok(WriteParams {
         namespace,
         precision: write_params.precision,
+        accept_partial: write_params.accept_partial,
+        field_families: write_params.field_families,
     })
 }
 
@@ -304,17 +308,61 @@ mod tests {
     test_parse_v1!(
         no_rp,
         query_string = "?db=bananas",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Nanosecond);
         }
     );
 
+    test_parse_v1!(
+        accept_partial_default,
+        query_string = "?db=bananas",
+        want = Ok(WriteParams {
+            accept_partial: false,
+            ..
+        })
+    );
+
+    test_parse_v1!(
+        accept_partial,
+        query_string = "?db=bananas&accept_partial=true",
+        want = Ok(WriteParams {
+            accept_partial: true,
+            ..
+        })
+    );
+
+    test_parse_v1!(
+        field_families_default,
+        query_string = "?db=bananas",
+        want = Ok(WriteParams {
+            field_families: false,
+            ..
+        })
+    );
+
+    test_parse_v1!(
+        field_families,
+        query_string = "?db=bananas&field_families=true",
+        want = Ok(WriteParams {
+            field_families: true,
+            ..
+        })
+    );
+
+    test_parse_v1!(
+        accept_partial_invalid,
+        query_string = "?db=bananas&accept_partial=bananas",
+        want = Err(WriteParseError::SingleTenantError(
+            SingleTenantExtractError::ParseV1Request(V1WriteParseError::DecodeFail(_))
+        ))
+    );
+
     // Permit `/` character in the DB
     test_parse_v1!(
         no_rp_db_with_rp_separator,
         query_string = "?db=bananas/are/great",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas/are/great");
             assert_matches!(precision, Precision::Nanosecond);
         }
@@ -324,7 +372,7 @@ mod tests {
     test_parse_v1!(
         rp_with_rp_separator,
         query_string = "?db=bananas&rp=are/great",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas/are/great");
             assert_matches!(precision, Precision::Nanosecond);
         }
@@ -334,7 +382,7 @@ mod tests {
     test_parse_v1!(
         db_with_rp_separator_and_rp,
         query_string = "?db=foo/bar&rp=my_rp",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "foo/bar/my_rp");
             assert_matches!(precision, Precision::Nanosecond);
         }
@@ -345,7 +393,7 @@ mod tests {
     test_parse_v1!(
         db_with_rp_separator_and_duplicate_rp,
         query_string = "?db=foo/my_rp&rp=my_rp",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "foo/my_rp/my_rp");
             assert_matches!(precision, Precision::Nanosecond);
         }
@@ -355,7 +403,7 @@ mod tests {
     test_parse_v1!(
         db_with_rp_separator_and_rp_autogen,
         query_string = "?db=foo/bar&rp=autogen",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "foo/bar");
             assert_matches!(precision, Precision::Nanosecond);
         }
@@ -365,7 +413,7 @@ mod tests {
     test_parse_v1!(
         db_with_rp_separator_and_rp_default,
         query_string = "?db=foo/bar&rp=default",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "foo/bar");
             assert_matches!(precision, Precision::Nanosecond);
         }
@@ -374,7 +422,7 @@ mod tests {
     test_parse_v1!(
         rp_empty,
         query_string = "?db=bananas&rp=",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Nanosecond);
         }
@@ -383,7 +431,7 @@ mod tests {
     test_parse_v1!(
         rp_empty_quotes,
         query_string = "?db=bananas&rp=''",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Nanosecond);
         }
@@ -392,7 +440,7 @@ mod tests {
     test_parse_v1!(
         rp_autogen,
         query_string = "?db=bananas&rp=autogen",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Nanosecond);
         }
@@ -401,7 +449,7 @@ mod tests {
     test_parse_v1!(
         rp_specified,
         query_string = "?db=bananas&rp=ageless",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas/ageless");
             assert_matches!(precision, Precision::Nanosecond);
         }
@@ -410,7 +458,7 @@ mod tests {
     test_parse_v1!(
         encoded_case_sensitive,
         query_string = "?db=BaNanas",
-        want = Ok(WriteParams{ namespace, precision: _ }) => {
+        want = Ok(WriteParams{ namespace, .. }) => {
             assert_eq!(namespace.as_str(), "BaNanas");
         }
     );
@@ -426,7 +474,7 @@ mod tests {
     test_parse_v1!(
         start_nonalphanumeric,
         query_string = "?db=_bananas",
-        want = Ok(WriteParams{ namespace, precision: _ }) => {
+        want = Ok(WriteParams{ namespace, .. }) => {
             assert_eq!(namespace.as_str(), "_bananas");
         }
     );
@@ -434,7 +482,7 @@ mod tests {
     test_parse_v1!(
         minimum_length_possible,
         query_string = "?db=d",
-        want = Ok(WriteParams{ namespace, precision: _ }) => {
+        want = Ok(WriteParams{ namespace, .. }) => {
             assert_eq!(namespace.as_str().len(), 1);
         }
     );
@@ -442,7 +490,7 @@ mod tests {
     test_parse_v1!(
         with_precision_s,
         query_string = "?db=bananas&rp=ageless&precision=s",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas/ageless");
             assert_matches!(precision, Precision::Second);
         }
@@ -451,7 +499,7 @@ mod tests {
     test_parse_v1!(
         with_precision_ms,
         query_string = "?db=bananas&rp=ageless&precision=ms",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas/ageless");
             assert_matches!(precision, Precision::Millisecond);
         }
@@ -460,7 +508,7 @@ mod tests {
     test_parse_v1!(
         with_precision_u,
         query_string = "?db=bananas&rp=ageless&precision=u",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas/ageless");
             assert_matches!(precision, Precision::Microsecond);
         }
@@ -469,7 +517,7 @@ mod tests {
     test_parse_v1!(
         with_precision_n,
         query_string = "?db=bananas&rp=ageless&precision=n",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas/ageless");
             assert_matches!(precision, Precision::Nanosecond);
         }
@@ -516,7 +564,7 @@ mod tests {
     test_parse_v2!(
         bucket_only,
         query_string = "?bucket=bananas",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Nanosecond);
         }
@@ -570,7 +618,8 @@ mod tests {
         query_string = "?org=wat&bucket=bananas",
         want = Ok(WriteParams {
             namespace,
-            precision
+            precision,
+            ..
         }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Nanosecond);
@@ -623,7 +672,8 @@ mod tests {
         query_string = "?bucket=bananas&precision=s",
         want = Ok(WriteParams {
             namespace,
-            precision
+            precision,
+            ..
         }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Second);
@@ -635,7 +685,8 @@ mod tests {
         query_string = "?bucket=bananas&precision=ms",
         want = Ok(WriteParams {
             namespace,
-            precision
+            precision,
+            ..
         }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Millisecond);
@@ -647,7 +698,8 @@ mod tests {
         query_string = "?bucket=bananas&precision=us",
         want = Ok(WriteParams {
             namespace,
-            precision
+            precision,
+            ..
         }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Microsecond);
@@ -659,10 +711,32 @@ mod tests {
         query_string = "?bucket=bananas&precision=ns",
         want = Ok(WriteParams {
             namespace,
-            precision
+            precision,
+            ..
         }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Nanosecond);
         }
     );
+
+    test_parse_v2!(
+        with_accept_partial,
+        query_string = "?bucket=bananas&accept_partial=true",
+        want = Ok(WriteParams {
+            namespace,
+            accept_partial: true,
+            ..
+        }) => {
+            assert_eq!(namespace.as_str(), "bananas");
+        }
+    );
+
+    test_parse_v2!(
+        with_field_families,
+        query_string = "?bucket=bananas&field_families=true",
+        want = Ok(WriteParams {
+            field_families: true,
+            ..
+        })
+    );
 }
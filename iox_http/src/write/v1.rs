@@ -69,6 +69,12 @@ pub struct WriteParamsV1 {
     // in the CST spec, we treat the `p` parameter as a token
     #[serde(rename(deserialize = "p"))]
     pub password: Option<String>,
+
+    #[serde(default)]
+    pub accept_partial: bool,
+
+    #[serde(default)]
+    pub field_families: bool,
 }
 
 impl TryFrom<&Request> for WriteParamsV1 {
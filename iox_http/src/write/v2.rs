@@ -32,6 +32,12 @@ pub struct WriteParamsV2 {
 
     #[serde(default)]
     pub precision: Precision,
+
+    #[serde(default)]
+    pub accept_partial: bool,
+
+    #[serde(default)]
+    pub field_families: bool,
 }
 
 impl TryFrom<&Request> for WriteParamsV2 {
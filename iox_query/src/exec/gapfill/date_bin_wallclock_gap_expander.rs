@@ -281,6 +281,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sub_millisecond_stride() {
+        let tz = Arc::from("Europe/London");
+        let gap_finder = DateBinWallclockGapExpander::new(10_000);
+        let array = arrow::array::TimestampNanosecondArray::from(vec![20_000, 50_000])
+            .with_timezone(Arc::clone(&tz));
+        let (pairs, input_rows) = <DateBinWallclockGapExpander as GapExpander>::expand_gaps(
+            &gap_finder,
+            Range {
+                start: Bound::Included(ScalarValue::TimestampNanosecond(
+                    Some(0),
+                    Some(Arc::clone(&tz)),
+                )),
+                end: Bound::Excluded(ScalarValue::TimestampNanosecond(
+                    Some(60_000),
+                    Some(Arc::clone(&tz)),
+                )),
+            },
+            &array,
+            usize::MAX,
+        )
+        .unwrap();
+        assert_eq!(input_rows, 2);
+        assert_eq!(
+            pairs,
+            vec![
+                (
+                    ScalarValue::TimestampNanosecond(Some(0), Some(Arc::clone(&tz))),
+                    None
+                ),
+                (
+                    ScalarValue::TimestampNanosecond(Some(10_000), Some(Arc::clone(&tz))),
+                    None
+                ),
+                (
+                    ScalarValue::TimestampNanosecond(Some(20_000), Some(Arc::clone(&tz))),
+                    Some(0)
+                ),
+                (
+                    ScalarValue::TimestampNanosecond(Some(30_000), Some(Arc::clone(&tz))),
+                    None
+                ),
+                (
+                    ScalarValue::TimestampNanosecond(Some(40_000), Some(Arc::clone(&tz))),
+                    None
+                ),
+                (
+                    ScalarValue::TimestampNanosecond(Some(50_000), Some(Arc::clone(&tz))),
+                    Some(1)
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn empty_array_missing_uppper_bound() {
         let tz = Arc::from("Europe/London");
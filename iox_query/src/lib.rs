@@ -167,6 +167,13 @@ pub trait QueryDatabase: Debug + Send + Sync + 'static {
         include_debug_info_tables: bool,
     ) -> Result<Option<Arc<dyn QueryNamespace>>, DataFusionError>;
 
+    /// Get the names of all namespaces.
+    async fn namespace_names(&self) -> Result<Vec<String>, DataFusionError> {
+        Err(DataFusionError::NotImplemented(
+            "listing namespaces".to_owned(),
+        ))
+    }
+
     /// Acquire concurrency-limiting semapahore
     async fn acquire_semaphore(&self, span: Option<Span>) -> InstrumentedAsyncOwnedSemaphorePermit;
 
@@ -114,6 +114,10 @@ impl QueryDatabase for TestDatabaseStore {
         Ok(databases.get(name).cloned().map(|ns| ns as _))
     }
 
+    async fn namespace_names(&self) -> Result<Vec<String>, DataFusionError> {
+        Ok(self.databases.lock().keys().cloned().collect())
+    }
+
     async fn acquire_semaphore(&self, span: Option<Span>) -> InstrumentedAsyncOwnedSemaphorePermit {
         Arc::clone(&self.query_semaphore)
             .acquire_owned(span)
@@ -3,10 +3,16 @@
 use datafusion::logical_expr::AggregateUDF;
 use std::sync::{Arc, LazyLock};
 
+mod holt_winters;
 mod mode;
+mod nelder_mead;
 mod percentile;
+mod sample;
 mod spread;
 
+pub(crate) use holt_winters::{HOLT_WINTERS_TIME_FIELD, HOLT_WINTERS_VALUE_FIELD};
+pub(crate) use sample::{SAMPLE_TIME_FIELD, SAMPLE_VALUE_FIELD, sample_other_field};
+
 /// Definition of the `PERCENTILE` user-defined aggregate function.
 pub(crate) static PERCENTILE: LazyLock<Arc<AggregateUDF>> =
     LazyLock::new(|| Arc::new(AggregateUDF::new_from_impl(percentile::PercentileUDF::new())));
@@ -16,3 +22,14 @@ pub(crate) static SPREAD: LazyLock<Arc<AggregateUDF>> =
 
 pub(crate) static MODE: LazyLock<Arc<AggregateUDF>> =
     LazyLock::new(|| Arc::new(AggregateUDF::new_from_impl(mode::ModeUDF::new())));
+
+/// Definition of the `HOLT_WINTERS` user-defined aggregate function.
+pub(crate) static HOLT_WINTERS: LazyLock<Arc<AggregateUDF>> = LazyLock::new(|| {
+    Arc::new(AggregateUDF::new_from_impl(
+        holt_winters::HoltWintersUDF::new(),
+    ))
+});
+
+/// Definition of the `SAMPLE` user-defined aggregate function.
+pub(crate) static SAMPLE: LazyLock<Arc<AggregateUDF>> =
+    LazyLock::new(|| Arc::new(AggregateUDF::new_from_impl(sample::SampleUDF::new())));
@@ -0,0 +1,641 @@
+use super::nelder_mead::Optimizer;
+use crate::error;
+use arrow::array::{
+    Array, ArrayRef, BooleanArray, Float64Array, Int64Array, ListArray, StructArray, as_list_array,
+};
+use arrow::buffer::OffsetBuffer;
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, FieldRef, Fields, TimeUnit};
+use datafusion::common::{Result, ScalarValue, downcast_value, plan_err};
+use datafusion::logical_expr::function::{AccumulatorArgs, StateFieldsArgs};
+use datafusion::logical_expr::{Accumulator, AggregateUDFImpl, Signature, Volatility};
+use datafusion::physical_expr::expressions::format_state_name;
+use std::any::Any;
+use std::sync::Arc;
+
+/// Arbitrary weight for initializing some initial guesses.
+/// This should be in the range [0,1].
+const HW_WEIGHT: f64 = 0.5;
+/// Epsilon value for the minimization process.
+const HW_DEFAULT_EPSILON: f64 = 1.0e-4;
+/// The lower bound of the grid of initial guesses for the parameters
+/// alpha, beta, gamma and phi. The grid is N^4, so it is kept small.
+const HW_GUESS_LOWER: f64 = 0.3;
+/// The upper bound of the grid of initial guesses.
+const HW_GUESS_UPPER: f64 = 1.0;
+/// The step between guesses.
+const HW_GUESS_STEP: f64 = 0.4;
+
+/// The name of the time field of each point returned by [`HoltWintersUDF`].
+pub(crate) const HOLT_WINTERS_TIME_FIELD: &str = "time";
+/// The name of the value field of each point returned by [`HoltWintersUDF`].
+pub(crate) const HOLT_WINTERS_VALUE_FIELD: &str = "value";
+
+/// The `HOLT_WINTERS` and `HOLT_WINTERS_WITH_FIT` aggregate function,
+/// which forecasts the values of a series using triple exponential
+/// smoothing.
+///
+/// The function expects the following arguments:
+///
+/// 1. the `time` of each point, which must be the start of a `GROUP BY time()` interval,
+/// 2. the value of each point,
+/// 3. `N`, the number of values to forecast,
+/// 4. `S`, the seasonal pattern length,
+/// 5. the nanosecond duration of the `GROUP BY time()` interval, and
+/// 6. `true` if the fitted values should be returned with the forecast.
+///
+/// The result is a list of `time` and `value` structs, which are expected
+/// to be unnested by the planner.
+///
+/// Unlike InfluxDB 1.x, a `SELECT` statement may not combine `HOLT_WINTERS`
+/// with other functions, as the forecast points do not share the timestamps
+/// of the other projections. Such statements are rejected when planning.
+#[derive(Debug)]
+pub(super) struct HoltWintersUDF {
+    signature: Signature,
+}
+
+impl HoltWintersUDF {
+    pub(super) fn new() -> Self {
+        Self {
+            // The value may be any numeric type or NULL, when the field does
+            // not exist, so the argument types are checked in `return_type`.
+            signature: Signature::any(6, Volatility::Immutable),
+        }
+    }
+}
+
+impl AggregateUDFImpl for HoltWintersUDF {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "holt_winters"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        match arg_types {
+            [
+                time @ DataType::Timestamp(TimeUnit::Nanosecond, _),
+                value,
+                DataType::Int64,
+                DataType::Int64,
+                DataType::Int64,
+                DataType::Boolean,
+            ] if value.is_numeric() || value.is_null() => Ok(DataType::new_list(
+                DataType::Struct(point_fields(time)),
+                true,
+            )),
+            _ => plan_err!("holt_winters() called with invalid arguments: {arg_types:?}"),
+        }
+    }
+
+    fn accumulator(&self, arg: AccumulatorArgs<'_>) -> Result<Box<dyn Accumulator>> {
+        let time_data_type = arg.exprs[0].data_type(arg.schema)?;
+        Ok(Box::new(HoltWintersAccumulator::new(time_data_type)))
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs<'_>) -> Result<Vec<FieldRef>> {
+        let time_list = DataType::new_list(args.input_fields[0].data_type().clone(), true);
+        let value_list = DataType::new_list(DataType::Float64, true);
+        Ok(vec![
+            Arc::new(Field::new(
+                format_state_name(args.name, "time"),
+                time_list,
+                true,
+            )),
+            Arc::new(Field::new(
+                format_state_name(args.name, "value"),
+                value_list,
+                true,
+            )),
+            Arc::new(Field::new(
+                format_state_name(args.name, "h"),
+                DataType::Int64,
+                true,
+            )),
+            Arc::new(Field::new(
+                format_state_name(args.name, "m"),
+                DataType::Int64,
+                true,
+            )),
+            Arc::new(Field::new(
+                format_state_name(args.name, "interval"),
+                DataType::Int64,
+                true,
+            )),
+            Arc::new(Field::new(
+                format_state_name(args.name, "include_fit_data"),
+                DataType::Boolean,
+                true,
+            )),
+        ])
+    }
+}
+
+/// The fields of the struct for each point returned by [`HoltWintersUDF`].
+fn point_fields(time_data_type: &DataType) -> Fields {
+    Fields::from(vec![
+        Field::new(HOLT_WINTERS_TIME_FIELD, time_data_type.clone(), false),
+        Field::new(HOLT_WINTERS_VALUE_FIELD, DataType::Float64, false),
+    ])
+}
+
+/// The constant arguments of a `HOLT_WINTERS` call.
+#[derive(Debug, Clone, Copy)]
+struct HoltWintersOptions {
+    h: i64,
+    m: i64,
+    interval: i64,
+    include_fit_data: bool,
+}
+
+#[derive(Debug)]
+struct HoltWintersAccumulator {
+    time_data_type: DataType,
+    times: Vec<i64>,
+    values: Vec<f64>,
+    options: Option<HoltWintersOptions>,
+}
+
+impl HoltWintersAccumulator {
+    fn new(time_data_type: DataType) -> Self {
+        Self {
+            time_data_type,
+            times: vec![],
+            values: vec![],
+            options: None,
+        }
+    }
+
+    fn update(&mut self, times: &ArrayRef, values: &ArrayRef) -> Result<()> {
+        let times = cast(times, &DataType::Int64)?;
+        let times = downcast_value!(times, Int64Array);
+        let values = cast(values, &DataType::Float64)?;
+        let values = downcast_value!(values, Float64Array);
+
+        for (time, value) in times.iter().zip(values) {
+            // Missing values are filled with NaN when the points are reduced.
+            if let (Some(time), Some(value)) = (time, value) {
+                self.times.push(time);
+                self.values.push(value);
+            }
+        }
+        Ok(())
+    }
+
+    fn set_options(
+        &mut self,
+        h: &ArrayRef,
+        m: &ArrayRef,
+        interval: &ArrayRef,
+        include_fit_data: &ArrayRef,
+    ) -> Result<()> {
+        if self.options.is_some() || h.is_empty() || h.is_null(0) {
+            return Ok(());
+        }
+
+        self.options = Some(HoltWintersOptions {
+            h: downcast_value!(h, Int64Array).value(0),
+            m: downcast_value!(m, Int64Array).value(0),
+            interval: downcast_value!(interval, Int64Array).value(0),
+            include_fit_data: downcast_value!(include_fit_data, BooleanArray).value(0),
+        });
+        Ok(())
+    }
+}
+
+impl Accumulator for HoltWintersAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        assert_eq!(values.len(), 6, "holt_winters expects six arguments");
+
+        self.set_options(&values[2], &values[3], &values[4], &values[5])?;
+        self.update(&values[0], &values[1])
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let points = match self.options {
+            Some(HoltWintersOptions {
+                h,
+                m,
+                interval,
+                include_fit_data,
+            }) if interval > 0 => {
+                let mut points = self
+                    .times
+                    .iter()
+                    .copied()
+                    .zip(self.values.iter().copied())
+                    .collect::<Vec<_>>();
+                // The points may be received in any order after merging states.
+                points.sort_by_key(|(time, _)| *time);
+
+                HoltWinters::new(h, m, include_fit_data, interval).emit(&points)
+            }
+            Some(_) => return error::internal("holt_winters interval must be positive"),
+            None => vec![],
+        };
+
+        let (times, values): (Vec<_>, Vec<_>) = points.into_iter().unzip();
+        let times = cast(
+            &(Arc::new(Int64Array::from(times)) as ArrayRef),
+            &self.time_data_type,
+        )?;
+        let values = Arc::new(Float64Array::from(values)) as ArrayRef;
+        let fields = point_fields(&self.time_data_type);
+        let points = StructArray::try_new(fields.clone(), vec![times, values], None)?;
+
+        Ok(ScalarValue::List(Arc::new(ListArray::new(
+            Arc::new(Field::new_list_field(DataType::Struct(fields), true)),
+            OffsetBuffer::from_lengths([points.len()]),
+            Arc::new(points),
+            None,
+        ))))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + std::mem::size_of::<i64>() * self.times.capacity()
+            + std::mem::size_of::<f64>() * self.values.capacity()
+    }
+
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        let times = self
+            .times
+            .iter()
+            .map(|time| ScalarValue::Int64(Some(*time)).cast_to(&self.time_data_type))
+            .collect::<Result<Vec<_>>>()?;
+        let values = self
+            .values
+            .iter()
+            .map(|value| ScalarValue::Float64(Some(*value)))
+            .collect::<Vec<_>>();
+        let options = self.options;
+
+        Ok(vec![
+            ScalarValue::List(ScalarValue::new_list_nullable(&times, &self.time_data_type)),
+            ScalarValue::List(ScalarValue::new_list_nullable(&values, &DataType::Float64)),
+            ScalarValue::Int64(options.map(|o| o.h)),
+            ScalarValue::Int64(options.map(|o| o.m)),
+            ScalarValue::Int64(options.map(|o| o.interval)),
+            ScalarValue::Boolean(options.map(|o| o.include_fit_data)),
+        ])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        assert_eq!(states.len(), 6);
+
+        let times = as_list_array(&states[0]);
+        let values = as_list_array(&states[1]);
+        for idx in 0..times.len() {
+            if states[2].is_valid(idx) {
+                self.set_options(
+                    &states[2].slice(idx, 1),
+                    &states[3].slice(idx, 1),
+                    &states[4].slice(idx, 1),
+                    &states[5].slice(idx, 1),
+                )?;
+            }
+            self.update(&times.value(idx), &values.value(idx))?;
+        }
+        Ok(())
+    }
+}
+
+/// Forecasts a series of values using the Holt-Winters triple exponential
+/// smoothing method.
+///
+/// This is a port of the `HoltWintersReducer` from InfluxDB 1.x, so that
+/// forecasts match those produced by InfluxDB 1.x for the same input.
+///
+/// See: <https://github.com/influxdata/influxdb/blob/1.8/query/functions.go>
+#[derive(Debug)]
+struct HoltWinters {
+    /// The number of values to forecast.
+    h: usize,
+    /// The season period.
+    m: usize,
+    seasonal: bool,
+    /// The interval between points.
+    interval: i64,
+    /// Half of the interval, used to perform rounding.
+    half_interval: i64,
+    /// Whether to include the fitted values or only the forecast values.
+    include_fit_data: bool,
+    optim: Optimizer,
+    /// Small difference bound for the optimizer.
+    epsilon: f64,
+    /// The values of the series, with NaN for missing intervals.
+    y: Vec<f64>,
+}
+
+impl HoltWinters {
+    fn new(h: i64, m: i64, include_fit_data: bool, interval: i64) -> Self {
+        Self {
+            h: h.max(0) as usize,
+            m: m.max(0) as usize,
+            seasonal: m >= 2,
+            interval,
+            half_interval: interval / 2,
+            include_fit_data,
+            optim: Optimizer::default(),
+            epsilon: HW_DEFAULT_EPSILON,
+            y: vec![],
+        }
+    }
+
+    /// Overflow safe rounding of `t` to the nearest interval.
+    fn round_time(&self, t: i64) -> i64 {
+        let remainder = t % self.interval;
+        if remainder > self.half_interval {
+            // Round up
+            (t / self.interval + 1) * self.interval
+        } else {
+            // Round down
+            (t / self.interval) * self.interval
+        }
+    }
+
+    /// Fit the model to the time ordered `points`, and return the forecast
+    /// points.
+    fn emit(&mut self, points: &[(i64, f64)]) -> Vec<(i64, f64)> {
+        let l = points.len();
+        if l < 2 || (self.seasonal && l < self.m) || self.h == 0 {
+            return vec![];
+        }
+
+        // First fill in y with values and NaNs for missing values.
+        let (first, last) = (points[0], points[l - 1]);
+        let (start, stop) = (self.round_time(first.0), self.round_time(last.0));
+        if (stop - start) / self.interval <= 0 {
+            return vec![];
+        }
+        self.y = vec![first.1];
+        let mut t = start;
+        for &(time, value) in &points[1..] {
+            let rounded = self.round_time(time);
+            if rounded <= t {
+                // Drop values that occur for the same time bucket.
+                continue;
+            }
+            t += self.interval;
+            // Add any missing values before the next point.
+            while rounded != t {
+                // Add in a NaN so we can skip it later.
+                self.y.push(f64::NAN);
+                t += self.interval;
+            }
+            self.y.push(value);
+        }
+        if self.seasonal && self.y.len() < self.m {
+            return vec![];
+        }
+
+        let m = self.m;
+        let y = &self.y;
+
+        // Starting guesses.
+        //
+        // NOTE: Since these values are guesses, in the cases where we
+        // were missing data, we can just skip the value and call it good.
+        let l0 = if self.seasonal {
+            y[..m]
+                .iter()
+                .filter(|v| !v.is_nan())
+                .map(|v| (1.0 / m as f64) * v)
+                .sum::<f64>()
+        } else {
+            HW_WEIGHT * y[0]
+        };
+
+        let b0 = if self.seasonal {
+            (0..m)
+                .take_while(|i| m + i < y.len())
+                .filter(|i| !y[*i].is_nan() && !y[m + i].is_nan())
+                .map(|i| 1.0 / (m * m) as f64 * (y[m + i] - y[i]))
+                .sum::<f64>()
+        } else if !y[1].is_nan() {
+            HW_WEIGHT * (y[1] - y[0])
+        } else {
+            0.0
+        };
+
+        let s = if self.seasonal {
+            y[..m]
+                .iter()
+                .map(|v| if v.is_nan() { 0.0 } else { v / l0 })
+                .collect()
+        } else {
+            vec![]
+        };
+
+        let mut parameters = vec![0.0; 6];
+        parameters[4] = l0;
+        parameters[5] = b0;
+        parameters.extend(s);
+
+        // Determine the best fit for the various parameters.
+        let mut min_sse = f64::INFINITY;
+        let mut best_params: Option<Vec<f64>> = None;
+        for alpha in guesses() {
+            for beta in guesses() {
+                for gamma in guesses() {
+                    for phi in guesses() {
+                        parameters[0] = alpha;
+                        parameters[1] = beta;
+                        parameters[2] = gamma;
+                        parameters[3] = phi;
+                        let (sse, params) = self.optim.optimize(
+                            |params| self.sse(params),
+                            &parameters,
+                            self.epsilon,
+                            1.0,
+                        );
+                        if sse < min_sse || best_params.is_none() {
+                            min_sse = sse;
+                            best_params = Some(params);
+                        }
+                    }
+                }
+            }
+        }
+        let Some(mut best_params) = best_params else {
+            return vec![];
+        };
+
+        // Forecast
+        let forecasted = self.forecast(self.h, &mut best_params);
+        if self.include_fit_data {
+            let start = first.0;
+            forecasted
+                .into_iter()
+                .enumerate()
+                .filter(|(_, v)| !v.is_nan())
+                .map(|(i, v)| (start + self.interval * i as i64, v))
+                .collect()
+        } else {
+            let stop = last.0;
+            forecasted
+                .into_iter()
+                .skip(self.y.len())
+                .enumerate()
+                .filter(|(_, v)| !v.is_nan())
+                .map(|(i, v)| (stop + self.interval * (i as i64 + 1), v))
+                .collect()
+        }
+    }
+
+    /// Using the recursive relations compute the next values.
+    #[expect(clippy::too_many_arguments)]
+    fn next(
+        alpha: f64,
+        beta: f64,
+        gamma: f64,
+        phi: f64,
+        phi_h: f64,
+        y_t: f64,
+        l_tp: f64,
+        b_tp: f64,
+        s_tm: f64,
+        s_tmh: f64,
+    ) -> (f64, f64, f64, f64) {
+        let l_t = alpha * (y_t / s_tm) + (1.0 - alpha) * (l_tp + phi * b_tp);
+        let b_t = beta * (l_t - l_tp) + (1.0 - beta) * phi * b_tp;
+        let s_t = gamma * (y_t / (l_tp + phi * b_tp)) + (1.0 - gamma) * s_tm;
+        let y_th = (l_t + phi_h * b_t) * s_tmh;
+        (y_th, l_t, b_t, s_t)
+    }
+
+    /// Forecast the data `h` points into the future.
+    ///
+    /// NOTE: the parameters are constrained and the seasonal values are
+    /// updated in place, which matches the behaviour of InfluxDB 1.x.
+    fn forecast(&self, h: usize, params: &mut [f64]) -> Vec<f64> {
+        // Constrain alpha, beta, gamma and phi to the range [0, 1].
+        for p in &mut params[..4] {
+            *p = p.clamp(0.0, 1.0);
+        }
+        let (alpha, beta, gamma, phi) = (params[0], params[1], params[2], params[3]);
+        let mut phi_h = phi;
+
+        let mut y_t = self.y[0];
+        let mut l_t = params[4];
+        let mut b_t = params[5];
+
+        // The seasonals are a ring buffer of past sT values.
+        let seasonals = &mut params[6..];
+        let (m, mut so) = if self.seasonal {
+            let m = seasonals.len();
+            if m == 1 {
+                seasonals[0] = 1.0;
+            }
+            // Season index offset
+            (m, m - 1)
+        } else {
+            (0, 0)
+        };
+
+        let l = self.y.len();
+        let mut forecasted = vec![0.0; l + h];
+        forecasted[0] = y_t;
+        let (mut s_tm, mut s_tmh) = (1.0, 1.0);
+        for (t, f) in forecasted.iter_mut().enumerate().skip(1) {
+            if self.seasonal {
+                let hm = t % m;
+                s_tm = seasonals[(t + so - m) % m];
+                s_tmh = seasonals[(t + so + hm - m) % m];
+            }
+            let s_t;
+            (y_t, l_t, b_t, s_t) =
+                Self::next(alpha, beta, gamma, phi, phi_h, y_t, l_t, b_t, s_tm, s_tmh);
+            phi_h += phi.powi(t as i32);
+
+            if self.seasonal {
+                seasonals[(t + so) % m] = s_t;
+                so += 1;
+            }
+
+            *f = y_t;
+        }
+        forecasted
+    }
+
+    /// Compute the sum squared error for the given parameters.
+    fn sse(&self, params: &mut [f64]) -> f64 {
+        let mut sse = 0.0;
+        let forecasted = self.forecast(0, params);
+        for (f, y) in forecasted.iter().zip(&self.y) {
+            // Skip missing values since we cannot use them to compute an error.
+            if !y.is_nan() {
+                if f.is_nan() {
+                    // Penalize forecasted NaNs
+                    return f64::INFINITY;
+                }
+                let diff = f - y;
+                sse += diff * diff;
+            }
+        }
+        sse
+    }
+}
+
+/// The grid of initial guesses for a single parameter.
+fn guesses() -> impl Iterator<Item = f64> {
+    std::iter::successors(Some(HW_GUESS_LOWER), |g| Some(g + HW_GUESS_STEP))
+        .take_while(|g| *g < HW_GUESS_UPPER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guesses() {
+        assert_eq!(guesses().count(), 2);
+    }
+
+    #[test]
+    fn test_round_time() {
+        let hw = HoltWinters::new(1, 0, false, 10);
+        assert_eq!(hw.round_time(14), 10);
+        assert_eq!(hw.round_time(15), 10);
+        assert_eq!(hw.round_time(16), 20);
+    }
+
+    #[test]
+    fn test_emit_insufficient_points() {
+        let mut hw = HoltWinters::new(3, 0, false, 10);
+        assert!(hw.emit(&[(0, 1.0)]).is_empty());
+
+        // A seasonal forecast requires at least one season of points.
+        let mut hw = HoltWinters::new(3, 4, false, 10);
+        assert!(hw.emit(&[(0, 1.0), (10, 2.0), (20, 3.0)]).is_empty());
+    }
+
+    #[test]
+    fn test_emit_times() {
+        let points = [(0, 1.0), (10, 2.0), (30, 4.0), (40, 5.0)];
+
+        let mut hw = HoltWinters::new(2, 0, false, 10);
+        let times = hw
+            .emit(&points)
+            .into_iter()
+            .map(|(t, _)| t)
+            .collect::<Vec<_>>();
+        assert_eq!(times, vec![50, 60]);
+
+        // The fitted values include a value for the missing interval.
+        let mut hw = HoltWinters::new(2, 0, true, 10);
+        let times = hw
+            .emit(&points)
+            .into_iter()
+            .map(|(t, _)| t)
+            .collect::<Vec<_>>();
+        assert_eq!(times, vec![0, 10, 20, 30, 40, 50, 60]);
+    }
+}
@@ -0,0 +1,222 @@
+//! A port of the Nelder-Mead simplex optimizer used by the InfluxDB 1.x
+//! `HOLT_WINTERS` implementation.
+//!
+//! The optimizer is deliberately kept identical to the original, so that
+//! the fitted parameters, and therefore the forecasts, match those
+//! produced by InfluxDB 1.x.
+//!
+//! See: <https://github.com/influxdata/influxdb/blob/1.8/query/neldermead/neldermead.go>
+
+/// The default maximum number of iterations performed by the optimizer.
+const DEFAULT_MAX_ITERATIONS: usize = 1000;
+/// The reflection coefficient.
+const DEFAULT_ALPHA: f64 = 1.0;
+/// The contraction coefficient.
+const DEFAULT_BETA: f64 = 0.5;
+/// The expansion coefficient.
+const DEFAULT_GAMMA: f64 = 2.0;
+
+/// Finds the minimum of an objective function using the Nelder-Mead
+/// simplex method.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Optimizer {
+    max_iterations: usize,
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+}
+
+impl Default for Optimizer {
+    fn default() -> Self {
+        Self {
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+            alpha: DEFAULT_ALPHA,
+            beta: DEFAULT_BETA,
+            gamma: DEFAULT_GAMMA,
+        }
+    }
+}
+
+impl Optimizer {
+    /// Minimise `objective`, starting from `start`, returning the minimum
+    /// value and the parameters that produced it.
+    ///
+    /// The objective function may modify the parameters it is passed, for
+    /// example, to constrain them to a valid range.
+    pub(super) fn optimize<F>(
+        &self,
+        mut objective: F,
+        start: &[f64],
+        epsilon: f64,
+        scale: f64,
+    ) -> (f64, Vec<f64>)
+    where
+        F: FnMut(&mut [f64]) -> f64,
+    {
+        let n = start.len();
+        let nf = n as f64;
+
+        // The vertices of the simplex and the value of the objective at each vertex.
+        let mut v = vec![start.to_vec(); n + 1];
+        let mut f = vec![0.0; n + 1];
+
+        // The coordinates of the reflection, expansion, contraction and centroid.
+        let mut vr = vec![0.0; n];
+        let mut ve = vec![0.0; n];
+        let mut vc = vec![0.0; n];
+        let mut vm = vec![0.0; n];
+
+        // Create the initial simplex, with one of the vertices at the start.
+        let pn = scale * ((nf + 1.0).sqrt() - 1.0 + nf) / (nf * 2_f64.sqrt());
+        let qn = scale * ((nf + 1.0).sqrt() - 1.0) / (nf * 2_f64.sqrt());
+
+        for (i, vertex) in v.iter_mut().enumerate().skip(1) {
+            for (j, x) in vertex.iter_mut().enumerate() {
+                *x = if i - 1 == j {
+                    pn + start[j]
+                } else {
+                    qn + start[j]
+                };
+            }
+        }
+
+        for (vertex, fx) in v.iter_mut().zip(f.iter_mut()) {
+            *fx = objective(vertex);
+        }
+
+        for _ in 0..self.max_iterations {
+            // Find the indexes of the largest and smallest values.
+            let mut vg = 0;
+            let mut vs = 0;
+            for (i, fx) in f.iter().enumerate() {
+                if *fx > f[vg] {
+                    vg = i;
+                }
+                if *fx < f[vs] {
+                    vs = i;
+                }
+            }
+            // Find the index of the second largest value.
+            let mut vh = vs;
+            for (i, fx) in f.iter().enumerate() {
+                if *fx > f[vh] && *fx < f[vg] {
+                    vh = i;
+                }
+            }
+
+            // Calculate the centroid, excluding the largest vertex.
+            for (i, c) in vm.iter_mut().enumerate() {
+                let cent = v
+                    .iter()
+                    .enumerate()
+                    .filter(|(m, _)| *m != vg)
+                    .map(|(_, vertex)| vertex[i])
+                    .sum::<f64>();
+                *c = cent / nf;
+            }
+
+            // Reflect vg to a new vertex vr.
+            for ((r, m), g) in vr.iter_mut().zip(&vm).zip(&v[vg]) {
+                *r = m + self.alpha * (m - g);
+            }
+            let fr = objective(&mut vr);
+
+            if fr < f[vh] && fr >= f[vs] {
+                v[vg].copy_from_slice(&vr);
+                f[vg] = fr;
+            }
+
+            // Investigate a step further in this direction.
+            if fr < f[vs] {
+                for ((e, m), r) in ve.iter_mut().zip(&vm).zip(&vr) {
+                    *e = m + self.gamma * (r - m);
+                }
+                let fe = objective(&mut ve);
+
+                // Using fe < fr, as opposed to fe < f[vs], matches the original.
+                if fe < fr {
+                    v[vg].copy_from_slice(&ve);
+                    f[vg] = fe;
+                } else {
+                    v[vg].copy_from_slice(&vr);
+                    f[vg] = fr;
+                }
+            }
+
+            // Check to see if a contraction is necessary.
+            if fr >= f[vh] {
+                if fr < f[vg] && fr >= f[vh] {
+                    // Perform an outside contraction.
+                    for ((c, m), r) in vc.iter_mut().zip(&vm).zip(&vr) {
+                        *c = m + self.beta * (r - m);
+                    }
+                } else {
+                    // Perform an inside contraction.
+                    for ((c, m), g) in vc.iter_mut().zip(&vm).zip(&v[vg]) {
+                        *c = m - self.beta * (m - g);
+                    }
+                }
+                let fc = objective(&mut vc);
+
+                if fc < f[vg] {
+                    v[vg].copy_from_slice(&vc);
+                    f[vg] = fc;
+                } else {
+                    // The contraction was not successful, so halve the
+                    // distance from vs to all the vertices of the simplex.
+                    let best = v[vs].clone();
+                    for (row, vertex) in v.iter_mut().enumerate() {
+                        if row != vs {
+                            for (x, b) in vertex.iter_mut().zip(&best) {
+                                *x = b + (*x - b) / 2.0;
+                            }
+                        }
+                    }
+                    f[vg] = objective(&mut v[vg]);
+                    f[vh] = objective(&mut v[vh]);
+                }
+            }
+
+            // Test for convergence.
+            let favg = f.iter().sum::<f64>() / (nf + 1.0);
+            let s = f
+                .iter()
+                .map(|fx| (fx - favg).powi(2) / nf)
+                .sum::<f64>()
+                .sqrt();
+            if s < epsilon {
+                break;
+            }
+        }
+
+        // Find the index of the smallest value.
+        let mut vs = 0;
+        for (i, fx) in f.iter().enumerate() {
+            if *fx < f[vs] {
+                vs = i;
+            }
+        }
+
+        let mut parameters = v.swap_remove(vs);
+        let min = objective(&mut parameters);
+        (min, parameters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_optimize_quadratic() {
+        let (min, params) = Optimizer::default().optimize(
+            |x| (x[0] - 1.0).powi(2) + (x[1] - 2.0).powi(2),
+            &[0.0, 0.0],
+            1e-10,
+            1.0,
+        );
+        assert!(min < 1e-4, "min = {min}");
+        assert!((params[0] - 1.0).abs() < 1e-2, "params = {params:?}");
+        assert!((params[1] - 2.0).abs() < 1e-2, "params = {params:?}");
+    }
+}
@@ -0,0 +1,454 @@
+use crate::error;
+use arrow::array::{
+    Array, ArrayRef, Int64Array, ListArray, StructArray, UInt64Array, as_list_array,
+    new_empty_array,
+};
+use arrow::buffer::OffsetBuffer;
+use arrow::datatypes::{DataType, Field, FieldRef, Fields, TimeUnit};
+use datafusion::common::{Result, ScalarValue, downcast_value, plan_err};
+use datafusion::logical_expr::function::{AccumulatorArgs, StateFieldsArgs};
+use datafusion::logical_expr::{Accumulator, AggregateUDFImpl, Signature, Volatility};
+use datafusion::physical_expr::expressions::format_state_name;
+use rand::Rng;
+use std::any::Any;
+use std::sync::Arc;
+
+/// The name of the value field of each point returned by [`SampleUDF`].
+pub(crate) const SAMPLE_VALUE_FIELD: &str = "value";
+/// The name of the time field of each point returned by [`SampleUDF`].
+pub(crate) const SAMPLE_TIME_FIELD: &str = "time";
+
+/// The name of the field for the additional argument at `idx` of each
+/// point returned by [`SampleUDF`], where `idx` starts at 1.
+pub(crate) fn sample_other_field(idx: usize) -> String {
+    format!("other_{idx}")
+}
+
+/// The `SAMPLE` selector function, which selects a random sample of
+/// `N` points using reservoir sampling.
+///
+/// The function expects the following arguments:
+///
+/// 1. the value of each point,
+/// 2. the `time` of each point,
+/// 3. `N`, the number of points to select, and
+/// 4. zero or more additional values, such as tags or fields, to select
+///    with each point.
+///
+/// The result is a list of structs, ordered by time, with a `value`,
+/// `time` and `other_N` field for each additional argument, which is
+/// expected to be unnested by the planner.
+#[derive(Debug)]
+pub(super) struct SampleUDF {
+    signature: Signature,
+}
+
+impl SampleUDF {
+    pub(super) fn new() -> Self {
+        Self {
+            // SAMPLE accepts values of any type, as well as any number of
+            // additional arguments, so the argument types are checked in
+            // `return_type`.
+            signature: Signature::variadic_any(Volatility::Immutable),
+        }
+    }
+}
+
+impl AggregateUDFImpl for SampleUDF {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "sample"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        match arg_types {
+            [
+                value,
+                time @ DataType::Timestamp(TimeUnit::Nanosecond, _),
+                DataType::Int64,
+                others @ ..,
+            ] => Ok(DataType::new_list(
+                DataType::Struct(point_fields(value, time, others)),
+                true,
+            )),
+            _ => plan_err!("sample() called with invalid arguments: {arg_types:?}"),
+        }
+    }
+
+    fn accumulator(&self, arg: AccumulatorArgs<'_>) -> Result<Box<dyn Accumulator>> {
+        let DataType::List(field) = arg.return_field.data_type() else {
+            return error::internal("sample return type must be a list");
+        };
+        let DataType::Struct(fields) = field.data_type() else {
+            return error::internal("sample return type must be a list of structs");
+        };
+        Ok(Box::new(SampleAccumulator::new(fields.clone())))
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs<'_>) -> Result<Vec<FieldRef>> {
+        Ok(vec![
+            Arc::new(Field::new(
+                format_state_name(args.name, "points"),
+                args.return_field.data_type().clone(),
+                true,
+            )),
+            Arc::new(Field::new(
+                format_state_name(args.name, "count"),
+                DataType::UInt64,
+                true,
+            )),
+            Arc::new(Field::new(
+                format_state_name(args.name, "n"),
+                DataType::Int64,
+                true,
+            )),
+        ])
+    }
+}
+
+/// The fields of the struct for each point returned by [`SampleUDF`].
+fn point_fields(value: &DataType, time: &DataType, others: &[DataType]) -> Fields {
+    [
+        Field::new(SAMPLE_VALUE_FIELD, value.clone(), true),
+        Field::new(SAMPLE_TIME_FIELD, time.clone(), true),
+    ]
+    .into_iter()
+    .chain(
+        others
+            .iter()
+            .enumerate()
+            .map(|(idx, dt)| Field::new(sample_other_field(idx + 1), dt.clone(), true)),
+    )
+    .collect()
+}
+
+/// A row of values for a single point, in the same order as the fields
+/// returned by [`point_fields`].
+type Point = Vec<ScalarValue>;
+
+#[derive(Debug)]
+struct SampleAccumulator {
+    fields: Fields,
+    /// The reservoir of selected points.
+    points: Vec<Point>,
+    /// The number of points observed.
+    count: u64,
+    /// The size of the reservoir.
+    n: Option<usize>,
+}
+
+impl SampleAccumulator {
+    fn new(fields: Fields) -> Self {
+        Self {
+            fields,
+            points: vec![],
+            count: 0,
+            n: None,
+        }
+    }
+
+    fn set_n(&mut self, array: &ArrayRef) -> Result<()> {
+        if self.n.is_none() && !array.is_empty() && array.is_valid(0) {
+            let n = downcast_value!(array, Int64Array).value(0);
+            self.n = Some(n.max(0) as usize);
+        }
+        Ok(())
+    }
+
+    /// Add the point at `idx` of `columns` to the reservoir.
+    ///
+    /// This follows the algorithm used by InfluxDB 1.x, which fills the
+    /// reservoir with the first `N` points and then replaces a random point
+    /// with decreasing probability.
+    ///
+    /// See: <https://github.com/influxdata/influxdb/blob/1.8/query/functions.go>
+    fn add(&mut self, columns: &[ArrayRef], idx: usize, rng: &mut impl Rng) -> Result<()> {
+        let n = self.n.unwrap_or_default();
+        self.count += 1;
+
+        let slot = if self.points.len() < n {
+            None
+        } else {
+            match rng.random_range(0..self.count) as usize {
+                rnd if rnd < self.points.len() => Some(rnd),
+                _ => return Ok(()),
+            }
+        };
+
+        let point = columns
+            .iter()
+            .map(|c| ScalarValue::try_from_array(c, idx))
+            .collect::<Result<Point>>()?;
+        match slot {
+            Some(rnd) => self.points[rnd] = point,
+            None => self.points.push(point),
+        }
+        Ok(())
+    }
+
+    /// Merge a reservoir of `points`, which were sampled from `count`
+    /// points, such that each of the `count + self.count` points have an
+    /// equal probability of being selected.
+    fn merge(&mut self, mut points: Vec<Point>, count: u64, rng: &mut impl Rng) {
+        let n = self.n.unwrap_or_default();
+        let mut mine = std::mem::take(&mut self.points);
+        let (mut remaining_mine, mut remaining_theirs) = (self.count, count);
+
+        while self.points.len() < n && (!mine.is_empty() || !points.is_empty()) {
+            let from_mine = points.is_empty()
+                || (!mine.is_empty()
+                    && rng.random_range(0..remaining_mine + remaining_theirs) < remaining_mine);
+            let (src, remaining) = if from_mine {
+                (&mut mine, &mut remaining_mine)
+            } else {
+                (&mut points, &mut remaining_theirs)
+            };
+            let idx = rng.random_range(0..src.len());
+            self.points.push(src.swap_remove(idx));
+            *remaining = remaining.saturating_sub(1);
+        }
+        self.count += count;
+    }
+
+    /// Returns the points of the reservoir, ordered by time.
+    fn to_list(&self) -> Result<ScalarValue> {
+        let mut points = self.points.clone();
+        points.sort_by(|a, b| a[1].partial_cmp(&b[1]).unwrap_or(std::cmp::Ordering::Equal));
+
+        let columns = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| {
+                if points.is_empty() {
+                    Ok(new_empty_array(field.data_type()))
+                } else {
+                    ScalarValue::iter_to_array(points.iter().map(|p| p[idx].clone()))
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let points = StructArray::try_new(self.fields.clone(), columns, None)?;
+
+        Ok(ScalarValue::List(Arc::new(ListArray::new(
+            Arc::new(Field::new_list_field(
+                DataType::Struct(self.fields.clone()),
+                true,
+            )),
+            OffsetBuffer::from_lengths([points.len()]),
+            Arc::new(points),
+            None,
+        ))))
+    }
+}
+
+impl Accumulator for SampleAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        assert!(values.len() >= 3, "sample expects at least three arguments");
+
+        self.set_n(&values[2])?;
+
+        // The columns of each point, skipping the N argument.
+        let columns = [&values[..2], &values[3..]].concat();
+        let nulls = values[0].nulls();
+
+        let mut rng = rand::rng();
+        for idx in 0..values[0].len() {
+            if nulls.is_none_or(|nb| nb.is_valid(idx)) {
+                self.add(&columns, idx, &mut rng)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        self.to_list()
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + self
+                .points
+                .iter()
+                .map(ScalarValue::size_of_vec)
+                .sum::<usize>()
+    }
+
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        Ok(vec![
+            self.to_list()?,
+            ScalarValue::UInt64(Some(self.count)),
+            ScalarValue::Int64(self.n.map(|n| n as i64)),
+        ])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        assert_eq!(states.len(), 3);
+
+        let lists = as_list_array(&states[0]);
+        let counts = downcast_value!(states[1], UInt64Array);
+        let mut rng = rand::rng();
+        for idx in 0..lists.len() {
+            self.set_n(&states[2].slice(idx, 1))?;
+
+            let list = lists.value(idx);
+            let points = downcast_value!(list, StructArray);
+            let points = (0..points.len())
+                .map(|row| {
+                    points
+                        .columns()
+                        .iter()
+                        .map(|c| ScalarValue::try_from_array(c, row))
+                        .collect::<Result<Point>>()
+                })
+                .collect::<Result<Vec<_>>>()?;
+            self.merge(points, counts.value(idx), &mut rng);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Float64Array, TimestampNanosecondArray};
+    use rand::{SeedableRng, rngs::StdRng};
+
+    fn accumulator(n: i64) -> SampleAccumulator {
+        let mut acc = SampleAccumulator::new(point_fields(
+            &DataType::Float64,
+            &DataType::Timestamp(TimeUnit::Nanosecond, None),
+            &[],
+        ));
+        acc.set_n(&(Arc::new(Int64Array::from(vec![n])) as ArrayRef))
+            .unwrap();
+        acc
+    }
+
+    fn update(acc: &mut SampleAccumulator, times: std::ops::Range<i64>) {
+        let len = times.end - times.start;
+        let values: ArrayRef = Arc::new(Float64Array::from_iter_values(
+            times.clone().map(|t| t as f64),
+        ));
+        let times: ArrayRef = Arc::new(TimestampNanosecondArray::from_iter_values(times));
+        let n: ArrayRef = Arc::new(Int64Array::from(vec![0; len as usize]));
+        acc.update_batch(&[values, times, n]).unwrap();
+    }
+
+    fn times(acc: &SampleAccumulator) -> Vec<i64> {
+        acc.points
+            .iter()
+            .map(|p| match &p[1] {
+                ScalarValue::TimestampNanosecond(Some(t), _) => *t,
+                v => panic!("unexpected time {v:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sample_fewer_than_n() {
+        let mut acc = accumulator(5);
+        update(&mut acc, 0..3);
+        assert_eq!(times(&acc), vec![0, 1, 2]);
+        assert_eq!(acc.count, 3);
+    }
+
+    #[test]
+    fn test_sample_n() {
+        let mut acc = accumulator(3);
+        update(&mut acc, 0..100);
+        assert_eq!(acc.count, 100);
+
+        let mut got = times(&acc);
+        got.sort_unstable();
+        got.dedup();
+        assert_eq!(got.len(), 3, "points must be distinct: {got:?}");
+    }
+
+    #[test]
+    fn test_sample_merge() {
+        let mut acc = accumulator(3);
+        update(&mut acc, 0..10);
+        let mut other = accumulator(3);
+        update(&mut other, 10..20);
+
+        let state = other
+            .state()
+            .unwrap()
+            .into_iter()
+            .map(|v| v.to_array().unwrap())
+            .collect::<Vec<_>>();
+        acc.merge_batch(&state).unwrap();
+
+        assert_eq!(acc.count, 20);
+        let mut got = times(&acc);
+        got.sort_unstable();
+        got.dedup();
+        assert_eq!(got.len(), 3, "points must be distinct: {got:?}");
+    }
+
+    /// Sample the points `0..100` with a seeded random number generator,
+    /// merging two partial reservoirs.
+    fn seeded_sample(seed: u64) -> SampleAccumulator {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let columns = |times: std::ops::Range<i64>| -> Vec<ArrayRef> {
+            vec![
+                Arc::new(Float64Array::from_iter_values(
+                    times.clone().map(|t| t as f64),
+                )),
+                Arc::new(TimestampNanosecondArray::from_iter_values(times)),
+            ]
+        };
+
+        let mut acc = accumulator(3);
+        let batch = columns(0..50);
+        for idx in 0..50 {
+            acc.add(&batch, idx, &mut rng).unwrap();
+        }
+
+        let mut other = accumulator(3);
+        let batch = columns(50..100);
+        for idx in 0..50 {
+            other.add(&batch, idx, &mut rng).unwrap();
+        }
+        acc.merge(other.points, other.count, &mut rng);
+        acc
+    }
+
+    #[test]
+    fn test_sample_seeded() {
+        let acc = seeded_sample(42);
+        assert_eq!(acc.count, 100);
+
+        // The same seed selects the same points.
+        let got = times(&acc);
+        assert_eq!(got, times(&seeded_sample(42)));
+
+        let mut distinct = got.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        assert_eq!(distinct.len(), 3, "points must be distinct: {got:?}");
+
+        // The evaluated list contains a single row for each point, ordered by time.
+        let ScalarValue::List(list) = acc.to_list().unwrap() else {
+            panic!("expected a list");
+        };
+        assert_eq!(list.value_length(0), 3);
+        let points = list.value(0);
+        let points = points.as_any().downcast_ref::<StructArray>().unwrap();
+        let point_times = points
+            .column(1)
+            .as_any()
+            .downcast_ref::<TimestampNanosecondArray>()
+            .unwrap()
+            .values()
+            .to_vec();
+        assert_eq!(point_times, distinct);
+    }
+}
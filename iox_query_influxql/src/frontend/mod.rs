@@ -1 +1,3 @@
+pub mod namespace;
 pub mod planner;
+pub mod select_into;
@@ -0,0 +1,301 @@
+//! Resolution of the database and retention policy of qualified measurement names.
+
+use std::collections::BTreeSet;
+use std::fmt::{Debug, Display};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::catalog::SchemaProvider as CatalogSchemaProvider;
+use datafusion::error::{DataFusionError, Result};
+use influxdb_influxql_parser::common::QualifiedMeasurementName;
+use influxdb_influxql_parser::show_measurements::ExtendedOnClause;
+use influxdb_influxql_parser::statement::Statement;
+use influxdb_influxql_parser::visit::{Recursion, Visitable, Visitor};
+
+/// A database and optional retention policy of a qualified measurement name,
+/// such as `"telegraf"."autogen"."cpu"`, or of the `ON` clause of a metadata
+/// statement.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QualifiedDatabase {
+    /// The name of the database.
+    pub database: String,
+    /// The name of the retention policy, if specified.
+    pub retention_policy: Option<String>,
+}
+
+impl Display for QualifiedDatabase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.database)?;
+        if let Some(rp) = &self.retention_policy {
+            write!(f, ".{rp}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Resolves the database and retention policy of qualified measurement names
+/// to the tables of a namespace, allowing a single InfluxQL statement to read
+/// from multiple namespaces.
+///
+/// The resolver is not responsible for authorization. Callers must verify the
+/// client is permitted to read every database returned by [`find_databases`]
+/// before planning the statement.
+#[async_trait]
+pub trait NamespaceResolver: Debug + Send + Sync {
+    /// Returns the schema of the namespace that `database` and `retention_policy`
+    /// map to, or `None` if the namespace does not exist.
+    async fn resolve(
+        &self,
+        database: &str,
+        retention_policy: Option<&str>,
+    ) -> Result<Option<Arc<dyn CatalogSchemaProvider>>>;
+
+    /// Returns the names of the databases visible to the caller, for the
+    /// `SHOW DATABASES` statement.
+    async fn database_names(&self) -> Result<Vec<String>> {
+        Err(DataFusionError::NotImplemented("SHOW DATABASES".to_owned()))
+    }
+
+    /// Returns the names of the retention policies of `database` visible to
+    /// the caller, for the `SHOW RETENTION POLICIES ON <database>` statement.
+    ///
+    /// The namespace named `database` is the `autogen` retention policy, and
+    /// the namespace of any other retention policy is named
+    /// `<database>/<retention policy>`.
+    async fn retention_policy_names(&self, _database: &str) -> Result<Vec<String>> {
+        Err(DataFusionError::NotImplemented(
+            "SHOW RETENTION POLICIES ON <database>".to_owned(),
+        ))
+    }
+}
+
+/// Returns the set of databases referenced by qualified measurement names in
+/// `stmt`, including those of subqueries.
+pub fn find_qualified_databases(stmt: &Statement) -> Result<BTreeSet<QualifiedDatabase>> {
+    struct Matcher<'a>(&'a mut BTreeSet<QualifiedDatabase>);
+    impl Visitor for Matcher<'_> {
+        type Error = DataFusionError;
+
+        fn pre_visit_qualified_measurement_name(
+            self,
+            n: &QualifiedMeasurementName,
+        ) -> Result<Recursion<Self>, Self::Error> {
+            if let Some(database) = &n.database {
+                self.0.insert(QualifiedDatabase {
+                    database: database.as_str().to_owned(),
+                    retention_policy: n.retention_policy.as_ref().map(|rp| rp.as_str().to_owned()),
+                });
+            }
+            Ok(Recursion::Continue(self))
+        }
+    }
+
+    let mut databases = BTreeSet::new();
+    stmt.accept(Matcher(&mut databases))?;
+    Ok(databases)
+}
+
+/// Returns the database targeted by the `ON` clause of a metadata statement,
+/// such as `SHOW TAG KEYS ON "telegraf"`.
+///
+/// `SHOW MEASUREMENTS ON *` does not target a single database and returns `None`.
+pub fn find_on_database(stmt: &Statement) -> Option<QualifiedDatabase> {
+    let (database, retention_policy) = match stmt {
+        Statement::Explain(explain) => return find_on_database(&explain.statement),
+        Statement::ShowMeasurements(s) => match &s.on {
+            Some(ExtendedOnClause::Database(db)) => (db, None),
+            Some(ExtendedOnClause::DatabaseRetentionPolicy(db, rp)) => (db, Some(rp)),
+            _ => return None,
+        },
+        Statement::ShowRetentionPolicies(s) => (&**s.database.as_ref()?, None),
+        Statement::ShowTagKeys(s) => (&**s.database.as_ref()?, None),
+        Statement::ShowTagValues(s) => (&**s.database.as_ref()?, None),
+        Statement::ShowFieldKeys(s) => (&**s.database.as_ref()?, None),
+        Statement::ShowMeasurementCardinality(s) => (&**s.database.as_ref()?, None),
+        Statement::ShowSeries(s) => (&**s.database.as_ref()?, None),
+        Statement::ShowSeriesCardinality(s) => (&**s.database.as_ref()?, None),
+        Statement::ShowTagValuesCardinality(s) => (&**s.database.as_ref()?, None),
+        _ => return None,
+    };
+
+    Some(QualifiedDatabase {
+        database: database.as_str().to_owned(),
+        retention_policy: retention_policy.map(|rp| rp.as_str().to_owned()),
+    })
+}
+
+/// The namespace that a `SELECT ... INTO` statement writes to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntoDatabase {
+    /// The session namespace, when the `INTO` clause does not specify a database.
+    Session,
+    /// The namespace of the database and retention policy of the `INTO` clause.
+    Qualified(QualifiedDatabase),
+}
+
+/// Returns the namespace that `stmt` writes to, if it is a `SELECT ... INTO`
+/// statement.
+///
+/// An `INTO` clause that specifies a retention policy without a database, such
+/// as `INTO "one_year"."cpu"`, is rejected, as the database of the session
+/// namespace is not known.
+pub fn find_into_database(stmt: &Statement) -> Result<Option<IntoDatabase>> {
+    let Statement::Select(select) = stmt else {
+        return Ok(None);
+    };
+    let Some(into) = select.into.as_ref() else {
+        return Ok(None);
+    };
+
+    Ok(Some(match (&into.database, &into.retention_policy) {
+        (Some(database), retention_policy) => IntoDatabase::Qualified(QualifiedDatabase {
+            database: database.as_str().to_owned(),
+            retention_policy: retention_policy.as_ref().map(|rp| rp.as_str().to_owned()),
+        }),
+        (None, None) => IntoDatabase::Session,
+        (None, Some(retention_policy)) => {
+            return Err(DataFusionError::Plan(format!(
+                "INTO clause with retention policy {retention_policy} must also specify the database"
+            )));
+        }
+    }))
+}
+
+/// Returns the set of databases other than the session namespace that `stmt`
+/// reads, which is those of [`find_qualified_databases`] and [`find_on_database`].
+pub fn find_databases(stmt: &Statement) -> Result<BTreeSet<QualifiedDatabase>> {
+    let mut databases = find_qualified_databases(stmt)?;
+    databases.extend(find_on_database(stmt));
+    Ok(databases)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::frontend::planner::InfluxQLQueryPlanner;
+    use test_helpers::assert_error;
+
+    #[test]
+    fn test_find_qualified_databases() {
+        fn find(q: &str) -> Vec<(String, Option<String>)> {
+            let s = InfluxQLQueryPlanner::query_to_statement(q).unwrap();
+            find_qualified_databases(&s)
+                .unwrap()
+                .into_iter()
+                .map(|d| (d.database, d.retention_policy))
+                .collect()
+        }
+
+        assert!(find("SELECT * FROM cpu").is_empty());
+        assert!(find("SELECT * FROM autogen.cpu").is_empty());
+        assert_eq!(
+            find(r#"SELECT * FROM "telegraf"."autogen"."cpu""#),
+            vec![("telegraf".into(), Some("autogen".into()))]
+        );
+        assert_eq!(
+            find("SELECT * FROM telegraf..cpu, telegraf../^disk/, cpu"),
+            vec![("telegraf".into(), None)]
+        );
+
+        // Finds databases of subqueries
+        assert_eq!(
+            find("SELECT * FROM db1..cpu, (SELECT * FROM db0.rp.cpu)"),
+            vec![("db0".into(), Some("rp".into())), ("db1".into(), None)]
+        );
+    }
+
+    #[test]
+    fn test_qualified_database_display() {
+        let mut db = QualifiedDatabase {
+            database: "telegraf".into(),
+            retention_policy: None,
+        };
+        assert_eq!(db.to_string(), "telegraf");
+        db.retention_policy = Some("autogen".into());
+        assert_eq!(db.to_string(), "telegraf.autogen");
+    }
+
+    #[test]
+    fn test_find_into_database() {
+        fn find(q: &str) -> Option<IntoDatabase> {
+            let s = InfluxQLQueryPlanner::query_to_statement(q).unwrap();
+            find_into_database(&s).unwrap()
+        }
+
+        assert_eq!(find("SELECT * FROM cpu"), None);
+        assert_eq!(find("EXPLAIN SELECT * INTO foo FROM cpu"), None);
+        assert_eq!(
+            find("SELECT * INTO foo FROM cpu"),
+            Some(IntoDatabase::Session)
+        );
+        assert_eq!(
+            find("SELECT * INTO telegraf..:MEASUREMENT FROM cpu"),
+            Some(IntoDatabase::Qualified(QualifiedDatabase {
+                database: "telegraf".into(),
+                retention_policy: None,
+            }))
+        );
+        assert_eq!(
+            find("SELECT * INTO telegraf.one_year.foo FROM cpu"),
+            Some(IntoDatabase::Qualified(QualifiedDatabase {
+                database: "telegraf".into(),
+                retention_policy: Some("one_year".into()),
+            }))
+        );
+
+        // The retention policy requires the database
+        let s = InfluxQLQueryPlanner::query_to_statement("SELECT * INTO one_year.foo FROM cpu")
+            .unwrap();
+        assert_error!(
+            find_into_database(&s),
+            DataFusionError::Plan(ref msg) if msg == "INTO clause with retention policy one_year must also specify the database"
+        );
+    }
+
+    #[test]
+    fn test_find_databases() {
+        fn find(q: &str) -> Vec<(String, Option<String>)> {
+            let s = InfluxQLQueryPlanner::query_to_statement(q).unwrap();
+            find_databases(&s)
+                .unwrap()
+                .into_iter()
+                .map(|d| (d.database, d.retention_policy))
+                .collect()
+        }
+
+        assert!(find("SHOW TAG KEYS").is_empty());
+        assert!(find("SHOW MEASUREMENTS ON *").is_empty());
+        assert_eq!(
+            find("SHOW TAG KEYS ON telegraf"),
+            vec![("telegraf".into(), None)]
+        );
+        assert_eq!(
+            find("SHOW FIELD KEYS ON telegraf"),
+            vec![("telegraf".into(), None)]
+        );
+        assert_eq!(
+            find("SHOW TAG VALUES ON telegraf WITH KEY = host"),
+            vec![("telegraf".into(), None)]
+        );
+        assert_eq!(
+            find("SHOW RETENTION POLICIES ON telegraf"),
+            vec![("telegraf".into(), None)]
+        );
+        assert_eq!(
+            find("SHOW SERIES ON telegraf"),
+            vec![("telegraf".into(), None)]
+        );
+        assert_eq!(
+            find("SHOW MEASUREMENT CARDINALITY ON telegraf"),
+            vec![("telegraf".into(), None)]
+        );
+        assert_eq!(
+            find("EXPLAIN SHOW MEASUREMENTS ON telegraf.autogen"),
+            vec![("telegraf".into(), Some("autogen".into()))]
+        );
+        assert_eq!(
+            find("SELECT * FROM db0..cpu, db1.rp.cpu"),
+            vec![("db0".into(), None), ("db1".into(), Some("rp".into()))]
+        );
+    }
+}
@@ -2,9 +2,14 @@ use arrow::datatypes::SchemaRef;
 
 use datafusion::physical_expr::execution_props::ExecutionProps;
 use influxdb_influxql_parser::show_field_keys::ShowFieldKeysStatement;
-use influxdb_influxql_parser::show_measurements::ShowMeasurementsStatement;
+use influxdb_influxql_parser::show_measurements::{
+    ShowMeasurementCardinalityStatement, ShowMeasurementsStatement,
+};
+use influxdb_influxql_parser::show_series::{ShowSeriesCardinalityStatement, ShowSeriesStatement};
 use influxdb_influxql_parser::show_tag_keys::ShowTagKeysStatement;
-use influxdb_influxql_parser::show_tag_values::ShowTagValuesStatement;
+use influxdb_influxql_parser::show_tag_values::{
+    ShowTagValuesCardinalityStatement, ShowTagValuesStatement,
+};
 use iox_query_params::StatementParams;
 use std::any::Any;
 use std::collections::{HashMap, HashSet};
@@ -13,7 +18,13 @@ use std::fmt::Debug;
 use std::ops::Deref;
 use std::sync::Arc;
 
+use super::namespace::{
+    NamespaceResolver, QualifiedDatabase, find_into_database, find_on_database,
+    find_qualified_databases,
+};
+use super::select_into::{SelectIntoExec, WriteSink};
 use crate::plan::{InfluxQLToLogicalPlan, SchemaProvider, parse_regex};
+use datafusion::catalog::SchemaProvider as CatalogSchemaProvider;
 use datafusion::datasource::provider_as_source;
 use datafusion::execution::context::{SessionState, TaskContext};
 use datafusion::logical_expr::{AggregateUDF, LogicalPlan, ScalarUDF, TableSource};
@@ -26,17 +37,113 @@ use datafusion::{
     error::{DataFusionError, Result},
     physical_plan::ExecutionPlan,
 };
-use influxdb_influxql_parser::common::MeasurementName;
+use influxdb_influxql_parser::common::{MeasurementName, QualifiedMeasurementName};
 use influxdb_influxql_parser::parse_statements;
 use influxdb_influxql_parser::statement::Statement;
-use influxdb_influxql_parser::visit::{Visitable, Visitor};
+use influxdb_influxql_parser::visit::{Recursion, Visitable, Visitor};
 use iox_query::exec::IOxSessionContext;
 use schema::Schema;
 use tracing::debug;
 
+/// The database, retention policy and measurement of a table of a qualified
+/// measurement name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct QualifiedTable {
+    database: QualifiedDatabase,
+    measurement: String,
+}
+
 struct ContextSchemaProvider<'a> {
     state: &'a SessionState,
     tables: HashMap<String, (Arc<dyn TableSource>, Schema)>,
+    /// `true` if qualified measurement names may be resolved.
+    has_resolver: bool,
+    /// The tables of qualified measurement names, keyed by database and retention policy.
+    qualified_tables: HashMap<QualifiedDatabase, Vec<String>>,
+    /// Maps the names of the tables of qualified measurement names to the table.
+    qualified_names: HashMap<String, QualifiedTable>,
+    /// The schemas of databases targeted by the `ON` clause of metadata statements.
+    databases: HashMap<QualifiedDatabase, ContextSchemaProvider<'a>>,
+    /// The names of the databases, when planning a `SHOW DATABASES` statement.
+    database_names: Option<Vec<String>>,
+    /// The names of the retention policies, keyed by database, when planning a
+    /// `SHOW RETENTION POLICIES ON <database>` statement.
+    retention_policy_names: HashMap<String, Vec<String>>,
+}
+
+impl<'a> ContextSchemaProvider<'a> {
+    fn new(state: &'a SessionState, has_resolver: bool) -> Self {
+        Self {
+            state,
+            tables: HashMap::new(),
+            has_resolver,
+            qualified_tables: HashMap::new(),
+            qualified_names: HashMap::new(),
+            databases: HashMap::new(),
+            database_names: None,
+            retention_policy_names: HashMap::new(),
+        }
+    }
+
+    /// Add the `table_names` of `schema`, which exist, to the tables of
+    /// this provider.
+    async fn add_tables(
+        &mut self,
+        schema: &dyn CatalogSchemaProvider,
+        table_names: &HashSet<String>,
+        ctx: &IOxSessionContext,
+    ) -> Result<()> {
+        use std::collections::hash_map::Entry;
+
+        for table_name in table_names {
+            if let Entry::Vacant(v) = self.tables.entry(table_name.to_string()) {
+                let mut ctx = ctx.child_ctx("get table schema");
+                ctx.set_metadata("table", table_name.to_owned());
+
+                if let Some(table) = schema.table(table_name).await? {
+                    let schema = Schema::try_from(table.schema())
+                        .map_err(|err| {
+                            DataFusionError::Internal(format!("unable to convert DataFusion schema for measurement {table_name} to IOx schema: {err}"))
+                        })?;
+                    v.insert((provider_as_source(table), schema));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Add the `table` of a qualified measurement name to the tables of this
+    /// provider, returning the name used to refer to it.
+    ///
+    /// The name is derived from the database, retention policy and measurement,
+    /// and is made unique should it match the name of another table.
+    fn add_qualified_table(
+        &mut self,
+        qualified: QualifiedTable,
+        table: (Arc<dyn TableSource>, Schema),
+    ) -> String {
+        let base = format!(
+            "{}.{}.{}",
+            qualified.database.database,
+            qualified
+                .database
+                .retention_policy
+                .as_deref()
+                .unwrap_or_default(),
+            qualified.measurement
+        );
+        let mut name = base.clone();
+        let mut n = 1;
+        while self.tables.contains_key(&name) {
+            name = format!("{base}#{n}");
+            n += 1;
+        }
+
+        self.tables.insert(name.clone(), table);
+        self.qualified_names.insert(name.clone(), qualified);
+        name
+    }
 }
 
 impl SchemaProvider for ContextSchemaProvider<'_> {
@@ -56,17 +163,86 @@ impl SchemaProvider for ContextSchemaProvider<'_> {
     }
 
     fn table_names(&self) -> Vec<&'_ str> {
-        self.tables.keys().map(|k| k.as_str()).collect::<Vec<_>>()
+        self.tables
+            .keys()
+            .filter(|k| !self.qualified_names.contains_key(*k))
+            .map(|k| k.as_str())
+            .collect::<Vec<_>>()
     }
 
     fn table_exists(&self, name: &str) -> bool {
-        self.tables.contains_key(name)
+        self.tables.contains_key(name) && !self.qualified_names.contains_key(name)
     }
 
     fn table_schema(&self, name: &str) -> Option<Schema> {
         self.tables.get(name).map(|(_, s)| s.clone())
     }
 
+    fn qualified_table_names(
+        &self,
+        database: &str,
+        retention_policy: Option<&str>,
+    ) -> Result<Vec<&'_ str>> {
+        if !self.has_resolver {
+            return Err(DataFusionError::NotImplemented(
+                "database prefix in qualified measurement syntax".to_owned(),
+            ));
+        }
+
+        let key = QualifiedDatabase {
+            database: database.to_owned(),
+            retention_policy: retention_policy.map(ToOwned::to_owned),
+        };
+        Ok(self
+            .qualified_tables
+            .get(&key)
+            .map(|tables| tables.iter().map(|t| t.as_str()).collect())
+            .unwrap_or_default())
+    }
+
+    fn measurement_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.qualified_names
+            .get(name)
+            .map_or(name, |table| table.measurement.as_str())
+    }
+
+    fn database_names(&self) -> Result<Vec<&'_ str>> {
+        match &self.database_names {
+            Some(names) => Ok(names.iter().map(String::as_str).collect()),
+            None => Err(DataFusionError::NotImplemented("SHOW DATABASES".to_owned())),
+        }
+    }
+
+    fn retention_policy_names(&self, database: &str) -> Result<Vec<&'_ str>> {
+        if !self.has_resolver {
+            return Err(DataFusionError::NotImplemented(
+                "SHOW RETENTION POLICIES ON <database>".to_owned(),
+            ));
+        }
+
+        Ok(self
+            .retention_policy_names
+            .get(database)
+            .map(|names| names.iter().map(String::as_str).collect())
+            .unwrap_or_default())
+    }
+
+    fn database_schema(
+        &self,
+        database: &str,
+        retention_policy: Option<&str>,
+    ) -> Result<Option<&dyn SchemaProvider>> {
+        if !self.has_resolver {
+            return Err(DataFusionError::NotImplemented("ON <database>".to_owned()));
+        }
+
+        let key = QualifiedDatabase {
+            database: database.to_owned(),
+            retention_policy: retention_policy.map(ToOwned::to_owned),
+        };
+        Ok(self.databases.get(&key).map(|sp| sp as &dyn SchemaProvider))
+    }
+
     fn execution_props(&self) -> &ExecutionProps {
         self.state.execution_props()
     }
@@ -181,16 +357,48 @@ pub struct InfluxQLQueryPlanner;
 impl InfluxQLQueryPlanner {
     /// Plan an InfluxQL query against the catalogs registered with `ctx`, and return a
     /// DataFusion physical execution plan that runs on the query executor.
+    ///
+    /// Qualified measurement names, such as `"telegraf"."autogen"."cpu"`, are resolved
+    /// using `resolver`, and are not supported when it is `None`.
+    ///
+    /// The points of a `SELECT ... INTO` statement are written to `sink`, and the
+    /// statement is not supported when it is `None`.
     pub async fn query(
         query: &str,
         params: impl Into<StatementParams> + Send,
+        resolver: Option<&dyn NamespaceResolver>,
+        sink: Option<Arc<dyn WriteSink>>,
         ctx: &IOxSessionContext,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Self::query_with_rewrite(query, params, resolver, sink, ctx, Ok).await
+    }
+
+    /// Plan an InfluxQL query like [`Self::query`], rewriting its logical plan
+    /// with `rewrite` before creating the physical plan.
+    pub async fn query_with_rewrite(
+        query: &str,
+        params: impl Into<StatementParams> + Send,
+        resolver: Option<&dyn NamespaceResolver>,
+        sink: Option<Arc<dyn WriteSink>>,
+        ctx: &IOxSessionContext,
+        rewrite: impl FnOnce(LogicalPlan) -> Result<LogicalPlan> + Send,
     ) -> Result<Arc<dyn ExecutionPlan>> {
         let ctx = ctx.child_ctx("InfluxQLQueryPlanner::query");
         debug!(text=%query, "planning InfluxQL query");
 
         let statement = Self::query_to_statement(query)?;
-        let logical_plan = Self::statement_to_plan(statement, params, &ctx).await?;
+        // reject INTO clauses that do not specify the namespace to write to
+        find_into_database(&statement)?;
+        let into = match &statement {
+            Statement::Select(select) => select.into.clone(),
+            _ => None,
+        };
+        if into.is_some() && sink.is_none() {
+            return Err(DataFusionError::NotImplemented("SELECT INTO".to_owned()));
+        }
+
+        let logical_plan =
+            rewrite(Self::statement_to_plan(statement, params, resolver, &ctx).await?)?;
         // add params to plan only when they're non-empty
         let input = ctx.create_physical_plan(&logical_plan).await?;
 
@@ -205,17 +413,20 @@ impl InfluxQLQueryPlanner {
             md,
         ));
 
-        Ok(Arc::new(SchemaExec::new(input, schema)))
+        let plan = Arc::new(SchemaExec::new(input, schema));
+        match (into, sink) {
+            (Some(into), Some(sink)) => Ok(Arc::new(SelectIntoExec::try_new(plan, into, sink)?)),
+            _ => Ok(plan),
+        }
     }
 
     /// Process a [`Statement`] and its associated params into a [`LogicalPlan`] fallibly
     pub async fn statement_to_plan(
         statement: Statement,
         params: impl Into<StatementParams> + Send,
+        resolver: Option<&dyn NamespaceResolver>,
         ctx: &IOxSessionContext,
     ) -> Result<LogicalPlan> {
-        use std::collections::hash_map::Entry;
-
         let ctx = ctx.child_ctx("statement_to_plan");
         let session_cfg = ctx.inner().copied_config();
         let cfg = session_cfg.options();
@@ -238,23 +449,74 @@ impl InfluxQLQueryPlanner {
         let names = schema.table_names();
         let query_tables = find_all_measurements(&statement, &names)?;
 
-        let mut sp = ContextSchemaProvider {
-            state: &ctx.inner().state(),
-            tables: HashMap::with_capacity(query_tables.len()),
-        };
+        let state = ctx.inner().state();
+        let mut sp = ContextSchemaProvider::new(&state, resolver.is_some());
+        sp.add_tables(schema.as_ref(), &query_tables, &ctx).await?;
 
-        for table_name in &query_tables {
-            if let Entry::Vacant(v) = sp.tables.entry(table_name.to_string()) {
-                let mut ctx = ctx.child_ctx("get table schema");
-                ctx.set_metadata("table", table_name.to_owned());
+        if let Some(resolver) = resolver {
+            if is_show_databases(&statement) {
+                sp.database_names = Some(resolver.database_names().await?);
+            }
 
-                if let Some(table) = schema.table(table_name).await? {
-                    let schema = Schema::try_from(table.schema())
+            if let Some(on) = find_on_database(&statement)
+                && is_show_retention_policies(&statement)
+            {
+                // The retention policies are those of the namespaces of the
+                // database, so the namespace of the database itself need not exist.
+                let names = resolver.retention_policy_names(&on.database).await?;
+                sp.retention_policy_names.insert(on.database, names);
+            } else if let Some(on) = find_on_database(&statement) {
+                let Some(schema) = resolver
+                    .resolve(&on.database, on.retention_policy.as_deref())
+                    .await?
+                else {
+                    return Err(DataFusionError::Plan(format!("database not found: {on}")));
+                };
+
+                let names = schema.table_names();
+                let query_tables = find_all_measurements(&statement, &names)?;
+                let mut db = ContextSchemaProvider::new(&state, false);
+                db.add_tables(schema.as_ref(), &query_tables, &ctx).await?;
+                sp.databases.insert(on, db);
+            }
+
+            for qualified in find_qualified_databases(&statement)? {
+                let QualifiedDatabase {
+                    database,
+                    retention_policy,
+                } = &qualified;
+
+                let Some(schema) = resolver
+                    .resolve(database, retention_policy.as_deref())
+                    .await?
+                else {
+                    return Err(DataFusionError::Plan(format!(
+                        "database not found: {qualified}"
+                    )));
+                };
+
+                let names = schema.table_names();
+                let mut tables = Vec::new();
+                for measurement in find_all_qualified_measurements(&statement, &qualified, &names)?
+                {
+                    let Some(table) = schema.table(&measurement).await? else {
+                        continue;
+                    };
+                    let table_schema = Schema::try_from(table.schema())
                         .map_err(|err| {
-                            DataFusionError::Internal(format!("unable to convert DataFusion schema for measurement {table_name} to IOx schema: {err}"))
+                            DataFusionError::Internal(format!("unable to convert DataFusion schema for measurement {measurement} to IOx schema: {err}"))
                         })?;
-                    v.insert((provider_as_source(table), schema));
+
+                    let table_name = sp.add_qualified_table(
+                        QualifiedTable {
+                            database: qualified.clone(),
+                            measurement,
+                        },
+                        (provider_as_source(table), table_schema),
+                    );
+                    tables.push(table_name);
                 }
+                sp.qualified_tables.insert(qualified, tables);
             }
         }
 
@@ -278,11 +540,94 @@ impl InfluxQLQueryPlanner {
     }
 }
 
+/// Returns the set of `tables` referenced by qualified measurement names of the
+/// `qualified` database and retention policy.
+fn find_all_qualified_measurements(
+    stmt: &Statement,
+    qualified: &QualifiedDatabase,
+    tables: &[String],
+) -> Result<HashSet<String>> {
+    struct Matcher<'a>(&'a mut HashSet<String>, &'a QualifiedDatabase, &'a [String]);
+    impl Visitor for Matcher<'_> {
+        type Error = DataFusionError;
+
+        fn pre_visit_qualified_measurement_name(
+            self,
+            n: &QualifiedMeasurementName,
+        ) -> Result<Recursion<Self>, Self::Error> {
+            let Some(database) = &n.database else {
+                return Ok(Recursion::Continue(self));
+            };
+            if database.as_str() != self.1.database
+                || n.retention_policy.as_ref().map(|rp| rp.as_str())
+                    != self.1.retention_policy.as_deref()
+            {
+                return Ok(Recursion::Stop(self));
+            }
+
+            match &n.name {
+                MeasurementName::Name(name) => {
+                    let name = name.deref();
+                    if self.2.contains(name) {
+                        self.0.insert(name.to_string());
+                    }
+                }
+                MeasurementName::Regex(re) => {
+                    let re = parse_regex(re)?;
+
+                    self.2
+                        .iter()
+                        .filter(|table| re.is_match(table))
+                        .for_each(|table| {
+                            self.0.insert(table.into());
+                        });
+                }
+            }
+
+            Ok(Recursion::Stop(self))
+        }
+    }
+
+    let mut m = HashSet::new();
+    let vis = Matcher(&mut m, qualified, tables);
+    stmt.accept(vis)?;
+
+    Ok(m)
+}
+
+/// Returns `true` if `stmt` is a `SHOW DATABASES` statement, which may be the
+/// subject of an `EXPLAIN` statement.
+fn is_show_databases(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::Explain(explain) => is_show_databases(&explain.statement),
+        stmt => stmt.is_show_databases(),
+    }
+}
+
+fn is_show_retention_policies(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::Explain(explain) => is_show_retention_policies(&explain.statement),
+        stmt => stmt.is_show_retention_policies(),
+    }
+}
+
 fn find_all_measurements(stmt: &Statement, tables: &[String]) -> Result<HashSet<String>> {
     struct Matcher<'a>(&'a mut HashSet<String>, &'a [String]);
     impl Visitor for Matcher<'_> {
         type Error = DataFusionError;
 
+        fn pre_visit_qualified_measurement_name(
+            self,
+            n: &QualifiedMeasurementName,
+        ) -> Result<Recursion<Self>, Self::Error> {
+            // Measurements qualified with a database are resolved separately
+            Ok(if n.database.is_some() {
+                Recursion::Stop(self)
+            } else {
+                Recursion::Continue(self)
+            })
+        }
+
         fn post_visit_measurement_name(
             self,
             mn: &MeasurementName,
@@ -352,6 +697,50 @@ fn find_all_measurements(stmt: &Statement, tables: &[String]) -> Result<HashSet<
 
             Ok(self)
         }
+
+        fn post_visit_show_tag_values_cardinality_statement(
+            self,
+            stv: &ShowTagValuesCardinalityStatement,
+        ) -> Result<Self, Self::Error> {
+            if stv.from.is_none() {
+                self.0.extend(self.1.iter().cloned());
+            }
+
+            Ok(self)
+        }
+
+        fn post_visit_show_measurement_cardinality_statement(
+            self,
+            smc: &ShowMeasurementCardinalityStatement,
+        ) -> Result<Self, Self::Error> {
+            if smc.from.is_none() {
+                self.0.extend(self.1.iter().cloned());
+            }
+
+            Ok(self)
+        }
+
+        fn post_visit_show_series_statement(
+            self,
+            ss: &ShowSeriesStatement,
+        ) -> Result<Self, Self::Error> {
+            if ss.from.is_none() {
+                self.0.extend(self.1.iter().cloned());
+            }
+
+            Ok(self)
+        }
+
+        fn post_visit_show_series_cardinality_statement(
+            self,
+            ssc: &ShowSeriesCardinalityStatement,
+        ) -> Result<Self, Self::Error> {
+            if ssc.from.is_none() {
+                self.0.extend(self.1.iter().cloned());
+            }
+
+            Ok(self)
+        }
     }
 
     let mut m = HashSet::new();
@@ -367,6 +756,53 @@ mod test {
     use itertools::Itertools;
     use test_helpers::assert_error;
 
+    #[test]
+    fn test_add_qualified_table() {
+        use datafusion::datasource::empty::EmptyTable;
+        use datafusion::execution::session_state::SessionStateBuilder;
+        use schema::SchemaBuilder;
+
+        let state = SessionStateBuilder::new().build();
+        let schema = SchemaBuilder::new().timestamp().build().unwrap();
+        let table = || {
+            (
+                provider_as_source(Arc::new(EmptyTable::new(schema.as_arrow()))),
+                schema.clone(),
+            )
+        };
+        let qualified =
+            |database: &str, retention_policy: Option<&str>, measurement: &str| QualifiedTable {
+                database: QualifiedDatabase {
+                    database: database.to_owned(),
+                    retention_policy: retention_policy.map(ToOwned::to_owned),
+                },
+                measurement: measurement.to_owned(),
+            };
+
+        let mut sp = ContextSchemaProvider::new(&state, true);
+        sp.tables.insert("telegraf.autogen.cpu".to_owned(), table());
+
+        let name = sp.add_qualified_table(qualified("telegraf", Some("autogen"), "cpu"), table());
+        assert_eq!(name, "telegraf.autogen.cpu#1");
+        assert_eq!(sp.measurement_name(&name), "cpu");
+        assert_eq!(
+            sp.measurement_name("telegraf.autogen.cpu"),
+            "telegraf.autogen.cpu"
+        );
+        assert!(sp.table_exists("telegraf.autogen.cpu"));
+        assert!(!sp.table_exists(&name));
+
+        // Tables of different databases and retention policies are distinct,
+        // even when their names are the same
+        let a = sp.add_qualified_table(qualified("a.b", Some("c"), "d"), table());
+        let b = sp.add_qualified_table(qualified("a", Some("b.c"), "d"), table());
+        assert_eq!(a, "a.b.c.d");
+        assert_eq!(b, "a.b.c.d#1");
+        assert_eq!(sp.qualified_names[&a], qualified("a.b", Some("c"), "d"));
+        assert_eq!(sp.qualified_names[&b], qualified("a", Some("b.c"), "d"));
+        assert_eq!(sp.table_names(), vec!["telegraf.autogen.cpu"]);
+    }
+
     #[test]
     fn test_query_to_statement() {
         // succeeds for a single statement
@@ -435,6 +871,10 @@ mod test {
         assert_eq!(find("SHOW TAG KEYS"), vec!["bar", "foo", "foobar"]);
         assert_eq!(find("SHOW TAG KEYS FROM /^foo/"), vec!["foo", "foobar"]);
 
+        // Ignores measurements qualified with a database
+        assert_eq!(find("SELECT * FROM foo, db..bar"), vec!["foo"]);
+        assert!(find("SELECT * FROM db.rp./^foo/").is_empty());
+
         // Finds no measurements
         assert!(find("SELECT * FROM none").is_empty());
         assert!(find("SELECT * FROM (SELECT * FROM none)").is_empty());
@@ -0,0 +1,653 @@
+//! Execution of InfluxQL `SELECT ... INTO` statements, which write the rows of
+//! the selection to a measurement, such as downsampling raw data to an hourly
+//! mean:
+//!
+//! ```text
+//! SELECT mean(usage_idle) INTO "telegraf"."one_year".:MEASUREMENT FROM cpu GROUP BY time(1h), *
+//! ```
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, ArrayRef, AsArray, DictionaryArray, Int32Array, Int64Array, RecordBatch, StringArray,
+    TimestampNanosecondArray,
+};
+use arrow::datatypes::{
+    DataType, Field, Float64Type, Int64Type, Schema as ArrowSchema, SchemaRef,
+    TimestampNanosecondType, UInt64Type,
+};
+use arrow_util::bitset::BitSet;
+use async_trait::async_trait;
+use datafusion::error::{DataFusionError, Result};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::EquivalenceProperties;
+use datafusion::physical_plan::execution_plan::{Boundedness, EmissionType};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning, PlanProperties,
+    SendableRecordBatchStream, execute_stream,
+};
+use futures::{TryStreamExt, stream};
+use generated_types::influxdata::iox::querier::v1::InfluxQlMetadata;
+use influxdb_influxql_parser::select::{IntoClause, IntoMeasurementName};
+use mutable_batch::MutableBatch;
+use mutable_batch::writer::Writer;
+use schema::{INFLUXQL_MEASUREMENT_COLUMN_NAME, INFLUXQL_METADATA_KEY, TIME_COLUMN_NAME};
+
+/// The name of the column of the result of a `SELECT ... INTO` statement,
+/// which is the number of points written.
+const WRITTEN_COLUMN_NAME: &str = "written";
+
+/// The size of the data, in bytes, of the points of a selection buffered
+/// before they are written.
+const WRITE_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+/// A destination for the points written by InfluxQL `SELECT ... INTO`
+/// statements, implemented by the host server.
+#[async_trait]
+pub trait WriteSink: Debug + Send + Sync {
+    /// Write `batches`, keyed by measurement name, to the namespace that
+    /// `database` and `retention_policy` map to.
+    ///
+    /// The points are written to the namespace of the query when `database`
+    /// is `None`.
+    async fn write(
+        &self,
+        database: Option<&str>,
+        retention_policy: Option<&str>,
+        batches: HashMap<String, MutableBatch>,
+    ) -> Result<()>;
+}
+
+/// A physical operator that writes the rows of the `input` plan of a
+/// `SELECT ... INTO` statement to a [`WriteSink`], and produces a single row
+/// with the number of points written.
+///
+/// The tag columns of each point are the `GROUP BY` tags of the selection, and
+/// every other column, except `iox::measurement` and `time`, is a field. Rows
+/// where all the fields are `NULL` are not written.
+///
+/// The points are buffered and written in chunks of about [`WRITE_CHUNK_SIZE`]
+/// bytes, so that the size of the selection is not limited by memory. The
+/// chunks written before an error remain written.
+pub(crate) struct SelectIntoExec {
+    input: Arc<dyn ExecutionPlan>,
+    into: IntoClause,
+    sink: Arc<dyn WriteSink>,
+
+    /// The size of the data, in bytes, of the points buffered before they are written.
+    chunk_size: usize,
+
+    /// The indexes of the `iox::measurement`, `time` and tag columns of `input`.
+    measurement_index: usize,
+    time_index: usize,
+    tag_indexes: Vec<usize>,
+
+    schema: SchemaRef,
+
+    /// Cache holding plan properties like equivalences, output partitioning, output ordering etc.
+    cache: PlanProperties,
+}
+
+impl SelectIntoExec {
+    pub(crate) fn try_new(
+        input: Arc<dyn ExecutionPlan>,
+        into: IntoClause,
+        sink: Arc<dyn WriteSink>,
+    ) -> Result<Self> {
+        let input_schema = input.schema();
+        let metadata = input_schema
+            .metadata()
+            .get(INFLUXQL_METADATA_KEY)
+            .ok_or_else(|| {
+                DataFusionError::Internal("SELECT INTO input is missing InfluxQL metadata".into())
+            })
+            .and_then(|data| {
+                serde_json::from_str::<InfluxQlMetadata>(data).map_err(|err| {
+                    DataFusionError::Internal(format!(
+                        "error deserializing InfluxQL metadata: {err}"
+                    ))
+                })
+            })?;
+        let time_index = input_schema.index_of(TIME_COLUMN_NAME)?;
+
+        let schema = Arc::new(ArrowSchema::new_with_metadata(
+            vec![
+                Field::new(
+                    INFLUXQL_MEASUREMENT_COLUMN_NAME,
+                    DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+                    false,
+                ),
+                Field::new(
+                    TIME_COLUMN_NAME,
+                    DataType::Timestamp(arrow::datatypes::TimeUnit::Nanosecond, None),
+                    false,
+                ),
+                Field::new(WRITTEN_COLUMN_NAME, DataType::Int64, false),
+            ],
+            HashMap::from([(
+                INFLUXQL_METADATA_KEY.to_owned(),
+                serde_json::to_string(&InfluxQlMetadata {
+                    measurement_column_index: 0,
+                    tag_key_columns: vec![],
+                })
+                .map_err(|err| {
+                    DataFusionError::Internal(format!("error serializing InfluxQL metadata: {err}"))
+                })?,
+            )]),
+        ));
+
+        let cache = PlanProperties::new(
+            EquivalenceProperties::new(Arc::clone(&schema)),
+            Partitioning::UnknownPartitioning(1),
+            EmissionType::Final,
+            Boundedness::Bounded,
+        );
+
+        Ok(Self {
+            input,
+            into,
+            sink,
+            chunk_size: WRITE_CHUNK_SIZE,
+            measurement_index: metadata.measurement_column_index as usize,
+            time_index,
+            tag_indexes: metadata
+                .tag_key_columns
+                .iter()
+                .map(|c| c.column_index as usize)
+                .collect(),
+            schema,
+            cache,
+        })
+    }
+}
+
+impl Debug for SelectIntoExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_as(DisplayFormatType::Default, f)
+    }
+}
+
+impl DisplayAs for SelectIntoExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default
+            | DisplayFormatType::Verbose
+            | DisplayFormatType::TreeRender => {
+                write!(f, "SelectIntoExec: {}", self.into)
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for SelectIntoExec {
+    fn name(&self) -> &str {
+        Self::static_name()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.input]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        assert_eq!(children.len(), 1);
+
+        Ok(Arc::new(Self::try_new(
+            Arc::clone(&children[0]),
+            self.into.clone(),
+            Arc::clone(&self.sink),
+        )?))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Internal(format!(
+                "SelectIntoExec invalid partition {partition}"
+            )));
+        }
+
+        let input = Arc::clone(&self.input);
+        let into = self.into.clone();
+        let sink = Arc::clone(&self.sink);
+        let chunk_size = self.chunk_size;
+        let columns = PointColumns {
+            measurement_index: self.measurement_index,
+            time_index: self.time_index,
+            tag_indexes: self.tag_indexes.clone(),
+        };
+        let schema = Arc::clone(&self.schema);
+
+        let output_schema = Arc::clone(&schema);
+        let fut = async move {
+            let mut input = execute_stream(input, context)?;
+
+            let mut batches = HashMap::new();
+            let mut written = 0;
+            while let Some(batch) = input.try_next().await? {
+                written += columns.write_batch(&batch, &into.name, &mut batches)?;
+                if batches.values().map(MutableBatch::size_data).sum::<usize>() >= chunk_size {
+                    write_chunk(sink.as_ref(), &into, std::mem::take(&mut batches)).await?;
+                }
+            }
+
+            if !batches.is_empty() {
+                write_chunk(sink.as_ref(), &into, batches).await?;
+            }
+
+            written_batch(output_schema, written)
+        };
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            schema,
+            stream::once(fut),
+        )))
+    }
+
+    fn statistics(&self) -> Result<datafusion::physical_plan::Statistics, DataFusionError> {
+        Ok(datafusion::physical_plan::Statistics::new_unknown(
+            &self.schema(),
+        ))
+    }
+}
+
+/// Write a chunk of the points of a selection, keyed by measurement name, to
+/// the namespace of the `into` clause.
+async fn write_chunk(
+    sink: &dyn WriteSink,
+    into: &IntoClause,
+    batches: HashMap<String, MutableBatch>,
+) -> Result<()> {
+    sink.write(
+        into.database.as_deref().map(String::as_str),
+        into.retention_policy.as_deref().map(String::as_str),
+        batches,
+    )
+    .await
+}
+
+/// Returns the result of a `SELECT ... INTO` statement, which is a single row
+/// of the `result` measurement with the number of points written.
+fn written_batch(schema: SchemaRef, written: usize) -> Result<RecordBatch> {
+    Ok(RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(DictionaryArray::try_new(
+                Int32Array::from(vec![0]),
+                Arc::new(StringArray::from(vec!["result"])),
+            )?),
+            Arc::new(TimestampNanosecondArray::from(vec![0])),
+            Arc::new(Int64Array::from(vec![written as i64])),
+        ],
+    )?)
+}
+
+/// Identifies the columns of the record batches of a selection that form the
+/// measurement, timestamp and tag set of each point.
+#[derive(Debug)]
+struct PointColumns {
+    measurement_index: usize,
+    time_index: usize,
+    tag_indexes: Vec<usize>,
+}
+
+impl PointColumns {
+    /// Append the rows of `batch` to `batches`, keyed by the `target`
+    /// measurement, and return the number of points.
+    fn write_batch(
+        &self,
+        batch: &RecordBatch,
+        target: &IntoMeasurementName,
+        batches: &mut HashMap<String, MutableBatch>,
+    ) -> Result<usize> {
+        let schema = batch.schema();
+        let measurements = as_strings(batch.column(self.measurement_index))?;
+        let times = batch
+            .column(self.time_index)
+            .as_primitive_opt::<TimestampNanosecondType>()
+            .ok_or_else(|| {
+                DataFusionError::Internal("SELECT INTO time column is not a timestamp".into())
+            })?;
+        let tags = self
+            .tag_indexes
+            .iter()
+            .map(|&idx| {
+                Ok((
+                    schema.field(idx).name().as_str(),
+                    as_strings(batch.column(idx))?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let fields = (0..batch.num_columns())
+            .filter(|idx| {
+                *idx != self.measurement_index
+                    && *idx != self.time_index
+                    && !self.tag_indexes.contains(idx)
+                    && *batch.column(*idx).data_type() != DataType::Null
+            })
+            .map(|idx| (schema.field(idx).name().as_str(), batch.column(idx)))
+            .collect::<Vec<_>>();
+
+        // Group the rows with at least one field value by target measurement.
+        let mut rows_by_measurement: HashMap<String, Vec<usize>> = HashMap::new();
+        for row in 0..batch.num_rows() {
+            if fields.iter().all(|(_, array)| array.is_null(row)) {
+                continue;
+            }
+            let measurement = match target {
+                IntoMeasurementName::Name(name) => name.as_str(),
+                IntoMeasurementName::Backreference => measurements.value(row),
+            };
+            rows_by_measurement
+                .entry(measurement.to_owned())
+                .or_default()
+                .push(row);
+        }
+
+        let mut written = 0;
+        for (measurement, rows) in rows_by_measurement {
+            let mb = batches.entry(measurement).or_default();
+            let mut writer = Writer::new(mb, rows.len());
+
+            for (name, values) in &tags {
+                let (mask, valid) = valid_rows(values, &rows);
+                writer
+                    .write_tag(name, Some(mask.bytes()), valid.map(|row| values.value(row)))
+                    .map_err(write_error)?;
+            }
+
+            for (name, array) in &fields {
+                let (mask, valid) = valid_rows(array.as_ref(), &rows);
+                let mask = Some(mask.bytes());
+                match array.data_type() {
+                    DataType::Float64 => {
+                        let values = array.as_primitive::<Float64Type>();
+                        writer.write_f64(name, mask, valid.map(|row| values.value(row)))
+                    }
+                    DataType::Int64 => {
+                        let values = array.as_primitive::<Int64Type>();
+                        writer.write_i64(name, mask, valid.map(|row| values.value(row)))
+                    }
+                    DataType::UInt64 => {
+                        let values = array.as_primitive::<UInt64Type>();
+                        writer.write_u64(name, mask, valid.map(|row| values.value(row)))
+                    }
+                    DataType::Boolean => {
+                        let values = array.as_boolean();
+                        writer.write_bool(name, mask, valid.map(|row| values.value(row)))
+                    }
+                    DataType::Utf8 | DataType::Dictionary(_, _) => {
+                        let values = as_strings(array)?;
+                        writer.write_string(name, mask, valid.map(|row| values.value(row)))
+                    }
+                    data_type => {
+                        return Err(DataFusionError::NotImplemented(format!(
+                            "SELECT INTO for field {name} of type {data_type}"
+                        )));
+                    }
+                }
+                .map_err(write_error)?;
+            }
+
+            writer
+                .write_time(TIME_COLUMN_NAME, rows.iter().map(|&row| times.value(row)))
+                .map_err(write_error)?;
+            writer.commit();
+
+            written += rows.len();
+        }
+
+        Ok(written)
+    }
+}
+
+/// Returns the validity mask of the `rows` of `array`, and the rows that are
+/// not `NULL`.
+fn valid_rows<'a>(
+    array: &'a dyn Array,
+    rows: &'a [usize],
+) -> (BitSet, impl Iterator<Item = usize> + 'a) {
+    let mut mask = BitSet::with_size(rows.len());
+    for (idx, row) in rows.iter().enumerate() {
+        if array.is_valid(*row) {
+            mask.set(idx);
+        }
+    }
+    (
+        mask,
+        rows.iter().copied().filter(|row| array.is_valid(*row)),
+    )
+}
+
+/// Cast a string or dictionary-encoded string `array` to a [`StringArray`].
+fn as_strings(array: &ArrayRef) -> Result<StringArray> {
+    Ok(arrow::compute::cast(array, &DataType::Utf8)?
+        .as_string::<i32>()
+        .clone())
+}
+
+fn write_error(err: mutable_batch::writer::Error) -> DataFusionError {
+    DataFusionError::Execution(format!("error writing SELECT INTO points: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::Float64Array;
+    use datafusion::datasource::memory::MemorySourceConfig;
+    use datafusion::datasource::source::DataSourceExec;
+    use datafusion::physical_plan::common::collect;
+    use generated_types::influxdata::iox::querier::v1::influx_ql_metadata::TagKeyColumn;
+    use influxdb_influxql_parser::identifier::Identifier;
+    use schema::Projection;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    struct MockSink {
+        writes: Mutex<
+            Vec<(
+                Option<String>,
+                Option<String>,
+                HashMap<String, MutableBatch>,
+            )>,
+        >,
+    }
+
+    #[async_trait]
+    impl WriteSink for MockSink {
+        async fn write(
+            &self,
+            database: Option<&str>,
+            retention_policy: Option<&str>,
+            batches: HashMap<String, MutableBatch>,
+        ) -> Result<()> {
+            self.writes.lock().unwrap().push((
+                database.map(ToOwned::to_owned),
+                retention_policy.map(ToOwned::to_owned),
+                batches,
+            ));
+            Ok(())
+        }
+    }
+
+    fn input() -> Arc<dyn ExecutionPlan> {
+        let metadata = InfluxQlMetadata {
+            measurement_column_index: 0,
+            tag_key_columns: vec![TagKeyColumn {
+                tag_key: "host".into(),
+                column_index: 2,
+                is_projected: false,
+            }],
+        };
+        let schema = Arc::new(ArrowSchema::new_with_metadata(
+            vec![
+                Field::new(
+                    INFLUXQL_MEASUREMENT_COLUMN_NAME,
+                    DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+                    false,
+                ),
+                Field::new(
+                    TIME_COLUMN_NAME,
+                    DataType::Timestamp(arrow::datatypes::TimeUnit::Nanosecond, None),
+                    false,
+                ),
+                Field::new(
+                    "host",
+                    DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+                    true,
+                ),
+                Field::new("mean", DataType::Float64, true),
+            ],
+            HashMap::from([(
+                INFLUXQL_METADATA_KEY.to_owned(),
+                serde_json::to_string(&metadata).unwrap(),
+            )]),
+        ));
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![
+                Arc::new(DictionaryArray::<arrow::datatypes::Int32Type>::from_iter([
+                    "cpu", "cpu", "cpu", "mem",
+                ])),
+                Arc::new(TimestampNanosecondArray::from(vec![0, 10, 20, 0])),
+                Arc::new(DictionaryArray::<arrow::datatypes::Int32Type>::from_iter([
+                    Some("a"),
+                    Some("b"),
+                    None,
+                    Some("a"),
+                ])),
+                Arc::new(Float64Array::from(vec![
+                    Some(1.0),
+                    None,
+                    Some(3.0),
+                    Some(4.0),
+                ])),
+            ],
+        )
+        .unwrap();
+
+        // the same rows in two batches
+        Arc::new(DataSourceExec::new(Arc::new(
+            MemorySourceConfig::try_new(
+                &[vec![batch.slice(0, 2), batch.slice(2, 2)]],
+                schema,
+                None,
+            )
+            .unwrap(),
+        )))
+    }
+
+    async fn run(into: IntoClause, sink: Arc<MockSink>) -> i64 {
+        run_with_chunk_size(into, sink, WRITE_CHUNK_SIZE).await
+    }
+
+    async fn run_with_chunk_size(into: IntoClause, sink: Arc<MockSink>, chunk_size: usize) -> i64 {
+        let mut exec = SelectIntoExec::try_new(input(), into, sink as _).unwrap();
+        exec.chunk_size = chunk_size;
+        let batches = collect(exec.execute(0, Arc::new(TaskContext::default())).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(batches.len(), 1);
+        batches[0]
+            .column_by_name(WRITTEN_COLUMN_NAME)
+            .unwrap()
+            .as_primitive::<Int64Type>()
+            .value(0)
+    }
+
+    #[tokio::test]
+    async fn test_select_into_measurement() {
+        let sink = Arc::new(MockSink::default());
+        let into = IntoClause {
+            database: Some(Identifier::from("telegraf")),
+            retention_policy: Some(Identifier::from("one_year")),
+            name: IntoMeasurementName::Name(Identifier::from("downsampled")),
+        };
+
+        // The row without a field value is not written
+        assert_eq!(run(into, Arc::clone(&sink)).await, 3);
+
+        let writes = sink.writes.lock().unwrap();
+        assert_eq!(writes.len(), 1);
+        let (database, retention_policy, batches) = &writes[0];
+        assert_eq!(database.as_deref(), Some("telegraf"));
+        assert_eq!(retention_policy.as_deref(), Some("one_year"));
+        assert_eq!(batches.len(), 1);
+
+        let mb = &batches["downsampled"];
+        assert_eq!(mb.rows(), 3);
+        let schema = mb.schema(Projection::All).unwrap();
+        assert_eq!(
+            schema
+                .tags_iter()
+                .map(|f| f.name().as_str())
+                .collect::<Vec<_>>(),
+            vec!["host"]
+        );
+        assert_eq!(
+            schema
+                .fields_iter()
+                .map(|f| f.name().as_str())
+                .collect::<Vec<_>>(),
+            vec!["mean"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_select_into_backreference() {
+        let sink = Arc::new(MockSink::default());
+        let into = IntoClause {
+            database: None,
+            retention_policy: None,
+            name: IntoMeasurementName::Backreference,
+        };
+
+        assert_eq!(run(into, Arc::clone(&sink)).await, 3);
+
+        let writes = sink.writes.lock().unwrap();
+        let (database, _, batches) = &writes[0];
+        assert_eq!(*database, None);
+        assert_eq!(batches["cpu"].rows(), 2);
+        assert_eq!(batches["mem"].rows(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_select_into_chunks() {
+        let sink = Arc::new(MockSink::default());
+        let into = IntoClause {
+            database: None,
+            retention_policy: None,
+            name: IntoMeasurementName::Backreference,
+        };
+
+        // Each input batch exceeds the chunk size, and is written separately
+        assert_eq!(run_with_chunk_size(into, Arc::clone(&sink), 1).await, 3);
+
+        let writes = sink.writes.lock().unwrap();
+        assert_eq!(writes.len(), 2);
+        assert_eq!(writes[0].2["cpu"].rows(), 1);
+        assert!(!writes[0].2.contains_key("mem"));
+        assert_eq!(writes[1].2["cpu"].rows(), 1);
+        assert_eq!(writes[1].2["mem"].rows(), 1);
+    }
+}
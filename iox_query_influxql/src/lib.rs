@@ -1,4 +1,17 @@
 //! Contains the IOx InfluxQL query planner
+//!
+//! # Differences from InfluxDB 1.x
+//!
+//! The planner aims to produce the same results as InfluxDB 1.x, with the
+//! following known exceptions:
+//!
+//! * `HOLT_WINTERS` and `HOLT_WINTERS_WITH_FIT` cannot be combined with other
+//!   functions in the same `SELECT` statement, and the query is rejected.
+//!   InfluxDB 1.x accepts such queries, however, the forecast points do not
+//!   share timestamps with the values of the other functions.
+//! * `SHOW MEASUREMENTS` and `SHOW MEASUREMENT CARDINALITY` without a `WHERE`
+//!   clause include every table of the namespace, including tables without
+//!   any rows, such as those whose data has expired.
 
 use arrow::datatypes::DataType;
 use datafusion::{common::Result, scalar::ScalarValue};
@@ -2,7 +2,10 @@ mod select;
 mod source_field_names;
 mod union;
 
-use crate::aggregate::{MODE, PERCENTILE, SPREAD};
+use crate::aggregate::{
+    HOLT_WINTERS, HOLT_WINTERS_TIME_FIELD, HOLT_WINTERS_VALUE_FIELD, MODE, PERCENTILE, SAMPLE,
+    SAMPLE_TIME_FIELD, SAMPLE_VALUE_FIELD, SPREAD, sample_other_field,
+};
 use crate::error;
 use crate::plan::ir::{DataSource, Field, Interval, Select, SelectQuery};
 use crate::plan::planner::select::{
@@ -13,15 +16,21 @@ use crate::plan::planner::source_field_names::SourceFieldNamesVisitor;
 use crate::plan::planner_time_range_expression::time_range_to_df_expr;
 use crate::plan::rewriter::{ProjectionType, find_table_names, rewrite_statement};
 use crate::plan::udf::{
-    cumulative_sum, derivative, difference, elapsed, find_integral_udfs, find_window_udfs,
-    integral, is_integral_udf, moving_average, non_negative_derivative, non_negative_difference,
+    chande_momentum_oscillator, cumulative_sum, derivative, difference,
+    double_exponential_moving_average, elapsed, exponential_moving_average, find_integral_udfs,
+    find_window_udfs, integral, is_integral_udf, kaufmans_adaptive_moving_average,
+    kaufmans_efficiency_ratio, moving_average, non_negative_derivative, non_negative_difference,
+    relative_strength_index, triple_exponential_derivative, triple_exponential_moving_average,
 };
 use crate::plan::util::{IQLSchema, binary_operator_to_df_operator, rebase_expr};
 use crate::plan::var_ref::var_ref_data_type_to_data_type;
 use crate::plan::{planner_rewrite_expression, udf};
 use crate::window::{
-    CUMULATIVE_SUM, DERIVATIVE, DIFFERENCE, ELAPSED, INTEGRAL, MOVING_AVERAGE,
-    NON_NEGATIVE_DERIVATIVE, NON_NEGATIVE_DIFFERENCE, PERCENT_ROW_NUMBER,
+    CHANDE_MOMENTUM_OSCILLATOR, CUMULATIVE_SUM, DERIVATIVE, DIFFERENCE,
+    DOUBLE_EXPONENTIAL_MOVING_AVERAGE, ELAPSED, EXPONENTIAL_MOVING_AVERAGE, INTEGRAL,
+    KAUFMANS_ADAPTIVE_MOVING_AVERAGE, KAUFMANS_EFFICIENCY_RATIO, MOVING_AVERAGE,
+    NON_NEGATIVE_DERIVATIVE, NON_NEGATIVE_DIFFERENCE, PERCENT_ROW_NUMBER, RELATIVE_STRENGTH_INDEX,
+    TRIPLE_EXPONENTIAL_DERIVATIVE, TRIPLE_EXPONENTIAL_MOVING_AVERAGE,
 };
 use arrow::array::{
     BooleanArray, DictionaryArray, Int32Array, Int64Array, StringArray, StringBuilder,
@@ -32,18 +41,22 @@ use arrow::record_batch::RecordBatch;
 use datafusion::common::tree_node::{Transformed, TransformedResult, TreeNode, TreeNodeRecursion};
 use datafusion::common::{
     DFSchema, DFSchemaRef, DataFusionError, ExprSchema, Result, ScalarValue, SchemaError,
-    ToDFSchema,
+    ToDFSchema, UnnestOptions,
 };
 use datafusion::datasource::{MemTable, provider_as_source};
 use datafusion::execution::FunctionRegistry;
 use datafusion::functions::datetime::date_bin::DateBinFunc;
-use datafusion::functions::{core::expr_ext::FieldAccessor, expr_fn::now};
+use datafusion::functions::{
+    core::expr_ext::FieldAccessor,
+    expr_fn::{concat, now, replace},
+};
 use datafusion::functions_aggregate::count::count;
 use datafusion::functions_aggregate::expr_fn::sum;
 use datafusion::functions_aggregate::{
     average::avg_udaf, count::count_udaf, median::median_udaf, stddev::stddev_udaf, sum::sum_udaf,
 };
 use datafusion::functions_array::expr_fn::make_array;
+use datafusion::functions_window::rank::dense_rank_udwf;
 use datafusion::functions_window::row_number::row_number_udwf;
 use datafusion::logical_expr::expr::{
     AggregateFunctionParams, Alias, ScalarFunction, WindowFunctionParams,
@@ -76,14 +89,18 @@ use influxdb_influxql_parser::functions::{
 };
 use influxdb_influxql_parser::literal::Number;
 use influxdb_influxql_parser::parameter::{BindParameterError, replace_bind_params_with_values};
-use influxdb_influxql_parser::select::{FillClause, GroupByClause};
+use influxdb_influxql_parser::select::{FillClause, GroupByClause, SLimitClause, SOffsetClause};
 use influxdb_influxql_parser::show_field_keys::ShowFieldKeysStatement;
 use influxdb_influxql_parser::show_measurements::{
-    ShowMeasurementsStatement, WithMeasurementClause,
+    ExtendedOnClause, ShowMeasurementCardinalityStatement, ShowMeasurementsStatement,
+    WithMeasurementClause,
 };
 use influxdb_influxql_parser::show_retention_policies::ShowRetentionPoliciesStatement;
+use influxdb_influxql_parser::show_series::{ShowSeriesCardinalityStatement, ShowSeriesStatement};
 use influxdb_influxql_parser::show_tag_keys::ShowTagKeysStatement;
-use influxdb_influxql_parser::show_tag_values::{ShowTagValuesStatement, WithKeyClause};
+use influxdb_influxql_parser::show_tag_values::{
+    ShowTagValuesCardinalityStatement, ShowTagValuesStatement, WithKeyClause,
+};
 use influxdb_influxql_parser::simple_from_clause::ShowFromClause;
 use influxdb_influxql_parser::time_range::{ReduceContext, TimeRange, split_cond};
 use influxdb_influxql_parser::timestamp::Timestamp;
@@ -127,6 +144,12 @@ use super::util::{contains_expr, number_to_scalar};
 /// The column index of the measurement column.
 const MEASUREMENT_COLUMN_INDEX: u32 = 0;
 
+/// The name of the column holding the points produced by the `HOLT_WINTERS` aggregate.
+const HOLT_WINTERS_COLUMN_NAME: &str = "__holt_winters";
+
+/// The name of the column holding the points selected by the `SAMPLE` aggregate.
+const SAMPLE_COLUMN_NAME: &str = "__sample";
+
 /// The `SchemaProvider` trait allows the InfluxQL query planner to obtain
 /// meta-data about tables referenced in InfluxQL statements.
 pub trait SchemaProvider {
@@ -150,6 +173,48 @@ pub trait SchemaProvider {
     /// Get the schema for the specified `table`.
     fn table_schema(&self, name: &str) -> Option<Schema>;
 
+    /// The collection of tables for the namespace identified by the `database` and
+    /// `retention_policy` of a qualified measurement name, such as
+    /// `"telegraf"."autogen"."cpu"`.
+    ///
+    /// The returned names refer to tables of this schema, and may differ from the
+    /// measurement name of the table, which is returned by
+    /// [`SchemaProvider::measurement_name`].
+    fn qualified_table_names(
+        &self,
+        _database: &str,
+        _retention_policy: Option<&str>,
+    ) -> Result<Vec<&'_ str>> {
+        error::not_implemented("database prefix in qualified measurement syntax")
+    }
+
+    /// Get the measurement name of the specified `table`.
+    fn measurement_name<'a>(&'a self, name: &'a str) -> &'a str {
+        name
+    }
+
+    /// The names of the databases, for the `SHOW DATABASES` statement.
+    fn database_names(&self) -> Result<Vec<&'_ str>> {
+        error::not_implemented("SHOW DATABASES")
+    }
+
+    /// The names of the retention policies of `database`, for the
+    /// `SHOW RETENTION POLICIES ON <database>` statement.
+    fn retention_policy_names(&self, _database: &str) -> Result<Vec<&'_ str>> {
+        error::not_implemented("SHOW RETENTION POLICIES ON <database>")
+    }
+
+    /// Get the schema of the namespace identified by the `database` and
+    /// `retention_policy` of the `ON` clause of a metadata statement, such as
+    /// `SHOW TAG KEYS ON "telegraf"`, or `None` if the namespace does not exist.
+    fn database_schema(
+        &self,
+        _database: &str,
+        _retention_policy: Option<&str>,
+    ) -> Result<Option<&dyn SchemaProvider>> {
+        error::not_implemented("ON <database>")
+    }
+
     fn execution_props(&self) -> &ExecutionProps;
 }
 
@@ -604,6 +669,25 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         }
     }
 
+    /// Returns a planner for the metadata statements that target the `database`
+    /// and `retention_policy` of an `ON` clause.
+    fn for_database(
+        &self,
+        database: &str,
+        retention_policy: Option<&str>,
+    ) -> Result<InfluxQLToLogicalPlan<'a>> {
+        match self.s.database_schema(database, retention_policy)? {
+            Some(s) => Ok(Self {
+                s,
+                iox_ctx: self.iox_ctx.child_ctx("database"),
+            }),
+            None => match retention_policy {
+                Some(rp) => error::query(format!("database not found: {database}.{rp}")),
+                None => error::query(format!("database not found: {database}")),
+            },
+        }
+    }
+
     pub fn statement_to_plan(&self, statement: Statement) -> Result<LogicalPlan> {
         match statement {
             Statement::CreateDatabase(_) => error::not_implemented("CREATE DATABASE"),
@@ -615,17 +699,27 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     .rewrite_select_statement(*select)
                     .map_err(|e| e.context("rewriting statement"))?,
             ),
-            Statement::ShowDatabases(_) => error::not_implemented("SHOW DATABASES"),
+            Statement::ShowDatabases(_) => self.show_databases_to_plan(),
             Statement::ShowMeasurements(show_measurements) => {
                 self.show_measurements_to_plan(*show_measurements)
             }
+            Statement::ShowMeasurementCardinality(show_measurement_cardinality) => {
+                self.show_measurement_cardinality_to_plan(*show_measurement_cardinality)
+            }
             Statement::ShowRetentionPolicies(show_retention_policies) => {
                 self.show_retention_policies_to_plan(*show_retention_policies)
             }
+            Statement::ShowSeries(show_series) => self.show_series_to_plan(*show_series),
+            Statement::ShowSeriesCardinality(show_series_cardinality) => {
+                self.show_series_cardinality_to_plan(*show_series_cardinality)
+            }
             Statement::ShowTagKeys(show_tag_keys) => self.show_tag_keys_to_plan(*show_tag_keys),
             Statement::ShowTagValues(show_tag_values) => {
                 self.show_tag_values_to_plan(*show_tag_values)
             }
+            Statement::ShowTagValuesCardinality(show_tag_values_cardinality) => {
+                self.show_tag_values_cardinality_to_plan(*show_tag_values_cardinality)
+            }
             Statement::ShowFieldKeys(show_field_keys) => {
                 self.show_field_keys_to_plan(*show_field_keys)
             }
@@ -645,6 +739,14 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
     }
 
     fn explain_statement_to_plan(&self, explain: ExplainStatement) -> Result<LogicalPlan> {
+        // The points of a `SELECT ... INTO` statement are not written when it is
+        // explained, so EXPLAIN ANALYZE would not reflect the executed statement.
+        if let Statement::Select(select) = explain.statement.as_ref()
+            && select.into.is_some()
+        {
+            return error::not_implemented("EXPLAIN SELECT INTO");
+        }
+
         let plan = self.statement_to_plan(*explain.statement)?;
         let plan = Arc::new(plan);
         let schema = LogicalPlan::explain_schema();
@@ -735,7 +837,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             //
             // See: https://github.com/influxdata/influxdb_iox/issues/8042
 
-            plans.push((table_name, plan));
+            plans.push((self.s.measurement_name(table_name), plan));
         }
 
         let plan = {
@@ -822,6 +924,16 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             &projection_tag_set,
         )?;
 
+        let plan = self.series_limit(
+            plan,
+            select.series_offset,
+            select.series_limit,
+            vec![time_sort_expr.clone()],
+            sort_by_measurement,
+            &group_by_tag_set,
+            &projection_tag_set,
+        )?;
+
         self.limit(
             plan,
             select.offset,
@@ -870,6 +982,16 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             &projection_tag_set,
         )?;
 
+        let plan = self.series_limit(
+            plan,
+            select.series_offset,
+            select.series_limit,
+            vec![time_sort_expr.clone()],
+            false,
+            &group_by_tag_set,
+            &projection_tag_set,
+        )?;
+
         Ok(Some(self.limit(
             plan,
             select.offset,
@@ -977,6 +1099,12 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             ProjectionType::TopBottomSelector => {
                 self.project_select_top_bottom_selector(ctx, input, fields, group_by_tag_set)
             }
+            ProjectionType::HoltWinters => {
+                self.project_select_holt_winters(ctx, input, fields, group_by_tag_set)
+            }
+            ProjectionType::SampleMixed => {
+                self.project_select_sample_mixed(ctx, input, fields, group_by_tag_set)
+            }
         }
     }
 
@@ -1139,8 +1267,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
     /// type. These a queries that include a single FIRST, LAST, MAX, MIN,
     /// PERCENTILE, or SAMPLE function call, possibly requesting additional
     /// tags or fields.
-    ///
-    /// N.B SAMPLE is not yet implemented.
     fn project_select_selector(
         &self,
         ctx: &Context<'_>,
@@ -1210,8 +1336,8 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
                 (idx, field_key, plan)
             }
-            (_, Selector::Sample { field_key: _, n: _ }) => {
-                return error::not_implemented("sample selector function");
+            (_, Selector::Sample { .. }) => {
+                return self.project_select_sample(ctx, input, fields, group_by_tag_set);
             }
 
             (_, s) => {
@@ -1235,6 +1361,155 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         project(plan, select_exprs)
     }
 
+    /// Plan the execution of SELECT queries that use the SAMPLE selector
+    /// function.
+    ///
+    /// The [`SAMPLE`] aggregate selects N random points for each group, along
+    /// with any additional tags or fields, which are unnested to produce a row
+    /// for each selected point.
+    fn project_select_sample(
+        &self,
+        ctx: &Context<'_>,
+        input: LogicalPlan,
+        fields: &[Field],
+        group_by_tag_set: &[&str],
+    ) -> Result<LogicalPlan> {
+        let schema = IQLSchema::new_from_fields(input.schema(), fields)?;
+
+        let (selector_index, field_key, n) = match Selector::find_enumerated(fields)? {
+            (idx, Selector::Sample { field_key, n }) => (idx, field_key, n),
+            (_, s) => {
+                return error::internal(format!(
+                    "project_select_sample used with unexpected selector function: {s}"
+                ));
+            }
+        };
+
+        let mut fields_vec = fields.to_vec();
+        fields_vec[selector_index].expr = IQLExpr::VarRef(VarRef {
+            name: field_key.clone(),
+            data_type: None,
+        });
+
+        // Transform InfluxQL AST field expressions to a list of DataFusion expressions.
+        let select_exprs =
+            self.field_list_to_exprs(&ctx.tz, &ctx.fill, &input, fields_vec.as_slice(), &schema)?;
+
+        // Any additional tags or fields are selected with each point, so they
+        // are passed as additional arguments to the SAMPLE aggregate.
+        let mut args = vec![
+            select_exprs[selector_index].clone().unalias(),
+            select_exprs[0].clone().unalias(),
+            lit(n),
+        ];
+        let mut other_fields = HashMap::new();
+        for (idx, (field, expr)) in fields_vec.iter().zip(&select_exprs).enumerate() {
+            if idx == 0 || idx == selector_index || group_by_tag_set.contains(&field.name.as_str())
+            {
+                continue;
+            }
+            args.push(expr.clone().unalias());
+            other_fields.insert(idx, sample_other_field(args.len() - 3));
+        }
+
+        let sample_expr = Expr::AggregateFunction(expr::AggregateFunction {
+            func: SAMPLE.clone(),
+            params: AggregateFunctionParams {
+                args,
+                distinct: false,
+                filter: None,
+                order_by: None,
+                null_treatment: None,
+            },
+        });
+
+        // Sample each series, and time interval when grouping by time.
+        let group_by_exprs = window_partition_by(ctx, input.schema(), group_by_tag_set);
+        let plan = LogicalPlanBuilder::from(input)
+            .aggregate(group_by_exprs, vec![sample_expr.alias(SAMPLE_COLUMN_NAME)])?
+            .unnest_column(SAMPLE_COLUMN_NAME)?
+            .build()?;
+
+        let select_exprs = fields_vec
+            .iter()
+            .zip(select_exprs)
+            .enumerate()
+            .map(|(idx, (field, expr))| {
+                let name = field.name.as_str();
+                let point_field = if idx == 0 {
+                    SAMPLE_TIME_FIELD
+                } else if idx == selector_index {
+                    SAMPLE_VALUE_FIELD
+                } else if let Some(other) = other_fields.get(&idx) {
+                    other.as_str()
+                } else if plan.schema().has_column_with_unqualified_name(name) {
+                    return name.as_expr().alias(name);
+                } else {
+                    return expr;
+                };
+                SAMPLE_COLUMN_NAME.as_expr().field(point_field).alias(name)
+            })
+            .collect::<Vec<_>>();
+
+        // Wrap the plan in a `LogicalPlan::Projection` from the select expressions
+        project(plan, select_exprs)
+    }
+
+    /// Plan the execution of SELECT queries that combine the SAMPLE selector
+    /// function with other SAMPLE, aggregate or selector functions.
+    ///
+    /// As with InfluxDB 1.x, the points of each function are combined by
+    /// position within each group, and the `time` of each row is the start
+    /// of the `GROUP BY TIME` interval, or the lower bound of the time range.
+    fn project_select_sample_mixed(
+        &self,
+        ctx: &Context<'_>,
+        input: LogicalPlan,
+        fields: &[Field],
+        group_by_tag_set: &[&str],
+    ) -> Result<LogicalPlan> {
+        if matches!(ctx.fill(), Some(FillClause::Value(_) | FillClause::Linear)) {
+            return error::not_implemented(
+                "FILL(<value>) or FILL(linear) with sample combined with other functions",
+            );
+        }
+
+        let plan = self.project_select_aggregate(ctx, input, fields, group_by_tag_set)?;
+
+        // Each SAMPLE column is a list of points, whereas the other functions
+        // produce a single value for each group. The latter are wrapped in a
+        // list, so that all the function columns are unnested together, which
+        // combines them by position and pads the shorter lists with NULLs.
+        let mut list_exprs = Vec::with_capacity(fields.len());
+        let mut select_exprs = Vec::with_capacity(fields.len());
+        let mut unnest_columns = vec![];
+        for field in fields {
+            let name = field.name.as_str();
+            if !is_aggregate_field(field) {
+                list_exprs.push(name.as_expr());
+                select_exprs.push(name.as_expr());
+                continue;
+            }
+
+            unnest_columns.push(Column::from_name(name));
+            let (_, column) = plan.schema().qualified_field_with_unqualified_name(name)?;
+            if let DataType::List(_) = column.data_type() {
+                list_exprs.push(name.as_expr());
+                select_exprs.push(name.as_expr().field(SAMPLE_VALUE_FIELD).alias(name));
+            } else {
+                list_exprs.push(make_array(vec![name.as_expr()]).alias(name));
+                select_exprs.push(name.as_expr());
+            }
+        }
+
+        let plan = LogicalPlanBuilder::from(project(plan, list_exprs)?)
+            .unnest_columns_with_options(unnest_columns, UnnestOptions::default())?
+            .build()?;
+
+        // Wrap the plan in a `LogicalPlan::Projection` from the select expressions
+        project(plan, select_exprs)
+    }
+
     /// Plan the execution of "TopBottomSelector" SELECT queries. These are
     /// queries that use the TOP or BOTTOM functions to select a number of
     /// rows from the ends of a partition..
@@ -1315,6 +1590,115 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         project(plan, select_exprs)
     }
 
+    /// Plan "HoltWinters" SELECT queries. These are queries that use the
+    /// HOLT_WINTERS or HOLT_WINTERS_WITH_FIT function to forecast the values
+    /// of a nested aggregate, grouped by time.
+    ///
+    /// The nested aggregate is planned as an "Aggregate" query, and the
+    /// results for each series are reduced by the [`HOLT_WINTERS`] aggregate
+    /// function, which produces a list of points that is unnested to form
+    /// the final projection.
+    fn project_select_holt_winters(
+        &self,
+        ctx: &Context<'_>,
+        input: LogicalPlan,
+        fields: &[Field],
+        group_by_tag_set: &[&str],
+    ) -> Result<LogicalPlan> {
+        let Some(interval) = ctx.interval else {
+            return error::internal("holt_winters requires a GROUP BY interval");
+        };
+
+        let Some((hw_index, call)) = fields
+            .iter()
+            .enumerate()
+            .find_map(|(idx, f)| match &f.expr {
+                IQLExpr::Call(c)
+                    if c.name == "holt_winters" || c.name == "holt_winters_with_fit" =>
+                {
+                    Some((idx, c))
+                }
+                _ => None,
+            })
+        else {
+            return error::internal("unable to find holt_winters function");
+        };
+
+        let (aggregate, h, m) = match call.args.as_slice() {
+            [
+                aggregate,
+                IQLExpr::Literal(Literal::Integer(h)),
+                IQLExpr::Literal(Literal::Integer(m)),
+            ] => (aggregate, *h, *m),
+            // The arguments were validated by the rewriter.
+            _ => return error::internal("invalid arguments for holt_winters"),
+        };
+        let include_fit_data = call.name == "holt_winters_with_fit";
+
+        // Plan the nested aggregate in place of the HOLT_WINTERS call.
+        let mut fields_vec = fields.to_vec();
+        fields_vec[hw_index].expr = aggregate.clone();
+        let plan = self.project_select_aggregate(ctx, input, &fields_vec, group_by_tag_set)?;
+
+        let time_alias = fields[0].name.as_str();
+        let value_alias = fields[hw_index].name.as_str();
+
+        let hw_expr = Expr::AggregateFunction(expr::AggregateFunction {
+            func: HOLT_WINTERS.clone(),
+            params: AggregateFunctionParams {
+                args: vec![
+                    time_alias.as_expr(),
+                    value_alias.as_expr(),
+                    lit(h),
+                    lit(m),
+                    lit(interval.duration),
+                    lit(include_fit_data),
+                ],
+                distinct: false,
+                filter: None,
+                order_by: None,
+                null_treatment: None,
+            },
+        });
+
+        // Forecast each series, which is identified by the GROUP BY tag set.
+        let group_by_exprs =
+            fields_to_exprs_no_nulls(plan.schema(), group_by_tag_set).collect::<Vec<_>>();
+        let plan = LogicalPlanBuilder::from(plan)
+            .aggregate(
+                group_by_exprs,
+                vec![hw_expr.alias(HOLT_WINTERS_COLUMN_NAME)],
+            )?
+            .unnest_column(HOLT_WINTERS_COLUMN_NAME)?
+            .build()?;
+
+        let select_exprs = fields
+            .iter()
+            .enumerate()
+            .map(|(idx, f)| {
+                let name = f.name.as_str();
+                if idx == 0 {
+                    HOLT_WINTERS_COLUMN_NAME
+                        .as_expr()
+                        .field(HOLT_WINTERS_TIME_FIELD)
+                        .alias(name)
+                } else if idx == hw_index {
+                    HOLT_WINTERS_COLUMN_NAME
+                        .as_expr()
+                        .field(HOLT_WINTERS_VALUE_FIELD)
+                        .alias(name)
+                } else if plan.schema().has_column_with_unqualified_name(name) {
+                    name.as_expr()
+                } else {
+                    lit(ScalarValue::Null).alias(name)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Wrap the plan in a `LogicalPlan::Projection` from the select expressions
+        project(plan, select_exprs)
+    }
+
     fn select_aggregate(
         &self,
         ctx: &Context<'_>,
@@ -1907,6 +2291,66 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                 }))
                 .alias(alias))
             }
+            Some(
+                wf @ (udf::WindowFunction::ExponentialMovingAverage
+                | udf::WindowFunction::DoubleExponentialMovingAverage
+                | udf::WindowFunction::TripleExponentialMovingAverage
+                | udf::WindowFunction::RelativeStrengthIndex
+                | udf::WindowFunction::TripleExponentialDerivative
+                | udf::WindowFunction::KaufmansEfficiencyRatio
+                | udf::WindowFunction::KaufmansAdaptiveMovingAverage
+                | udf::WindowFunction::ChandeMomentumOscillator),
+            ) => {
+                let fun = match wf {
+                    udf::WindowFunction::ExponentialMovingAverage => {
+                        EXPONENTIAL_MOVING_AVERAGE.clone()
+                    }
+                    udf::WindowFunction::DoubleExponentialMovingAverage => {
+                        DOUBLE_EXPONENTIAL_MOVING_AVERAGE.clone()
+                    }
+                    udf::WindowFunction::TripleExponentialMovingAverage => {
+                        TRIPLE_EXPONENTIAL_MOVING_AVERAGE.clone()
+                    }
+                    udf::WindowFunction::RelativeStrengthIndex => RELATIVE_STRENGTH_INDEX.clone(),
+                    udf::WindowFunction::TripleExponentialDerivative => {
+                        TRIPLE_EXPONENTIAL_DERIVATIVE.clone()
+                    }
+                    udf::WindowFunction::KaufmansEfficiencyRatio => {
+                        KAUFMANS_EFFICIENCY_RATIO.clone()
+                    }
+                    udf::WindowFunction::KaufmansAdaptiveMovingAverage => {
+                        KAUFMANS_ADAPTIVE_MOVING_AVERAGE.clone()
+                    }
+                    udf::WindowFunction::ChandeMomentumOscillator => {
+                        CHANDE_MOMENTUM_OSCILLATOR.clone()
+                    }
+                    udf::WindowFunction::MovingAverage
+                    | udf::WindowFunction::Difference
+                    | udf::WindowFunction::NonNegativeDifference
+                    | udf::WindowFunction::Derivative
+                    | udf::WindowFunction::NonNegativeDerivative
+                    | udf::WindowFunction::CumulativeSum
+                    | udf::WindowFunction::Elapsed => {
+                        unreachable!("not a technical analysis function")
+                    }
+                };
+                Ok(Expr::WindowFunction(Box::new(WindowFunction {
+                    fun,
+                    params: WindowFunctionParams {
+                        args,
+                        partition_by,
+                        order_by,
+                        window_frame: WindowFrame::new_bounds(
+                            WindowFrameUnits::Rows,
+                            WindowFrameBound::Preceding(ScalarValue::Null),
+                            WindowFrameBound::Following(ScalarValue::Null),
+                        ),
+                        null_treatment: None,
+                        distinct: false,
+                    },
+                }))
+                .alias(alias))
+            }
             None => error::internal(format!(
                 "unexpected user-defined window function: {}",
                 func.name()
@@ -2114,30 +2558,8 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                 .transpose()
                 .map_err(|_| error::map::query("offset out of range".to_owned()))?;
 
-            // a reference to the ROW_NUMBER column.
-            let row_alias = IOX_ROW_ALIAS.as_expr();
-
-            let row_filter_expr = match (limit, offset) {
-                // WHERE "iox::row" BETWEEN OFFSET + 1 AND OFFSET + LIMIT
-                (Some(limit), Some(offset)) => {
-                    let low = offset + 1;
-                    let high = offset + limit;
-
-                    Expr::Between(Between {
-                        expr: Box::new(row_alias),
-                        negated: false,
-                        low: Box::new(lit(low)),
-                        high: Box::new(lit(high)),
-                    })
-                }
-
-                // WHERE "iox::row" <= LIMIT
-                (Some(limit), None) => row_alias.lt_eq(lit(limit)),
-
-                // WHERE "iox::row" > OFFSET
-                (None, Some(offset)) => row_alias.gt(lit(offset)),
-                (None, None) => unreachable!("limit and offset cannot not be None"),
-            };
+            // Filter using the ROW_NUMBER column.
+            let row_filter_expr = limit_offset_filter(IOX_ROW_ALIAS.as_expr(), limit, offset);
 
             let plan = LogicalPlanBuilder::from(plan)
                 // Filter by the LIMIT and OFFSET clause
@@ -2158,6 +2580,120 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         }
     }
 
+    /// Wrap the input logical plan with the operators necessary to restrict the output
+    /// to the series selected by the `SLIMIT` and `SOFFSET` clauses.
+    ///
+    /// A series is identified by the measurement and the tag set from the `GROUP BY` clause,
+    /// and series are numbered in the same order as the tabular output, so that `SOFFSET`
+    /// skips and `SLIMIT` returns whole series.
+    ///
+    /// Arguments:
+    ///
+    /// - `input`: The plan to apply the series limit and offset to, sorted by series.
+    /// - `series_offset`: The number of series to skip.
+    /// - `series_limit`: The maximum number of series to return in the output plan.
+    /// - `sort_exprs`: The sort expressions used to order the rows of each series.
+    /// - `sort_by_measurement`: `true` if the `input` must be sorted by the measurement column.
+    /// - `group_by_tag_set`: Tag columns from the `input` plan that identify a series.
+    /// - `projection_tag_set`: Additional tag columns that should be used to sort the `output`
+    ///   plan.
+    #[expect(clippy::too_many_arguments)]
+    fn series_limit(
+        &self,
+        input: LogicalPlan,
+        series_offset: Option<SOffsetClause>,
+        series_limit: Option<SLimitClause>,
+        sort_exprs: Vec<SortExpr>,
+        sort_by_measurement: bool,
+        group_by_tag_set: &[&str],
+        projection_tag_set: &[&str],
+    ) -> Result<LogicalPlan> {
+        if series_offset.is_none() && series_limit.is_none() {
+            return Ok(input);
+        }
+
+        let series_limit = series_limit
+            .map(|v| <u64 as TryInto<i64>>::try_into(*v))
+            .transpose()
+            .map_err(|_| error::map::query("series limit out of range"))?;
+        let series_offset = series_offset
+            .map(|v| <u64 as TryInto<i64>>::try_into(*v))
+            .transpose()
+            .map_err(|_| error::map::query("series offset out of range"))?;
+
+        let series_key = if sort_by_measurement {
+            iter::once(INFLUXQL_MEASUREMENT_COLUMN_NAME.as_expr())
+                .chain(fields_to_exprs_no_nulls(input.schema(), group_by_tag_set))
+                .collect::<Vec<_>>()
+        } else {
+            fields_to_exprs_no_nulls(input.schema(), group_by_tag_set).collect::<Vec<_>>()
+        };
+
+        if series_key.is_empty() {
+            // The output is a single series, which is either returned in its entirety
+            // or not at all.
+            let skip = series_offset.unwrap_or(0);
+            let fetch = series_limit.unwrap_or(1);
+            return if skip == 0 && fetch > 0 {
+                Ok(input)
+            } else {
+                LogicalPlanBuilder::from(input).limit(0, Some(0))?.build()
+            };
+        }
+
+        // The name of the DENSE_RANK window expression
+        const IOX_SERIES_ALIAS: &str = "iox::series";
+
+        // Construct a DENSE_RANK window expression, which assigns each row
+        // the 1-based position of its series:
+        //
+        // DENSE_RANK() OVER (
+        //   ORDER BY [iox::measurement, group_by_tag_set]
+        // ) AS iox::series
+        //
+        // The series key is sorted in the same order as `plan_with_sort`.
+        let order_by = series_key
+            .into_iter()
+            .map(|e| e.sort(true, false))
+            .collect::<Vec<_>>();
+
+        let window_func_exprs = vec![
+            Expr::WindowFunction(Box::new(WindowFunction::new(
+                WindowFunctionDefinition::WindowUDF(dense_rank_udwf()),
+                vec![],
+            )))
+            .order_by(order_by)
+            .build()?
+            .alias(IOX_SERIES_ALIAS),
+        ];
+
+        // Prepare new projection.
+        let proj_exprs = input
+            .schema()
+            .iter()
+            .map(|(_qualifier, field)| Expr::Column(Column::new_unqualified(field.name())))
+            .collect::<Vec<_>>();
+
+        let series_filter_expr =
+            limit_offset_filter(IOX_SERIES_ALIAS.as_expr(), series_limit, series_offset);
+
+        let plan = LogicalPlanBuilder::from(input)
+            .window(window_func_exprs)?
+            // Filter by the SLIMIT and SOFFSET clause
+            .filter(series_filter_expr)?
+            // Project the output without the IOX_SERIES_ALIAS column
+            .project(proj_exprs)?
+            .build()?;
+
+        plan_with_sort(
+            plan,
+            sort_exprs,
+            sort_by_measurement,
+            group_by_tag_set,
+            projection_tag_set,
+        )
+    }
+
     /// Map the InfluxQL `SELECT` projection list into a list of DataFusion expressions.
     fn field_list_to_exprs(
         &self,
@@ -2690,6 +3226,31 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     },
                 }))
             }
+            "sample" => {
+                // A single SAMPLE function, which may select additional tags or fields,
+                // is handled as a `ProjectionType::Selector`. This arm plans a SAMPLE
+                // that is combined with other functions.
+                let expr = self.expr_to_df_expr(tz, fill_config, scope, &args[0], schema)?;
+
+                // If the expression is a Null or a literal number, we can return it directly
+                // without needing to create an aggregate function.
+                if is_literal_null_or_number(&expr) {
+                    return Ok(expr);
+                }
+
+                check_arg_count(name, args, 2)?;
+                let nexpr = self.expr_to_df_expr(tz, fill_config, scope, &args[1], schema)?;
+                Ok(Expr::AggregateFunction(expr::AggregateFunction {
+                    func: SAMPLE.clone(),
+                    params: AggregateFunctionParams {
+                        args: vec![expr, "time".as_expr(), nexpr],
+                        distinct: false,
+                        filter: None,
+                        order_by: None,
+                        null_treatment: None,
+                    },
+                }))
+            }
             "spread" => {
                 let expr = self.expr_to_df_expr(tz, fill_config, scope, &args[0], schema)?;
 
@@ -2842,8 +3403,20 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
                 Ok(moving_average(vec![arg0, lit(arg1)]))
             }
-            "derivative" => {
-                check_arg_count_range(name, args, 1, 2)?;
+            name @ ("exponential_moving_average"
+            | "double_exponential_moving_average"
+            | "triple_exponential_moving_average"
+            | "relative_strength_index"
+            | "triple_exponential_derivative"
+            | "kaufmans_efficiency_ratio"
+            | "kaufmans_adaptive_moving_average"
+            | "chande_momentum_oscillator") => {
+                // Kaufman's functions do not accept a warmup type.
+                let is_kaufmans = matches!(
+                    name,
+                    "kaufmans_efficiency_ratio" | "kaufmans_adaptive_moving_average"
+                );
+                check_arg_count_range(name, args, 2, if is_kaufmans { 3 } else { 4 })?;
 
                 // arg0 should be a column or function
                 let arg0 = self.expr_to_df_expr(tz, fill_config, scope, &args[0], schema)?;
@@ -2855,13 +3428,87 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                 //  Int64(NULL)
                 //
                 // Without this check, the query plan will look like:
-                //  coalesce_struct(derivative(Int64(NULL)), Int64(X))
+                //  coalesce_struct(exponential_moving_average(Int64(NULL)), Int64(X))
                 if is_literal_null_or_number(&arg0) {
                     return Ok(arg0);
                 }
 
-                let mut eargs = vec![arg0];
-                if args.len() > 1 {
+                // arg1 is the period and the optional arg2 is the hold period,
+                // which defaults to -1 to indicate the hold period should be
+                // derived from the period.
+                let integer_arg = |idx: usize, default: i64| -> Result<ScalarValue> {
+                    let Some(arg) = args.get(idx) else {
+                        return Ok(ScalarValue::Int64(Some(default)));
+                    };
+                    Ok(ScalarValue::Int64(Some(
+                        match self.expr_to_df_expr(tz, fill_config, scope, arg, schema)? {
+                            Expr::Literal(ScalarValue::Int64(Some(v)), _) => v,
+                            Expr::Literal(ScalarValue::UInt64(Some(v)), _) => v as i64,
+                            _ => {
+                                return error::query(format!(
+                                    "{name} expects number for argument {}",
+                                    idx + 1
+                                ));
+                            }
+                        },
+                    )))
+                };
+                let period = integer_arg(1, 0)?;
+                let hold = integer_arg(2, -1)?;
+
+                let mut eargs = vec![arg0, lit(period), lit(hold)];
+                if !is_kaufmans {
+                    // The optional arg3 is the warmup type, where the chande_momentum_oscillator
+                    // defaults to no smoothing.
+                    let warmup = match args.get(3) {
+                        None if name == "chande_momentum_oscillator" => lit("none"),
+                        None => lit("exponential"),
+                        Some(arg) => {
+                            match self.expr_to_df_expr(tz, fill_config, scope, arg, schema)? {
+                                e @ Expr::Literal(ScalarValue::Utf8(Some(_)), _) => e,
+                                _ => {
+                                    return error::query(format!(
+                                        "{name} expects string for fourth argument"
+                                    ));
+                                }
+                            }
+                        }
+                    };
+                    eargs.push(warmup);
+                }
+
+                Ok(match name {
+                    "exponential_moving_average" => exponential_moving_average(eargs),
+                    "double_exponential_moving_average" => double_exponential_moving_average(eargs),
+                    "triple_exponential_moving_average" => triple_exponential_moving_average(eargs),
+                    "relative_strength_index" => relative_strength_index(eargs),
+                    "triple_exponential_derivative" => triple_exponential_derivative(eargs),
+                    "kaufmans_efficiency_ratio" => kaufmans_efficiency_ratio(eargs),
+                    "kaufmans_adaptive_moving_average" => kaufmans_adaptive_moving_average(eargs),
+                    "chande_momentum_oscillator" => chande_momentum_oscillator(eargs),
+                    _ => unreachable!(),
+                })
+            }
+            "derivative" => {
+                check_arg_count_range(name, args, 1, 2)?;
+
+                // arg0 should be a column or function
+                let arg0 = self.expr_to_df_expr(tz, fill_config, scope, &args[0], schema)?;
+
+                // If the expression is a Null or a literal number, we can return it directly
+                // without needing to create an aggregate function.
+                //
+                // So the query plan will look like:
+                //  Int64(NULL)
+                //
+                // Without this check, the query plan will look like:
+                //  coalesce_struct(derivative(Int64(NULL)), Int64(X))
+                if is_literal_null_or_number(&arg0) {
+                    return Ok(arg0);
+                }
+
+                let mut eargs = vec![arg0];
+                if args.len() > 1 {
                     let arg1 = self.expr_to_df_expr(tz, fill_config, scope, &args[1], schema)?;
                     eargs.push(arg1);
                 }
@@ -3285,10 +3932,14 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         }
     }
 
-    fn show_tag_keys_to_plan(&self, show_tag_keys: ShowTagKeysStatement) -> Result<LogicalPlan> {
-        if show_tag_keys.database.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW TAG KEYS ON <database>");
+    fn show_tag_keys_to_plan(
+        &self,
+        mut show_tag_keys: ShowTagKeysStatement,
+    ) -> Result<LogicalPlan> {
+        if let Some(database) = show_tag_keys.database.take() {
+            return self
+                .for_database(&database, None)?
+                .show_tag_keys_to_plan(show_tag_keys);
         }
 
         let tag_key_col = "tagKey";
@@ -3453,11 +4104,12 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
     fn show_field_keys_to_plan(
         &self,
-        show_field_keys: ShowFieldKeysStatement,
+        mut show_field_keys: ShowFieldKeysStatement,
     ) -> Result<LogicalPlan> {
-        if show_field_keys.database.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW FIELD KEYS ON <database>");
+        if let Some(database) = show_field_keys.database.take() {
+            return self
+                .for_database(&database, None)?
+                .show_field_keys_to_plan(show_field_keys);
         }
 
         let field_key_col = "fieldKey";
@@ -3533,13 +4185,198 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
     fn show_tag_values_to_plan(
         &self,
-        show_tag_values: ShowTagValuesStatement,
+        mut show_tag_values: ShowTagValuesStatement,
+    ) -> Result<LogicalPlan> {
+        if let Some(database) = show_tag_values.database.take() {
+            return self
+                .for_database(&database, None)?
+                .show_tag_values_to_plan(show_tag_values);
+        }
+
+        let key_col = "key";
+        let value_col = "value";
+        let plan = self.tag_values_plan(
+            show_tag_values.from,
+            &show_tag_values.with_key,
+            &show_tag_values.condition,
+        )?;
+        let plan = LogicalPlanBuilder::from(plan)
+            .sort([
+                Expr::Column(Column::new_unqualified(INFLUXQL_MEASUREMENT_COLUMN_NAME))
+                    .sort(true, false),
+                Expr::Column(Column::new_unqualified(key_col)).sort(true, false),
+                Expr::Column(Column::new_unqualified(value_col)).sort(true, false),
+            ])?
+            .build()?;
+        let plan = plan_with_metadata(
+            plan,
+            &InfluxQlMetadata {
+                measurement_column_index: MEASUREMENT_COLUMN_INDEX,
+                tag_key_columns: vec![],
+            },
+        )?;
+        let plan = self.limit(
+            plan,
+            show_tag_values.offset,
+            show_tag_values.limit,
+            vec![
+                Expr::Column(Column::new_unqualified(key_col)).sort(true, false),
+                Expr::Column(Column::new_unqualified(value_col)).sort(true, false),
+            ],
+            true,
+            &[],
+            &[],
+        )?;
+
+        Ok(plan)
+    }
+
+    fn show_tag_values_cardinality_to_plan(
+        &self,
+        mut show_tag_values: ShowTagValuesCardinalityStatement,
+    ) -> Result<LogicalPlan> {
+        if let Some(database) = show_tag_values.database.take() {
+            return self
+                .for_database(&database, None)?
+                .show_tag_values_cardinality_to_plan(show_tag_values);
+        }
+
+        let plan = self.tag_values_plan(
+            show_tag_values.from,
+            &show_tag_values.with_key,
+            &show_tag_values.condition,
+        )?;
+        cardinality_plan(plan)
+    }
+
+    fn show_series_to_plan(&self, mut show_series: ShowSeriesStatement) -> Result<LogicalPlan> {
+        if let Some(database) = show_series.database.take() {
+            return self
+                .for_database(&database, None)?
+                .show_series_to_plan(show_series);
+        }
+
+        let key_col = "key";
+        let plan = self.series_plan(show_series.from, &show_series.condition)?;
+        let plan = LogicalPlanBuilder::from(plan)
+            .sort([
+                Expr::Column(Column::new_unqualified(INFLUXQL_MEASUREMENT_COLUMN_NAME))
+                    .sort(true, false),
+                Expr::Column(Column::new_unqualified(key_col)).sort(true, false),
+            ])?
+            .build()?;
+        let plan = plan_with_metadata(
+            plan,
+            &InfluxQlMetadata {
+                measurement_column_index: MEASUREMENT_COLUMN_INDEX,
+                tag_key_columns: vec![],
+            },
+        )?;
+        let plan = self.limit(
+            plan,
+            show_series.offset,
+            show_series.limit,
+            vec![Expr::Column(Column::new_unqualified(key_col)).sort(true, false)],
+            true,
+            &[],
+            &[],
+        )?;
+
+        Ok(plan)
+    }
+
+    fn show_series_cardinality_to_plan(
+        &self,
+        mut show_series_cardinality: ShowSeriesCardinalityStatement,
+    ) -> Result<LogicalPlan> {
+        if let Some(database) = show_series_cardinality.database.take() {
+            return self
+                .for_database(&database, None)?
+                .show_series_cardinality_to_plan(show_series_cardinality);
+        }
+
+        let plan = self.series_plan(
+            show_series_cardinality.from,
+            &show_series_cardinality.condition,
+        )?;
+        cardinality_plan(plan)
+    }
+
+    /// Returns a plan of the distinct series keys of the measurements of the `from`
+    /// clause, such as `cpu,host=server01,region=uswest`.
+    ///
+    /// The plan produces the `iox::measurement` and `key` columns, and is not sorted.
+    fn series_plan(
+        &self,
+        from: Option<ShowFromClause>,
+        condition: &Option<WhereClause>,
     ) -> Result<LogicalPlan> {
-        if show_tag_values.database.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW TAG VALUES ON <database>");
+        let key_col = "key";
+        let output_schema = Arc::new(ArrowSchema::new(vec![
+            ArrowField::new(INFLUXQL_MEASUREMENT_COLUMN_NAME, DataType::Utf8, false),
+            ArrowField::new(key_col, DataType::Utf8, false),
+        ]));
+
+        let tables = self.expand_show_from_clause(from)?;
+        let metadata_cutoff = self.metadata_cutoff();
+
+        let mut union_plan = None;
+        for table in tables {
+            let Some(table_schema) = self.s.table_schema(&table) else {
+                continue;
+            };
+            let tags = table_schema
+                .tags_iter()
+                .map(|field| field.name().as_str())
+                .sorted()
+                .collect::<Vec<_>>();
+
+            let Some((plan, measurement_expr)) = self.create_table_ref(&table)? else {
+                continue;
+            };
+
+            let ds = DataSource::Table(table.clone());
+            let schema = IQLSchema::new_from_ds_schema(plan.schema(), ds.schema(self.s)?)?;
+            let plan = self.plan_where_clause(plan, condition, metadata_cutoff, &schema)?;
+
+            let key_expr = series_key_expr(self.s.measurement_name(&table), &tags)?.alias(key_col);
+            let plan = if tags.is_empty() {
+                // A measurement without tags has a single series, if it has any rows.
+                LogicalPlanBuilder::from(plan)
+                    .limit(0, Some(1))?
+                    .project(measurement_expr.into_iter().chain([key_expr]))?
+                    .build()?
+            } else {
+                LogicalPlanBuilder::from(plan)
+                    .project(tags.iter().map(|tag| Expr::Column(Column::from_name(*tag))))?
+                    .distinct()?
+                    .project(measurement_expr.into_iter().chain([key_expr]))?
+                    .build()?
+            };
+
+            union_plan = Some(union_flattened(union_plan, plan)?);
         }
 
+        Ok(match union_plan {
+            Some(plan) => plan,
+            None => LogicalPlan::EmptyRelation(EmptyRelation {
+                produce_one_row: false,
+                schema: output_schema.to_dfschema_ref()?,
+            }),
+        })
+    }
+
+    /// Returns a plan of the distinct tag values of the measurements of the `from`
+    /// clause, which have a tag key matching `with_key`.
+    ///
+    /// The plan produces the `iox::measurement`, `key` and `value` columns, and is
+    /// not sorted.
+    fn tag_values_plan(
+        &self,
+        from: Option<ShowFromClause>,
+        with_key: &WithKeyClause,
+        condition: &Option<WhereClause>,
+    ) -> Result<LogicalPlan> {
         let key_col = "key";
         let value_col = "value";
         let output_schema = Arc::new(ArrowSchema::new(vec![
@@ -3548,7 +4385,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             ArrowField::new(value_col, DataType::Utf8, false),
         ]));
 
-        let tables = self.expand_show_from_clause(show_tag_values.from)?;
+        let tables = self.expand_show_from_clause(from)?;
         let metadata_cutoff = self.metadata_cutoff();
 
         let mut union_plan = None;
@@ -3559,7 +4396,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
             let keys = eval_with_key_clause(
                 schema.tags_iter().map(|field| field.name().as_str()),
-                &show_tag_values.with_key,
+                with_key,
             )?;
             if keys.is_empty() {
                 // don't bother to create a plan for this table
@@ -3572,8 +4409,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
             let ds = DataSource::Table(table.clone());
             let schema = IQLSchema::new_from_ds_schema(plan.schema(), ds.schema(self.s)?)?;
-            let plan =
-                self.plan_where_clause(plan, &show_tag_values.condition, metadata_cutoff, &schema)?;
+            let plan = self.plan_where_clause(plan, condition, metadata_cutoff, &schema)?;
 
             for key in keys {
                 let idx = plan
@@ -3590,76 +4426,40 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     ]))?
                     .build()?;
 
-                union_plan = match union_plan {
-                    Some(union_plan) => match union_plan {
-                        LogicalPlan::Union(Union { inputs, schema }) => {
-                            // Flattens nested Unions to prevent a recursion stack overflow
-                            // later during DataFusion optimization.
-                            let mut inputs = inputs
-                                .iter()
-                                .flat_map(|plan| match plan.as_ref() {
-                                    LogicalPlan::Union(Union { inputs, .. }) => inputs.to_vec(),
-                                    _ => {
-                                        vec![Arc::clone(plan)]
-                                    }
-                                })
-                                .collect::<Vec<_>>();
-                            inputs.push(Arc::new(plan));
-
-                            Some(LogicalPlan::Union(Union { inputs, schema }))
-                        }
-                        _ => Some(union_and_coerce(union_plan, plan)?),
-                    },
-                    None => Some(plan),
-                };
+                union_plan = Some(union_flattened(union_plan, plan)?);
             }
         }
 
-        let plan = match union_plan {
+        Ok(match union_plan {
             Some(plan) => plan,
             None => LogicalPlan::EmptyRelation(EmptyRelation {
                 produce_one_row: false,
                 schema: output_schema.to_dfschema_ref()?,
             }),
-        };
-        let plan = LogicalPlanBuilder::from(plan)
-            .sort([
-                Expr::Column(Column::new_unqualified(INFLUXQL_MEASUREMENT_COLUMN_NAME))
-                    .sort(true, false),
-                Expr::Column(Column::new_unqualified(key_col)).sort(true, false),
-                Expr::Column(Column::new_unqualified(value_col)).sort(true, false),
-            ])?
-            .build()?;
-        let plan = plan_with_metadata(
-            plan,
-            &InfluxQlMetadata {
-                measurement_column_index: MEASUREMENT_COLUMN_INDEX,
-                tag_key_columns: vec![],
-            },
-        )?;
-        let plan = self.limit(
-            plan,
-            show_tag_values.offset,
-            show_tag_values.limit,
-            vec![
-                Expr::Column(Column::new_unqualified(key_col)).sort(true, false),
-                Expr::Column(Column::new_unqualified(value_col)).sort(true, false),
-            ],
-            true,
-            &[],
-            &[],
-        )?;
-
-        Ok(plan)
+        })
     }
 
     fn show_measurements_to_plan(
         &self,
-        show_measurements: ShowMeasurementsStatement,
+        mut show_measurements: ShowMeasurementsStatement,
     ) -> Result<LogicalPlan> {
-        if show_measurements.on.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW MEASUREMENTS ON <database>");
+        match show_measurements.on.take() {
+            Some(ExtendedOnClause::Database(database)) => {
+                return self
+                    .for_database(&database, None)?
+                    .show_measurements_to_plan(show_measurements);
+            }
+            Some(ExtendedOnClause::DatabaseRetentionPolicy(database, retention_policy)) => {
+                return self
+                    .for_database(&database, Some(&retention_policy))?
+                    .show_measurements_to_plan(show_measurements);
+            }
+            Some(ExtendedOnClause::AllDatabases)
+            | Some(ExtendedOnClause::AllDatabasesAndRetentionPolicies) => {
+                // Listing the measurements of every namespace is not supported.
+                return error::not_implemented("SHOW MEASUREMENTS ON *");
+            }
+            None => {}
         }
 
         let tables = self.expand_with_measurement_clause(show_measurements.with_measurement)?;
@@ -3771,16 +4571,179 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         Ok(plan)
     }
 
-    /// A limited implementation of SHOW RETENTION POLICIES that assumes
-    /// any database has a single, default, retention policy.
+    /// Plan the SHOW MEASUREMENT CARDINALITY statement.
+    ///
+    /// With a `WHERE` clause, the measurements with at least one row matching
+    /// the condition are counted. Without one, every measurement of the
+    /// catalog is counted, including those without any rows, consistent with
+    /// the measurements listed by SHOW MEASUREMENTS.
+    fn show_measurement_cardinality_to_plan(
+        &self,
+        mut show_measurement_cardinality: ShowMeasurementCardinalityStatement,
+    ) -> Result<LogicalPlan> {
+        if let Some(database) = show_measurement_cardinality.database.take() {
+            return self
+                .for_database(&database, None)?
+                .show_measurement_cardinality_to_plan(show_measurement_cardinality);
+        }
+
+        let tables = self.expand_show_from_clause(show_measurement_cardinality.from)?;
+
+        let count_col = "count";
+        let dummy_measurement_name = "measurements";
+
+        let plan = match show_measurement_cardinality.condition {
+            Some(condition) => {
+                // Count the measurements with at least one row matching the condition
+                let name_col = "name";
+                let condition = Some(condition);
+                let metadata_cutoff = self.metadata_cutoff();
+
+                let mut union_plan = None;
+                for table in tables {
+                    let Some((plan, _measurement_expr)) = self.create_table_ref(&table)? else {
+                        continue;
+                    };
+
+                    let ds = DataSource::Table(table.clone());
+                    let schema = IQLSchema::new_from_ds_schema(plan.schema(), ds.schema(self.s)?)?;
+                    let plan =
+                        self.plan_where_clause(plan, &condition, metadata_cutoff, &schema)?;
+
+                    let plan = LogicalPlanBuilder::from(plan)
+                        .limit(0, Some(1))?
+                        .project([lit_dict(&table).alias(name_col)])?
+                        .build()?;
+
+                    union_plan = Some(union_flattened(union_plan, plan)?);
+                }
+
+                let plan = match union_plan {
+                    Some(plan) => plan,
+                    None => LogicalPlan::EmptyRelation(EmptyRelation {
+                        produce_one_row: false,
+                        schema: Arc::new(ArrowSchema::new(vec![ArrowField::new(
+                            name_col,
+                            DataType::Utf8,
+                            false,
+                        )]))
+                        .to_dfschema_ref()?,
+                    }),
+                };
+                LogicalPlanBuilder::from(plan)
+                    .aggregate(Vec::<Expr>::new(), [count(lit(1)).alias(count_col)])?
+                    .project([
+                        lit_dict(dummy_measurement_name).alias(INFLUXQL_MEASUREMENT_COLUMN_NAME),
+                        count_col.as_expr(),
+                    ])?
+                    .build()?
+            }
+            None => {
+                let output_schema = Arc::new(ArrowSchema::new(vec![
+                    ArrowField::new(
+                        INFLUXQL_MEASUREMENT_COLUMN_NAME,
+                        (&InfluxColumnType::Tag).into(),
+                        false,
+                    ),
+                    ArrowField::new(
+                        count_col,
+                        (&InfluxColumnType::Field(InfluxFieldType::Integer)).into(),
+                        false,
+                    ),
+                ]));
+                let record_batch = RecordBatch::try_new(
+                    Arc::clone(&output_schema),
+                    vec![
+                        Arc::new(DictionaryArray::try_new(
+                            Int32Array::from(vec![0]),
+                            Arc::new(StringArray::from(vec![Some(dummy_measurement_name)])),
+                        )?),
+                        Arc::new(Int64Array::from(vec![tables.len() as i64])),
+                    ],
+                )?;
+                let table = Arc::new(MemTable::try_new(output_schema, vec![vec![record_batch]])?);
+                LogicalPlanBuilder::scan(
+                    "measurement cardinality",
+                    provider_as_source(table),
+                    None,
+                )?
+                .build()?
+            }
+        };
+
+        plan_with_metadata(
+            plan,
+            &InfluxQlMetadata {
+                measurement_column_index: MEASUREMENT_COLUMN_INDEX,
+                tag_key_columns: vec![],
+            },
+        )
+    }
+
+    fn show_databases_to_plan(&self) -> Result<LogicalPlan> {
+        let mut names = self.s.database_names()?;
+        names.sort_unstable();
+        names.dedup();
+
+        let output_schema = Arc::new(ArrowSchema::new(vec![
+            ArrowField::new(
+                INFLUXQL_MEASUREMENT_COLUMN_NAME,
+                (&InfluxColumnType::Tag).into(),
+                false,
+            ),
+            ArrowField::new(
+                "name",
+                (&InfluxColumnType::Field(InfluxFieldType::String)).into(),
+                false,
+            ),
+        ]));
+        let record_batch = RecordBatch::try_new(
+            Arc::clone(&output_schema),
+            vec![
+                Arc::new(DictionaryArray::try_new(
+                    Int32Array::from(vec![0; names.len()]),
+                    Arc::new(StringArray::from(vec![Some("databases")])),
+                )?),
+                Arc::new(StringArray::from(names)),
+            ],
+        )?;
+        let table = Arc::new(MemTable::try_new(output_schema, vec![vec![record_batch]])?);
+        let plan =
+            LogicalPlanBuilder::scan("databases", provider_as_source(table), None)?.build()?;
+        plan_with_metadata(
+            plan,
+            &InfluxQlMetadata {
+                measurement_column_index: MEASUREMENT_COLUMN_INDEX,
+                tag_key_columns: vec![],
+            },
+        )
+    }
+
+    /// A limited implementation of SHOW RETENTION POLICIES.
+    ///
+    /// The session namespace is assumed to have a single, default, retention
+    /// policy. The retention policies of the database of an `ON` clause are
+    /// those of its namespaces, of which `autogen` is the default. As the
+    /// retention period of a namespace is not known to the planner, the
+    /// duration of every retention policy is reported as infinite.
     fn show_retention_policies_to_plan(
         &self,
         show_retention_policies: ShowRetentionPoliciesStatement,
     ) -> Result<LogicalPlan> {
-        if show_retention_policies.database.is_some() {
-            // This syntax is not yet handled.
-            return error::not_implemented("SHOW RETENTION POLICIES ON <database>");
-        }
+        let names = match show_retention_policies.database {
+            Some(database) => {
+                let database = database.as_str();
+                let mut names = self.s.retention_policy_names(database)?;
+                if names.is_empty() {
+                    return error::query(format!("database not found: {database}"));
+                }
+                names.sort_unstable();
+                names.dedup();
+                names
+            }
+            None => vec!["autogen"],
+        };
+        let num_rows = names.len();
 
         let output_schema = Arc::new(ArrowSchema::new(vec![
             ArrowField::new(
@@ -3818,14 +4781,19 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             Arc::clone(&output_schema),
             vec![
                 Arc::new(DictionaryArray::try_new(
-                    Int32Array::from(vec![0]),
+                    Int32Array::from(vec![0; num_rows]),
                     Arc::new(StringArray::from(vec![Some("retention_policies")])),
                 )?),
-                Arc::new(StringArray::from(vec![Some("autogen")])),
-                Arc::new(StringArray::from(vec![Some("0s")])),
-                Arc::new(StringArray::from(vec![Some("168h0m0s")])),
-                Arc::new(Int64Array::from(vec![1])),
-                Arc::new(BooleanArray::from(vec![true])),
+                Arc::new(StringArray::from_iter_values(names.iter().copied())),
+                Arc::new(StringArray::from(vec!["0s"; num_rows])),
+                Arc::new(StringArray::from(vec!["168h0m0s"; num_rows])),
+                Arc::new(Int64Array::from(vec![1; num_rows])),
+                Arc::new(BooleanArray::from(
+                    names
+                        .iter()
+                        .map(|name| *name == "autogen")
+                        .collect::<Vec<_>>(),
+                )),
             ],
         )?;
         let table = Arc::new(MemTable::try_new(output_schema, vec![vec![record_batch]])?);
@@ -4145,6 +5113,81 @@ fn find_time_column_index(fields: &[Field]) -> Option<usize> {
         .map(|(i, _)| i)
 }
 
+/// Returns the union of `union_plan` and `plan`, or `plan` if `union_plan` is `None`.
+///
+/// Nested unions are flattened to prevent a recursion stack overflow later during
+/// DataFusion optimization.
+fn union_flattened(union_plan: Option<LogicalPlan>, plan: LogicalPlan) -> Result<LogicalPlan> {
+    match union_plan {
+        Some(LogicalPlan::Union(Union { inputs, schema })) => {
+            let mut inputs = inputs
+                .iter()
+                .flat_map(|plan| match plan.as_ref() {
+                    LogicalPlan::Union(Union { inputs, .. }) => inputs.to_vec(),
+                    _ => {
+                        vec![Arc::clone(plan)]
+                    }
+                })
+                .collect::<Vec<_>>();
+            inputs.push(Arc::new(plan));
+
+            Ok(LogicalPlan::Union(Union { inputs, schema }))
+        }
+        Some(union_plan) => union_and_coerce(union_plan, plan),
+        None => Ok(plan),
+    }
+}
+
+/// Returns a plan that counts the rows of `input` for each measurement, such as
+/// the series of a `SHOW SERIES CARDINALITY` statement.
+fn cardinality_plan(input: LogicalPlan) -> Result<LogicalPlan> {
+    let plan = LogicalPlanBuilder::from(input)
+        .aggregate(
+            [INFLUXQL_MEASUREMENT_COLUMN_NAME.as_expr()],
+            [count(lit(1)).alias("count")],
+        )?
+        .sort([INFLUXQL_MEASUREMENT_COLUMN_NAME.as_expr().sort(true, false)])?
+        .build()?;
+    plan_with_metadata(
+        plan,
+        &InfluxQlMetadata {
+            measurement_column_index: MEASUREMENT_COLUMN_INDEX,
+            tag_key_columns: vec![],
+        },
+    )
+}
+
+/// Returns an expression that evaluates to the series key of a row of the
+/// `measurement`, such as `cpu,host=server01,region=uswest`.
+///
+/// Tags which are `NULL` are omitted from the series key, and the `tags` must be
+/// sorted by name.
+fn series_key_expr(measurement: &str, tags: &[&str]) -> Result<Expr> {
+    /// Escape the special characters of a tag key or value.
+    fn escape_tag(expr: Expr) -> Expr {
+        [",", "=", " "]
+            .into_iter()
+            .fold(expr, |expr, c| replace(expr, lit(c), lit(format!("\\{c}"))))
+    }
+
+    let mut args = vec![lit(measurement.replace(',', "\\,").replace(' ', "\\ "))];
+    for tag in tags {
+        let value = cast(Expr::Column(Column::from_name(*tag)), DataType::Utf8);
+        let key = tag
+            .replace(',', "\\,")
+            .replace('=', "\\=")
+            .replace(' ', "\\ ");
+        args.push(
+            when(
+                value.clone().is_not_null(),
+                concat(vec![lit(format!(",{key}=")), escape_tag(value)]),
+            )
+            .otherwise(lit(""))?,
+        );
+    }
+    Ok(concat(args))
+}
+
 /// Evaluate [`WithKeyClause`] on the given list of keys.
 ///
 /// This may fail if the clause contains an invalid regex.
@@ -4220,6 +5263,32 @@ fn find_var_refs(select: &Select) -> BTreeSet<&VarRef> {
     var_refs
 }
 
+/// Returns a predicate that restricts the 1-based position `expr` to the
+/// range described by the `limit` and `offset`.
+fn limit_offset_filter(expr: Expr, limit: Option<i64>, offset: Option<i64>) -> Expr {
+    match (limit, offset) {
+        // WHERE expr BETWEEN OFFSET + 1 AND OFFSET + LIMIT
+        (Some(limit), Some(offset)) => {
+            let low = offset + 1;
+            let high = offset + limit;
+
+            Expr::Between(Between {
+                expr: Box::new(expr),
+                negated: false,
+                low: Box::new(lit(low)),
+                high: Box::new(lit(high)),
+            })
+        }
+
+        // WHERE expr <= LIMIT
+        (Some(limit), None) => expr.lt_eq(lit(limit)),
+
+        // WHERE expr > OFFSET
+        (None, Some(offset)) => expr.gt(lit(offset)),
+        (None, None) => unreachable!("limit and offset cannot not be None"),
+    }
+}
+
 /// Calculate the partitioning for window functions.
 fn window_partition_by(
     ctx: &Context<'_>,
@@ -4351,6 +5420,43 @@ mod tests {
             );
         }
 
+        // Tables only accessible using a qualified measurement name
+        sp.add_qualified_schema(
+            "telegraf",
+            Some("autogen"),
+            SchemaBuilder::new()
+                .measurement("cpu")
+                .timestamp()
+                .tag("host")
+                .influx_field("usage_idle", InfluxFieldType::Float)
+                .build()
+                .unwrap(),
+        );
+        sp.add_qualified_schema(
+            "telegraf",
+            None,
+            SchemaBuilder::new()
+                .measurement("cpu")
+                .timestamp()
+                .tag("host")
+                .influx_field("usage_idle", InfluxFieldType::Float)
+                .build()
+                .unwrap(),
+        );
+
+        // Databases only accessible using the ON clause of metadata statements
+        let disk = SchemaBuilder::new()
+            .measurement("disk")
+            .timestamp()
+            .tag("host")
+            .tag("device")
+            .influx_field("free", InfluxFieldType::Integer)
+            .build()
+            .unwrap();
+        sp.add_database("telegraf", None, [disk.clone()]);
+        sp.add_database("telegraf", Some("autogen"), [disk.clone()]);
+        sp.add_database("telegraf", Some("one_week"), [disk]);
+
         let iox_ctx = IOxSessionContext::with_testing();
         let planner = InfluxQLToLogicalPlan::new(&sp, &iox_ctx);
 
@@ -4453,11 +5559,12 @@ mod tests {
         assert_snapshot!(plan("CREATE DATABASE foo"), @"This feature is not implemented: CREATE DATABASE");
         assert_snapshot!(plan("DELETE FROM foo"), @"This feature is not implemented: DELETE");
         assert_snapshot!(plan("DROP MEASUREMENT foo"), @"This feature is not implemented: DROP MEASUREMENT");
-        assert_snapshot!(plan("SHOW DATABASES"), @"This feature is not implemented: SHOW DATABASES");
     }
 
     mod metadata_queries {
         use super::*;
+        use arrow_util::assert_batches_eq;
+        use test_helpers::assert_contains;
 
         #[test]
         fn test_show_field_keys() {
@@ -5015,7 +6122,143 @@ mod tests {
         #[test]
         fn test_show_retention_policies() {
             assert_snapshot!(plan("SHOW RETENTION POLICIES"), @"TableScan: retention policies [iox::measurement:Dictionary(Int32, Utf8), name:Utf8, duration:Utf8, shardGroupDuration:Utf8, replicaN:Int64, default:Boolean]");
-            assert_snapshot!(plan("SHOW RETENTION POLICIES ON my_db"), @"This feature is not implemented: SHOW RETENTION POLICIES ON <database>");
+            assert_snapshot!(plan("SHOW RETENTION POLICIES ON telegraf"), @"TableScan: retention policies [iox::measurement:Dictionary(Int32, Utf8), name:Utf8, duration:Utf8, shardGroupDuration:Utf8, replicaN:Int64, default:Boolean]");
+            assert_snapshot!(plan("SHOW RETENTION POLICIES ON my_db"), @"Error during planning: database not found: my_db");
+        }
+
+        #[tokio::test]
+        async fn test_show_retention_policies_rows() {
+            async fn rows(sql: &str) -> Vec<RecordBatch> {
+                let ctx = IOxSessionContext::with_testing();
+                let plan = ctx
+                    .create_physical_plan(&logical_plan(sql).unwrap())
+                    .await
+                    .unwrap();
+                ctx.collect(plan).await.unwrap()
+            }
+
+            // The session namespace has a single, default, retention policy
+            assert_batches_eq!(
+                [
+                    "+--------------------+---------+----------+--------------------+----------+---------+",
+                    "| iox::measurement   | name    | duration | shardGroupDuration | replicaN | default |",
+                    "+--------------------+---------+----------+--------------------+----------+---------+",
+                    "| retention_policies | autogen | 0s       | 168h0m0s           | 1        | true    |",
+                    "+--------------------+---------+----------+--------------------+----------+---------+",
+                ],
+                &rows("SHOW RETENTION POLICIES").await
+            );
+
+            // The retention policies of the `telegraf` and `telegraf/one_week` namespaces
+            assert_batches_eq!(
+                [
+                    "+--------------------+----------+----------+--------------------+----------+---------+",
+                    "| iox::measurement   | name     | duration | shardGroupDuration | replicaN | default |",
+                    "+--------------------+----------+----------+--------------------+----------+---------+",
+                    "| retention_policies | autogen  | 0s       | 168h0m0s           | 1        | true    |",
+                    "| retention_policies | one_week | 0s       | 168h0m0s           | 1        | false   |",
+                    "+--------------------+----------+----------+--------------------+----------+---------+",
+                ],
+                &rows("SHOW RETENTION POLICIES ON telegraf").await
+            );
+        }
+
+        #[test]
+        fn test_show_on_database() {
+            // Plans against the tables of the database of the ON clause
+            let p = plan("SHOW TAG KEYS ON telegraf FROM disk WHERE host = 'a'");
+            assert_contains!(p, "TableScan: disk");
+            let p = plan("SHOW TAG VALUES ON telegraf WITH KEY = device WHERE host = 'a'");
+            assert_contains!(p, "TableScan: disk");
+            let p = plan("SHOW FIELD KEYS ON telegraf FROM disk");
+            assert_contains!(p, "TableScan: field_keys");
+            let p = plan("SHOW MEASUREMENTS ON telegraf WHERE host = 'a'");
+            assert_contains!(p, "TableScan: disk");
+            let p = plan("SHOW MEASUREMENTS ON telegraf.autogen WHERE host = 'a'");
+            assert_contains!(p, "TableScan: disk");
+
+            // Tables of the session namespace are not visible
+            let p = plan("SHOW TAG KEYS ON telegraf FROM cpu WHERE host = 'a'");
+            assert!(!p.contains("TableScan: cpu"), "{p}");
+
+            // Fallible cases
+            assert_snapshot!(plan("SHOW TAG KEYS ON my_db"), @"Error during planning: database not found: my_db");
+            assert_snapshot!(plan("SHOW FIELD KEYS ON my_db"), @"Error during planning: database not found: my_db");
+            assert_snapshot!(plan("SHOW TAG VALUES ON my_db WITH KEY = host"), @"Error during planning: database not found: my_db");
+            assert_snapshot!(plan("SHOW MEASUREMENTS ON telegraf.my_rp"), @"Error during planning: database not found: telegraf.my_rp");
+            assert_snapshot!(plan("SHOW MEASUREMENTS ON *"), @"This feature is not implemented: SHOW MEASUREMENTS ON *");
+            assert_snapshot!(plan("SHOW MEASUREMENTS ON *.*"), @"This feature is not implemented: SHOW MEASUREMENTS ON *");
+        }
+
+        #[test]
+        fn test_show_databases() {
+            assert_snapshot!(plan("SHOW DATABASES"), @r###"
+            TableScan: databases [iox::measurement:Dictionary(Int32, Utf8), name:Utf8]
+            "###);
+        }
+
+        #[test]
+        fn test_show_series() {
+            let p = plan("SHOW SERIES");
+            assert_contains!(p, "TableScan: cpu");
+            assert_contains!(p, "TableScan: disk");
+            assert_contains!(
+                p,
+                "Sort: iox::measurement ASC NULLS LAST, key ASC NULLS LAST"
+            );
+
+            let p = plan("SHOW SERIES FROM cpu WHERE host = 'a' LIMIT 1 OFFSET 2");
+            assert_contains!(p, "TableScan: cpu");
+            assert!(!p.contains("TableScan: disk"), "{p}");
+            assert_contains!(p, "Filter: iox::row BETWEEN Int64(3) AND Int64(3)");
+
+            let p = plan("SHOW SERIES ON telegraf");
+            assert_contains!(p, "TableScan: disk");
+            assert!(!p.contains("TableScan: cpu"), "{p}");
+
+            // A FROM clause that matches no measurements produces an empty plan
+            let p = plan("SHOW SERIES FROM does_not_exist");
+            assert_contains!(p, "EmptyRelation");
+
+            assert_snapshot!(plan("SHOW SERIES ON my_db"), @"Error during planning: database not found: my_db");
+        }
+
+        #[test]
+        fn test_show_cardinality() {
+            let p = plan("SHOW SERIES CARDINALITY FROM cpu");
+            assert_contains!(
+                p,
+                "Aggregate: groupBy=[[iox::measurement]], aggr=[[count(Int64(1)) AS count]]"
+            );
+            assert_contains!(p, "TableScan: cpu");
+
+            // IOx always computes the exact cardinality
+            assert_eq!(
+                plan("SHOW SERIES EXACT CARDINALITY FROM cpu"),
+                plan("SHOW SERIES CARDINALITY FROM cpu")
+            );
+
+            let p = plan("SHOW TAG VALUES CARDINALITY FROM cpu WITH KEY = host");
+            assert_contains!(
+                p,
+                "Aggregate: groupBy=[[iox::measurement]], aggr=[[count(Int64(1)) AS count]]"
+            );
+            assert_contains!(p, "TableScan: cpu");
+
+            let p = plan("SHOW MEASUREMENT CARDINALITY");
+            assert_contains!(p, "TableScan: measurement cardinality");
+
+            let p = plan("SHOW MEASUREMENT EXACT CARDINALITY WHERE host = 'a'");
+            assert_contains!(
+                p,
+                "Aggregate: groupBy=[[]], aggr=[[count(Int64(1)) AS count]]"
+            );
+            assert_contains!(p, "TableScan: cpu");
+
+            let p = plan("SHOW SERIES CARDINALITY ON telegraf");
+            assert_contains!(p, "TableScan: disk");
+
+            assert_snapshot!(plan("SHOW MEASUREMENT CARDINALITY ON my_db"), @"Error during planning: database not found: my_db");
         }
     }
 
@@ -5024,6 +6267,20 @@ mod tests {
     mod select {
         use super::*;
 
+        /// The planner plans the selection of a `SELECT ... INTO` statement, and
+        /// the points are written when the plan is executed.
+        #[test]
+        fn into() {
+            assert_eq!(
+                plan("SELECT mean(usage_idle) INTO cpu_1h FROM cpu GROUP BY time(1h), host"),
+                plan("SELECT mean(usage_idle) FROM cpu GROUP BY time(1h), host")
+            );
+
+            assert_snapshot!(plan("SELECT usage_idle FROM (SELECT usage_idle INTO foo FROM cpu)"), @"Error during planning: INTO clause is not permitted in a subquery");
+            assert_snapshot!(plan("EXPLAIN SELECT usage_idle INTO foo FROM cpu"), @"This feature is not implemented: EXPLAIN SELECT INTO");
+            assert_snapshot!(plan("EXPLAIN ANALYZE SELECT usage_idle INTO foo FROM cpu"), @"This feature is not implemented: EXPLAIN SELECT INTO");
+        }
+
         mod subqueries {
             use super::*;
 
@@ -5323,6 +6580,150 @@ mod tests {
                 "###);
             }
 
+            #[test]
+            fn test_exponential_moving_average() {
+                // no aggregates
+                assert_snapshot!(plan("SELECT EXPONENTIAL_MOVING_AVERAGE(usage_idle, 3) FROM cpu"), @r#"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), exponential_moving_average:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, exponential_moving_average(cpu.usage_idle,Int64(3),Int64(-1),Utf8("exponential")) AS exponential_moving_average [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), exponential_moving_average:Float64;N]
+                    Filter: exponential_moving_average(cpu.usage_idle,Int64(3),Int64(-1),Utf8("exponential")) IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, exponential_moving_average(cpu.usage_idle,Int64(3),Int64(-1),Utf8("exponential")):Float64;N]
+                      WindowAggr: windowExpr=[[exponential_moving_average(cpu.usage_idle, Int64(3), Int64(-1), Utf8("exponential")) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS exponential_moving_average(cpu.usage_idle,Int64(3),Int64(-1),Utf8("exponential"))]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, exponential_moving_average(cpu.usage_idle,Int64(3),Int64(-1),Utf8("exponential")):Float64;N]
+                        Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                          TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "#);
+
+                // aggregate, with hold period and warmup type
+                assert_snapshot!(plan("SELECT RELATIVE_STRENGTH_INDEX(MEAN(usage_idle), 3, 2, 'simple') FROM cpu GROUP BY TIME(10s)"), @r#"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, relative_strength_index:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, relative_strength_index(avg(cpu.usage_idle),Int64(3),Int64(2),Utf8("simple")) AS relative_strength_index [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, relative_strength_index:Float64;N]
+                    Filter: relative_strength_index(avg(cpu.usage_idle),Int64(3),Int64(2),Utf8("simple")) IS NOT NULL [time:Timestamp(Nanosecond, None);N, avg(cpu.usage_idle):Float64;N, relative_strength_index(avg(cpu.usage_idle),Int64(3),Int64(2),Utf8("simple")):Float64;N]
+                      WindowAggr: windowExpr=[[relative_strength_index(avg(cpu.usage_idle), Int64(3), Int64(2), Utf8("simple")) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS relative_strength_index(avg(cpu.usage_idle),Int64(3),Int64(2),Utf8("simple"))]] [time:Timestamp(Nanosecond, None);N, avg(cpu.usage_idle):Float64;N, relative_strength_index(avg(cpu.usage_idle),Int64(3),Int64(2),Utf8("simple")):Float64;N]
+                        GapFill: groupBy=[time], aggr=[[avg(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 0, nanoseconds: 10000000000 }"), range=Unbounded..Included(Literal(TimestampNanosecond(1672531200000000000, None), None)) [time:Timestamp(Nanosecond, None);N, avg(cpu.usage_idle):Float64;N]
+                          Aggregate: groupBy=[[date_bin_wallclock(IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 0, nanoseconds: 10000000000 }"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[avg(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, avg(cpu.usage_idle):Float64;N]
+                            Filter: cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                              Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                                TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "#);
+            }
+
+            #[test]
+            fn test_kaufmans_and_chande_momentum_oscillator() {
+                // no aggregates
+                assert_snapshot!(plan("SELECT KAUFMANS_EFFICIENCY_RATIO(usage_idle, 3) FROM cpu"), @r#"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), kaufmans_efficiency_ratio:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, kaufmans_efficiency_ratio(cpu.usage_idle,Int64(3),Int64(-1)) AS kaufmans_efficiency_ratio [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), kaufmans_efficiency_ratio:Float64;N]
+                    Filter: kaufmans_efficiency_ratio(cpu.usage_idle,Int64(3),Int64(-1)) IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, kaufmans_efficiency_ratio(cpu.usage_idle,Int64(3),Int64(-1)):Float64;N]
+                      WindowAggr: windowExpr=[[kaufmans_efficiency_ratio(cpu.usage_idle, Int64(3), Int64(-1)) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS kaufmans_efficiency_ratio(cpu.usage_idle,Int64(3),Int64(-1))]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, kaufmans_efficiency_ratio(cpu.usage_idle,Int64(3),Int64(-1)):Float64;N]
+                        Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                          TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "#);
+
+                // aggregate
+                assert_snapshot!(plan("SELECT CHANDE_MOMENTUM_OSCILLATOR(MEAN(usage_idle), 3) FROM cpu GROUP BY TIME(10s)"), @r#"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, chande_momentum_oscillator:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, chande_momentum_oscillator(avg(cpu.usage_idle),Int64(3),Int64(-1),Utf8("none")) AS chande_momentum_oscillator [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, chande_momentum_oscillator:Float64;N]
+                    Filter: chande_momentum_oscillator(avg(cpu.usage_idle),Int64(3),Int64(-1),Utf8("none")) IS NOT NULL [time:Timestamp(Nanosecond, None);N, avg(cpu.usage_idle):Float64;N, chande_momentum_oscillator(avg(cpu.usage_idle),Int64(3),Int64(-1),Utf8("none")):Float64;N]
+                      WindowAggr: windowExpr=[[chande_momentum_oscillator(avg(cpu.usage_idle), Int64(3), Int64(-1), Utf8("none")) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS chande_momentum_oscillator(avg(cpu.usage_idle),Int64(3),Int64(-1),Utf8("none"))]] [time:Timestamp(Nanosecond, None);N, avg(cpu.usage_idle):Float64;N, chande_momentum_oscillator(avg(cpu.usage_idle),Int64(3),Int64(-1),Utf8("none")):Float64;N]
+                        GapFill: groupBy=[time], aggr=[[avg(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 0, nanoseconds: 10000000000 }"), range=Unbounded..Included(Literal(TimestampNanosecond(1672531200000000000, None), None)) [time:Timestamp(Nanosecond, None);N, avg(cpu.usage_idle):Float64;N]
+                          Aggregate: groupBy=[[date_bin_wallclock(IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 0, nanoseconds: 10000000000 }"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[avg(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, avg(cpu.usage_idle):Float64;N]
+                            Filter: cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                              Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                                TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "#);
+            }
+
+            #[test]
+            fn test_holt_winters() {
+                for (sql, with_fit) in [
+                    (
+                        "SELECT HOLT_WINTERS(MEAN(usage_idle), 3, 2) FROM cpu GROUP BY TIME(10s), host",
+                        false,
+                    ),
+                    (
+                        "SELECT HOLT_WINTERS_WITH_FIT(MEAN(usage_idle), 3, 2) FROM cpu GROUP BY TIME(10s), host",
+                        true,
+                    ),
+                ] {
+                    let plan = logical_plan(sql).unwrap();
+                    let fields = plan
+                        .schema()
+                        .fields()
+                        .iter()
+                        .map(|f| (f.name().as_str(), f.data_type().clone()))
+                        .collect::<Vec<_>>();
+                    let name = if with_fit {
+                        "holt_winters_with_fit"
+                    } else {
+                        "holt_winters"
+                    };
+                    assert_eq!(
+                        fields,
+                        vec![
+                            (
+                                INFLUXQL_MEASUREMENT_COLUMN_NAME,
+                                DataType::Dictionary(
+                                    Box::new(DataType::Int32),
+                                    Box::new(DataType::Utf8)
+                                )
+                            ),
+                            (
+                                "time",
+                                DataType::Timestamp(arrow::datatypes::TimeUnit::Nanosecond, None)
+                            ),
+                            (
+                                "host",
+                                DataType::Dictionary(
+                                    Box::new(DataType::Int32),
+                                    Box::new(DataType::Utf8)
+                                )
+                            ),
+                            (name, DataType::Float64),
+                        ]
+                    );
+                }
+
+                // The points are produced by the holt_winters aggregate, grouped by series.
+                assert_snapshot!(plan("SELECT HOLT_WINTERS(MEAN(usage_idle), 3, 2) FROM cpu GROUP BY TIME(10s)"), @r#"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, holt_winters:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, get_field(__holt_winters, Utf8("time")) AS time, get_field(__holt_winters, Utf8("value")) AS holt_winters [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, holt_winters:Float64;N]
+                    Unnest: lists[__holt_winters|depth=1] structs[] [__holt_winters:Struct([Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "value", data_type: Float64, nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                      Aggregate: groupBy=[[]], aggr=[[holt_winters(time, holt_winters, Int64(3), Int64(2), Int64(10000000000), Boolean(false)) AS __holt_winters]] [__holt_winters:List(Field { name: "item", data_type: Struct([Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "value", data_type: Float64, nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }]), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} });N]
+                        Projection: time, avg(cpu.usage_idle) AS holt_winters [time:Timestamp(Nanosecond, None);N, holt_winters:Float64;N]
+                          GapFill: groupBy=[time], aggr=[[avg(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 0, nanoseconds: 10000000000 }"), range=Unbounded..Included(Literal(TimestampNanosecond(1672531200000000000, None), None)) [time:Timestamp(Nanosecond, None);N, avg(cpu.usage_idle):Float64;N]
+                            Aggregate: groupBy=[[date_bin_wallclock(IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 0, nanoseconds: 10000000000 }"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[avg(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, avg(cpu.usage_idle):Float64;N]
+                              Filter: cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                                Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "#);
+                assert_snapshot!(plan("SELECT HOLT_WINTERS_WITH_FIT(MEAN(usage_idle), 3, 2) FROM cpu GROUP BY TIME(10s)"), @r#"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, holt_winters_with_fit:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, get_field(__holt_winters, Utf8("time")) AS time, get_field(__holt_winters, Utf8("value")) AS holt_winters_with_fit [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, holt_winters_with_fit:Float64;N]
+                    Unnest: lists[__holt_winters|depth=1] structs[] [__holt_winters:Struct([Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "value", data_type: Float64, nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                      Aggregate: groupBy=[[]], aggr=[[holt_winters(time, holt_winters_with_fit, Int64(3), Int64(2), Int64(10000000000), Boolean(true)) AS __holt_winters]] [__holt_winters:List(Field { name: "item", data_type: Struct([Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "value", data_type: Float64, nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }]), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} });N]
+                        Projection: time, avg(cpu.usage_idle) AS holt_winters_with_fit [time:Timestamp(Nanosecond, None);N, holt_winters_with_fit:Float64;N]
+                          GapFill: groupBy=[time], aggr=[[avg(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 0, nanoseconds: 10000000000 }"), range=Unbounded..Included(Literal(TimestampNanosecond(1672531200000000000, None), None)) [time:Timestamp(Nanosecond, None);N, avg(cpu.usage_idle):Float64;N]
+                            Aggregate: groupBy=[[date_bin_wallclock(IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 0, nanoseconds: 10000000000 }"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[avg(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, avg(cpu.usage_idle):Float64;N]
+                              Filter: cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                                Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "#);
+
+                assert_snapshot!(plan("SELECT HOLT_WINTERS(MEAN(usage_idle), 3, 2), MEAN(usage_idle) FROM cpu GROUP BY TIME(10s)"), @r"
+                rewriting statement
+                caused by
+                gather information about select statement
+                caused by
+                Error during planning: holt_winters cannot be combined with other functions
+                ");
+                assert_snapshot!(plan("SELECT HOLT_WINTERS(MEAN(usage_idle), 3, 2) * 2 FROM cpu GROUP BY TIME(10s)"), @r"
+                rewriting statement
+                caused by
+                gather information about select statement
+                caused by
+                Error during planning: holt_winters cannot be used in an expression
+                ");
+            }
+
             #[test]
             fn test_derivative() {
                 // no aggregates
@@ -5753,6 +7154,41 @@ mod tests {
             "#);
         }
 
+        #[test]
+        fn test_sample() {
+            assert_snapshot!(plan("SELECT sample(usage_idle, 3) FROM cpu"), @r#"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, sample:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, get_field(__sample, Utf8("time")) AS time, get_field(__sample, Utf8("value")) AS sample [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, sample:Float64;N]
+                Unnest: lists[__sample|depth=1] structs[] [__sample:Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                  Aggregate: groupBy=[[]], aggr=[[sample(cpu.usage_idle, cpu.time, Int64(3)) AS __sample]] [__sample:List(Field { name: "item", data_type: Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} });N]
+                    Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "#);
+
+            // additional tags and fields are selected with each point
+            assert_snapshot!(plan("SELECT sample(usage_idle, 3), usage_system, host FROM cpu GROUP BY cpu"), @r#"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, sample:Float64;N, usage_system:Float64;N, host:Dictionary(Int32, Utf8);N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, get_field(__sample, Utf8("time")) AS time, cpu.cpu AS cpu, get_field(__sample, Utf8("value")) AS sample, get_field(__sample, Utf8("other_1")) AS usage_system, get_field(__sample, Utf8("other_2")) AS host [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, sample:Float64;N, usage_system:Float64;N, host:Dictionary(Int32, Utf8);N]
+                Unnest: lists[__sample|depth=1] structs[] [cpu:Dictionary(Int32, Utf8);N, __sample:Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "other_1", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "other_2", data_type: Dictionary(Int32, Utf8), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                  Aggregate: groupBy=[[cpu.cpu]], aggr=[[sample(cpu.usage_idle, cpu.time, Int64(3), cpu.usage_system, cpu.host) AS __sample]] [cpu:Dictionary(Int32, Utf8);N, __sample:List(Field { name: "item", data_type: Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "other_1", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "other_2", data_type: Dictionary(Int32, Utf8), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} });N]
+                    Filter: cpu.usage_idle IS NOT NULL OR cpu.usage_system IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "#);
+
+            // the points of each function are combined by position
+            assert_snapshot!(plan("SELECT sample(usage_idle, 2), max(usage_idle) FROM cpu"), @r#"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), sample:Float64;N, max:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, get_field(sample, Utf8("value")) AS sample, max [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), sample:Float64;N, max:Float64;N]
+                Unnest: lists[sample|depth=1, max|depth=1] structs[] [time:Timestamp(Nanosecond, None), sample:Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N, max:Float64;N]
+                  Projection: time, sample, make_array(max) AS max [time:Timestamp(Nanosecond, None), sample:List(Field { name: "item", data_type: Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} });N, max:List(Field { name: "item", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} })]
+                    Projection: TimestampNanosecond(0, None) AS time, sample(cpu.usage_idle,cpu.time,Int64(2)) AS sample, get_field(selector_max(cpu.usage_idle,cpu.time), Utf8("value")) AS max [time:Timestamp(Nanosecond, None), sample:List(Field { name: "item", data_type: Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} });N, max:Float64;N]
+                      Filter: __aggr_count > Int64(0) [sample(cpu.usage_idle,cpu.time,Int64(2)):List(Field { name: "item", data_type: Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} });N, selector_max(cpu.usage_idle,cpu.time):Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N, __aggr_count:Int64]
+                        Aggregate: groupBy=[[]], aggr=[[sample(cpu.usage_idle, cpu.time, Int64(2)), selector_max(cpu.usage_idle, cpu.time), count(Int64(1)) AS __aggr_count]] [sample(cpu.usage_idle,cpu.time,Int64(2)):List(Field { name: "item", data_type: Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} });N, selector_max(cpu.usage_idle,cpu.time):Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N, __aggr_count:Int64]
+                          Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                            TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "#);
+        }
+
         #[test]
         fn test_top() {
             assert_snapshot!(plan("SELECT top(usage_idle,10) FROM cpu"), @r###"
@@ -6211,7 +7647,10 @@ mod tests {
               TableScan: retention policies [iox::measurement:Dictionary(Int32, Utf8), name:Utf8, duration:Utf8, shardGroupDuration:Utf8, replicaN:Int64, default:Boolean]
             "###);
 
-            assert_snapshot!(plan("EXPLAIN SHOW DATABASES"), @"This feature is not implemented: SHOW DATABASES");
+            assert_snapshot!(plan("EXPLAIN SHOW DATABASES"), @r###"
+            Explain [plan_type:Utf8, plan:Utf8]
+              TableScan: databases [iox::measurement:Dictionary(Int32, Utf8), name:Utf8]
+            "###);
             assert_snapshot!(plan("EXPLAIN EXPLAIN SELECT f64_field::string FROM data"), @r###"
             Explain [plan_type:Utf8, plan:Utf8]
               Explain [plan_type:Utf8, plan:Utf8]
@@ -6818,6 +8257,114 @@ mod tests {
                 "#);
             }
 
+            #[test]
+            fn group_by_time_precision() {
+                // Microseconds, using either the `u` or `µ` unit
+                assert_snapshot!(plan("SELECT count(f64_field) FROM data GROUP BY TIME(10u) FILL(none)"), @r#"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, count:Int64]
+                  Projection: Dictionary(Int32, Utf8("data")) AS iox::measurement, time, count(data.f64_field) AS count [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, count:Int64]
+                    Aggregate: groupBy=[[date_bin_wallclock(IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 0, nanoseconds: 10000 }"), data.time, TimestampNanosecond(0, None)) AS time]], aggr=[[count(data.f64_field)]] [time:Timestamp(Nanosecond, None);N, count(data.f64_field):Int64]
+                      Filter: data.time <= TimestampNanosecond(1672531200000000000, None) [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+                        Filter: data.f64_field IS NOT NULL [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+                          TableScan: data [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+                "#);
+                assert_snapshot!(plan("SELECT count(f64_field) FROM data GROUP BY TIME(10µ) FILL(none)"), @r#"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, count:Int64]
+                  Projection: Dictionary(Int32, Utf8("data")) AS iox::measurement, time, count(data.f64_field) AS count [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, count:Int64]
+                    Aggregate: groupBy=[[date_bin_wallclock(IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 0, nanoseconds: 10000 }"), data.time, TimestampNanosecond(0, None)) AS time]], aggr=[[count(data.f64_field)]] [time:Timestamp(Nanosecond, None);N, count(data.f64_field):Int64]
+                      Filter: data.time <= TimestampNanosecond(1672531200000000000, None) [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+                        Filter: data.f64_field IS NOT NULL [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+                          TableScan: data [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+                "#);
+
+                // Nanoseconds
+                assert_snapshot!(plan("SELECT count(f64_field) FROM data GROUP BY TIME(100ns) FILL(none)"), @r#"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, count:Int64]
+                  Projection: Dictionary(Int32, Utf8("data")) AS iox::measurement, time, count(data.f64_field) AS count [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, count:Int64]
+                    Aggregate: groupBy=[[date_bin_wallclock(IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 0, nanoseconds: 100 }"), data.time, TimestampNanosecond(0, None)) AS time]], aggr=[[count(data.f64_field)]] [time:Timestamp(Nanosecond, None);N, count(data.f64_field):Int64]
+                      Filter: data.time <= TimestampNanosecond(1672531200000000000, None) [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+                        Filter: data.f64_field IS NOT NULL [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+                          TableScan: data [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+                "#);
+
+                // Gap filling uses the same sub-millisecond stride
+                assert_snapshot!(plan("SELECT count(f64_field) FROM data GROUP BY TIME(10u) FILL(null)"), @r#"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, count:Int64]
+                  Projection: Dictionary(Int32, Utf8("data")) AS iox::measurement, time, count(data.f64_field) AS count [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, count:Int64]
+                    GapFill: groupBy=[time], aggr=[[COALESCE(0, count(data.f64_field))]], time_column=time, stride=IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 0, nanoseconds: 10000 }"), range=Unbounded..Included(Literal(TimestampNanosecond(1672531200000000000, None), None)) [time:Timestamp(Nanosecond, None);N, count(data.f64_field):Int64]
+                      Aggregate: groupBy=[[date_bin_wallclock(IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 0, nanoseconds: 10000 }"), data.time, TimestampNanosecond(0, None)) AS time]], aggr=[[count(data.f64_field)]] [time:Timestamp(Nanosecond, None);N, count(data.f64_field):Int64]
+                        Filter: data.time <= TimestampNanosecond(1672531200000000000, None) [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+                          Filter: data.f64_field IS NOT NULL [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+                            TableScan: data [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+                "#);
+                assert_snapshot!(plan("SELECT count(f64_field) FROM data GROUP BY TIME(1ns) FILL(null)"), @r#"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, count:Int64]
+                  Projection: Dictionary(Int32, Utf8("data")) AS iox::measurement, time, count(data.f64_field) AS count [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, count:Int64]
+                    GapFill: groupBy=[time], aggr=[[COALESCE(0, count(data.f64_field))]], time_column=time, stride=IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 0, nanoseconds: 1 }"), range=Unbounded..Included(Literal(TimestampNanosecond(1672531200000000000, None), None)) [time:Timestamp(Nanosecond, None);N, count(data.f64_field):Int64]
+                      Aggregate: groupBy=[[date_bin_wallclock(IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 0, nanoseconds: 1 }"), data.time, TimestampNanosecond(0, None)) AS time]], aggr=[[count(data.f64_field)]] [time:Timestamp(Nanosecond, None);N, count(data.f64_field):Int64]
+                        Filter: data.time <= TimestampNanosecond(1672531200000000000, None) [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+                          Filter: data.f64_field IS NOT NULL [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+                            TableScan: data [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+                "#);
+            }
+
+            /// Executes a sub-millisecond `GROUP BY TIME` query, to verify the rows are
+            /// binned by `date_bin_wallclock` and gap filled using the same stride.
+            #[tokio::test]
+            async fn group_by_time_precision_rows() {
+                use arrow::array::{ArrayRef, Float64Array, TimestampNanosecondArray};
+                use arrow_util::assert_batches_eq;
+
+                let mut sp = MockSchemaProvider::default();
+                let schema = SchemaBuilder::new()
+                    .measurement("hf")
+                    .timestamp()
+                    .influx_field("v", InfluxFieldType::Float)
+                    .build()
+                    .unwrap()
+                    .sort_fields_by_name();
+                let batch = RecordBatch::try_new(
+                    schema.as_arrow(),
+                    vec![
+                        Arc::new(TimestampNanosecondArray::from(vec![20_000, 25_000, 50_000]))
+                            as ArrayRef,
+                        Arc::new(Float64Array::from(vec![1.0, 2.0, 3.0])),
+                    ],
+                )
+                .unwrap();
+                sp.add_schema_with_batches(schema, vec![batch]);
+
+                let iox_ctx = IOxSessionContext::with_testing();
+                let planner = InfluxQLToLogicalPlan::new(&sp, &iox_ctx);
+                let mut statements = parse_statements(
+                    "SELECT count(v), mean(v) FROM hf \
+                     WHERE time >= '1970-01-01T00:00:00Z' AND time < '1970-01-01T00:00:00.00006Z' \
+                     GROUP BY TIME(10u) FILL(null)",
+                )
+                .unwrap();
+                let plan = planner
+                    .statement_to_plan(statements.pop().unwrap())
+                    .unwrap();
+                let plan = iox_ctx.create_physical_plan(&plan).await.unwrap();
+                let batches = iox_ctx.collect(plan).await.unwrap();
+
+                assert_batches_eq!(
+                    [
+                        "+------------------+----------------------------+-------+------+",
+                        "| iox::measurement | time                       | count | mean |",
+                        "+------------------+----------------------------+-------+------+",
+                        "| hf               | 1970-01-01T00:00:00        | 0     |      |",
+                        "| hf               | 1970-01-01T00:00:00.000010 | 0     |      |",
+                        "| hf               | 1970-01-01T00:00:00.000020 | 2     | 1.5  |",
+                        "| hf               | 1970-01-01T00:00:00.000030 | 0     |      |",
+                        "| hf               | 1970-01-01T00:00:00.000040 | 0     |      |",
+                        "| hf               | 1970-01-01T00:00:00.000050 | 1     | 3.0  |",
+                        "+------------------+----------------------------+-------+------+",
+                    ],
+                    &batches
+                );
+            }
+
             #[test]
             fn with_limit_or_offset() {
                 assert_snapshot!(plan("SELECT count(f64_field) FROM data GROUP BY foo LIMIT 1"), @r#"
@@ -6872,25 +8419,6 @@ mod tests {
                 assert_snapshot!(plan(format!("SELECT count(f64_field) FROM data GROUP BY foo LIMIT {max}")), @"Error during planning: limit out of range");
                 assert_snapshot!(plan(format!("SELECT count(f64_field) FROM data GROUP BY foo OFFSET {max}")), @"Error during planning: offset out of range");
             }
-
-            /// These tests validate the planner returns an error when using features that
-            /// are not implemented or supported.
-            mod not_implemented {
-                use super::*;
-
-                /// Tracked by <https://github.com/influxdata/influxdb_iox/issues/7204>
-                #[test]
-                fn group_by_time_precision() {
-                    assert_snapshot!(plan("SELECT count(f64_field) FROM data GROUP BY TIME(10u) FILL(none)"), @r#"
-                    Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, count:Int64]
-                      Projection: Dictionary(Int32, Utf8("data")) AS iox::measurement, time, count(data.f64_field) AS count [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, count:Int64]
-                        Aggregate: groupBy=[[date_bin_wallclock(IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 0, nanoseconds: 10000 }"), data.time, TimestampNanosecond(0, None)) AS time]], aggr=[[count(data.f64_field)]] [time:Timestamp(Nanosecond, None);N, count(data.f64_field):Int64]
-                          Filter: data.time <= TimestampNanosecond(1672531200000000000, None) [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
-                            Filter: data.f64_field IS NOT NULL [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
-                              TableScan: data [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
-                    "#);
-                }
-            }
         }
     }
 
@@ -6898,6 +8426,7 @@ mod tests {
     /// aggregates or `GROUP BY time()` with gap filling.
     mod select_raw {
         use super::*;
+        use test_helpers::assert_contains;
 
         /// Select data from a single measurement
         #[test]
@@ -7181,6 +8710,122 @@ mod tests {
             "###);
         }
 
+        #[test]
+        fn test_select_qualified_measurement() {
+            // The measurement column is the name of the measurement, rather than the table
+            let plan_str = plan(r#"SELECT usage_idle FROM "telegraf"."autogen"."cpu""#);
+            assert_contains!(
+                plan_str,
+                r#"Dictionary(Int32, Utf8("cpu")) AS iox::measurement"#
+            );
+            assert_contains!(plan_str, "TableScan: telegraf.autogen.cpu");
+
+            // Without a retention policy
+            let plan_str = plan(r#"SELECT usage_idle FROM "telegraf".."cpu""#);
+            assert_contains!(plan_str, "TableScan: telegraf..cpu");
+
+            // Regular expression
+            let plan_str = plan(r#"SELECT usage_idle FROM "telegraf"."autogen"./^cp/"#);
+            assert_contains!(plan_str, "TableScan: telegraf.autogen.cpu");
+
+            // Combined with tables of the current database
+            let plan_str = plan(r#"SELECT usage_idle FROM cpu, "telegraf"."autogen"."cpu""#);
+            assert_contains!(plan_str, "TableScan: telegraf.autogen.cpu");
+            assert_contains!(plan_str, "TableScan: cpu");
+
+            // Qualified tables are not visible without the database prefix
+            let plan_str = plan("SELECT usage_idle FROM /autogen/");
+            assert!(!plan_str.contains("TableScan"), "{plan_str}");
+
+            // Measurement does not exist in the database
+            let plan_str = plan(r#"SELECT usage_idle FROM "telegraf"."autogen"."disk""#);
+            assert!(!plan_str.contains("TableScan"), "{plan_str}");
+        }
+
+        #[test]
+        fn test_select_group_by_series_limit_offset() {
+            // Series are numbered using the same order as the output
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu SLIMIT 2 SOFFSET 1"), @r#"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::series BETWEEN Int64(2) AND Int64(3) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64]
+                  WindowAggr: windowExpr=[[dense_rank() ORDER BY [cpu ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64]
+                    Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                        Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                          TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "#);
+
+            // No series are returned
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu SLIMIT 0"), @r#"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::series <= Int64(0) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64]
+                  WindowAggr: windowExpr=[[dense_rank() ORDER BY [cpu ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64]
+                    Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                        Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                          TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "#);
+
+            // Multiple measurements include the measurement in the series key
+            assert_snapshot!(plan("SELECT usage_idle, bytes_free FROM cpu, disk GROUP BY host SLIMIT 1"), @r#"
+            Sort: iox::measurement ASC NULLS LAST, host ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), host:Utf8;N, usage_idle:Float64;N, bytes_free:Int64;N]
+              Projection: iox::measurement, time, host, usage_idle, bytes_free [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), host:Utf8;N, usage_idle:Float64;N, bytes_free:Int64;N]
+                Filter: iox::series <= Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), host:Utf8;N, usage_idle:Float64;N, bytes_free:Int64;N, iox::series:UInt64]
+                  WindowAggr: windowExpr=[[dense_rank() ORDER BY [iox::measurement ASC NULLS LAST, host ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), host:Utf8;N, usage_idle:Float64;N, bytes_free:Int64;N, iox::series:UInt64]
+                    Sort: iox::measurement ASC NULLS LAST, host ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), host:Utf8;N, usage_idle:Float64;N, bytes_free:Int64;N]
+                      Union [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), host:Utf8;N, usage_idle:Float64;N, bytes_free:Int64;N]
+                        Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, CAST(cpu.host AS Utf8) AS host, cpu.usage_idle AS usage_idle, CAST(NULL AS Int64) AS bytes_free [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), host:Utf8;N, usage_idle:Float64;N, bytes_free:Int64;N]
+                          Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                            TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                        Projection: Dictionary(Int32, Utf8("disk")) AS iox::measurement, disk.time AS time, CAST(disk.host AS Utf8) AS host, CAST(NULL AS Float64) AS usage_idle, disk.bytes_free AS bytes_free [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), host:Utf8;N, usage_idle:Float64;N, bytes_free:Int64;N]
+                          Filter: disk.bytes_free IS NOT NULL [bytes_free:Int64;N, bytes_used:Int64;N, device:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None)]
+                            TableScan: disk [bytes_free:Int64;N, bytes_used:Int64;N, device:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None)]
+            "#);
+
+            // The series limit is applied before the row limit
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu LIMIT 1 SOFFSET 1"), @r#"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::row <= Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::row:UInt64]
+                  WindowAggr: windowExpr=[[row_number() PARTITION BY [cpu] ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::row]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::row:UInt64]
+                    Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                        Filter: iox::series > Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64]
+                          WindowAggr: windowExpr=[[dense_rank() ORDER BY [cpu ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64]
+                            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                                Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "#);
+
+            // Without tags, the series key is empty and the output is a single series,
+            // which is returned when the offset is zero
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu SLIMIT 1"), @r#"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "#);
+
+            // and otherwise, no series are returned
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu SOFFSET 1"), @r#"
+            Limit: skip=0, fetch=0 [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+              Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                  Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                    TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "#);
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu SLIMIT 0"), @r#"
+            Limit: skip=0, fetch=0 [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+              Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                  Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                    TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "#);
+        }
+
         #[test]
         fn test_select_function_tag_column() {
             assert_snapshot!(plan("SELECT last(foo) as foo, first(usage_idle) from cpu group by foo"), @r#"
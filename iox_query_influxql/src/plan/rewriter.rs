@@ -68,47 +68,6 @@ fn rewrite_select(s: &dyn SchemaProvider, stmt: &SelectStatement) -> Result<Sele
     rw.rewrite(s, stmt)
 }
 
-/// Asserts that the `SELECT` statement does not use any unimplemented features.
-///
-/// The list of unimplemented or unsupported features are listed below.
-///
-/// # `SLIMIT` and `SOFFSET`
-///
-/// * `SLIMIT` and `SOFFSET` don't work as expected per issue [#7571]
-/// * This issue [is noted](https://docs.influxdata.com/influxdb/v1.8/query_language/explore-data/#the-slimit-clause) in our official documentation
-///
-/// # Database prefix in qualified measurement name
-///
-/// Measurement selectors that qualify a database - for example `database..measurement` - are
-/// currently not supported in InfluxDB 3.0. See [EAR#5194]
-///
-/// [#7571]: https://github.com/influxdata/influxdb/issues/7571
-/// [EAR#5194]: https://github.com/influxdata/EAR/issues/5194
-fn check_features(stmt: &SelectStatement) -> Result<()> {
-    if stmt.series_limit.is_some() || stmt.series_offset.is_some() {
-        return error::not_implemented("SLIMIT or SOFFSET");
-    }
-    check_qualified_measurements(stmt)?;
-    Ok(())
-}
-
-fn check_qualified_measurements(stmt: &SelectStatement) -> Result<()> {
-    for ms in &*stmt.from {
-        match ms {
-            MeasurementSelection::Name(QualifiedMeasurementName {
-                database: Some(_), ..
-            }) => {
-                return error::not_implemented(
-                    "database prefix in qualified measurement syntax".to_string(),
-                );
-            }
-            MeasurementSelection::Subquery(q) => check_qualified_measurements(q)?,
-            _ => (),
-        }
-    }
-    Ok(())
-}
-
 #[derive(Default)]
 struct RewriteSelect {
     /// The depth of the `SELECT` statement currently processed by the rewriter.
@@ -119,8 +78,6 @@ impl RewriteSelect {
     /// Transform a `SelectStatement` to a `Select`, which is an intermediate representation used by
     /// the InfluxQL planner. Transformations include expanding wildcards.
     fn rewrite(&self, s: &dyn SchemaProvider, stmt: &SelectStatement) -> Result<Select> {
-        check_features(stmt)?;
-
         let from = self.expand_from(s, stmt)?;
         let tag_set = from_tag_set(s, &from);
         let (fields, group_by) = self
@@ -193,6 +150,8 @@ impl RewriteSelect {
             order_by: stmt.order_by,
             limit: stmt.limit,
             offset: stmt.offset,
+            series_limit: stmt.series_limit,
+            series_offset: stmt.series_offset,
             timezone: stmt.timezone.map(|v| *v),
             has_integral,
         })
@@ -206,6 +165,10 @@ impl RewriteSelect {
 
     /// Rewrite the `SELECT` statement by applying specific rules for subqueries.
     fn rewrite_subquery(&self, s: &dyn SchemaProvider, stmt: &SelectStatement) -> Result<Select> {
+        if stmt.into.is_some() {
+            return error::query("INTO clause is not permitted in a subquery");
+        }
+
         let rw = Self {
             depth: self.depth + 1,
         };
@@ -383,6 +346,33 @@ impl RewriteSelect {
         for ms in &*stmt.from {
             match ms {
                 MeasurementSelection::Name(qmn) => match qmn {
+                    QualifiedMeasurementName {
+                        database: Some(database),
+                        retention_policy,
+                        name,
+                    } => {
+                        let tables = s.qualified_table_names(
+                            database.as_str(),
+                            retention_policy.as_ref().map(|rp| rp.as_str()),
+                        )?;
+                        match name {
+                            MeasurementName::Name(name) => tables
+                                .into_iter()
+                                .filter(|table| s.measurement_name(table) == name.as_str())
+                                .for_each(|table| {
+                                    new_from.push(DataSource::Table(table.to_owned()))
+                                }),
+                            MeasurementName::Regex(re) => {
+                                let re = util::parse_regex(re)?;
+                                tables
+                                    .into_iter()
+                                    .filter(|table| re.is_match(s.measurement_name(table)))
+                                    .for_each(|table| {
+                                        new_from.push(DataSource::Table(table.to_owned()))
+                                    });
+                            }
+                        }
+                    }
                     QualifiedMeasurementName {
                         name: MeasurementName::Name(name),
                         ..
@@ -990,6 +980,12 @@ struct FieldChecker {
     /// `true` when the projection contains an `INTEGRAL` function.
     has_integral: bool,
 
+    /// `true` when the projection contains a `HOLT_WINTERS` or `HOLT_WINTERS_WITH_FIT` function.
+    has_holt_winters: bool,
+
+    /// The number of `SAMPLE` functions in the projection.
+    sample_count: usize,
+
     /// Accumulator for the number of aggregate or window expressions for the statement.
     aggregate_count: usize,
 
@@ -1013,6 +1009,27 @@ impl FieldChecker {
     ) -> Result<SelectStatementInfo> {
         fields.iter().try_for_each(|f| self.check_expr(&f.expr))?;
 
+        // HOLT_WINTERS replaces the rows of the nested aggregate with the
+        // forecast, so it cannot be an operand of another expression.
+        if self.has_holt_winters
+            && !fields.iter().any(|f| {
+                matches!(&f.expr, Expr::Call(c) if c.name == "holt_winters" || c.name == "holt_winters_with_fit")
+            })
+        {
+            return error::query("holt_winters cannot be used in an expression");
+        }
+
+        // Each SAMPLE function produces a number of points, which are not
+        // values that can be used as the operand of another expression.
+        if self.sample_count
+            != fields
+                .iter()
+                .filter(|f| matches!(&f.expr, Expr::Call(c) if c.name == "sample"))
+                .count()
+        {
+            return error::query("sample cannot be used in an expression");
+        }
+
         match self.function_count() {
             0 => {
                 // FILL(PREVIOUS) and FILL(<value>) are both supported for non-aggregate queries
@@ -1041,6 +1058,15 @@ impl FieldChecker {
                     "selector functions top and bottom cannot be combined with other functions",
                 );
             }
+            // The HOLT_WINTERS functions and their nested aggregate count as two functions.
+            //
+            // NOTE: InfluxDB 1.x permits combining HOLT_WINTERS with other functions,
+            // however, the forecast points do not share timestamps with the other
+            // projections, so this is not supported. This is documented as a known
+            // difference in the crate documentation.
+            3.. if self.has_holt_winters => {
+                return error::query("holt_winters cannot be combined with other functions");
+            }
             _ => {}
         }
 
@@ -1072,6 +1098,22 @@ impl FieldChecker {
 
         let projection_type = if self.has_top_bottom {
             ProjectionType::TopBottomSelector
+        } else if self.has_holt_winters {
+            ProjectionType::HoltWinters
+        } else if self.sample_count > 0 {
+            if self.window_count > 0 {
+                return error::not_implemented("sample combined with window functions");
+            }
+
+            if self.function_count() > 1 {
+                ProjectionType::SampleMixed
+            } else {
+                // SAMPLE selects points, rather than aggregating them,
+                // even when grouping by time.
+                ProjectionType::Selector {
+                    has_fields: self.has_non_aggregate_fields,
+                }
+            }
         } else if self.has_group_by_time {
             if self.window_count > 0 {
                 if self.window_count >= self.aggregate_count + self.selector_count {
@@ -1257,6 +1299,7 @@ impl FieldChecker {
 
     fn check_sample(&mut self, args: &[Expr]) -> Result<()> {
         self.inc_selector_count();
+        self.sample_count += 1;
 
         check_exp_args!("sample", 2, args);
         let v = lit_integer!("sample", args, 1);
@@ -1505,6 +1548,7 @@ impl FieldChecker {
 
     fn check_holt_winters(&mut self, name: &str, args: &[Expr]) -> Result<()> {
         self.inc_aggregate_count();
+        self.has_holt_winters = true;
         check_exp_args!(name, 3, args);
 
         let v = lit_integer!(name, args, 1);
@@ -1614,6 +1658,12 @@ pub(crate) enum ProjectionType {
     },
     /// A query that projects the `top` or `bottom` selector function.
     TopBottomSelector,
+    /// A query that projects the `holt_winters` or `holt_winters_with_fit`
+    /// function, which forecasts the values of a nested aggregate.
+    HoltWinters,
+    /// A query that combines the `sample` selector function with other
+    /// `sample`, aggregate or selector functions.
+    SampleMixed,
 }
 
 /// Holds high-level information as the result of analysing
@@ -1764,6 +1814,15 @@ mod test {
         .unwrap();
         assert_matches!(info.extra_intervals, 4);
 
+        let info = select_statement_info(&parse_select(
+            "SELECT sample(foo, 2) FROM cpu GROUP BY TIME(10s)",
+        ))
+        .unwrap();
+        assert_matches!(
+            info.projection_type,
+            ProjectionType::Selector { has_fields: false }
+        );
+
         let info = select_statement_info(&parse_select("SELECT last(foo), bar FROM cpu")).unwrap();
         assert_matches!(
             info.projection_type,
@@ -1797,6 +1856,28 @@ mod test {
 
         let info = select_statement_info(&parse_select("SELECT top(foo, 3) FROM cpu")).unwrap();
         assert_matches!(info.projection_type, ProjectionType::TopBottomSelector);
+
+        let info = select_statement_info(&parse_select(
+            "SELECT holt_winters(mean(foo), 2, 3) FROM cpu GROUP BY time(30s)",
+        ))
+        .unwrap();
+        assert_matches!(info.projection_type, ProjectionType::HoltWinters);
+
+        let info = select_statement_info(&parse_select("SELECT sample(foo, 2) FROM cpu")).unwrap();
+        assert_matches!(
+            info.projection_type,
+            ProjectionType::Selector { has_fields: false }
+        );
+
+        let info = select_statement_info(&parse_select("SELECT sample(foo, 2), max(bar) FROM cpu"))
+            .unwrap();
+        assert_matches!(info.projection_type, ProjectionType::SampleMixed);
+
+        let info = select_statement_info(&parse_select(
+            "SELECT sample(foo, 2), sample(bar, 3) FROM cpu GROUP BY time(10s)",
+        ))
+        .unwrap();
+        assert_matches!(info.projection_type, ProjectionType::SampleMixed);
     }
 
     /// Verify all the aggregate, window-like and selector functions are handled
@@ -1831,6 +1912,14 @@ mod test {
         assert_error!(select_statement_info(&sel), DataFusionError::Plan(ref s) if s == "invalid number of arguments for sample, expected 2, got 1");
         let sel = parse_select("SELECT sample(foo, -2) FROM cpu");
         assert_error!(select_statement_info(&sel), DataFusionError::Plan(ref s) if s == "sample window must be greater than 1, got -2");
+        let sel = parse_select("SELECT sample(foo, 2) + 1 FROM cpu");
+        assert_error!(select_statement_info(&sel), DataFusionError::Plan(ref s) if s == "sample cannot be used in an expression");
+        let sel = parse_select(
+            "SELECT sample(foo, 2), difference(mean(bar)) FROM cpu GROUP BY time(10s)",
+        );
+        assert_error!(select_statement_info(&sel), DataFusionError::NotImplemented(ref s) if s == "sample combined with window functions");
+        let sel = parse_select("SELECT sample(foo, 2), sample(bar, 2), foo FROM cpu");
+        assert_error!(select_statement_info(&sel), DataFusionError::Plan(ref s) if s == "mixing multiple selector functions with tags or fields is not supported");
 
         // distinct
         let sel = parse_select("SELECT distinct(foo) FROM cpu");
@@ -2012,6 +2101,17 @@ mod test {
         assert_error!(select_statement_info(&sel), DataFusionError::Plan(ref s) if s == "holt_winters N argument must be greater than 0, got 0");
         let sel = parse_select("SELECT holt_winters(foo, 1, -3) FROM cpu GROUP BY time(30s)");
         assert_error!(select_statement_info(&sel), DataFusionError::Plan(ref s) if s == "holt_winters S argument cannot be negative, got -3");
+        let sel = parse_select(
+            "SELECT holt_winters(mean(foo), 2, 3), mean(foo) FROM cpu GROUP BY time(30s)",
+        );
+        assert_error!(select_statement_info(&sel), DataFusionError::Plan(ref s) if s == "holt_winters cannot be combined with other functions");
+        let sel =
+            parse_select("SELECT holt_winters(mean(foo), 2, 3) * 2 FROM cpu GROUP BY time(30s)");
+        assert_error!(select_statement_info(&sel), DataFusionError::Plan(ref s) if s == "holt_winters cannot be used in an expression");
+        let sel = parse_select(
+            "SELECT abs(holt_winters_with_fit(mean(foo), 2, 3)) FROM cpu GROUP BY time(30s)",
+        );
+        assert_error!(select_statement_info(&sel), DataFusionError::Plan(ref s) if s == "holt_winters cannot be used in an expression");
 
         // max, min, first, last
         for name in [
@@ -2440,6 +2540,36 @@ mod test {
             );
         }
 
+        #[test]
+        fn series_limit_offset() {
+            let namespace = MockSchemaProvider::default();
+
+            let stmt = parse_select("SELECT usage_idle FROM cpu GROUP BY host SLIMIT 2");
+            let stmt = rewrite_select_statement(&namespace, &stmt).unwrap();
+            assert_eq!(
+                stmt.to_string(),
+                "SELECT time::timestamp AS time, usage_idle::float AS usage_idle FROM cpu GROUP BY host::tag SLIMIT 2"
+            );
+
+            let stmt =
+                parse_select("SELECT usage_idle FROM cpu GROUP BY host LIMIT 3 SLIMIT 2 SOFFSET 1");
+            let stmt = rewrite_select_statement(&namespace, &stmt).unwrap();
+            assert_eq!(
+                stmt.to_string(),
+                "SELECT time::timestamp AS time, usage_idle::float AS usage_idle FROM cpu GROUP BY host::tag LIMIT 3 SLIMIT 2 SOFFSET 1"
+            );
+
+            // Retained by subqueries
+            let stmt = parse_select(
+                "SELECT usage_idle FROM (SELECT usage_idle FROM cpu GROUP BY host SOFFSET 1)",
+            );
+            let stmt = rewrite_select_statement(&namespace, &stmt).unwrap();
+            assert_eq!(
+                stmt.to_string(),
+                "SELECT time::timestamp AS time, usage_idle::float AS usage_idle FROM (SELECT time::timestamp AS time, usage_idle::float AS usage_idle FROM cpu GROUP BY host::tag SOFFSET 1)"
+            );
+        }
+
         /// Uncategorized fallible cases
         #[test]
         fn fallible() {
@@ -2486,20 +2616,6 @@ mod test {
                 err.to_string(),
                 "Error during planning: unable to use tag as wildcard in count()"
             );
-
-            let stmt = parse_select("SELECT usage_idle FROM cpu SLIMIT 1");
-            let err = rewrite_select_statement(&namespace, &stmt).unwrap_err();
-            assert_eq!(
-                err.to_string(),
-                "This feature is not implemented: SLIMIT or SOFFSET"
-            );
-
-            let stmt = parse_select("SELECT usage_idle FROM cpu SOFFSET 1");
-            let err = rewrite_select_statement(&namespace, &stmt).unwrap_err();
-            assert_eq!(
-                err.to_string(),
-                "This feature is not implemented: SLIMIT or SOFFSET"
-            );
         }
 
         /// Verify subqueries
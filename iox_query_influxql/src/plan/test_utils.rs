@@ -3,8 +3,10 @@
 
 use crate::error;
 use crate::plan::SchemaProvider;
+use arrow::record_batch::RecordBatch;
 use chrono::{DateTime, NaiveDate, Utc};
 use datafusion::common::Result as DataFusionResult;
+use datafusion::datasource::MemTable;
 use datafusion::datasource::empty::EmptyTable;
 use datafusion::datasource::provider_as_source;
 use datafusion::logical_expr::{AggregateUDF, ScalarUDF, TableSource};
@@ -135,6 +137,11 @@ pub(crate) mod database {
 pub(crate) struct MockSchemaProvider {
     execution_props: ExecutionProps,
     tables: HashMap<String, (Arc<dyn TableSource>, Schema)>,
+    /// Maps the tables of qualified measurement names to the database,
+    /// retention policy and measurement name.
+    qualified_tables: HashMap<String, (String, Option<String>, String)>,
+    /// The schemas of other databases, keyed by database and retention policy.
+    databases: HashMap<(String, Option<String>), MockSchemaProvider>,
 }
 
 impl Default for MockSchemaProvider {
@@ -150,6 +157,8 @@ impl Default for MockSchemaProvider {
         let mut res = Self {
             execution_props,
             tables: HashMap::new(),
+            qualified_tables: HashMap::new(),
+            databases: HashMap::new(),
         };
         res.add_schemas(database::schemas());
         res
@@ -166,9 +175,69 @@ impl MockSchemaProvider {
             .insert(table_name, (provider_as_source(s), schema));
     }
 
+    /// Add a table with the rows of `batches`, which must have the schema
+    /// of `schema` with its fields sorted by name.
+    pub(crate) fn add_schema_with_batches(&mut self, schema: Schema, batches: Vec<RecordBatch>) {
+        let schema = schema.sort_fields_by_name();
+
+        let table_name = schema.measurement().unwrap().clone();
+        let s = Arc::new(MemTable::try_new(schema.as_arrow(), vec![batches]).unwrap());
+        self.tables
+            .insert(table_name, (provider_as_source(s), schema));
+    }
+
     pub(crate) fn add_schemas(&mut self, schemas: impl IntoIterator<Item = Schema>) {
         schemas.into_iter().for_each(|s| self.add_schema(s));
     }
+
+    /// Add a table which is only accessible using a qualified measurement name,
+    /// such as `"database"."retention_policy"."measurement"`.
+    pub(crate) fn add_qualified_schema(
+        &mut self,
+        database: &str,
+        retention_policy: Option<&str>,
+        schema: Schema,
+    ) {
+        let schema = schema.sort_fields_by_name();
+
+        let measurement = schema.measurement().unwrap().clone();
+        let table_name = format!(
+            "{database}.{}.{measurement}",
+            retention_policy.unwrap_or_default()
+        );
+        let s = Arc::new(EmptyTable::new(schema.as_arrow()));
+        self.tables
+            .insert(table_name.clone(), (provider_as_source(s), schema));
+        self.qualified_tables.insert(
+            table_name,
+            (
+                database.to_owned(),
+                retention_policy.map(ToOwned::to_owned),
+                measurement,
+            ),
+        );
+    }
+
+    /// Add a database, which is only accessible using the `ON` clause of
+    /// a metadata statement, with the tables of `schemas`.
+    pub(crate) fn add_database(
+        &mut self,
+        database: &str,
+        retention_policy: Option<&str>,
+        schemas: impl IntoIterator<Item = Schema>,
+    ) {
+        let mut db = Self {
+            execution_props: self.execution_props.clone(),
+            tables: HashMap::new(),
+            qualified_tables: HashMap::new(),
+            databases: HashMap::new(),
+        };
+        db.add_schemas(schemas);
+        self.databases.insert(
+            (database.to_owned(), retention_policy.map(ToOwned::to_owned)),
+            db,
+        );
+    }
 }
 
 impl SchemaProvider for MockSchemaProvider {
@@ -190,6 +259,7 @@ impl SchemaProvider for MockSchemaProvider {
     fn table_names(&self) -> Vec<&'_ str> {
         self.tables
             .keys()
+            .filter(|k| !self.qualified_tables.contains_key(*k))
             .map(|k| k.as_str())
             .sorted()
             .collect::<Vec<_>>()
@@ -199,6 +269,54 @@ impl SchemaProvider for MockSchemaProvider {
         self.tables.get(name).map(|(_, s)| s.clone())
     }
 
+    fn qualified_table_names(
+        &self,
+        database: &str,
+        retention_policy: Option<&str>,
+    ) -> DataFusionResult<Vec<&'_ str>> {
+        Ok(self
+            .qualified_tables
+            .iter()
+            .filter(|(_, (db, rp, _))| db == database && rp.as_deref() == retention_policy)
+            .map(|(k, _)| k.as_str())
+            .sorted()
+            .collect())
+    }
+
+    fn measurement_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.qualified_tables
+            .get(name)
+            .map_or(name, |(_, _, measurement)| measurement.as_str())
+    }
+
+    fn database_names(&self) -> DataFusionResult<Vec<&'_ str>> {
+        Ok(self
+            .databases
+            .keys()
+            .map(|(database, _)| database.as_str())
+            .collect())
+    }
+
+    fn retention_policy_names(&self, database: &str) -> DataFusionResult<Vec<&'_ str>> {
+        Ok(self
+            .databases
+            .keys()
+            .filter(|(db, _)| db == database)
+            .map(|(_, rp)| rp.as_deref().unwrap_or("autogen"))
+            .collect())
+    }
+
+    fn database_schema(
+        &self,
+        database: &str,
+        retention_policy: Option<&str>,
+    ) -> DataFusionResult<Option<&dyn SchemaProvider>> {
+        Ok(self
+            .databases
+            .get(&(database.to_owned(), retention_policy.map(ToOwned::to_owned)))
+            .map(|db| db as &dyn SchemaProvider))
+    }
+
     fn execution_props(&self) -> &ExecutionProps {
         &self.execution_props
     }
@@ -28,6 +28,14 @@ pub(super) enum WindowFunction {
     NonNegativeDerivative,
     CumulativeSum,
     Elapsed,
+    ExponentialMovingAverage,
+    DoubleExponentialMovingAverage,
+    TripleExponentialMovingAverage,
+    RelativeStrengthIndex,
+    TripleExponentialDerivative,
+    KaufmansEfficiencyRatio,
+    KaufmansAdaptiveMovingAverage,
+    ChandeMomentumOscillator,
 }
 
 impl WindowFunction {
@@ -41,6 +49,18 @@ impl WindowFunction {
             NON_NEGATIVE_DERIVATIVE_UDF_NAME => Some(Self::NonNegativeDerivative),
             CUMULATIVE_SUM_UDF_NAME => Some(Self::CumulativeSum),
             ELAPSED_UDF_NAME => Some(Self::Elapsed),
+            EXPONENTIAL_MOVING_AVERAGE_UDF_NAME => Some(Self::ExponentialMovingAverage),
+            DOUBLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME => {
+                Some(Self::DoubleExponentialMovingAverage)
+            }
+            TRIPLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME => {
+                Some(Self::TripleExponentialMovingAverage)
+            }
+            RELATIVE_STRENGTH_INDEX_UDF_NAME => Some(Self::RelativeStrengthIndex),
+            TRIPLE_EXPONENTIAL_DERIVATIVE_UDF_NAME => Some(Self::TripleExponentialDerivative),
+            KAUFMANS_EFFICIENCY_RATIO_UDF_NAME => Some(Self::KaufmansEfficiencyRatio),
+            KAUFMANS_ADAPTIVE_MOVING_AVERAGE_UDF_NAME => Some(Self::KaufmansAdaptiveMovingAverage),
+            CHANDE_MOMENTUM_OSCILLATOR_UDF_NAME => Some(Self::ChandeMomentumOscillator),
             _ => None,
         }
     }
@@ -504,3 +524,175 @@ static CUMULATIVE_SUM: LazyLock<Arc<ScalarUDF>> = LazyLock::new(|| {
         ),
     }))
 });
+
+const EXPONENTIAL_MOVING_AVERAGE_UDF_NAME: &str = "exponential_moving_average";
+const DOUBLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME: &str = "double_exponential_moving_average";
+const TRIPLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME: &str = "triple_exponential_moving_average";
+const RELATIVE_STRENGTH_INDEX_UDF_NAME: &str = "relative_strength_index";
+const TRIPLE_EXPONENTIAL_DERIVATIVE_UDF_NAME: &str = "triple_exponential_derivative";
+const KAUFMANS_EFFICIENCY_RATIO_UDF_NAME: &str = "kaufmans_efficiency_ratio";
+const KAUFMANS_ADAPTIVE_MOVING_AVERAGE_UDF_NAME: &str = "kaufmans_adaptive_moving_average";
+const CHANDE_MOMENTUM_OSCILLATOR_UDF_NAME: &str = "chande_momentum_oscillator";
+
+/// The period, hold period and warmup type arguments of the exponential
+/// moving average functions.
+const EXPONENTIAL_MOVING_AVERAGE_ARGS: &[DataType] =
+    &[DataType::Int64, DataType::Int64, DataType::Utf8];
+
+/// The period and hold period arguments of Kaufman's functions.
+const KAUFMANS_ARGS: &[DataType] = &[DataType::Int64, DataType::Int64];
+
+/// A stand-in for the technical analysis functions, such as
+/// `exponential_moving_average`, which take a numeric input followed by
+/// constant arguments and return a float.
+#[derive(Debug)]
+struct TechnicalAnalysisUDF {
+    name: &'static str,
+    signature: Signature,
+}
+
+impl TechnicalAnalysisUDF {
+    /// Create a new stand-in for the function `name`, where `args` are
+    /// the types of the constant arguments following the input.
+    fn new_udf(name: &'static str, args: &[DataType]) -> Arc<ScalarUDF> {
+        Arc::new(ScalarUDF::from(Self {
+            name,
+            signature: Signature::one_of(
+                NUMERICS
+                    .iter()
+                    .map(|dt| {
+                        let mut arg_types = vec![dt.clone()];
+                        arg_types.extend_from_slice(args);
+                        TypeSignature::Exact(arg_types)
+                    })
+                    .collect(),
+                Volatility::Immutable,
+            ),
+        }))
+    }
+}
+
+impl ScalarUDFImpl for TechnicalAnalysisUDF {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Float64)
+    }
+
+    fn invoke_with_args(&self, _args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        error::internal(format!(
+            "{} should not exist in the final logical plan",
+            self.name
+        ))
+    }
+}
+
+/// Create an expression to represent the `EXPONENTIAL_MOVING_AVERAGE` function.
+pub(crate) fn exponential_moving_average(args: Vec<Expr>) -> Expr {
+    EXPONENTIAL_MOVING_AVERAGE.call(args)
+}
+
+/// Definition of the `EXPONENTIAL_MOVING_AVERAGE` function.
+static EXPONENTIAL_MOVING_AVERAGE: LazyLock<Arc<ScalarUDF>> = LazyLock::new(|| {
+    TechnicalAnalysisUDF::new_udf(
+        EXPONENTIAL_MOVING_AVERAGE_UDF_NAME,
+        EXPONENTIAL_MOVING_AVERAGE_ARGS,
+    )
+});
+
+/// Create an expression to represent the `DOUBLE_EXPONENTIAL_MOVING_AVERAGE` function.
+pub(crate) fn double_exponential_moving_average(args: Vec<Expr>) -> Expr {
+    DOUBLE_EXPONENTIAL_MOVING_AVERAGE.call(args)
+}
+
+/// Definition of the `DOUBLE_EXPONENTIAL_MOVING_AVERAGE` function.
+static DOUBLE_EXPONENTIAL_MOVING_AVERAGE: LazyLock<Arc<ScalarUDF>> = LazyLock::new(|| {
+    TechnicalAnalysisUDF::new_udf(
+        DOUBLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME,
+        EXPONENTIAL_MOVING_AVERAGE_ARGS,
+    )
+});
+
+/// Create an expression to represent the `TRIPLE_EXPONENTIAL_MOVING_AVERAGE` function.
+pub(crate) fn triple_exponential_moving_average(args: Vec<Expr>) -> Expr {
+    TRIPLE_EXPONENTIAL_MOVING_AVERAGE.call(args)
+}
+
+/// Definition of the `TRIPLE_EXPONENTIAL_MOVING_AVERAGE` function.
+static TRIPLE_EXPONENTIAL_MOVING_AVERAGE: LazyLock<Arc<ScalarUDF>> = LazyLock::new(|| {
+    TechnicalAnalysisUDF::new_udf(
+        TRIPLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME,
+        EXPONENTIAL_MOVING_AVERAGE_ARGS,
+    )
+});
+
+/// Create an expression to represent the `RELATIVE_STRENGTH_INDEX` function.
+pub(crate) fn relative_strength_index(args: Vec<Expr>) -> Expr {
+    RELATIVE_STRENGTH_INDEX.call(args)
+}
+
+/// Definition of the `RELATIVE_STRENGTH_INDEX` function.
+static RELATIVE_STRENGTH_INDEX: LazyLock<Arc<ScalarUDF>> = LazyLock::new(|| {
+    TechnicalAnalysisUDF::new_udf(
+        RELATIVE_STRENGTH_INDEX_UDF_NAME,
+        EXPONENTIAL_MOVING_AVERAGE_ARGS,
+    )
+});
+
+/// Create an expression to represent the `TRIPLE_EXPONENTIAL_DERIVATIVE` function.
+pub(crate) fn triple_exponential_derivative(args: Vec<Expr>) -> Expr {
+    TRIPLE_EXPONENTIAL_DERIVATIVE.call(args)
+}
+
+/// Definition of the `TRIPLE_EXPONENTIAL_DERIVATIVE` function.
+static TRIPLE_EXPONENTIAL_DERIVATIVE: LazyLock<Arc<ScalarUDF>> = LazyLock::new(|| {
+    TechnicalAnalysisUDF::new_udf(
+        TRIPLE_EXPONENTIAL_DERIVATIVE_UDF_NAME,
+        EXPONENTIAL_MOVING_AVERAGE_ARGS,
+    )
+});
+
+/// Create an expression to represent the `KAUFMANS_EFFICIENCY_RATIO` function.
+pub(crate) fn kaufmans_efficiency_ratio(args: Vec<Expr>) -> Expr {
+    KAUFMANS_EFFICIENCY_RATIO.call(args)
+}
+
+/// Definition of the `KAUFMANS_EFFICIENCY_RATIO` function.
+static KAUFMANS_EFFICIENCY_RATIO: LazyLock<Arc<ScalarUDF>> = LazyLock::new(|| {
+    TechnicalAnalysisUDF::new_udf(KAUFMANS_EFFICIENCY_RATIO_UDF_NAME, KAUFMANS_ARGS)
+});
+
+/// Create an expression to represent the `KAUFMANS_ADAPTIVE_MOVING_AVERAGE` function.
+pub(crate) fn kaufmans_adaptive_moving_average(args: Vec<Expr>) -> Expr {
+    KAUFMANS_ADAPTIVE_MOVING_AVERAGE.call(args)
+}
+
+/// Definition of the `KAUFMANS_ADAPTIVE_MOVING_AVERAGE` function.
+static KAUFMANS_ADAPTIVE_MOVING_AVERAGE: LazyLock<Arc<ScalarUDF>> = LazyLock::new(|| {
+    TechnicalAnalysisUDF::new_udf(KAUFMANS_ADAPTIVE_MOVING_AVERAGE_UDF_NAME, KAUFMANS_ARGS)
+});
+
+/// Create an expression to represent the `CHANDE_MOMENTUM_OSCILLATOR` function.
+pub(crate) fn chande_momentum_oscillator(args: Vec<Expr>) -> Expr {
+    CHANDE_MOMENTUM_OSCILLATOR.call(args)
+}
+
+/// Definition of the `CHANDE_MOMENTUM_OSCILLATOR` function.
+///
+/// The arguments are the same as the exponential moving average functions.
+static CHANDE_MOMENTUM_OSCILLATOR: LazyLock<Arc<ScalarUDF>> = LazyLock::new(|| {
+    TechnicalAnalysisUDF::new_udf(
+        CHANDE_MOMENTUM_OSCILLATOR_UDF_NAME,
+        EXPONENTIAL_MOVING_AVERAGE_ARGS,
+    )
+});
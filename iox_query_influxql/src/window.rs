@@ -1,17 +1,33 @@
 //! User defined window functions implementing influxQL features.
 
 use datafusion::logical_expr::{WindowFunctionDefinition, WindowUDF};
+use exponential_moving_average::{ExponentialKind, ExponentialMovingAverageUDWF};
+use kaufmans::{KaufmansKind, KaufmansUDWF};
 use std::sync::{Arc, LazyLock};
 
+mod chande_momentum_oscillator;
 mod cumulative_sum;
 mod derivative;
 mod difference;
 mod elapsed;
+mod ema;
+mod exponential_moving_average;
+mod indicator;
 mod integral;
+mod kaufmans;
+mod momentum;
 mod moving_average;
 mod non_negative;
 mod percent_row_number;
 
+/// Definition of the `CHANDE_MOMENTUM_OSCILLATOR` user-defined window function.
+pub(crate) static CHANDE_MOMENTUM_OSCILLATOR: LazyLock<WindowFunctionDefinition> =
+    LazyLock::new(|| {
+        WindowFunctionDefinition::WindowUDF(Arc::new(WindowUDF::new_from_impl(
+            chande_momentum_oscillator::ChandeMomentumOscillatorUDWF::new(),
+        )))
+    });
+
 /// Definition of the `CUMULATIVE_SUM` user-defined window function.
 pub(crate) static CUMULATIVE_SUM: LazyLock<WindowFunctionDefinition> = LazyLock::new(|| {
     WindowFunctionDefinition::WindowUDF(Arc::new(WindowUDF::new_from_impl(
@@ -33,6 +49,14 @@ pub(crate) static DIFFERENCE: LazyLock<WindowFunctionDefinition> = LazyLock::new
     )))
 });
 
+/// Definition of the `DOUBLE_EXPONENTIAL_MOVING_AVERAGE` user-defined window function.
+pub(crate) static DOUBLE_EXPONENTIAL_MOVING_AVERAGE: LazyLock<WindowFunctionDefinition> =
+    LazyLock::new(|| {
+        WindowFunctionDefinition::WindowUDF(Arc::new(WindowUDF::new_from_impl(
+            ExponentialMovingAverageUDWF::new(ExponentialKind::DoubleExponentialMovingAverage),
+        )))
+    });
+
 /// Definition of the `ELAPSED` user-defined window function.
 pub(crate) static ELAPSED: LazyLock<WindowFunctionDefinition> = LazyLock::new(|| {
     WindowFunctionDefinition::WindowUDF(Arc::new(WindowUDF::new_from_impl(
@@ -40,6 +64,14 @@ pub(crate) static ELAPSED: LazyLock<WindowFunctionDefinition> = LazyLock::new(||
     )))
 });
 
+/// Definition of the `EXPONENTIAL_MOVING_AVERAGE` user-defined window function.
+pub(crate) static EXPONENTIAL_MOVING_AVERAGE: LazyLock<WindowFunctionDefinition> =
+    LazyLock::new(|| {
+        WindowFunctionDefinition::WindowUDF(Arc::new(WindowUDF::new_from_impl(
+            ExponentialMovingAverageUDWF::new(ExponentialKind::ExponentialMovingAverage),
+        )))
+    });
+
 /// Definition of the internal `INTEGRAL_WINDOW` user-defined window function.
 pub(crate) static INTEGRAL: LazyLock<WindowFunctionDefinition> = LazyLock::new(|| {
     WindowFunctionDefinition::WindowUDF(Arc::new(WindowUDF::new_from_impl(
@@ -47,6 +79,22 @@ pub(crate) static INTEGRAL: LazyLock<WindowFunctionDefinition> = LazyLock::new(|
     )))
 });
 
+/// Definition of the `KAUFMANS_ADAPTIVE_MOVING_AVERAGE` user-defined window function.
+pub(crate) static KAUFMANS_ADAPTIVE_MOVING_AVERAGE: LazyLock<WindowFunctionDefinition> =
+    LazyLock::new(|| {
+        WindowFunctionDefinition::WindowUDF(Arc::new(WindowUDF::new_from_impl(KaufmansUDWF::new(
+            KaufmansKind::AdaptiveMovingAverage,
+        ))))
+    });
+
+/// Definition of the `KAUFMANS_EFFICIENCY_RATIO` user-defined window function.
+pub(crate) static KAUFMANS_EFFICIENCY_RATIO: LazyLock<WindowFunctionDefinition> =
+    LazyLock::new(|| {
+        WindowFunctionDefinition::WindowUDF(Arc::new(WindowUDF::new_from_impl(KaufmansUDWF::new(
+            KaufmansKind::EfficiencyRatio,
+        ))))
+    });
+
 /// Definition of the `MOVING_AVERAGE` user-defined window function.
 pub(crate) static MOVING_AVERAGE: LazyLock<WindowFunctionDefinition> = LazyLock::new(|| {
     WindowFunctionDefinition::WindowUDF(Arc::new(WindowUDF::new_from_impl(
@@ -81,3 +129,27 @@ pub(crate) static PERCENT_ROW_NUMBER: LazyLock<WindowFunctionDefinition> = LazyL
         percent_row_number::PercentRowNumberUDWF::new(),
     )))
 });
+
+/// Definition of the `RELATIVE_STRENGTH_INDEX` user-defined window function.
+pub(crate) static RELATIVE_STRENGTH_INDEX: LazyLock<WindowFunctionDefinition> =
+    LazyLock::new(|| {
+        WindowFunctionDefinition::WindowUDF(Arc::new(WindowUDF::new_from_impl(
+            ExponentialMovingAverageUDWF::new(ExponentialKind::RelativeStrengthIndex),
+        )))
+    });
+
+/// Definition of the `TRIPLE_EXPONENTIAL_DERIVATIVE` user-defined window function.
+pub(crate) static TRIPLE_EXPONENTIAL_DERIVATIVE: LazyLock<WindowFunctionDefinition> =
+    LazyLock::new(|| {
+        WindowFunctionDefinition::WindowUDF(Arc::new(WindowUDF::new_from_impl(
+            ExponentialMovingAverageUDWF::new(ExponentialKind::TripleExponentialDerivative),
+        )))
+    });
+
+/// Definition of the `TRIPLE_EXPONENTIAL_MOVING_AVERAGE` user-defined window function.
+pub(crate) static TRIPLE_EXPONENTIAL_MOVING_AVERAGE: LazyLock<WindowFunctionDefinition> =
+    LazyLock::new(|| {
+        WindowFunctionDefinition::WindowUDF(Arc::new(WindowUDF::new_from_impl(
+            ExponentialMovingAverageUDWF::new(ExponentialKind::TripleExponentialMovingAverage),
+        )))
+    });
@@ -0,0 +1,116 @@
+use crate::window::ema::{Cmos, WarmupType};
+use crate::window::indicator::{Indicator, evaluate_indicator};
+use crate::window::momentum::Cmo;
+use crate::{NUMERICS, error};
+use arrow::array::{Array, ArrayRef, Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, FieldRef};
+use datafusion::common::{Result, downcast_value};
+use datafusion::logical_expr::function::{PartitionEvaluatorArgs, WindowUDFFieldArgs};
+use datafusion::logical_expr::{
+    PartitionEvaluator, Signature, TypeSignature, Volatility, WindowUDFImpl,
+};
+use std::sync::Arc;
+
+#[derive(Debug)]
+pub(super) struct ChandeMomentumOscillatorUDWF {
+    signature: Signature,
+}
+
+impl ChandeMomentumOscillatorUDWF {
+    pub(super) fn new() -> Self {
+        Self {
+            signature: Signature::one_of(
+                NUMERICS
+                    .iter()
+                    .map(|dt| {
+                        TypeSignature::Exact(vec![
+                            dt.clone(),
+                            DataType::Int64,
+                            DataType::Int64,
+                            DataType::Utf8,
+                        ])
+                    })
+                    .collect(),
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl WindowUDFImpl for ChandeMomentumOscillatorUDWF {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "chande_momentum_oscillator"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn field(&self, field_args: WindowUDFFieldArgs<'_>) -> Result<FieldRef> {
+        Ok(Arc::new(Field::new(
+            field_args.name(),
+            DataType::Float64,
+            true,
+        )))
+    }
+
+    fn partition_evaluator(
+        &self,
+        _args: PartitionEvaluatorArgs<'_>,
+    ) -> Result<Box<dyn PartitionEvaluator>> {
+        Ok(Box::new(ChandeMomentumOscillatorPartitionEvaluator {}))
+    }
+}
+
+/// PartitionEvaluator which returns the Chande momentum oscillator of
+/// the input data.
+#[derive(Debug)]
+struct ChandeMomentumOscillatorPartitionEvaluator {}
+
+impl PartitionEvaluator for ChandeMomentumOscillatorPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], _num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert_eq!(
+            values.len(),
+            4,
+            "chande_momentum_oscillator expects four arguments"
+        );
+
+        // The remaining arguments are the period, hold period and warmup type.
+        //
+        // INVARIANT:
+        // The planner and rewriter guarantee that these arguments are
+        // always constants, and the planner supplies the defaults when
+        // they are omitted from the query.
+        //
+        // See: FieldChecker::check_chande_momentum_oscillator
+        let period = downcast_value!(&values[1], Int64Array).value(0) as usize;
+        let hold = downcast_value!(&values[2], Int64Array).value(0);
+        let warmup = downcast_value!(&values[3], StringArray).value(0);
+
+        // A warmup type of `none` computes the oscillator without smoothing.
+        let indicator: Box<dyn Indicator> = match warmup {
+            "none" => Box::new(Cmo::new(period)),
+            warmup => match WarmupType::try_from_str(warmup) {
+                Some(warmup) => Box::new(Cmos::new(period, warmup)),
+                None => {
+                    return error::internal(format!(
+                        "invalid warmup type for chande_momentum_oscillator ({warmup})"
+                    ));
+                }
+            },
+        };
+        evaluate_indicator("chande_momentum_oscillator", &values[0], indicator, hold)
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,337 @@
+//! Exponential smoothing algorithms used by the InfluxQL technical
+//! analysis window functions.
+//!
+//! These are ports of the `gota` package used by InfluxDB 1.x, so that
+//! results match those produced by InfluxDB 1.8 for the same input.
+//!
+//! See: <https://github.com/influxdata/influxdb/tree/1.8/query/internal/gota>
+
+use super::indicator::Indicator;
+
+/// The method used to compute values before the algorithm has
+/// received enough values to be "warmed".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum WarmupType {
+    /// Apply the exponential moving average from the first value.
+    Exponential,
+    /// Use a simple moving average until the algorithm is warmed.
+    Simple,
+}
+
+impl WarmupType {
+    /// Parse the InfluxQL representation of a warmup type.
+    pub(super) fn try_from_str(s: &str) -> Option<Self> {
+        match s {
+            "exponential" => Some(Self::Exponential),
+            "simple" => Some(Self::Simple),
+            _ => None,
+        }
+    }
+}
+
+/// Exponential moving average.
+#[derive(Debug, Clone)]
+pub(super) struct Ema {
+    period: usize,
+    last: f64,
+    count: usize,
+    alpha: f64,
+    warmup: WarmupType,
+}
+
+impl Ema {
+    pub(super) fn new(period: usize, warmup: WarmupType) -> Self {
+        Self {
+            period,
+            last: 0.0,
+            count: 0,
+            alpha: 2.0 / (period as f64 + 1.0),
+            warmup,
+        }
+    }
+
+    fn warmed(&self) -> bool {
+        self.count == self.period
+    }
+
+    fn last(&self) -> f64 {
+        self.last
+    }
+}
+
+impl Indicator for Ema {
+    fn warm_count(&self) -> usize {
+        self.period - 1
+    }
+
+    fn add(&mut self, v: f64) -> f64 {
+        let avg = if self.count == 0 {
+            v
+        } else if !self.warmed() && self.warmup == WarmupType::Simple {
+            (self.last * self.count as f64 + v) / (self.count + 1) as f64
+        } else {
+            (v - self.last) * self.alpha + self.last
+        };
+
+        self.last = avg;
+        // Stop counting once warmed, to avoid an overflow.
+        if self.count < self.period {
+            self.count += 1;
+        }
+        avg
+    }
+}
+
+/// Double exponential moving average.
+#[derive(Debug)]
+pub(super) struct Dema {
+    ema1: Ema,
+    ema2: Ema,
+}
+
+impl Dema {
+    pub(super) fn new(period: usize, warmup: WarmupType) -> Self {
+        Self {
+            ema1: Ema::new(period, warmup),
+            ema2: Ema::new(period, warmup),
+        }
+    }
+}
+
+impl Indicator for Dema {
+    fn warm_count(&self) -> usize {
+        match self.ema1.warmup {
+            WarmupType::Exponential => self.ema1.warm_count(),
+            WarmupType::Simple => self.ema1.warm_count() + self.ema2.warm_count(),
+        }
+    }
+
+    fn add(&mut self, v: f64) -> f64 {
+        let avg1 = self.ema1.add(v);
+        let avg2 = if self.ema1.warmed() || self.ema1.warmup == WarmupType::Exponential {
+            self.ema2.add(avg1)
+        } else {
+            avg1
+        };
+        2.0 * avg1 - avg2
+    }
+}
+
+/// Triple exponential moving average.
+#[derive(Debug)]
+pub(super) struct Tema {
+    ema1: Ema,
+    ema2: Ema,
+    ema3: Ema,
+}
+
+impl Tema {
+    pub(super) fn new(period: usize, warmup: WarmupType) -> Self {
+        Self {
+            ema1: Ema::new(period, warmup),
+            ema2: Ema::new(period, warmup),
+            ema3: Ema::new(period, warmup),
+        }
+    }
+}
+
+impl Indicator for Tema {
+    fn warm_count(&self) -> usize {
+        match self.ema1.warmup {
+            WarmupType::Exponential => self.ema1.warm_count(),
+            WarmupType::Simple => {
+                self.ema1.warm_count() + self.ema2.warm_count() + self.ema3.warm_count()
+            }
+        }
+    }
+
+    fn add(&mut self, v: f64) -> f64 {
+        let avg1 = self.ema1.add(v);
+        let avg2 = if self.ema1.warmed() || self.ema1.warmup == WarmupType::Exponential {
+            self.ema2.add(avg1)
+        } else {
+            avg1
+        };
+        let avg3 = if self.ema2.warmed() || self.ema2.warmup == WarmupType::Exponential {
+            self.ema3.add(avg2)
+        } else {
+            avg2
+        };
+        3.0 * avg1 - 3.0 * avg2 + avg3
+    }
+}
+
+/// Triple exponential derivative (TRIX), the percentage rate of change
+/// of a triple exponential moving average.
+#[derive(Debug)]
+pub(super) struct Trix {
+    ema1: Ema,
+    ema2: Ema,
+    ema3: Ema,
+    last: f64,
+}
+
+impl Trix {
+    pub(super) fn new(period: usize, warmup: WarmupType) -> Self {
+        Self {
+            ema1: Ema::new(period, warmup),
+            ema2: Ema::new(period, warmup),
+            ema3: Ema::new(period, warmup),
+            last: 0.0,
+        }
+    }
+}
+
+impl Indicator for Trix {
+    fn warm_count(&self) -> usize {
+        match self.ema1.warmup {
+            WarmupType::Exponential => self.ema1.warm_count() + 1,
+            WarmupType::Simple => self.ema1.warm_count() * 3 + 1,
+        }
+    }
+
+    fn add(&mut self, v: f64) -> f64 {
+        let mut cur = self.ema1.add(v);
+        if self.ema1.warmed() || self.ema1.warmup == WarmupType::Exponential {
+            cur = self.ema2.add(cur);
+            if self.ema2.warmed() || self.ema2.warmup == WarmupType::Exponential {
+                cur = self.ema3.add(cur);
+            }
+        }
+        let rate = ((cur / self.last) - 1.0) * 100.0;
+        self.last = cur;
+        rate
+    }
+}
+
+/// Relative strength index.
+#[derive(Debug)]
+pub(super) struct Rsi {
+    ema_up: Ema,
+    ema_down: Ema,
+    last: f64,
+}
+
+impl Rsi {
+    pub(super) fn new(period: usize, warmup: WarmupType) -> Self {
+        let mut ema = Ema::new(period + 1, warmup);
+        ema.alpha = 1.0 / period as f64;
+        Self {
+            ema_up: ema.clone(),
+            ema_down: ema,
+            last: 0.0,
+        }
+    }
+}
+
+impl Indicator for Rsi {
+    fn warm_count(&self) -> usize {
+        self.ema_up.warm_count()
+    }
+
+    fn add(&mut self, v: f64) -> f64 {
+        // NOTE: the first change is computed against zero, which matches
+        // the behaviour of InfluxDB 1.x.
+        let (up, down) = if v > self.last {
+            (v - self.last, 0.0)
+        } else if v < self.last {
+            (0.0, self.last - v)
+        } else {
+            (0.0, 0.0)
+        };
+        self.ema_up.add(up);
+        self.ema_down.add(down);
+        self.last = v;
+        100.0 - (100.0 / (1.0 + self.ema_up.last() / self.ema_down.last()))
+    }
+}
+
+/// Chande momentum oscillator, smoothed using exponential moving averages
+/// of the upward and downward movements.
+#[derive(Debug)]
+pub(super) struct Cmos {
+    ema_up: Ema,
+    ema_down: Ema,
+    last: f64,
+}
+
+impl Cmos {
+    pub(super) fn new(period: usize, warmup: WarmupType) -> Self {
+        let mut ema = Ema::new(period + 1, warmup);
+        ema.alpha = 1.0 / period as f64;
+        Self {
+            ema_up: ema.clone(),
+            ema_down: ema,
+            last: 0.0,
+        }
+    }
+}
+
+impl Indicator for Cmos {
+    fn warm_count(&self) -> usize {
+        self.ema_up.warm_count()
+    }
+
+    fn add(&mut self, v: f64) -> f64 {
+        let (up, down) = if v > self.last {
+            (v - self.last, 0.0)
+        } else if v < self.last {
+            (0.0, self.last - v)
+        } else {
+            (0.0, 0.0)
+        };
+        self.ema_up.add(up);
+        self.ema_down.add(down);
+        self.last = v;
+        let (up, down) = (self.ema_up.last(), self.ema_down.last());
+        100.0 * ((up - down) / (up + down))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(mut ind: impl Indicator, values: &[f64]) -> Vec<f64> {
+        values.iter().map(|v| ind.add(*v)).collect()
+    }
+
+    #[test]
+    fn test_ema_exponential() {
+        let got = run(Ema::new(3, WarmupType::Exponential), &[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(got, vec![1.0, 1.5, 2.25, 3.125]);
+    }
+
+    #[test]
+    fn test_ema_simple() {
+        let got = run(Ema::new(3, WarmupType::Simple), &[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(got, vec![1.0, 1.5, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_warm_count() {
+        assert_eq!(Ema::new(5, WarmupType::Exponential).warm_count(), 4);
+        assert_eq!(Dema::new(5, WarmupType::Exponential).warm_count(), 4);
+        assert_eq!(Dema::new(5, WarmupType::Simple).warm_count(), 8);
+        assert_eq!(Tema::new(5, WarmupType::Simple).warm_count(), 12);
+        assert_eq!(Trix::new(5, WarmupType::Exponential).warm_count(), 5);
+        assert_eq!(Trix::new(5, WarmupType::Simple).warm_count(), 13);
+        assert_eq!(Rsi::new(5, WarmupType::Exponential).warm_count(), 5);
+        assert_eq!(Cmos::new(5, WarmupType::Exponential).warm_count(), 5);
+    }
+
+    #[test]
+    fn test_dema_constant() {
+        // A constant input has a constant average.
+        let got = run(Dema::new(3, WarmupType::Exponential), &[2.0; 5]);
+        assert_eq!(got, vec![2.0; 5]);
+    }
+
+    #[test]
+    fn test_rsi_increasing() {
+        // Values that only increase have no downward movement,
+        // so the RSI is always 100.
+        let got = run(Rsi::new(2, WarmupType::Exponential), &[1.0, 2.0, 3.0]);
+        assert_eq!(got, vec![100.0; 3]);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::window::ema::{Dema, Ema, Rsi, Tema, Trix, WarmupType};
+use crate::window::indicator::{Indicator, evaluate_indicator};
+use crate::{NUMERICS, error};
+use arrow::array::{Array, ArrayRef, Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, FieldRef};
+use datafusion::common::{Result, downcast_value};
+use datafusion::logical_expr::function::{PartitionEvaluatorArgs, WindowUDFFieldArgs};
+use datafusion::logical_expr::{
+    PartitionEvaluator, Signature, TypeSignature, Volatility, WindowUDFImpl,
+};
+use std::sync::Arc;
+
+/// The exponential smoothing algorithm computed by an
+/// [`ExponentialMovingAverageUDWF`].
+#[derive(Debug, Clone, Copy)]
+pub(super) enum ExponentialKind {
+    ExponentialMovingAverage,
+    DoubleExponentialMovingAverage,
+    TripleExponentialMovingAverage,
+    RelativeStrengthIndex,
+    TripleExponentialDerivative,
+}
+
+impl ExponentialKind {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::ExponentialMovingAverage => "exponential_moving_average",
+            Self::DoubleExponentialMovingAverage => "double_exponential_moving_average",
+            Self::TripleExponentialMovingAverage => "triple_exponential_moving_average",
+            Self::RelativeStrengthIndex => "relative_strength_index",
+            Self::TripleExponentialDerivative => "triple_exponential_derivative",
+        }
+    }
+
+    fn indicator(&self, period: usize, warmup: WarmupType) -> Box<dyn Indicator> {
+        match self {
+            Self::ExponentialMovingAverage => Box::new(Ema::new(period, warmup)),
+            Self::DoubleExponentialMovingAverage => Box::new(Dema::new(period, warmup)),
+            Self::TripleExponentialMovingAverage => Box::new(Tema::new(period, warmup)),
+            Self::RelativeStrengthIndex => Box::new(Rsi::new(period, warmup)),
+            Self::TripleExponentialDerivative => Box::new(Trix::new(period, warmup)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct ExponentialMovingAverageUDWF {
+    kind: ExponentialKind,
+    signature: Signature,
+}
+
+impl ExponentialMovingAverageUDWF {
+    pub(super) fn new(kind: ExponentialKind) -> Self {
+        Self {
+            kind,
+            signature: Signature::one_of(
+                NUMERICS
+                    .iter()
+                    .map(|dt| {
+                        TypeSignature::Exact(vec![
+                            dt.clone(),
+                            DataType::Int64,
+                            DataType::Int64,
+                            DataType::Utf8,
+                        ])
+                    })
+                    .collect(),
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl WindowUDFImpl for ExponentialMovingAverageUDWF {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        self.kind.name()
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn field(&self, field_args: WindowUDFFieldArgs<'_>) -> Result<FieldRef> {
+        Ok(Arc::new(Field::new(
+            field_args.name(),
+            DataType::Float64,
+            true,
+        )))
+    }
+
+    fn partition_evaluator(
+        &self,
+        _args: PartitionEvaluatorArgs<'_>,
+    ) -> Result<Box<dyn PartitionEvaluator>> {
+        Ok(Box::new(ExponentialPartitionEvaluator { kind: self.kind }))
+    }
+}
+
+/// PartitionEvaluator which applies an exponential smoothing algorithm
+/// to the input data.
+#[derive(Debug)]
+struct ExponentialPartitionEvaluator {
+    kind: ExponentialKind,
+}
+
+impl PartitionEvaluator for ExponentialPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], _num_rows: usize) -> Result<Arc<dyn Array>> {
+        let name = self.kind.name();
+        assert_eq!(values.len(), 4, "{name} expects four arguments");
+
+        // The remaining arguments are the period, hold period and warmup type.
+        //
+        // INVARIANT:
+        // The planner and rewriter guarantee that these arguments are
+        // always constants, and the planner supplies the defaults when
+        // they are omitted from the query.
+        //
+        // See: FieldChecker::check_exponential_moving_average
+        let period = downcast_value!(&values[1], Int64Array).value(0);
+        let hold = downcast_value!(&values[2], Int64Array).value(0);
+        let warmup = downcast_value!(&values[3], StringArray).value(0);
+        let Some(warmup) = WarmupType::try_from_str(warmup) else {
+            return error::internal(format!("invalid warmup type for {name} ({warmup})"));
+        };
+
+        let indicator = self.kind.indicator(period as usize, warmup);
+        evaluate_indicator(name, &values[0], indicator, hold)
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,63 @@
+//! Shared evaluation of the InfluxQL technical analysis window functions.
+
+use crate::error;
+use arrow::array::{Array, ArrayRef};
+use datafusion::common::{Result, ScalarValue};
+use std::sync::Arc;
+
+/// A technical analysis algorithm that processes one value at a time.
+pub(super) trait Indicator: std::fmt::Debug + Send {
+    /// The number of values that must be added before the output is
+    /// considered valid, used as the default hold period.
+    fn warm_count(&self) -> usize;
+
+    /// Add the next value and return the current output of the algorithm.
+    fn add(&mut self, v: f64) -> f64;
+}
+
+/// Apply `indicator` to each non-null value of `array`.
+///
+/// The output for the first `hold` non-null values is null, to match the
+/// behaviour of InfluxDB 1.x. A `hold` of `-1` uses the
+/// [`Indicator::warm_count`] of the algorithm.
+pub(super) fn evaluate_indicator(
+    name: &str,
+    array: &ArrayRef,
+    mut indicator: Box<dyn Indicator>,
+    hold: i64,
+) -> Result<ArrayRef> {
+    let hold = if hold == -1 {
+        indicator.warm_count()
+    } else {
+        hold as usize
+    };
+
+    let mut count: usize = 0;
+    let mut res: Vec<ScalarValue> = Vec::with_capacity(array.len());
+    for idx in 0..array.len() {
+        let value = match ScalarValue::try_from_array(array, idx)? {
+            ScalarValue::Float64(o) => o,
+            ScalarValue::Int64(o) => o.map(|v| v as f64),
+            ScalarValue::UInt64(o) => o.map(|v| v as f64),
+            _ => {
+                return error::internal(format!(
+                    "unsupported data type for {name} ({})",
+                    array.data_type()
+                ));
+            }
+        };
+        let Some(v) = value else {
+            res.push(ScalarValue::Float64(None));
+            continue;
+        };
+
+        let v = indicator.add(v);
+        count += 1;
+        if count <= hold {
+            res.push(ScalarValue::Float64(None));
+        } else {
+            res.push(ScalarValue::Float64(Some(v)));
+        }
+    }
+    Ok(Arc::new(ScalarValue::iter_to_array(res)?))
+}
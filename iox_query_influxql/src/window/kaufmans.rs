@@ -0,0 +1,122 @@
+use crate::NUMERICS;
+use crate::window::indicator::{Indicator, evaluate_indicator};
+use crate::window::momentum::{Kama, Ker};
+use arrow::array::{Array, ArrayRef, Int64Array};
+use arrow::datatypes::{DataType, Field, FieldRef};
+use datafusion::common::{Result, downcast_value};
+use datafusion::logical_expr::function::{PartitionEvaluatorArgs, WindowUDFFieldArgs};
+use datafusion::logical_expr::{
+    PartitionEvaluator, Signature, TypeSignature, Volatility, WindowUDFImpl,
+};
+use std::sync::Arc;
+
+/// The algorithm computed by a [`KaufmansUDWF`].
+#[derive(Debug, Clone, Copy)]
+pub(super) enum KaufmansKind {
+    EfficiencyRatio,
+    AdaptiveMovingAverage,
+}
+
+impl KaufmansKind {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::EfficiencyRatio => "kaufmans_efficiency_ratio",
+            Self::AdaptiveMovingAverage => "kaufmans_adaptive_moving_average",
+        }
+    }
+
+    fn indicator(&self, period: usize) -> Box<dyn Indicator> {
+        match self {
+            Self::EfficiencyRatio => Box::new(Ker::new(period)),
+            Self::AdaptiveMovingAverage => Box::new(Kama::new(period)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct KaufmansUDWF {
+    kind: KaufmansKind,
+    signature: Signature,
+}
+
+impl KaufmansUDWF {
+    pub(super) fn new(kind: KaufmansKind) -> Self {
+        Self {
+            kind,
+            signature: Signature::one_of(
+                NUMERICS
+                    .iter()
+                    .map(|dt| {
+                        TypeSignature::Exact(vec![dt.clone(), DataType::Int64, DataType::Int64])
+                    })
+                    .collect(),
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl WindowUDFImpl for KaufmansUDWF {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        self.kind.name()
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn field(&self, field_args: WindowUDFFieldArgs<'_>) -> Result<FieldRef> {
+        Ok(Arc::new(Field::new(
+            field_args.name(),
+            DataType::Float64,
+            true,
+        )))
+    }
+
+    fn partition_evaluator(
+        &self,
+        _args: PartitionEvaluatorArgs<'_>,
+    ) -> Result<Box<dyn PartitionEvaluator>> {
+        Ok(Box::new(KaufmansPartitionEvaluator { kind: self.kind }))
+    }
+}
+
+/// PartitionEvaluator which applies one of Kaufman's algorithms to the
+/// input data.
+#[derive(Debug)]
+struct KaufmansPartitionEvaluator {
+    kind: KaufmansKind,
+}
+
+impl PartitionEvaluator for KaufmansPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], _num_rows: usize) -> Result<Arc<dyn Array>> {
+        let name = self.kind.name();
+        assert_eq!(values.len(), 3, "{name} expects three arguments");
+
+        // The remaining arguments are the period and hold period.
+        //
+        // INVARIANT:
+        // The planner and rewriter guarantee that these arguments are
+        // always constants, and the planner supplies the default hold
+        // period when it is omitted from the query.
+        //
+        // See: FieldChecker::check_kaufmans
+        let period = downcast_value!(&values[1], Int64Array).value(0);
+        let hold = downcast_value!(&values[2], Int64Array).value(0);
+
+        let indicator = self.kind.indicator(period as usize);
+        evaluate_indicator(name, &values[0], indicator, hold)
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,201 @@
+//! Momentum algorithms used by the InfluxQL technical analysis window
+//! functions.
+//!
+//! These are ports of the `gota` package used by InfluxDB 1.x, so that
+//! results match those produced by InfluxDB 1.8 for the same input.
+//!
+//! See: <https://github.com/influxdata/influxdb/tree/1.8/query/internal/gota>
+
+use super::indicator::Indicator;
+
+/// The smoothing constant of the fastest exponential moving average
+/// used by [`Kama`], for a period of 2.
+const KAMA_FAST_SC: f64 = 2.0 / (2.0 + 1.0);
+
+/// The smoothing constant of the slowest exponential moving average
+/// used by [`Kama`], for a period of 30.
+const KAMA_SLOW_SC: f64 = 2.0 / (30.0 + 1.0);
+
+#[derive(Debug, Clone, Copy, Default)]
+struct KerPoint {
+    price: f64,
+    diff: f64,
+}
+
+/// Kaufman's efficiency ratio.
+#[derive(Debug)]
+pub(super) struct Ker {
+    points: Vec<KerPoint>,
+    noise: f64,
+    count: usize,
+    /// The index of the newest point.
+    idx: usize,
+}
+
+impl Ker {
+    pub(super) fn new(period: usize) -> Self {
+        Self {
+            points: vec![KerPoint::default(); period],
+            noise: 0.0,
+            count: 0,
+            idx: 0,
+        }
+    }
+
+    fn warmed(&self) -> bool {
+        self.count == self.points.len() + 1
+    }
+}
+
+impl Indicator for Ker {
+    fn warm_count(&self) -> usize {
+        self.points.len()
+    }
+
+    fn add(&mut self, v: f64) -> f64 {
+        let idx_oldest = (self.idx + 1) % self.points.len();
+
+        let signal = (v - self.points[idx_oldest].price).abs();
+        let point = KerPoint {
+            price: v,
+            diff: (v - self.points[self.idx].price).abs(),
+        };
+        self.noise -= self.points[idx_oldest].diff;
+        self.noise += point.diff;
+
+        self.idx = idx_oldest;
+        self.points[self.idx] = point;
+        if !self.warmed() {
+            self.count += 1;
+        }
+
+        if signal == 0.0 || self.noise == 0.0 {
+            0.0
+        } else {
+            signal / self.noise
+        }
+    }
+}
+
+/// Kaufman's adaptive moving average.
+#[derive(Debug)]
+pub(super) struct Kama {
+    ker: Ker,
+    last: f64,
+}
+
+impl Kama {
+    pub(super) fn new(period: usize) -> Self {
+        Self {
+            ker: Ker::new(period),
+            last: 0.0,
+        }
+    }
+}
+
+impl Indicator for Kama {
+    fn warm_count(&self) -> usize {
+        self.ker.warm_count()
+    }
+
+    fn add(&mut self, v: f64) -> f64 {
+        if !self.ker.warmed() {
+            // initialize with the last value
+            self.last = self.ker.points[self.ker.idx].price;
+        }
+        let er = self.ker.add(v);
+        let sc = (er * (KAMA_FAST_SC - KAMA_SLOW_SC) + KAMA_SLOW_SC).powi(2);
+        self.last += sc * (v - self.last);
+        self.last
+    }
+}
+
+/// Chande momentum oscillator, without smoothing.
+#[derive(Debug)]
+pub(super) struct Cmo {
+    /// The most recent changes in value, used as a ring buffer.
+    changes: Vec<f64>,
+    sum_up: f64,
+    sum_down: f64,
+    last: Option<f64>,
+    /// The index of the oldest change.
+    idx: usize,
+}
+
+impl Cmo {
+    pub(super) fn new(period: usize) -> Self {
+        Self {
+            changes: vec![0.0; period],
+            sum_up: 0.0,
+            sum_down: 0.0,
+            last: None,
+            idx: 0,
+        }
+    }
+}
+
+impl Indicator for Cmo {
+    fn warm_count(&self) -> usize {
+        self.changes.len()
+    }
+
+    fn add(&mut self, v: f64) -> f64 {
+        let change = self.last.map(|last| v - last).unwrap_or_default();
+        self.last = Some(v);
+
+        // remove the oldest change from the sums
+        let oldest = self.changes[self.idx];
+        if oldest > 0.0 {
+            self.sum_up -= oldest;
+        } else {
+            self.sum_down += oldest;
+        }
+
+        if change > 0.0 {
+            self.sum_up += change;
+        } else {
+            self.sum_down -= change;
+        }
+        self.changes[self.idx] = change;
+        self.idx = (self.idx + 1) % self.changes.len();
+
+        let total = self.sum_up + self.sum_down;
+        if total == 0.0 {
+            0.0
+        } else {
+            100.0 * ((self.sum_up - self.sum_down) / total)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(mut ind: impl Indicator, values: &[f64]) -> Vec<f64> {
+        values.iter().map(|v| ind.add(*v)).collect()
+    }
+
+    #[test]
+    fn test_ker() {
+        // A monotonic series is perfectly efficient once warmed.
+        let got = run(Ker::new(2), &[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(got[2..], [1.0, 1.0]);
+
+        // A series that returns to its starting value is not.
+        let got = run(Ker::new(2), &[1.0, 2.0, 1.0]);
+        assert_eq!(got[2], 0.0);
+    }
+
+    #[test]
+    fn test_kama_constant() {
+        let got = run(Kama::new(2), &[3.0; 4]);
+        assert_eq!(got[2..], [3.0, 3.0]);
+    }
+
+    #[test]
+    fn test_cmo() {
+        let got = run(Cmo::new(2), &[1.0, 2.0, 3.0, 2.0, 1.0]);
+        assert_eq!(got[2..], [100.0, 0.0, -100.0]);
+    }
+}
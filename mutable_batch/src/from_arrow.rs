@@ -0,0 +1,322 @@
+//! Conversion of Arrow [`RecordBatch`] into [`MutableBatch`], the inverse of
+//! [`MutableBatch::try_into_arrow`].
+
+use arrow::{
+    array::{Array, ArrayRef, AsArray},
+    buffer::NullBuffer,
+    datatypes::{DataType, Float64Type, Int32Type, Int64Type, TimestampNanosecondType, UInt64Type},
+    record_batch::RecordBatch,
+};
+use schema::{InfluxColumnType, InfluxFieldType, Schema, TIME_COLUMN_NAME};
+use snafu::ResultExt;
+
+use crate::{
+    InvalidArrowSchemaSnafu, MissingTimeColumnSnafu, MutableBatch, NullTimeSnafu, Result,
+    UnsupportedArrowTypeSnafu, noop_validator::NoopValidator, writer::Writer,
+};
+
+impl MutableBatch {
+    /// Create a [`MutableBatch`] from an Arrow [`RecordBatch`].
+    ///
+    /// Every column of `batch` must carry the InfluxDB column type metadata
+    /// produced by [`Schema`], as is the case for the output of
+    /// [`MutableBatch::try_into_arrow`]. The batch must contain a non-null
+    /// [`TIME_COLUMN_NAME`] column.
+    pub fn from_record_batch(batch: &RecordBatch) -> Result<Self> {
+        let mut mb = Self::new();
+        mb.extend_from_record_batch(batch)?;
+        Ok(mb)
+    }
+
+    /// Extend this [`MutableBatch`] with the contents of the Arrow
+    /// [`RecordBatch`] `batch`.
+    ///
+    /// See [`MutableBatch::from_record_batch`] for the requirements placed
+    /// on `batch`. On error `self` is left unmodified.
+    pub fn extend_from_record_batch(&mut self, batch: &RecordBatch) -> Result<()> {
+        let schema = Schema::try_from(batch.schema()).context(InvalidArrowSchemaSnafu)?;
+
+        let time = batch
+            .column_by_name(TIME_COLUMN_NAME)
+            .ok_or_else(|| MissingTimeColumnSnafu.build())?;
+        if time.null_count() != 0 {
+            return NullTimeSnafu.fail();
+        }
+
+        let mut writer = Writer::new(self, batch.num_rows());
+        for ((influx_type, field), array) in schema.iter().zip(batch.columns()) {
+            write_array(&mut writer, field.name(), influx_type, array)?;
+        }
+        writer.commit();
+
+        Ok(())
+    }
+}
+
+fn write_array(
+    writer: &mut Writer<'_, NoopValidator>,
+    name: &str,
+    influx_type: InfluxColumnType,
+    array: &ArrayRef,
+) -> Result<()> {
+    let nulls = array.logical_nulls();
+    let mask = valid_mask(nulls.as_ref());
+    let mask = mask.as_deref();
+
+    match (influx_type, array.data_type()) {
+        (InfluxColumnType::Timestamp, _) => writer.write_time(
+            name,
+            array
+                .as_primitive::<TimestampNanosecondType>()
+                .values()
+                .iter()
+                .copied(),
+        )?,
+        (InfluxColumnType::Tag, DataType::Utf8) => {
+            writer.write_tag(name, mask, array.as_string::<i32>().iter().flatten())?
+        }
+        (InfluxColumnType::Tag, DataType::Dictionary(_, _)) => {
+            // A non-null key may reference a null dictionary value, so the
+            // keys are selected using the logical nulls of the array.
+            let dictionary = array.as_dictionary::<Int32Type>();
+            let keys = dictionary.keys();
+            let values = dictionary.values().as_string::<i32>();
+            writer.write_tag_dict(
+                name,
+                mask,
+                (0..keys.len())
+                    .filter(|idx| nulls.as_ref().is_none_or(|n| n.is_valid(*idx)))
+                    .map(|idx| keys.value(idx) as usize),
+                values.iter().map(|v| v.unwrap_or_default()),
+            )?
+        }
+        (InfluxColumnType::Field(InfluxFieldType::Float), _) => writer.write_f64(
+            name,
+            mask,
+            array.as_primitive::<Float64Type>().iter().flatten(),
+        )?,
+        (InfluxColumnType::Field(InfluxFieldType::Integer), _) => writer.write_i64(
+            name,
+            mask,
+            array.as_primitive::<Int64Type>().iter().flatten(),
+        )?,
+        (InfluxColumnType::Field(InfluxFieldType::UInteger), _) => writer.write_u64(
+            name,
+            mask,
+            array.as_primitive::<UInt64Type>().iter().flatten(),
+        )?,
+        (InfluxColumnType::Field(InfluxFieldType::Boolean), _) => {
+            writer.write_bool(name, mask, array.as_boolean().iter().flatten())?
+        }
+        (InfluxColumnType::Field(InfluxFieldType::String), _) => {
+            writer.write_string(name, mask, array.as_string::<i32>().iter().flatten())?
+        }
+        (_, data_type) => {
+            return UnsupportedArrowTypeSnafu {
+                column: name,
+                data_type: data_type.clone(),
+            }
+            .fail();
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the LSB-first validity bitmap for `nulls`, starting at bit 0, or
+/// [`None`] if every value is valid.
+fn valid_mask(nulls: Option<&NullBuffer>) -> Option<Vec<u8>> {
+    nulls
+        .filter(|n| n.null_count() > 0)
+        .map(|n| n.inner().sliced().as_slice().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{DictionaryArray, StringArray, TimestampNanosecondArray},
+        datatypes::{Field, Schema as ArrowSchema},
+    };
+    use arrow_util::assert_batches_eq;
+    use assert_matches::assert_matches;
+    use schema::{Projection, builder::SchemaBuilder};
+
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn test_roundtrip() {
+        let lp = r#"cpu,tag1=v1,tag2=v2 val=2i,s="foo" 0
+cpu,tag1=v4,tag2=v1 val=2i,b=true 0
+cpu,tag2=v2 val=3i,f=1.5 1
+cpu,tag1=v1,tag2=v2 u=22u 5
+cpu,tag1=v1 s="" 6"#;
+
+        let batch = mutable_batch_lp::lines_to_batches(lp, 0)
+            .unwrap()
+            .remove("cpu")
+            .unwrap();
+        let rb = batch.try_into_arrow(Projection::All).unwrap();
+
+        let mut mb = MutableBatch::from_record_batch(&rb).unwrap();
+        assert_eq!(mb.rows(), 5);
+        assert_eq!(
+            mb.clone().try_into_arrow(Projection::All).unwrap(),
+            rb.clone()
+        );
+
+        // Sliced batches carry offset validity buffers
+        mb.extend_from_record_batch(&rb.slice(3, 2)).unwrap();
+
+        assert_batches_eq!(
+            [
+                "+------+-----+-----+------+------+--------------------------------+----+-----+",
+                "| b    | f   | s   | tag1 | tag2 | time                           | u  | val |",
+                "+------+-----+-----+------+------+--------------------------------+----+-----+",
+                "|      |     | foo | v1   | v2   | 1970-01-01T00:00:00Z           |    | 2   |",
+                "| true |     |     | v4   | v1   | 1970-01-01T00:00:00Z           |    | 2   |",
+                "|      | 1.5 |     |      | v2   | 1970-01-01T00:00:00.000000001Z |    | 3   |",
+                "|      |     |     | v1   | v2   | 1970-01-01T00:00:00.000000005Z | 22 |     |",
+                "|      |     |     | v1   |      | 1970-01-01T00:00:00.000000006Z |    |     |",
+                "|      |     |     | v1   | v2   | 1970-01-01T00:00:00.000000005Z | 22 |     |",
+                "|      |     |     | v1   |      | 1970-01-01T00:00:00.000000006Z |    |     |",
+                "+------+-----+-----+------+------+--------------------------------+----+-----+",
+            ],
+            &[mb.try_into_arrow(Projection::All).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_utf8_tag() {
+        let schema = SchemaBuilder::new()
+            .tag("host")
+            .timestamp()
+            .build()
+            .unwrap();
+
+        // Tags may be provided as plain strings rather than dictionaries
+        let schema = ArrowSchema::new(
+            schema
+                .as_arrow()
+                .fields()
+                .iter()
+                .map(|f| match f.name().as_str() {
+                    "host" => {
+                        Field::new("host", DataType::Utf8, true).with_metadata(f.metadata().clone())
+                    }
+                    _ => f.as_ref().clone(),
+                })
+                .collect::<Vec<_>>(),
+        );
+        let rb = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(StringArray::from(vec![Some("a"), None, Some("a")])),
+                Arc::new(TimestampNanosecondArray::from(vec![1, 2, 3])),
+            ],
+        )
+        .unwrap();
+
+        let mb = MutableBatch::from_record_batch(&rb).unwrap();
+        assert_batches_eq!(
+            [
+                "+------+--------------------------------+",
+                "| host | time                           |",
+                "+------+--------------------------------+",
+                "| a    | 1970-01-01T00:00:00.000000001Z |",
+                "|      | 1970-01-01T00:00:00.000000002Z |",
+                "| a    | 1970-01-01T00:00:00.000000003Z |",
+                "+------+--------------------------------+",
+            ],
+            &[mb.try_into_arrow(Projection::All).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_sliced_dictionary_tag() {
+        let schema = SchemaBuilder::new()
+            .tag("host")
+            .timestamp()
+            .build()
+            .unwrap();
+
+        let tags: DictionaryArray<Int32Type> = vec![Some("b"), None, Some("b"), Some("c")]
+            .into_iter()
+            .collect();
+        let tags = tags.slice(2, 2);
+        let rb = RecordBatch::try_new(
+            schema.as_arrow(),
+            vec![
+                Arc::new(tags),
+                Arc::new(TimestampNanosecondArray::from(vec![1, 2])),
+            ],
+        )
+        .unwrap();
+
+        let mb = MutableBatch::from_record_batch(&rb).unwrap();
+        assert_batches_eq!(
+            [
+                "+------+--------------------------------+",
+                "| host | time                           |",
+                "+------+--------------------------------+",
+                "| b    | 1970-01-01T00:00:00.000000001Z |",
+                "| c    | 1970-01-01T00:00:00.000000002Z |",
+                "+------+--------------------------------+",
+            ],
+            &[mb.try_into_arrow(Projection::All).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_missing_metadata() {
+        let rb = RecordBatch::try_new(
+            Arc::new(ArrowSchema::new(vec![Field::new(
+                "host",
+                DataType::Utf8,
+                true,
+            )])),
+            vec![Arc::new(StringArray::from(vec!["a"]))],
+        )
+        .unwrap();
+
+        let err = MutableBatch::from_record_batch(&rb).unwrap_err();
+        assert_matches!(err, Error::InvalidArrowSchema { .. });
+    }
+
+    #[test]
+    fn test_missing_time() {
+        let schema = SchemaBuilder::new().tag("host").build().unwrap();
+        let rb = RecordBatch::try_new(
+            schema.as_arrow(),
+            vec![Arc::new(
+                vec![Some("a")]
+                    .into_iter()
+                    .collect::<DictionaryArray<Int32Type>>(),
+            )],
+        )
+        .unwrap();
+
+        let err = MutableBatch::from_record_batch(&rb).unwrap_err();
+        assert_matches!(err, Error::MissingTimeColumn);
+    }
+
+    #[test]
+    fn test_type_conflict_leaves_batch_unmodified() {
+        let mut mb = mutable_batch_lp::lines_to_batches("cpu val=1i 1", 0)
+            .unwrap()
+            .remove("cpu")
+            .unwrap();
+        let other = mutable_batch_lp::lines_to_batches("cpu val=1.5 2", 0)
+            .unwrap()
+            .remove("cpu")
+            .unwrap()
+            .try_into_arrow(Projection::All)
+            .unwrap();
+
+        let err = mb.extend_from_record_batch(&other).unwrap_err();
+        assert_matches!(err, Error::WriterError { .. });
+        assert_eq!(mb.rows(), 1);
+    }
+}
@@ -25,8 +25,10 @@ use snafu::{OptionExt, ResultExt, Snafu};
 use std::{collections::BTreeSet, ops::Range};
 
 pub mod column;
+mod from_arrow;
 mod noop_validator;
 pub mod payload;
+pub mod policy;
 pub mod writer;
 
 pub use payload::*;
@@ -51,6 +53,21 @@ pub enum Error {
 
     #[snafu(context(false))]
     WriterError { source: writer::Error },
+
+    #[snafu(display("Invalid arrow schema: {}", source))]
+    InvalidArrowSchema { source: schema::Error },
+
+    #[snafu(display("Record batch has no {} column", TIME_COLUMN_NAME))]
+    MissingTimeColumn,
+
+    #[snafu(display("Record batch contains null values in the {} column", TIME_COLUMN_NAME))]
+    NullTime,
+
+    #[snafu(display("Unsupported arrow type {} for column {}", data_type, column))]
+    UnsupportedArrowType {
+        column: String,
+        data_type: arrow::datatypes::DataType,
+    },
 }
 
 /// A specialized `Error` for [`MutableBatch`] errors
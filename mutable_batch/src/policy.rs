@@ -0,0 +1,534 @@
+//! Declarative schema-on-write policies, enforced through the
+//! [`ColumnInsertValidator`] hook of the [`Writer`](crate::writer::Writer).
+//!
+//! A [`SchemaPolicy`] maps table names to a [`TableSchemaPolicy`] which may
+//! restrict:
+//!
+//! * the names of tag and field columns, as a set of regular expressions
+//!   that must match the entire column name,
+//! * the maximum number of columns in the table,
+//! * the tags every row must specify, and
+//! * the type of named field columns.
+
+use std::{
+    cell::Cell,
+    collections::{HashMap, HashSet},
+};
+
+use regex::Regex;
+use schema::{InfluxColumnType, InfluxFieldType};
+use snafu::Snafu;
+
+use crate::writer::{ColumnInsertValidator, InvalidInsertionError};
+
+/// A rule of a [`TableSchemaPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyRule {
+    /// Tag names must match one of the allowed patterns.
+    AllowedTags,
+    /// Field names must match one of the allowed patterns.
+    AllowedFields,
+    /// The table must not exceed a maximum number of columns.
+    MaxColumns,
+    /// Every row must specify the required tags.
+    RequiredTags,
+    /// A field must have the pinned type.
+    FieldType,
+}
+
+impl PolicyRule {
+    /// Returns the string representation of this rule.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AllowedTags => "allowed_tags",
+            Self::AllowedFields => "allowed_fields",
+            Self::MaxColumns => "max_columns",
+            Self::RequiredTags => "required_tags",
+            Self::FieldType => "field_type",
+        }
+    }
+}
+
+impl std::fmt::Display for PolicyRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A violation of a [`TableSchemaPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq, Snafu)]
+#[expect(missing_docs)]
+pub enum PolicyViolation {
+    #[snafu(display(
+        "schema policy violation ({}): tag '{column}' is not allowed",
+        PolicyRule::AllowedTags
+    ))]
+    TagNotAllowed { column: String },
+
+    #[snafu(display(
+        "schema policy violation ({}): field '{column}' is not allowed",
+        PolicyRule::AllowedFields
+    ))]
+    FieldNotAllowed { column: String },
+
+    #[snafu(display(
+        "schema policy violation ({}): adding column '{column}' exceeds the limit of {max} columns",
+        PolicyRule::MaxColumns
+    ))]
+    TooManyColumns { column: String, max: usize },
+
+    #[snafu(display(
+        "schema policy violation ({}): missing required tag '{tag}'",
+        PolicyRule::RequiredTags
+    ))]
+    MissingRequiredTag { tag: String },
+
+    #[snafu(display(
+        "schema policy violation ({}): column '{column}' must be type {expected}, but type {given} was given",
+        PolicyRule::FieldType
+    ))]
+    FieldTypeMismatch {
+        column: String,
+        expected: InfluxColumnType,
+        given: InfluxColumnType,
+    },
+}
+
+impl PolicyViolation {
+    /// Returns the [`PolicyRule`] that was violated.
+    pub fn rule(&self) -> PolicyRule {
+        match self {
+            Self::TagNotAllowed { .. } => PolicyRule::AllowedTags,
+            Self::FieldNotAllowed { .. } => PolicyRule::AllowedFields,
+            Self::TooManyColumns { .. } => PolicyRule::MaxColumns,
+            Self::MissingRequiredTag { .. } => PolicyRule::RequiredTags,
+            Self::FieldTypeMismatch { .. } => PolicyRule::FieldType,
+        }
+    }
+}
+
+/// The schema policy of a single table.
+///
+/// The default policy imposes no restrictions.
+#[derive(Debug, Clone, Default)]
+pub struct TableSchemaPolicy {
+    /// If `Some`, tag names must match one of these patterns.
+    allowed_tags: Option<Vec<Regex>>,
+    /// If `Some`, field names must match one of these patterns.
+    allowed_fields: Option<Vec<Regex>>,
+    /// If `Some`, the maximum number of columns (including the time column).
+    max_columns: Option<usize>,
+    /// Tags every row must specify.
+    required_tags: Vec<String>,
+    /// The type of field columns, keyed by name.
+    field_types: HashMap<String, InfluxFieldType>,
+}
+
+impl TableSchemaPolicy {
+    /// Only allow tags with a name matching one of `patterns` in its entirety.
+    pub fn with_allowed_tags<'a>(
+        self,
+        patterns: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self, regex::Error> {
+        Ok(Self {
+            allowed_tags: Some(compile_patterns(patterns)?),
+            ..self
+        })
+    }
+
+    /// Only allow fields with a name matching one of `patterns` in its
+    /// entirety.
+    pub fn with_allowed_fields<'a>(
+        self,
+        patterns: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self, regex::Error> {
+        Ok(Self {
+            allowed_fields: Some(compile_patterns(patterns)?),
+            ..self
+        })
+    }
+
+    /// Limit the table to `max_columns` columns, including the time column.
+    pub fn with_max_columns(self, max_columns: usize) -> Self {
+        Self {
+            max_columns: Some(max_columns),
+            ..self
+        }
+    }
+
+    /// Require every row to specify the tags in `tags`.
+    pub fn with_required_tags(self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            required_tags: tags.into_iter().map(Into::into).collect(),
+            ..self
+        }
+    }
+
+    /// Require the field `column` to be of type `field_type`.
+    pub fn with_field_type(
+        mut self,
+        column: impl Into<String>,
+        field_type: InfluxFieldType,
+    ) -> Self {
+        self.field_types.insert(column.into(), field_type);
+        self
+    }
+
+    /// Returns an error if any of the required tags is not in `tags`.
+    pub fn validate_required_tags<'a>(
+        &self,
+        tags: impl IntoIterator<Item = &'a str> + Clone,
+    ) -> Result<(), PolicyViolation> {
+        match self
+            .required_tags
+            .iter()
+            .find(|required| !tags.clone().into_iter().any(|tag| tag == required.as_str()))
+        {
+            Some(tag) => MissingRequiredTagSnafu { tag }.fail(),
+            None => Ok(()),
+        }
+    }
+
+    /// Validate adding a new column `column` of type `column_type` to a table
+    /// that has `existing_columns` columns.
+    pub fn validate_column(
+        &self,
+        column: &str,
+        column_type: InfluxColumnType,
+        existing_columns: usize,
+    ) -> Result<(), PolicyViolation> {
+        self.validate_column_type(column, column_type)?;
+
+        match self.max_columns {
+            Some(max) if existing_columns >= max => TooManyColumnsSnafu { column, max }.fail(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Validate the name and type of the column `column` of type
+    /// `column_type`.
+    fn validate_column_type(
+        &self,
+        column: &str,
+        column_type: InfluxColumnType,
+    ) -> Result<(), PolicyViolation> {
+        match column_type {
+            InfluxColumnType::Tag => {
+                if !is_allowed(self.allowed_tags.as_deref(), column) {
+                    return TagNotAllowedSnafu { column }.fail();
+                }
+            }
+            InfluxColumnType::Field(_) => {
+                if !is_allowed(self.allowed_fields.as_deref(), column) {
+                    return FieldNotAllowedSnafu { column }.fail();
+                }
+            }
+            InfluxColumnType::Timestamp => {}
+        }
+
+        // A pinned field name cannot be used by a column of another type
+        if let Some(expected) = self
+            .field_types
+            .get(column)
+            .copied()
+            .map(InfluxColumnType::Field)
+            && expected != column_type
+        {
+            return FieldTypeMismatchSnafu {
+                column,
+                expected,
+                given: column_type,
+            }
+            .fail();
+        }
+
+        Ok(())
+    }
+
+    /// Returns a [`ColumnInsertValidator`] enforcing this policy for a
+    /// [`Writer`](crate::writer::Writer) of a batch of a table that has
+    /// `existing_columns` columns.
+    ///
+    /// The columns of the table are those of the batch, unless the validator
+    /// is given the columns the table already has with
+    /// [`TableSchemaPolicyValidator::with_table_columns()`].
+    pub fn validator(&self, existing_columns: usize) -> TableSchemaPolicyValidator<'_> {
+        TableSchemaPolicyValidator {
+            policy: self,
+            columns: Cell::new(existing_columns),
+            table_columns: None,
+        }
+    }
+}
+
+/// A [`ColumnInsertValidator`] enforcing a [`TableSchemaPolicy`], created by
+/// [`TableSchemaPolicy::validator()`].
+#[derive(Debug)]
+pub struct TableSchemaPolicyValidator<'a> {
+    policy: &'a TableSchemaPolicy,
+    /// The number of columns in the table, including those inserted by the
+    /// writer so far.
+    columns: Cell<usize>,
+    /// The columns the table already has, if known.
+    table_columns: Option<&'a HashSet<String>>,
+}
+
+impl<'a> TableSchemaPolicyValidator<'a> {
+    /// Validate the insertion of columns into a batch of a table that already
+    /// has the columns `table_columns`, such as those of the table in the
+    /// catalog.
+    ///
+    /// Inserting one of `table_columns` into the batch does not add a column
+    /// to the table, and so does not count towards the column limit. The
+    /// `existing_columns` of [`TableSchemaPolicy::validator()`] must include
+    /// `table_columns`.
+    pub fn with_table_columns(self, table_columns: &'a HashSet<String>) -> Self {
+        Self {
+            table_columns: Some(table_columns),
+            ..self
+        }
+    }
+}
+
+impl ColumnInsertValidator for TableSchemaPolicyValidator<'_> {
+    fn validate_insertion(
+        &self,
+        col_name: &str,
+        col_type: InfluxColumnType,
+    ) -> Result<(), InvalidInsertionError> {
+        let res = match self.table_columns {
+            Some(table_columns) if table_columns.contains(col_name) => {
+                self.policy.validate_column_type(col_name, col_type)
+            }
+            _ => self
+                .policy
+                .validate_column(col_name, col_type, self.columns.get())
+                .map(|_| self.columns.set(self.columns.get() + 1)),
+        };
+        res.map_err(|source| InvalidInsertionError::SchemaPolicy { source })
+    }
+}
+
+/// A [`TableSchemaPolicy`] for each table, with an optional default for
+/// tables without a policy of their own.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaPolicy {
+    tables: HashMap<String, TableSchemaPolicy>,
+    default: Option<TableSchemaPolicy>,
+}
+
+impl SchemaPolicy {
+    /// Apply `policy` to the table named `table`.
+    pub fn with_table(mut self, table: impl Into<String>, policy: TableSchemaPolicy) -> Self {
+        self.tables.insert(table.into(), policy);
+        self
+    }
+
+    /// Apply `policy` to all tables without a policy of their own.
+    pub fn with_default(self, policy: TableSchemaPolicy) -> Self {
+        Self {
+            default: Some(policy),
+            ..self
+        }
+    }
+
+    /// Returns the [`TableSchemaPolicy`] for `table`, if any.
+    pub fn table(&self, table: &str) -> Option<&TableSchemaPolicy> {
+        self.tables.get(table).or(self.default.as_ref())
+    }
+}
+
+fn compile_patterns<'a>(
+    patterns: impl IntoIterator<Item = &'a str>,
+) -> Result<Vec<Regex>, regex::Error> {
+    patterns
+        .into_iter()
+        .map(|p| Regex::new(&format!("^(?:{p})$")))
+        .collect()
+}
+
+fn is_allowed(patterns: Option<&[Regex]>, name: &str) -> bool {
+    patterns.is_none_or(|patterns| patterns.iter().any(|p| p.is_match(name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use schema::TIME_COLUMN_NAME;
+
+    use super::*;
+    use crate::{
+        MutableBatch,
+        writer::{self, Writer},
+    };
+
+    fn policy() -> TableSchemaPolicy {
+        TableSchemaPolicy::default()
+            .with_allowed_tags(["host", "region|zone"])
+            .unwrap()
+            .with_allowed_fields(["usage_.*"])
+            .unwrap()
+            .with_max_columns(4)
+            .with_required_tags(["host"])
+            .with_field_type("usage_idle", InfluxFieldType::Float)
+    }
+
+    #[test]
+    fn test_allowed_names() {
+        let policy = policy();
+
+        assert_matches!(
+            policy.validate_column("host", InfluxColumnType::Tag, 0),
+            Ok(())
+        );
+        assert_matches!(
+            policy.validate_column("zone", InfluxColumnType::Tag, 0),
+            Ok(())
+        );
+
+        // Patterns match the entire name
+        let err = policy
+            .validate_column("hostname", InfluxColumnType::Tag, 0)
+            .unwrap_err();
+        assert_eq!(err.rule(), PolicyRule::AllowedTags);
+        assert_eq!(
+            err.to_string(),
+            "schema policy violation (allowed_tags): tag 'hostname' is not allowed"
+        );
+
+        let float = InfluxColumnType::Field(InfluxFieldType::Float);
+        assert_matches!(policy.validate_column("usage_user", float, 0), Ok(()));
+        assert_eq!(
+            policy.validate_column("host", float, 0).unwrap_err().rule(),
+            PolicyRule::AllowedFields
+        );
+        assert_matches!(
+            policy.validate_column(TIME_COLUMN_NAME, InfluxColumnType::Timestamp, 0),
+            Ok(())
+        );
+
+        assert_matches!(
+            TableSchemaPolicy::default().with_allowed_tags(["("]),
+            Err(_)
+        );
+    }
+
+    #[test]
+    fn test_field_type() {
+        let policy = policy();
+
+        let err = policy
+            .validate_column(
+                "usage_idle",
+                InfluxColumnType::Field(InfluxFieldType::Integer),
+                0,
+            )
+            .unwrap_err();
+        assert_eq!(err.rule(), PolicyRule::FieldType);
+        assert_matches!(
+            policy.validate_column(
+                "usage_idle",
+                InfluxColumnType::Field(InfluxFieldType::Float),
+                0
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_required_tags() {
+        let policy = policy();
+
+        assert_matches!(policy.validate_required_tags(["region", "host"]), Ok(()));
+        assert_matches!(
+            policy.validate_required_tags(["region"]),
+            Err(PolicyViolation::MissingRequiredTag { tag }) if tag == "host"
+        );
+    }
+
+    #[test]
+    fn test_validator() {
+        let policy = policy();
+        let mut batch = MutableBatch::new();
+
+        let mut writer = Writer::new_with_column_validator(&mut batch, 1, policy.validator(0));
+        writer
+            .write_tag("host", None, std::iter::once("a"))
+            .unwrap();
+        writer
+            .write_f64("usage_idle", None, std::iter::once(1.0))
+            .unwrap();
+        writer.write_time("time", std::iter::once(1)).unwrap();
+        writer.commit();
+        assert_eq!(batch.columns().len(), 3);
+
+        // The fifth column exceeds the limit
+        let columns = batch.columns().len();
+        let mut writer =
+            Writer::new_with_column_validator(&mut batch, 1, policy.validator(columns));
+        writer
+            .write_tag("region", None, std::iter::once("r"))
+            .unwrap();
+        let err = writer
+            .write_f64("usage_user", None, std::iter::once(1.0))
+            .unwrap_err();
+        assert_matches!(
+            err,
+            writer::Error::ColumnInsertionRejected {
+                source: InvalidInsertionError::SchemaPolicy {
+                    source: PolicyViolation::TooManyColumns { max: 4, .. }
+                }
+            }
+        );
+        drop(writer);
+        assert_eq!(batch.rows(), 1);
+    }
+
+    #[test]
+    fn test_validator_table_columns() {
+        let policy = policy();
+        let table_columns = HashSet::from(["host".to_owned(), "region".to_owned()]);
+        let mut batch = MutableBatch::new();
+
+        // The columns of the table count towards the limit before they are
+        // inserted into the batch
+        let mut writer = Writer::new_with_column_validator(
+            &mut batch,
+            1,
+            policy.validator(2).with_table_columns(&table_columns),
+        );
+        writer
+            .write_tag("region", None, std::iter::once("r"))
+            .unwrap();
+        writer
+            .write_tag("host", None, std::iter::once("a"))
+            .unwrap();
+        writer.write_time("time", std::iter::once(1)).unwrap();
+        writer
+            .write_f64("usage_idle", None, std::iter::once(1.0))
+            .unwrap();
+        let err = writer
+            .write_f64("usage_user", None, std::iter::once(1.0))
+            .unwrap_err();
+        assert_matches!(
+            err,
+            writer::Error::ColumnInsertionRejected {
+                source: InvalidInsertionError::SchemaPolicy {
+                    source: PolicyViolation::TooManyColumns { max: 4, .. }
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn test_schema_policy_tables() {
+        let policy = SchemaPolicy::default()
+            .with_table("cpu", TableSchemaPolicy::default().with_max_columns(1));
+        assert!(policy.table("cpu").is_some());
+        assert!(policy.table("mem").is_none());
+
+        let policy = policy.with_default(TableSchemaPolicy::default());
+        assert!(policy.table("mem").is_some());
+    }
+}
@@ -4,6 +4,7 @@ use crate::{
     MutableBatch,
     column::{Column, ColumnData, NULL_DID},
     noop_validator::NoopValidator,
+    policy::PolicyViolation,
 };
 use arrow_util::bitset::{BitSet, iter_set_positions, iter_set_positions_with_offset};
 use data_types::{IsNan, StatValues, Statistics};
@@ -46,6 +47,9 @@ pub enum InvalidInsertionError {
         table_type: InfluxColumnType,
         given: InfluxColumnType,
     },
+
+    #[snafu(display("{source}"))]
+    SchemaPolicy { source: PolicyViolation },
 }
 
 /// A type capable of checking the validity of a column insertion into a
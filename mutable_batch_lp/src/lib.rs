@@ -7,14 +7,20 @@
 use criterion as _;
 use workspace_hack as _;
 
+use bytes::Bytes;
 use hashbrown::{HashMap, HashSet, hash_map::Entry};
-use influxdb_line_protocol::{FieldValue, ParsedLine, parse_lines};
+use influxdb_line_protocol::{
+    EscapedStr, FieldValue, LineSplitter, ParsedLine, parse_lines,
+    v3::{self, FIELD_FAMILY_DELIMITER, FieldName},
+};
 use mutable_batch::MutableBatch;
-use mutable_batch::writer::{ColumnInsertValidator, Writer};
+use mutable_batch::policy::{PolicyViolation, SchemaPolicy};
+use mutable_batch::writer::{self, ColumnInsertValidator, InvalidInsertionError, Writer};
 use snafu::{ResultExt, Snafu};
+use std::time::Duration;
 
 /// A limit on the number of errors to return from a partial LP write.
-const MAXIMUM_RETURNED_ERRORS: usize = 100;
+pub const MAXIMUM_RETURNED_ERRORS: usize = 100;
 
 /// Error type for a conversion attempt on a set of line protocol lines
 #[derive(Debug, Snafu)]
@@ -43,8 +49,123 @@ pub enum LineError {
     #[snafu(display("error writing line {} (1-based): {}", line, source))]
     Write { source: LineWriteError, line: usize },
 
+    #[snafu(display("invalid UTF-8 on line {} (1-based): {}", line, source))]
+    InvalidUtf8 {
+        source: std::str::Utf8Error,
+        line: usize,
+    },
+
     #[snafu(display("timestamp overflows i64 on line {} (1-based)", line))]
     TimestampOverflow { line: usize },
+
+    #[snafu(display(
+        "timestamp {} on line {} (1-based) is older than the minimum accepted time {}",
+        timestamp,
+        line,
+        min
+    ))]
+    TimestampTooOld {
+        line: usize,
+        timestamp: i64,
+        min: i64,
+    },
+
+    #[snafu(display(
+        "timestamp {} on line {} (1-based) is later than the maximum accepted time {}",
+        timestamp,
+        line,
+        max
+    ))]
+    TimestampTooFarInFuture {
+        line: usize,
+        timestamp: i64,
+        max: i64,
+    },
+}
+
+impl LineError {
+    /// Returns the line number (1-based) this error occurred on.
+    pub fn line(&self) -> usize {
+        match self {
+            Self::LineProtocol { line, .. }
+            | Self::Write { line, .. }
+            | Self::InvalidUtf8 { line, .. }
+            | Self::TimestampOverflow { line }
+            | Self::TimestampTooOld { line, .. }
+            | Self::TimestampTooFarInFuture { line, .. } => *line,
+        }
+    }
+
+    /// Returns the [`LineErrorCode`] classifying this error.
+    pub fn code(&self) -> LineErrorCode {
+        match self {
+            Self::LineProtocol { .. } | Self::InvalidUtf8 { .. } => {
+                LineErrorCode::InvalidLineProtocol
+            }
+            Self::Write { source, .. } if source.policy_violation().is_some() => {
+                LineErrorCode::SchemaPolicyViolation
+            }
+            Self::Write {
+                source: LineWriteError::MutableBatch { .. } | LineWriteError::SchemaPolicy { .. },
+                ..
+            } => LineErrorCode::SchemaConflict,
+            Self::Write {
+                source: LineWriteError::DuplicateTag { .. },
+                ..
+            } => LineErrorCode::DuplicateTag,
+            Self::Write {
+                source: LineWriteError::ConflictedFieldTypes { .. },
+                ..
+            } => LineErrorCode::ConflictedFieldTypes,
+            Self::TimestampOverflow { .. } => LineErrorCode::TimestampOverflow,
+            Self::TimestampTooOld { .. } => LineErrorCode::TimestampTooOld,
+            Self::TimestampTooFarInFuture { .. } => LineErrorCode::TimestampTooFarInFuture,
+        }
+    }
+}
+
+/// A stable, machine readable classification of a [`LineError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineErrorCode {
+    /// The line is not valid line protocol.
+    InvalidLineProtocol,
+    /// The line conflicts with the schema of previously written lines.
+    SchemaConflict,
+    /// A tag is specified more than once in the line.
+    DuplicateTag,
+    /// A field is specified more than once in the line with differing types.
+    ConflictedFieldTypes,
+    /// The line timestamp overflows an i64 once converted to nanoseconds.
+    TimestampOverflow,
+    /// The line violates the [`SchemaPolicy`] of its table.
+    SchemaPolicyViolation,
+    /// The line timestamp is older than the [`TimestampPolicy`] accepts.
+    TimestampTooOld,
+    /// The line timestamp is further in the future than the
+    /// [`TimestampPolicy`] accepts.
+    TimestampTooFarInFuture,
+}
+
+impl LineErrorCode {
+    /// Returns the string representation of this code.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::InvalidLineProtocol => "invalid_line_protocol",
+            Self::SchemaConflict => "schema_conflict",
+            Self::DuplicateTag => "duplicate_tag",
+            Self::ConflictedFieldTypes => "conflicted_field_types",
+            Self::TimestampOverflow => "timestamp_overflow",
+            Self::SchemaPolicyViolation => "schema_policy_violation",
+            Self::TimestampTooOld => "timestamp_too_old",
+            Self::TimestampTooFarInFuture => "timestamp_too_far_in_future",
+        }
+    }
+}
+
+impl std::fmt::Display for LineErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Result type for line protocol conversion
@@ -57,6 +178,148 @@ pub struct PayloadStatistics {
     pub num_fields: usize,
     /// The number of lines
     pub num_lines: usize,
+    /// The number of lines rejected due to a [`LineError`]
+    pub num_rejected_lines: usize,
+    /// The number of line timestamps truncated by the [`TimestampPolicy`]
+    pub num_truncated_timestamps: usize,
+    /// The number of line timestamps older than the [`TimestampPolicy`]
+    /// accepts, whether the line was rejected or given the default time
+    pub num_too_old_timestamps: usize,
+    /// The number of line timestamps further in the future than the
+    /// [`TimestampPolicy`] accepts, whether the line was rejected or given
+    /// the default time
+    pub num_future_timestamps: usize,
+    /// The number of out of range line timestamps replaced with the default
+    /// time
+    pub num_default_time_substitutions: usize,
+}
+
+impl PayloadStatistics {
+    /// Count the [`TimestampAdjustment`] of an accepted line.
+    fn record_adjustment(&mut self, adjustment: TimestampAdjustment) {
+        self.num_truncated_timestamps += usize::from(adjustment.truncated);
+        self.num_default_time_substitutions += usize::from(adjustment.default_time_substituted);
+    }
+}
+
+/// The action taken for a line with a timestamp outside of the range
+/// accepted by a [`TimestampPolicy`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutOfRangeTimestamp {
+    /// Reject the line with a [`LineError`].
+    #[default]
+    Reject,
+    /// Accept the line, replacing its timestamp with the default time of the
+    /// [`LinesConverter`].
+    UseDefaultTime,
+}
+
+/// Ingest policies applied to the timestamps of lines written to a
+/// [`LinesConverter`], after conversion to nanoseconds.
+///
+/// Timestamps are truncated before they are checked against the accepted
+/// range, which is relative to the default time of the [`LinesConverter`],
+/// normally the time of the write. Lines without a timestamp are written with
+/// the truncated default time, and are not subject to the range check.
+///
+/// The default policy accepts all timestamps unmodified.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TimestampPolicy {
+    /// If `Some`, truncate timestamps to a multiple of this many nanoseconds.
+    truncate_to: Option<i64>,
+    /// If `Some`, the maximum age of a timestamp, in nanoseconds.
+    max_age: Option<i64>,
+    /// If `Some`, the maximum distance of a timestamp into the future, in
+    /// nanoseconds.
+    max_future: Option<i64>,
+    /// The action taken for out of range timestamps.
+    out_of_range: OutOfRangeTimestamp,
+}
+
+impl TimestampPolicy {
+    /// Truncate timestamps to a multiple of `precision`, such as
+    /// [`Duration::from_secs(1)`](Duration::from_secs) to store second
+    /// precision timestamps. A zero `precision` disables truncation.
+    pub fn with_truncation(self, precision: Duration) -> Self {
+        Self {
+            truncate_to: Some(duration_nanos(precision)).filter(|p| *p > 0),
+            ..self
+        }
+    }
+
+    /// Treat timestamps older than `max_age`, such as the retention period
+    /// of the namespace, as out of range.
+    pub fn with_max_age(self, max_age: Duration) -> Self {
+        Self {
+            max_age: Some(duration_nanos(max_age)),
+            ..self
+        }
+    }
+
+    /// Treat timestamps more than `max_future` in the future as out of range.
+    pub fn with_max_future(self, max_future: Duration) -> Self {
+        Self {
+            max_future: Some(duration_nanos(max_future)),
+            ..self
+        }
+    }
+
+    /// Set the action taken for out of range timestamps, defaulting to
+    /// [`OutOfRangeTimestamp::Reject`].
+    pub fn with_out_of_range(self, out_of_range: OutOfRangeTimestamp) -> Self {
+        Self {
+            out_of_range,
+            ..self
+        }
+    }
+}
+
+fn duration_nanos(d: Duration) -> i64 {
+    i64::try_from(d.as_nanos()).unwrap_or(i64::MAX)
+}
+
+/// The changes made to the timestamp of a single line by the
+/// [`TimestampPolicy`].
+#[derive(Debug, Clone, Copy, Default)]
+struct TimestampAdjustment {
+    truncated: bool,
+    default_time_substituted: bool,
+}
+
+/// The outcome of a [`LinesConverter::write_lp_partial()`] call.
+///
+/// Lines that fail to parse or write are rejected individually, all other
+/// lines are accepted and buffered in the [`LinesConverter`].
+#[derive(Debug, Default)]
+pub struct PartialWrite {
+    /// The number of lines accepted
+    pub accepted_lines: usize,
+    /// The number of lines rejected, which may be more than the number of
+    /// returned `errors`
+    pub rejected_lines: usize,
+    /// The errors of the rejected lines, capped at the first
+    /// [`MAXIMUM_RETURNED_ERRORS`]
+    pub errors: Vec<LineError>,
+}
+
+impl PartialWrite {
+    /// Returns true if any line was rejected.
+    pub fn is_partial(&self) -> bool {
+        self.rejected_lines > 0
+    }
+
+    /// Count a line, rejected if `maybe_err` is `Some`.
+    fn record(&mut self, maybe_err: Option<LineError>) {
+        match maybe_err {
+            None => self.accepted_lines += 1,
+            Some(line_err) => {
+                self.rejected_lines += 1;
+                if self.errors.len() < MAXIMUM_RETURNED_ERRORS {
+                    self.errors.push(line_err);
+                }
+            }
+        }
+    }
 }
 
 /// Converts line protocol to a set of [`MutableBatch`]
@@ -70,6 +333,18 @@ pub struct LinesConverter {
     stats: PayloadStatistics,
     /// The current batches
     batches: HashMap<String, MutableBatch>,
+    /// The schema policy lines are validated against, if any
+    schema_policy: Option<SchemaPolicy>,
+    /// The columns each table already has, keyed by table name
+    table_columns: HashMap<String, std::collections::HashSet<String>>,
+    /// The policy applied to line timestamps
+    timestamp_policy: TimestampPolicy,
+    /// Parse line protocol v3 field families
+    field_families: bool,
+    /// Splits line protocol written with [`LinesConverter::write_lp_chunk`]
+    splitter: LineSplitter,
+    /// The number of lines written with [`LinesConverter::write_lp_chunk`]
+    chunk_lines: usize,
 }
 
 impl LinesConverter {
@@ -80,6 +355,70 @@ impl LinesConverter {
             timestamp_base: 1,
             stats: Default::default(),
             batches: Default::default(),
+            schema_policy: None,
+            table_columns: Default::default(),
+            timestamp_policy: Default::default(),
+            field_families: false,
+            splitter: Default::default(),
+            chunk_lines: 0,
+        }
+    }
+
+    /// Parse lines as line protocol v3, accepting fields qualified with a
+    /// field family such as `family::name=1i`.
+    ///
+    /// Qualified fields are written to a column named `family::name`, so the
+    /// fields of a family are grouped by the common column name prefix.
+    /// Unqualified fields are written as with line protocol v1.
+    pub fn with_field_families(self, field_families: bool) -> Self {
+        Self {
+            field_families,
+            ..self
+        }
+    }
+
+    /// Multiply line timestamps by `timestamp_base` to convert them to
+    /// nanoseconds, such as `1_000_000_000` for second precision timestamps.
+    pub fn with_timestamp_base(self, timestamp_base: i64) -> Self {
+        Self {
+            timestamp_base,
+            ..self
+        }
+    }
+
+    /// Apply `timestamp_policy` to line timestamps.
+    pub fn with_timestamp_policy(self, timestamp_policy: TimestampPolicy) -> Self {
+        Self {
+            timestamp_policy,
+            ..self
+        }
+    }
+
+    /// Reject lines that violate the [`TableSchemaPolicy`] of their table in
+    /// `schema_policy` with a [`LineWriteError::SchemaPolicy`].
+    ///
+    /// [`TableSchemaPolicy`]: mutable_batch::policy::TableSchemaPolicy
+    pub fn with_schema_policy(self, schema_policy: SchemaPolicy) -> Self {
+        Self {
+            schema_policy: Some(schema_policy),
+            ..self
+        }
+    }
+
+    /// The columns each table already has, keyed by table name, such as the
+    /// columns of the tables of the namespace in the catalog.
+    ///
+    /// The column limit of a [`SchemaPolicy`] applies to the columns of the
+    /// table, which are `table_columns` and the columns of the lines written.
+    /// Without the columns of a table, the limit applies to the columns of the
+    /// lines written to the table by this converter.
+    pub fn with_table_columns(
+        self,
+        table_columns: HashMap<String, std::collections::HashSet<String>>,
+    ) -> Self {
+        Self {
+            table_columns,
+            ..self
         }
     }
 
@@ -103,41 +442,198 @@ impl LinesConverter {
     ///     [`mutable_batch::writer::Error::TypeMismatch`]
     ///
     pub fn write_lp(&mut self, lines: &str) -> Result<()> {
-        let mut errors = Vec::new();
-        for line_err in parse_lines(lines)
-            .enumerate()
-            .filter_map(|(line_idx, maybe_line)| {
-                maybe_line
-                    .context(LineProtocolSnafu { line: line_idx + 1 })
-                    .and_then(|line| self.rebase_timestamp(line, line_idx))
-                    .and_then(|line| self.add_line_to_batch(line, line_idx))
-                    .err()
-            })
-        {
-            if errors.len() < MAXIMUM_RETURNED_ERRORS {
-                errors.push(line_err);
-            }
-        }
-
+        let PartialWrite { errors, .. } = self.write_lp_partial(lines);
         if !errors.is_empty() {
             return Err(Error::PerLine { lines: errors });
         }
         Ok(())
     }
 
+    /// Write some line protocol data, accepting all valid lines and
+    /// rejecting only the lines that fail to parse or write.
+    ///
+    /// Follows the same semantics as [`LinesConverter::write_lp()`], but
+    /// returns the number of accepted and rejected lines instead of an
+    /// [`Error::PerLine`], allowing the caller to decide whether a partial
+    /// write is acceptable. The accepted lines are kept and returned from
+    /// [`LinesConverter::finish()`] either way.
+    pub fn write_lp_partial(&mut self, lines: &str) -> PartialWrite {
+        let mut res = PartialWrite::default();
+        for (line_idx, maybe_line) in Self::parse(lines, self.field_families).enumerate() {
+            res.record(self.write_line(maybe_line, line_idx));
+        }
+
+        self.stats.num_rejected_lines += res.rejected_lines;
+        res
+    }
+
+    /// Write a chunk of a line protocol payload that is received
+    /// incrementally, such as a frame of an HTTP request body.
+    ///
+    /// The lines completed by `chunk` are written as by
+    /// [`LinesConverter::write_lp_partial()`], without buffering the rest of
+    /// the payload. A line split across chunks is retained until it is
+    /// completed by a later chunk, or by [`LinesConverter::finish_lp_chunks()`]
+    /// once the end of the payload is reached. Each line is validated as
+    /// UTF-8 individually, and line numbers count from the start of the
+    /// payload rather than the chunk.
+    pub fn write_lp_chunk(&mut self, chunk: Bytes) -> PartialWrite {
+        let lines = self.splitter.push(chunk);
+        self.write_chunk_lines(lines)
+    }
+
+    /// Write the final line of a payload passed to
+    /// [`LinesConverter::write_lp_chunk()`], if it is not terminated by a
+    /// newline.
+    ///
+    /// Must be called once the end of the payload is reached, before
+    /// [`LinesConverter::finish()`].
+    pub fn finish_lp_chunks(&mut self) -> PartialWrite {
+        let line = self.splitter.finish();
+        let res = self.write_chunk_lines(line);
+        self.chunk_lines = 0;
+        res
+    }
+
+    fn write_chunk_lines(&mut self, lines: impl IntoIterator<Item = Bytes>) -> PartialWrite {
+        let mut res = PartialWrite::default();
+        for line in lines {
+            let line = match std::str::from_utf8(&line) {
+                Ok(line) => line,
+                // Empty and comment lines are ignored, as when they are parsed.
+                Err(_) if is_blank_or_comment(&line) => continue,
+                Err(source) => {
+                    self.chunk_lines += 1;
+                    res.record(Some(LineError::InvalidUtf8 {
+                        source,
+                        line: self.chunk_lines,
+                    }));
+                    continue;
+                }
+            };
+
+            // Empty and comment lines yield no parsed lines, and are not
+            // numbered, as with write_lp_partial().
+            for maybe_line in Self::parse(line, self.field_families) {
+                let line_idx = self.chunk_lines;
+                self.chunk_lines += 1;
+                res.record(self.write_line(maybe_line, line_idx));
+            }
+        }
+
+        self.stats.num_rejected_lines += res.rejected_lines;
+        res
+    }
+
+    /// Parse `lines` as line protocol v1, or v3 if `field_families` is set.
+    fn parse(
+        lines: &str,
+        field_families: bool,
+    ) -> Box<dyn Iterator<Item = Result<ParsedLine<'_>, influxdb_line_protocol::Error>> + '_> {
+        match field_families {
+            true => Box::new(v3::parse_lines(lines).map(|line| line.map(flatten_field_families))),
+            false => Box::new(parse_lines(lines)),
+        }
+    }
+
+    /// Write the parsed line at (0-based) `line_idx`, returning the error if
+    /// it was rejected.
+    fn write_line(
+        &mut self,
+        maybe_line: Result<ParsedLine<'_>, influxdb_line_protocol::Error>,
+        line_idx: usize,
+    ) -> Option<LineError> {
+        maybe_line
+            .context(LineProtocolSnafu { line: line_idx + 1 })
+            .and_then(|line| self.rebase_timestamp(line, line_idx))
+            .and_then(|(line, adjustment)| {
+                self.add_line_to_batch(line, line_idx)?;
+                self.stats.record_adjustment(adjustment);
+                Ok(())
+            })
+            .err()
+    }
+
+    /// Convert the timestamp of `line` to nanoseconds and apply the
+    /// [`TimestampPolicy`], returning the modified line and how its timestamp
+    /// was adjusted.
     fn rebase_timestamp<'a>(
-        &self,
+        &mut self,
         mut line: ParsedLine<'a>,
         line_idx: usize,
-    ) -> Result<ParsedLine<'a>, LineError> {
-        if let Some(t) = line.timestamp.as_mut() {
-            let updated_timestamp = match t.checked_mul(self.timestamp_base) {
-                Some(t) => t,
-                None => return Err(LineError::TimestampOverflow { line: line_idx + 1 }),
-            };
-            *t = updated_timestamp;
-        }
-        Ok(line)
+    ) -> Result<(ParsedLine<'a>, TimestampAdjustment), LineError> {
+        let mut adjustment = TimestampAdjustment::default();
+        let policy = self.timestamp_policy;
+        let default_time = self.default_time;
+        let line_number = line_idx + 1;
+
+        let truncate = |timestamp: i64| match policy.truncate_to {
+            Some(precision) if timestamp.rem_euclid(precision) != 0 => timestamp
+                .checked_sub(timestamp.rem_euclid(precision))
+                .map(|t| (t, true))
+                .ok_or(LineError::TimestampOverflow { line: line_number }),
+            _ => Ok((timestamp, false)),
+        };
+
+        // Lines without a timestamp, and lines with an out of range timestamp
+        // replaced by the default time, are written with the truncated default
+        // time.
+        let (truncated_default_time, default_time_truncated) = truncate(default_time)?;
+
+        let Some(timestamp) = line.timestamp else {
+            if default_time_truncated {
+                adjustment.truncated = true;
+                line.timestamp = Some(truncated_default_time);
+            }
+            return Ok((line, adjustment));
+        };
+        let timestamp = timestamp
+            .checked_mul(self.timestamp_base)
+            .ok_or(LineError::TimestampOverflow { line: line_number })?;
+
+        // Timestamps are truncated before the range check, so that the stored
+        // timestamp is the one checked.
+        let (timestamp, truncated) = truncate(timestamp)?;
+
+        let min = policy.max_age.map(|age| default_time.saturating_sub(age));
+        let max = policy
+            .max_future
+            .map(|future| default_time.saturating_add(future));
+
+        let out_of_range = match (min, max) {
+            (Some(min), _) if timestamp < min => {
+                self.stats.num_too_old_timestamps += 1;
+                Some(LineError::TimestampTooOld {
+                    line: line_number,
+                    timestamp,
+                    min,
+                })
+            }
+            (_, Some(max)) if timestamp > max => {
+                self.stats.num_future_timestamps += 1;
+                Some(LineError::TimestampTooFarInFuture {
+                    line: line_number,
+                    timestamp,
+                    max,
+                })
+            }
+            _ => None,
+        };
+
+        line.timestamp = Some(match (out_of_range, policy.out_of_range) {
+            (Some(e), OutOfRangeTimestamp::Reject) => return Err(e),
+            (Some(_), OutOfRangeTimestamp::UseDefaultTime) => {
+                adjustment.default_time_substituted = true;
+                adjustment.truncated = default_time_truncated;
+                truncated_default_time
+            }
+            (None, _) => {
+                adjustment.truncated = truncated;
+                timestamp
+            }
+        });
+
+        Ok((line, adjustment))
     }
 
     fn add_line_to_batch(
@@ -154,17 +650,44 @@ impl LinesConverter {
             .or_insert_with(|| (measurement.to_string(), MutableBatch::new()));
 
         // TODO: Reuse writer
-        let mut writer = Writer::new(batch, 1);
-        match write_line(&mut writer, &line, self.default_time)
-            .context(WriteSnafu { line: line_idx + 1 })
+        let res = match self
+            .schema_policy
+            .as_ref()
+            .and_then(|policy| policy.table(measurement))
         {
-            Ok(_) => {
-                writer.commit();
-                self.stats.num_lines += 1;
-                self.stats.num_fields += line.field_set.len();
+            Some(policy) => {
+                let tags = line
+                    .series
+                    .tag_set
+                    .iter()
+                    .flatten()
+                    .map(|(key, _)| key.as_str());
+                policy
+                    .validate_required_tags(tags)
+                    .context(SchemaPolicySnafu)
+                    .and_then(|_| {
+                        let validator = match self.table_columns.get(measurement) {
+                            Some(table_columns) => {
+                                let new_columns = batch
+                                    .columns()
+                                    .filter(|(name, _)| !table_columns.contains(*name))
+                                    .count();
+                                policy
+                                    .validator(table_columns.len() + new_columns)
+                                    .with_table_columns(table_columns)
+                            }
+                            None => policy.validator(batch.columns().len()),
+                        };
+                        let writer = Writer::new_with_column_validator(batch, 1, validator);
+                        write_line_and_commit(writer, &line, self.default_time)
+                    })
             }
-            Err(e) => return Err(e),
+            None => write_line_and_commit(Writer::new(batch, 1), &line, self.default_time),
         };
+        res.context(WriteSnafu { line: line_idx + 1 })?;
+
+        self.stats.num_lines += 1;
+        self.stats.num_fields += line.field_set.len();
         Ok(())
     }
 
@@ -189,6 +712,37 @@ impl LinesConverter {
     }
 }
 
+/// Convert a line protocol v3 line to a v1 [`ParsedLine`], naming qualified
+/// fields `family::name`.
+fn flatten_field_families(line: v3::ParsedLine<'_>) -> ParsedLine<'_> {
+    let v3::ParsedLine {
+        series,
+        field_set,
+        timestamp,
+    } = line;
+
+    let field_set = field_set
+        .into_iter()
+        .map(|(name, value)| {
+            let name = match name {
+                FieldName::Unqualified(name) => name,
+                FieldName::Qualified(family, name) => EscapedStr::CopiedValue(format!(
+                    "{}{FIELD_FAMILY_DELIMITER}{}",
+                    family.as_str(),
+                    name.as_str()
+                )),
+            };
+            (name, value)
+        })
+        .collect();
+
+    ParsedLine {
+        series,
+        field_set,
+        timestamp,
+    }
+}
+
 /// Converts the provided lines of line protocol to a set of [`MutableBatch`]
 /// keyed by measurement name
 pub fn lines_to_batches(lines: &str, default_time: i64) -> Result<HashMap<String, MutableBatch>> {
@@ -219,6 +773,13 @@ pub enum LineWriteError {
         name: String,
     },
 
+    /// The line violates the schema policy of its table.
+    #[snafu(display("{}", source))]
+    SchemaPolicy {
+        /// The violated policy rule.
+        source: PolicyViolation,
+    },
+
     /// The specified field name appears twice in one LP line, with conflicting
     /// types.
     #[snafu(display(
@@ -231,6 +792,37 @@ pub enum LineWriteError {
     },
 }
 
+impl LineWriteError {
+    /// Returns the [`PolicyViolation`] if this error is caused by a
+    /// violation of a schema policy.
+    pub fn policy_violation(&self) -> Option<&PolicyViolation> {
+        match self {
+            Self::SchemaPolicy { source }
+            | Self::MutableBatch {
+                source:
+                    writer::Error::ColumnInsertionRejected {
+                        source: InvalidInsertionError::SchemaPolicy { source },
+                    },
+            } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Writes `line` with `writer`, committing the write if successful.
+fn write_line_and_commit<T>(
+    mut writer: Writer<'_, T>,
+    line: &ParsedLine<'_>,
+    default_time: i64,
+) -> Result<(), LineWriteError>
+where
+    T: ColumnInsertValidator,
+{
+    write_line(&mut writer, line, default_time)?;
+    writer.commit();
+    Ok(())
+}
+
 /// Writes the [`ParsedLine`] to the [`MutableBatch`], respecting the edge case
 /// semantics described in [`LinesConverter::write_lp()`].
 pub fn write_line<T>(
@@ -365,6 +957,15 @@ pub mod test_helpers {
     }
 }
 
+/// Returns `true` if `line` is empty or a comment once leading whitespace is
+/// removed, and so yields no parsed lines.
+fn is_blank_or_comment(line: &[u8]) -> bool {
+    matches!(
+        line.iter().find(|b| !matches!(b, b' ' | b'\t' | b'\n')),
+        None | Some(b'#')
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -744,6 +1345,49 @@ cpu val=4u";
         lines_to_batches(lp, 5).unwrap();
     }
 
+    #[test]
+    fn write_lp_partial_counts_lines() {
+        let lp = "cpu,tag1=v1 val=2i 0
+cpu val=4u 1
+cpu,tag1=v1,tag1=v2 val=3i 2
+bad_line
+mem,tag1=v2 ival=3i 0";
+
+        let mut converter = LinesConverter::new(10);
+        let res = converter.write_lp_partial(lp);
+        assert!(res.is_partial());
+        assert_eq!(res.accepted_lines, 2);
+        assert_eq!(res.rejected_lines, 3);
+
+        let got = res
+            .errors
+            .iter()
+            .map(|e| (e.line(), e.code()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            got,
+            [
+                (2, LineErrorCode::SchemaConflict),
+                (3, LineErrorCode::DuplicateTag),
+                (4, LineErrorCode::InvalidLineProtocol),
+            ]
+        );
+
+        let (batches, stats) = converter.finish().unwrap();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(stats.num_lines, 2);
+        assert_eq!(stats.num_rejected_lines, 3);
+    }
+
+    #[test]
+    fn write_lp_partial_all_accepted() {
+        let mut converter = LinesConverter::new(10);
+        let res = converter.write_lp_partial("cpu val=1i 0\ncpu val=2i 1");
+        assert!(!res.is_partial());
+        assert_eq!(res.accepted_lines, 2);
+        assert!(res.errors.is_empty());
+    }
+
     #[test]
     fn batch_creation_continues_after_line_error_limit() {
         const WANT_GOOD_ROWS: usize = 5;
@@ -767,11 +1411,383 @@ cpu val=4u";
         assert_matches!(converter.write_lp(&lp), Err(Error::PerLine { lines }) => {
             assert_eq!(lines.len(), MAXIMUM_RETURNED_ERRORS);
         });
-        let (batches, _) = converter.finish().unwrap();
+        let (batches, stats) = converter.finish().unwrap();
+
+        // The total number of rejected lines is reported, not just the
+        // returned errors.
+        assert_eq!(stats.num_rejected_lines, MAXIMUM_RETURNED_ERRORS + 1);
 
         // 1 table, with 5 rows.
         assert_eq!(batches.len(), 1);
         let total_rows: usize = batches.iter().map(|(_table, batch)| batch.rows()).sum();
         assert_eq!(total_rows, WANT_GOOD_ROWS);
     }
+
+    #[test]
+    fn test_schema_policy() {
+        use mutable_batch::policy::{PolicyRule, TableSchemaPolicy};
+        use schema::InfluxFieldType;
+
+        let policy = SchemaPolicy::default().with_table(
+            "cpu",
+            TableSchemaPolicy::default()
+                .with_allowed_tags(["host", "region"])
+                .unwrap()
+                .with_max_columns(4)
+                .with_required_tags(["host"])
+                .with_field_type("idle", InfluxFieldType::Float),
+        );
+
+        let lp = "cpu,host=a usage=1.0 1
+cpu,host=a,pod=p usage=2.0 2
+cpu,region=r usage=3.0 3
+cpu,host=b idle=4i 4
+cpu,host=c,region=r usage=5.0 5
+cpu,host=d,region=r usage=6.0,idle=1.0 6
+mem,pod=p free=1i 7";
+
+        let mut converter = LinesConverter::new(0).with_schema_policy(policy);
+        let res = converter.write_lp_partial(lp);
+        assert_eq!(res.accepted_lines, 3);
+        assert_eq!(res.rejected_lines, 4);
+
+        let rules = res
+            .errors
+            .iter()
+            .map(|e| {
+                assert_eq!(e.code(), LineErrorCode::SchemaPolicyViolation);
+                let LineError::Write { source, line } = e else {
+                    panic!("unexpected error {e}");
+                };
+                (*line, source.policy_violation().unwrap().rule())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rules,
+            [
+                (2, PolicyRule::AllowedTags),
+                (3, PolicyRule::RequiredTags),
+                (4, PolicyRule::FieldType),
+                (6, PolicyRule::MaxColumns),
+            ]
+        );
+        assert_eq!(
+            res.errors[0].to_string(),
+            "error writing line 2 (1-based): Could not insert column: \
+             schema policy violation (allowed_tags): tag 'pod' is not allowed"
+        );
+
+        // Tables without a policy are unrestricted
+        let (batches, _) = converter.finish().unwrap();
+        assert_batches_eq!(
+            &[
+                "+------+--------+--------------------------------+-------+",
+                "| host | region | time                           | usage |",
+                "+------+--------+--------------------------------+-------+",
+                "| a    |        | 1970-01-01T00:00:00.000000001Z | 1.0   |",
+                "| c    | r      | 1970-01-01T00:00:00.000000005Z | 5.0   |",
+                "+------+--------+--------------------------------+-------+",
+            ],
+            &[batches["cpu"]
+                .clone()
+                .try_into_arrow(Projection::All)
+                .unwrap()]
+        );
+        assert_eq!(batches["mem"].rows(), 1);
+    }
+
+    #[test]
+    fn test_schema_policy_table_columns() {
+        use mutable_batch::policy::{PolicyRule, TableSchemaPolicy};
+
+        let policy = SchemaPolicy::default()
+            .with_table("cpu", TableSchemaPolicy::default().with_max_columns(4));
+        let table_columns = HashMap::from([(
+            "cpu".to_owned(),
+            ["host", "region", "time"].map(ToOwned::to_owned).into(),
+        )]);
+
+        // The limit applies to the columns of the table, not only those written
+        let lp = "cpu,host=a usage=1.0 1
+cpu,host=a idle=2.0 2
+cpu,host=b,region=r usage=3.0 3";
+
+        let mut converter = LinesConverter::new(0)
+            .with_schema_policy(policy)
+            .with_table_columns(table_columns);
+        let res = converter.write_lp_partial(lp);
+        assert_eq!(res.accepted_lines, 2);
+        assert_eq!(res.rejected_lines, 1);
+        assert_matches!(
+            &res.errors[0],
+            LineError::Write { line: 2, source } if source.policy_violation().unwrap().rule() == PolicyRule::MaxColumns
+        );
+    }
+
+    #[test]
+    fn test_timestamp_policy() {
+        const SECOND: i64 = 1_000_000_000;
+        let now = 1_000 * SECOND;
+        let lp = format!(
+            "cpu val=1i {}
+cpu val=2i {}
+cpu val=3i {}
+cpu val=4i",
+            now - 10 * SECOND + 5,
+            now - 60 * SECOND,
+            now + 120 * SECOND,
+        );
+        let policy = TimestampPolicy::default()
+            .with_truncation(Duration::from_secs(1))
+            .with_max_age(Duration::from_secs(30))
+            .with_max_future(Duration::from_secs(60));
+
+        let mut converter = LinesConverter::new(now).with_timestamp_policy(policy);
+        let res = converter.write_lp_partial(&lp);
+        assert_eq!(res.accepted_lines, 2);
+        let got = res
+            .errors
+            .iter()
+            .map(|e| (e.line(), e.code()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            got,
+            [
+                (2, LineErrorCode::TimestampTooOld),
+                (3, LineErrorCode::TimestampTooFarInFuture),
+            ]
+        );
+        assert_matches!(
+            &res.errors[0],
+            LineError::TimestampTooOld { timestamp, min, .. } => {
+                assert_eq!(*timestamp, now - 60 * SECOND);
+                assert_eq!(*min, now - 30 * SECOND);
+            }
+        );
+
+        let (batches, stats) = converter.finish().unwrap();
+        assert_eq!(stats.num_lines, 2);
+        assert_eq!(stats.num_rejected_lines, 2);
+        assert_eq!(stats.num_truncated_timestamps, 1);
+        assert_eq!(stats.num_too_old_timestamps, 1);
+        assert_eq!(stats.num_future_timestamps, 1);
+        assert_eq!(stats.num_default_time_substitutions, 0);
+        assert_eq!(
+            batches["cpu"].timestamp_summary().unwrap().stats.min,
+            Some(now - 10 * SECOND)
+        );
+
+        // Out of range timestamps are replaced with the default time
+        let mut converter = LinesConverter::new(now)
+            .with_timestamp_policy(policy.with_out_of_range(OutOfRangeTimestamp::UseDefaultTime));
+        let res = converter.write_lp_partial(&lp);
+        assert!(!res.is_partial());
+        assert_eq!(res.accepted_lines, 4);
+
+        let (batches, stats) = converter.finish().unwrap();
+        assert_eq!(stats.num_rejected_lines, 0);
+        assert_eq!(stats.num_truncated_timestamps, 1);
+        assert_eq!(stats.num_too_old_timestamps, 1);
+        assert_eq!(stats.num_future_timestamps, 1);
+        assert_eq!(stats.num_default_time_substitutions, 2);
+
+        let rb = batches["cpu"]
+            .clone()
+            .try_into_arrow(Projection::All)
+            .unwrap();
+        assert_batches_eq!(
+            [
+                "+----------------------+-----+",
+                "| time                 | val |",
+                "+----------------------+-----+",
+                "| 1970-01-01T00:16:30Z | 1   |",
+                "| 1970-01-01T00:16:40Z | 2   |",
+                "| 1970-01-01T00:16:40Z | 3   |",
+                "| 1970-01-01T00:16:40Z | 4   |",
+                "+----------------------+-----+",
+            ],
+            &[rb]
+        );
+    }
+
+    #[test]
+    fn test_timestamp_policy_truncates_before_range_check() {
+        const SECOND: i64 = 1_000_000_000;
+        let now = 1_000 * SECOND + SECOND / 2;
+        let lp = format!(
+            "cpu val=1i {}
+cpu val=2i {}
+cpu val=3i",
+            now + SECOND / 5,
+            now - 30 * SECOND + SECOND / 5,
+        );
+        let policy = TimestampPolicy::default()
+            .with_truncation(Duration::from_secs(1))
+            .with_max_age(Duration::from_secs(30))
+            .with_max_future(Duration::ZERO);
+
+        // The first timestamp is only in range once truncated, and the second
+        // is only out of range once truncated
+        let mut converter = LinesConverter::new(now).with_timestamp_policy(policy);
+        let res = converter.write_lp_partial(&lp);
+        assert_eq!(res.accepted_lines, 2);
+        assert_matches!(
+            &res.errors[..],
+            [LineError::TimestampTooOld { line: 2, timestamp, min }] => {
+                assert_eq!(*timestamp, 970 * SECOND);
+                assert_eq!(*min, now - 30 * SECOND);
+            }
+        );
+
+        // The default time of the line without a timestamp is truncated too
+        let (batches, stats) = converter.finish().unwrap();
+        assert_eq!(stats.num_truncated_timestamps, 2);
+        let summary = batches["cpu"].timestamp_summary().unwrap().stats;
+        assert_eq!(summary.min, Some(1_000 * SECOND));
+        assert_eq!(summary.max, Some(1_000 * SECOND));
+
+        // as is the default time substituted for an out of range timestamp
+        let mut converter = LinesConverter::new(now)
+            .with_timestamp_policy(policy.with_out_of_range(OutOfRangeTimestamp::UseDefaultTime));
+        let res = converter.write_lp_partial(&lp);
+        assert_eq!(res.accepted_lines, 3);
+
+        let (batches, stats) = converter.finish().unwrap();
+        assert_eq!(stats.num_truncated_timestamps, 3);
+        assert_eq!(stats.num_default_time_substitutions, 1);
+        let summary = batches["cpu"].timestamp_summary().unwrap().stats;
+        assert_eq!(summary.min, Some(1_000 * SECOND));
+        assert_eq!(summary.max, Some(1_000 * SECOND));
+    }
+
+    #[test]
+    fn test_timestamp_policy_base() {
+        let policy = TimestampPolicy::default().with_truncation(Duration::from_secs(60));
+        let mut converter = LinesConverter::new(0)
+            .with_timestamp_base(1_000_000_000)
+            .with_timestamp_policy(policy);
+        converter.write_lp("cpu val=1i 119\ncpu val=2i -1").unwrap();
+
+        let (batches, stats) = converter.finish().unwrap();
+        assert_eq!(stats.num_truncated_timestamps, 2);
+        let summary = batches["cpu"].timestamp_summary().unwrap().stats;
+        assert_eq!(summary.min, Some(-60_000_000_000));
+        assert_eq!(summary.max, Some(60_000_000_000));
+    }
+
+    #[test]
+    fn test_field_families() {
+        let lp = r#"cpu,host=a sensor::temp=21.5,sensor::hum=3i,load=2 1
+cpu,host=b my\ sensor::temp=20.0,load=1 2
+cpu,host=c sensor::temp="bananas" 3"#;
+
+        let mut converter = LinesConverter::new(0).with_field_families(true);
+        let res = converter.write_lp_partial(lp);
+        assert_eq!(res.accepted_lines, 2);
+        assert_matches!(
+            &res.errors[..],
+            [LineError::Write {
+                line: 3,
+                source: LineWriteError::MutableBatch { .. },
+            }]
+        );
+
+        let (batches, _) = converter.finish().unwrap();
+        assert_batches_eq!(
+            [
+                "+------+------+-----------------+-------------+--------------+--------------------------------+",
+                "| host | load | my sensor::temp | sensor::hum | sensor::temp | time                           |",
+                "+------+------+-----------------+-------------+--------------+--------------------------------+",
+                "| a    | 2.0  |                 | 3           | 21.5         | 1970-01-01T00:00:00.000000001Z |",
+                "| b    | 1.0  | 20.0            |             |              | 1970-01-01T00:00:00.000000002Z |",
+                "+------+------+-----------------+-------------+--------------+--------------------------------+",
+            ],
+            &[batches["cpu"]
+                .clone()
+                .try_into_arrow(Projection::All)
+                .unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_write_lp_chunk() {
+        let lp = "cpu,host=a val=1i,str=\"multi\nline\" 1
+
+# comment
+cpu,host=b val=2i 2
+cpu,host=c val=bananas 3
+mem,host=ä free=3i 4";
+
+        let mut converter = LinesConverter::new(0);
+        assert_eq!(converter.write_lp_partial(lp).rejected_lines, 1);
+        let (want, _) = converter.finish().unwrap();
+
+        for chunk_size in [1, 2, 7, lp.len()] {
+            let mut converter = LinesConverter::new(0);
+            let mut errors = vec![];
+            for chunk in lp.as_bytes().chunks(chunk_size) {
+                let res = converter.write_lp_chunk(Bytes::copy_from_slice(chunk));
+                errors.extend(res.errors.iter().map(|e| (e.line(), e.code())));
+            }
+            let res = converter.finish_lp_chunks();
+            errors.extend(res.errors.iter().map(|e| (e.line(), e.code())));
+            assert_eq!(errors, [(3, LineErrorCode::InvalidLineProtocol)]);
+
+            let (got, stats) = converter.finish().unwrap();
+            assert_eq!(stats.num_lines, 3);
+            assert_eq!(stats.num_rejected_lines, 1);
+            for table in ["cpu", "mem"] {
+                assert_eq!(
+                    got[table].clone().try_into_arrow(Projection::All).unwrap(),
+                    want[table].clone().try_into_arrow(Projection::All).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_write_lp_chunk_invalid_utf8() {
+        let mut converter = LinesConverter::new(0);
+        let res = converter.write_lp_chunk(Bytes::from_static(b"cpu val=1i 1\ncpu,ho"));
+        assert_eq!(res.accepted_lines, 1);
+        let res = converter.write_lp_chunk(Bytes::from_static(b"st=\xff val=2i 2\ncpu val=3i 3"));
+        assert_matches!(&res.errors[..], [LineError::InvalidUtf8 { line: 2, .. }]);
+        let res = converter.finish_lp_chunks();
+        assert!(!res.is_partial());
+        assert_eq!(res.accepted_lines, 1);
+
+        let (batches, stats) = converter.finish().unwrap();
+        assert_eq!(batches["cpu"].rows(), 2);
+        assert_eq!(stats.num_rejected_lines, 1);
+    }
+
+    #[test]
+    fn test_write_lp_chunk_invalid_utf8_line_numbers() {
+        // Empty and comment lines are not numbered, whether or not they are
+        // valid UTF-8
+        let lp: &[u8] =
+            b"cpu val=1i 1\n\n# comment \xff\n  \t\ncpu,host=\xff val=2i 2\ncpu val=bananas 3";
+
+        let mut converter = LinesConverter::new(0);
+        let mut res = converter.write_lp_chunk(Bytes::from_static(lp));
+        let end = converter.finish_lp_chunks();
+        res.errors.extend(end.errors);
+        assert_eq!(
+            res.errors
+                .iter()
+                .map(|e| (e.line(), e.code()))
+                .collect::<Vec<_>>(),
+            [
+                (2, LineErrorCode::InvalidLineProtocol),
+                (3, LineErrorCode::InvalidLineProtocol),
+            ]
+        );
+
+        // The same numbering as the valid UTF-8 payload
+        let mut converter = LinesConverter::new(0);
+        let res = converter.write_lp_partial(
+            "cpu val=1i 1\n\n# comment\n  \t\ncpu,host=a val=2i 2\ncpu val=bananas 3",
+        );
+        assert_eq!(res.errors.iter().map(|e| e.line()).collect::<Vec<_>>(), [3]);
+    }
 }
@@ -0,0 +1,691 @@
+//! Code to decode [`MutableBatch`] from pbdata protobuf
+
+use arrow_util::bitset::iter_set_positions;
+use data_types::TableId;
+use generated_types::influxdata::pbdata::v1::{
+    Column as PbColumn, DatabaseBatch, InternedStrings, PackedStrings, TableBatch,
+    column::{SemanticType, Values as PbValues},
+};
+use hashbrown::{HashMap, HashSet};
+use mutable_batch::{
+    MutableBatch,
+    writer::{ColumnInsertValidator, Writer},
+};
+use schema::TIME_COLUMN_NAME;
+use snafu::{OptionExt, ResultExt, Snafu, ensure};
+
+/// Error type for a conversion attempt on a pbdata [`TableBatch`]
+#[derive(Debug, Snafu)]
+#[expect(missing_docs)]
+pub enum Error {
+    #[snafu(display("error writing column {}: {}", column, source))]
+    Write {
+        source: mutable_batch::writer::Error,
+        column: String,
+    },
+
+    #[snafu(display("duplicate column name: {}", column))]
+    DuplicateColumnName { column: String },
+
+    #[snafu(display("table batch must contain a time column"))]
+    MissingTime,
+
+    #[snafu(display("time column must not contain nulls"))]
+    NullTime,
+
+    #[snafu(display(
+        "column {} has semantic type time, only the {} column may be a timestamp",
+        column,
+        TIME_COLUMN_NAME
+    ))]
+    InvalidTimeColumn { column: String },
+
+    #[snafu(display("column {} has no values", column))]
+    EmptyColumn { column: String },
+
+    #[snafu(display("column {} has an unspecified semantic type", column))]
+    UnspecifiedType { column: String },
+
+    #[snafu(display("column {} has an unsupported or ambiguous value type", column))]
+    InvalidType { column: String },
+
+    #[snafu(display(
+        "column {} has {} non-null rows but {} values",
+        column,
+        expected,
+        actual
+    ))]
+    ValueCount {
+        column: String,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[snafu(display("column {} has invalid string offsets", column))]
+    InvalidStringOffsets { column: String },
+}
+
+/// Result type for pbdata conversion
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Decodes a [`DatabaseBatch`] to a map of [`MutableBatch`] keyed by [`TableId`]
+///
+/// The data for a table may be split across multiple [`TableBatch`], all of
+/// which are written to the same [`MutableBatch`].
+pub fn decode_database_batch(
+    database_batch: &DatabaseBatch,
+) -> Result<HashMap<TableId, MutableBatch>> {
+    let mut id_to_data = HashMap::with_capacity(database_batch.table_batches.len());
+
+    for table_batch in &database_batch.table_batches {
+        let batch = id_to_data
+            .entry(TableId::new(table_batch.table_id))
+            .or_insert_with(MutableBatch::new);
+
+        write_table_batch(batch, table_batch)?;
+    }
+
+    // Drop any tables that received no rows.
+    id_to_data.retain(|_, batch| batch.rows() > 0);
+
+    Ok(id_to_data)
+}
+
+/// Writes the provided [`TableBatch`] to a [`MutableBatch`] on error any changes made
+/// to `batch` are reverted
+///
+/// As with `mutable_batch_lp::write_line`, a column may only appear once per
+/// batch and must not conflict with the type of an existing column of the
+/// same name in `batch`.
+pub fn write_table_batch(batch: &mut MutableBatch, table_batch: &TableBatch) -> Result<()> {
+    let to_insert = table_batch.row_count as usize;
+    if to_insert == 0 {
+        return Ok(());
+    }
+
+    // Verify columns are unique and that the time column is present.
+    let mut columns = HashSet::with_capacity(table_batch.columns.len());
+    for col in &table_batch.columns {
+        ensure!(
+            columns.insert(col.column_name.as_str()),
+            DuplicateColumnNameSnafu {
+                column: &col.column_name
+            }
+        );
+    }
+    ensure!(columns.contains(TIME_COLUMN_NAME), MissingTimeSnafu);
+
+    let mut writer = Writer::new(batch, to_insert);
+    for column in &table_batch.columns {
+        write_column(&mut writer, column, to_insert)?;
+    }
+
+    writer.commit();
+    Ok(())
+}
+
+fn write_column<T>(writer: &mut Writer<'_, T>, column: &PbColumn, to_insert: usize) -> Result<()>
+where
+    T: ColumnInsertValidator,
+{
+    let name = column.column_name.as_str();
+    let semantic_type = column.semantic_type();
+
+    // Count the non-null rows without allocating a validity mask, as the
+    // row count is not bounded by the size of the message.
+    let nulls = iter_set_positions(&column.null_mask)
+        .take_while(|idx| *idx < to_insert)
+        .count();
+    let expected = to_insert - nulls;
+
+    if semantic_type == SemanticType::Time || name == TIME_COLUMN_NAME {
+        ensure!(
+            semantic_type == SemanticType::Time && name == TIME_COLUMN_NAME,
+            InvalidTimeColumnSnafu { column: name }
+        );
+        ensure!(expected == to_insert, NullTimeSnafu);
+    } else if expected == 0 {
+        // A column containing only nulls carries no information - it is
+        // padded with nulls in the same way as an omitted column.
+        return Ok(());
+    }
+
+    let values = column
+        .values
+        .as_ref()
+        .context(EmptyColumnSnafu { column: name })?;
+
+    let actual = match (semantic_type, value_types(values).as_slice()) {
+        (SemanticType::Unspecified, _) => return UnspecifiedTypeSnafu { column: name }.fail(),
+        (SemanticType::Time, _) => values.i64_values.len(),
+        (_, [value_type]) => value_count(values, *value_type),
+        _ => return InvalidTypeSnafu { column: name }.fail(),
+    };
+    check_value_count(name, expected, actual)?;
+
+    let valid_mask = compute_valid_mask(&column.null_mask, to_insert);
+    let valid_mask = valid_mask.as_deref();
+
+    match semantic_type {
+        SemanticType::Unspecified => unreachable!("unspecified semantic type checked above"),
+        SemanticType::Time => {
+            ensure!(
+                value_types(values) == [ValueType::I64],
+                InvalidTypeSnafu { column: name }
+            );
+            writer
+                .write_time(name, values.i64_values.iter().copied())
+                .context(WriteSnafu { column: name })
+        }
+        SemanticType::Tag => match value_types(values).as_slice() {
+            [ValueType::String] => writer.write_tag(
+                name,
+                valid_mask,
+                values.string_values.iter().map(|v| v.as_str()),
+            ),
+            [ValueType::PackedString] => {
+                let packed = values.packed_string_values.as_ref().unwrap();
+                let strings = unpack_strings(name, packed)?;
+                writer.write_tag(name, valid_mask, strings.into_iter())
+            }
+            [ValueType::InternedString] => {
+                let interned = values.interned_string_values.as_ref().unwrap();
+                let dictionary = interned_dictionary(name, interned)?;
+                writer.write_tag_dict(
+                    name,
+                    valid_mask,
+                    interned.values.iter().map(|v| *v as usize),
+                    dictionary.into_iter(),
+                )
+            }
+            _ => return InvalidTypeSnafu { column: name }.fail(),
+        }
+        .context(WriteSnafu { column: name }),
+        SemanticType::Field => match value_types(values).as_slice() {
+            [ValueType::I64] => {
+                writer.write_i64(name, valid_mask, values.i64_values.iter().copied())
+            }
+            [ValueType::F64] => {
+                writer.write_f64(name, valid_mask, values.f64_values.iter().copied())
+            }
+            [ValueType::U64] => {
+                writer.write_u64(name, valid_mask, values.u64_values.iter().copied())
+            }
+            [ValueType::Bool] => {
+                writer.write_bool(name, valid_mask, values.bool_values.iter().copied())
+            }
+            [ValueType::String] => writer.write_string(
+                name,
+                valid_mask,
+                values.string_values.iter().map(|v| v.as_str()),
+            ),
+            [ValueType::PackedString] => {
+                let packed = values.packed_string_values.as_ref().unwrap();
+                let strings = unpack_strings(name, packed)?;
+                writer.write_string(name, valid_mask, strings.into_iter())
+            }
+            [ValueType::InternedString] => {
+                let interned = values.interned_string_values.as_ref().unwrap();
+                let dictionary = interned_dictionary(name, interned)?;
+                let strings = interned
+                    .values
+                    .iter()
+                    .map(|v| {
+                        dictionary
+                            .get(*v as usize)
+                            .copied()
+                            .context(InvalidStringOffsetsSnafu { column: name })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                writer.write_string(name, valid_mask, strings.into_iter())
+            }
+            _ => return InvalidTypeSnafu { column: name }.fail(),
+        }
+        .context(WriteSnafu { column: name }),
+    }
+}
+
+/// The value encodings of a pbdata column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueType {
+    I64,
+    F64,
+    U64,
+    String,
+    Bool,
+    Bytes,
+    PackedString,
+    InternedString,
+}
+
+/// Returns the value encodings that contain data, of which exactly one is
+/// expected.
+fn value_types(values: &PbValues) -> Vec<ValueType> {
+    [
+        (!values.i64_values.is_empty(), ValueType::I64),
+        (!values.f64_values.is_empty(), ValueType::F64),
+        (!values.u64_values.is_empty(), ValueType::U64),
+        (!values.string_values.is_empty(), ValueType::String),
+        (!values.bool_values.is_empty(), ValueType::Bool),
+        (!values.bytes_values.is_empty(), ValueType::Bytes),
+        (
+            values
+                .packed_string_values
+                .as_ref()
+                .is_some_and(|v| v.offsets.len() > 1),
+            ValueType::PackedString,
+        ),
+        (
+            values
+                .interned_string_values
+                .as_ref()
+                .is_some_and(|v| !v.values.is_empty()),
+            ValueType::InternedString,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(present, t)| present.then_some(t))
+    .collect()
+}
+
+/// Returns the number of values of `value_type` in `values`.
+fn value_count(values: &PbValues, value_type: ValueType) -> usize {
+    match value_type {
+        ValueType::I64 => values.i64_values.len(),
+        ValueType::F64 => values.f64_values.len(),
+        ValueType::U64 => values.u64_values.len(),
+        ValueType::String => values.string_values.len(),
+        ValueType::Bool => values.bool_values.len(),
+        ValueType::Bytes => values.bytes_values.len(),
+        ValueType::PackedString => values
+            .packed_string_values
+            .as_ref()
+            .map_or(0, |v| v.offsets.len().saturating_sub(1)),
+        ValueType::InternedString => values
+            .interned_string_values
+            .as_ref()
+            .map_or(0, |v| v.values.len()),
+    }
+}
+
+fn check_value_count(column: &str, expected: usize, actual: usize) -> Result<()> {
+    ensure!(
+        expected == actual,
+        ValueCountSnafu {
+            column,
+            expected,
+            actual
+        }
+    );
+    Ok(())
+}
+
+/// Returns the strings of a [`PackedStrings`] message.
+fn unpack_strings<'a>(column: &str, packed: &'a PackedStrings) -> Result<Vec<&'a str>> {
+    packed
+        .offsets
+        .windows(2)
+        .map(|w| {
+            packed
+                .values
+                .get(w[0] as usize..w[1] as usize)
+                .context(InvalidStringOffsetsSnafu { column })
+        })
+        .collect()
+}
+
+/// Returns the dictionary values of an [`InternedStrings`] message.
+fn interned_dictionary<'a>(column: &str, interned: &'a InternedStrings) -> Result<Vec<&'a str>> {
+    match interned.dictionary.as_ref() {
+        Some(dictionary) => unpack_strings(column, dictionary),
+        None => Ok(vec![]),
+    }
+}
+
+/// Converts a potentially truncated null mask to a valid mask
+fn compute_valid_mask(null_mask: &[u8], to_insert: usize) -> Option<Vec<u8>> {
+    if null_mask.is_empty() || null_mask.iter().all(|x| *x == 0) {
+        return None;
+    }
+
+    // The expected length of the validity mask
+    let expected_len = to_insert.div_ceil(8);
+
+    // The number of bits over the byte boundary
+    let overrun = to_insert & 7;
+
+    let mut mask: Vec<_> = (0..expected_len)
+        .map(|x| match null_mask.get(x) {
+            Some(v) => !*v,
+            None => 0xFF,
+        })
+        .collect();
+
+    if overrun != 0 {
+        *mask.last_mut().unwrap() &= (1 << overrun) - 1;
+    }
+
+    Some(mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_util::assert_batches_eq;
+    use assert_matches::assert_matches;
+    use schema::Projection;
+
+    fn column(name: &str, semantic_type: SemanticType, values: PbValues) -> PbColumn {
+        PbColumn {
+            column_name: name.to_string(),
+            semantic_type: semantic_type as _,
+            values: Some(values),
+            null_mask: vec![],
+        }
+    }
+
+    fn with_nulls(mut column: PbColumn, null_mask: &[u8]) -> PbColumn {
+        column.null_mask = null_mask.to_vec();
+        column
+    }
+
+    fn time_column(values: &[i64]) -> PbColumn {
+        column(
+            TIME_COLUMN_NAME,
+            SemanticType::Time,
+            PbValues {
+                i64_values: values.to_vec(),
+                ..Default::default()
+            },
+        )
+    }
+
+    fn packed(values: &[&str]) -> PackedStrings {
+        let mut offsets = vec![0];
+        let mut s = String::new();
+        for v in values {
+            s.push_str(v);
+            offsets.push(s.len() as u32);
+        }
+        PackedStrings { values: s, offsets }
+    }
+
+    #[test]
+    fn test_basic() {
+        let mut table_batch = TableBatch {
+            table_id: 42,
+            columns: vec![
+                with_nulls(
+                    column(
+                        "tag1",
+                        SemanticType::Tag,
+                        PbValues {
+                            string_values: vec![
+                                "v1".to_string(),
+                                "v1".to_string(),
+                                "v2".to_string(),
+                            ],
+                            ..Default::default()
+                        },
+                    ),
+                    &[0b00000100],
+                ),
+                column(
+                    "tag2",
+                    SemanticType::Tag,
+                    PbValues {
+                        interned_string_values: Some(InternedStrings {
+                            dictionary: Some(packed(&["v1", "v2"])),
+                            values: vec![1, 0, 0, 1],
+                        }),
+                        ..Default::default()
+                    },
+                ),
+                with_nulls(
+                    column(
+                        "f64",
+                        SemanticType::Field,
+                        PbValues {
+                            f64_values: vec![3., 5.],
+                            ..Default::default()
+                        },
+                    ),
+                    &[0b00001010],
+                ),
+                with_nulls(
+                    column(
+                        "str",
+                        SemanticType::Field,
+                        PbValues {
+                            packed_string_values: Some(packed(&["hello", "", "world"])),
+                            ..Default::default()
+                        },
+                    ),
+                    &[0b00000001],
+                ),
+                column(
+                    "b",
+                    SemanticType::Field,
+                    PbValues {
+                        bool_values: vec![true, false, true, false],
+                        ..Default::default()
+                    },
+                ),
+                time_column(&[1, 2, 3, 4]),
+            ],
+            row_count: 4,
+        };
+
+        let mut batch = MutableBatch::new();
+        write_table_batch(&mut batch, &table_batch).unwrap();
+
+        let expected = &[
+            "+-------+-----+-------+------+------+--------------------------------+",
+            "| b     | f64 | str   | tag1 | tag2 | time                           |",
+            "+-------+-----+-------+------+------+--------------------------------+",
+            "| true  | 3.0 |       | v1   | v2   | 1970-01-01T00:00:00.000000001Z |",
+            "| false |     | hello | v1   | v1   | 1970-01-01T00:00:00.000000002Z |",
+            "| true  | 5.0 |       |      | v1   | 1970-01-01T00:00:00.000000003Z |",
+            "| false |     | world | v2   | v2   | 1970-01-01T00:00:00.000000004Z |",
+            "+-------+-----+-------+------+------+--------------------------------+",
+        ];
+        assert_batches_eq!(
+            expected,
+            &[batch.clone().try_into_arrow(Projection::All).unwrap()]
+        );
+
+        // A failed write leaves the batch unchanged
+        table_batch.columns[0].semantic_type = SemanticType::Field as _;
+        assert_matches!(
+            write_table_batch(&mut batch, &table_batch),
+            Err(Error::Write { column, .. }) if column == "tag1"
+        );
+        assert_batches_eq!(expected, &[batch.try_into_arrow(Projection::All).unwrap()]);
+    }
+
+    #[test]
+    fn test_database_batch() {
+        let table_batch = |table_id, times: &[i64]| TableBatch {
+            table_id,
+            columns: vec![
+                column(
+                    "v",
+                    SemanticType::Field,
+                    PbValues {
+                        i64_values: times.iter().map(|t| t * 10).collect(),
+                        ..Default::default()
+                    },
+                ),
+                time_column(times),
+            ],
+            row_count: times.len() as u32,
+        };
+
+        let database_batch = DatabaseBatch {
+            database_id: 1,
+            partition_key: String::new(),
+            table_batches: vec![
+                table_batch(1, &[1, 2]),
+                table_batch(2, &[3]),
+                table_batch(1, &[4]),
+                table_batch(3, &[]),
+            ],
+        };
+
+        let batches = decode_database_batch(&database_batch).unwrap();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[&TableId::new(1)].rows(), 3);
+        assert_eq!(batches[&TableId::new(2)].rows(), 1);
+    }
+
+    #[test]
+    fn test_errors() {
+        let write = |columns: Vec<PbColumn>, row_count: u32| {
+            let mut batch = MutableBatch::new();
+            write_table_batch(
+                &mut batch,
+                &TableBatch {
+                    table_id: 1,
+                    columns,
+                    row_count,
+                },
+            )
+        };
+        let i64_field = |name: &str, values: &[i64]| {
+            column(
+                name,
+                SemanticType::Field,
+                PbValues {
+                    i64_values: values.to_vec(),
+                    ..Default::default()
+                },
+            )
+        };
+
+        assert_matches!(
+            write(vec![i64_field("v", &[1])], 1),
+            Err(Error::MissingTime)
+        );
+
+        assert_matches!(
+            write(vec![i64_field("v", &[1]), i64_field("v", &[1]), time_column(&[1])], 1),
+            Err(Error::DuplicateColumnName { column }) if column == "v"
+        );
+
+        assert_matches!(
+            write(vec![with_nulls(time_column(&[1]), &[0b10])], 2),
+            Err(Error::NullTime)
+        );
+
+        assert_matches!(
+            write(vec![i64_field(TIME_COLUMN_NAME, &[1])], 1),
+            Err(Error::InvalidTimeColumn { column }) if column == TIME_COLUMN_NAME
+        );
+
+        assert_matches!(
+            write(vec![i64_field("v", &[1, 2]), time_column(&[1])], 1),
+            Err(Error::ValueCount { column, expected: 1, actual: 2 }) if column == "v"
+        );
+
+        assert_matches!(
+            write(vec![i64_field("v", &[1]), time_column(&[1, 2])], 2),
+            Err(Error::ValueCount { column, expected: 2, actual: 1 }) if column == "v"
+        );
+
+        // The row count is checked against the values before the validity
+        // mask is allocated
+        assert_matches!(
+            write(vec![with_nulls(i64_field("v", &[1]), &[0b10]), time_column(&[1])], u32::MAX),
+            Err(Error::ValueCount { column, expected, actual: 1 })
+                if column == "v" && expected == u32::MAX as usize - 1
+        );
+
+        let mut ambiguous = i64_field("v", &[1]);
+        ambiguous.values.as_mut().unwrap().f64_values = vec![1.0];
+        assert_matches!(
+            write(vec![ambiguous, time_column(&[1])], 1),
+            Err(Error::InvalidType { column }) if column == "v"
+        );
+
+        let mut unspecified = i64_field("v", &[1]);
+        unspecified.semantic_type = SemanticType::Unspecified as _;
+        assert_matches!(
+            write(vec![unspecified, time_column(&[1])], 1),
+            Err(Error::UnspecifiedType { column }) if column == "v"
+        );
+
+        let tag = column(
+            "t",
+            SemanticType::Tag,
+            PbValues {
+                i64_values: vec![1],
+                ..Default::default()
+            },
+        );
+        assert_matches!(
+            write(vec![tag, time_column(&[1])], 1),
+            Err(Error::InvalidType { column }) if column == "t"
+        );
+
+        let tag = column(
+            "t",
+            SemanticType::Tag,
+            PbValues {
+                interned_string_values: Some(InternedStrings {
+                    dictionary: Some(packed(&["a"])),
+                    values: vec![1],
+                }),
+                ..Default::default()
+            },
+        );
+        assert_matches!(
+            write(vec![tag, time_column(&[1])], 1),
+            Err(Error::Write { column, source: mutable_batch::writer::Error::KeyNotFound { key: 1 } }) if column == "t"
+        );
+    }
+
+    #[test]
+    fn test_schema_conflict() {
+        let (_, mut batch) = mutable_batch_lp::test_helpers::lp_to_mutable_batch("m,t=a v=1i 1");
+
+        let table_batch = TableBatch {
+            table_id: 1,
+            columns: vec![
+                column(
+                    "v",
+                    SemanticType::Field,
+                    PbValues {
+                        f64_values: vec![1.0],
+                        ..Default::default()
+                    },
+                ),
+                time_column(&[2]),
+            ],
+            row_count: 1,
+        };
+
+        assert_matches!(
+            write_table_batch(&mut batch, &table_batch),
+            Err(Error::Write {
+                column,
+                source: mutable_batch::writer::Error::TypeMismatch { .. }
+            }) if column == "v"
+        );
+        assert_eq!(batch.rows(), 1);
+    }
+
+    #[test]
+    fn test_compute_valid_mask() {
+        assert_eq!(compute_valid_mask(&[], 10), None);
+        assert_eq!(compute_valid_mask(&[0, 0], 10), None);
+        assert_eq!(
+            compute_valid_mask(&[0b00100000, 0b00000001], 10).unwrap(),
+            [0b11011111, 0b00000010]
+        );
+        // Trailing off bits may be omitted
+        assert_eq!(
+            compute_valid_mask(&[0b00000001], 10).unwrap(),
+            [0b11111110, 0b00000011]
+        );
+    }
+}
@@ -0,0 +1,297 @@
+//! Code to encode [`MutableBatch`] as pbdata protobuf
+
+use arrow_util::bitset::BitSet;
+use data_types::{NamespaceId, TableId};
+use generated_types::influxdata::pbdata::v1::{
+    Column as PbColumn, DatabaseBatch, InternedStrings, PackedStrings, TableBatch,
+    column::{SemanticType, Values as PbValues},
+};
+use mutable_batch::{
+    MutableBatch,
+    column::{Column, ColumnData},
+};
+use schema::InfluxColumnType;
+use snafu::{OptionExt, Snafu};
+
+/// Error type for encoding a [`MutableBatch`] as pbdata
+#[derive(Debug, Snafu)]
+#[expect(missing_docs)]
+pub enum Error {
+    #[snafu(display(
+        "table {} has {} rows, more than a pbdata table batch can hold",
+        table_id,
+        rows
+    ))]
+    TooManyRows { table_id: TableId, rows: usize },
+}
+
+/// Result type for pbdata encoding
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Convert the provided [`MutableBatch`] keyed by [`TableId`] to a
+/// [`DatabaseBatch`] for the namespace identified by `database_id`
+///
+/// The tables are encoded in ascending [`TableId`] order.
+pub fn encode_database_batch<'a>(
+    database_id: NamespaceId,
+    partition_key: impl Into<String>,
+    tables: impl IntoIterator<Item = (TableId, &'a MutableBatch)>,
+) -> Result<DatabaseBatch> {
+    let mut table_batches = tables
+        .into_iter()
+        .filter(|(_, batch)| batch.rows() > 0)
+        .map(|(table_id, batch)| encode_batch(table_id, batch))
+        .collect::<Result<Vec<_>>>()?;
+    table_batches.sort_unstable_by_key(|t| t.table_id);
+
+    Ok(DatabaseBatch {
+        database_id: database_id.get(),
+        partition_key: partition_key.into(),
+        table_batches,
+    })
+}
+
+/// Convert a [`MutableBatch`] to a [`TableBatch`]
+///
+/// Tag columns are encoded first, followed by field columns and then the time
+/// column, each group ordered by name. Columns that contain only nulls are
+/// omitted.
+///
+/// Returns an error if `batch` has more rows than fit in the `u32` row count
+/// of a [`TableBatch`].
+pub fn encode_batch(table_id: TableId, batch: &MutableBatch) -> Result<TableBatch> {
+    let row_count = row_count(table_id, batch.rows())?;
+
+    let mut columns = batch
+        .columns()
+        .filter(|(_, column)| column.valid_mask().count_ones() > 0)
+        .map(|(name, column)| encode_column(name, column))
+        .collect::<Vec<_>>();
+    columns.sort_unstable_by(|a, b| {
+        column_order(a.semantic_type())
+            .cmp(&column_order(b.semantic_type()))
+            .then_with(|| a.column_name.cmp(&b.column_name))
+    });
+
+    Ok(TableBatch {
+        table_id: table_id.get(),
+        columns,
+        row_count,
+    })
+}
+
+fn row_count(table_id: TableId, rows: usize) -> Result<u32> {
+    u32::try_from(rows)
+        .ok()
+        .context(TooManyRowsSnafu { table_id, rows })
+}
+
+fn column_order(semantic_type: SemanticType) -> u8 {
+    match semantic_type {
+        SemanticType::Tag => 0,
+        SemanticType::Field | SemanticType::Unspecified => 1,
+        SemanticType::Time => 2,
+    }
+}
+
+fn encode_column(column_name: &str, column: &Column) -> PbColumn {
+    let valid_mask = column.valid_mask();
+
+    let semantic_type = match column.influx_type() {
+        InfluxColumnType::Tag => SemanticType::Tag,
+        InfluxColumnType::Field(_) => SemanticType::Field,
+        InfluxColumnType::Timestamp => SemanticType::Time,
+    };
+
+    let values = match column.data() {
+        ColumnData::F64(data, _) => PbValues {
+            f64_values: iter_valid(valid_mask, data).copied().collect(),
+            ..Default::default()
+        },
+        ColumnData::I64(data, _) => PbValues {
+            i64_values: iter_valid(valid_mask, data).copied().collect(),
+            ..Default::default()
+        },
+        ColumnData::U64(data, _) => PbValues {
+            u64_values: iter_valid(valid_mask, data).copied().collect(),
+            ..Default::default()
+        },
+        ColumnData::Bool(data, _) => PbValues {
+            bool_values: valid_positions(valid_mask)
+                .map(|idx| data.get(idx))
+                .collect(),
+            ..Default::default()
+        },
+        ColumnData::String(data, _) => PbValues {
+            packed_string_values: Some(pack_strings(
+                valid_positions(valid_mask).map(|idx| data.get(idx).unwrap()),
+            )),
+            ..Default::default()
+        },
+        ColumnData::Tag(data, dictionary, _) => PbValues {
+            interned_string_values: Some(InternedStrings {
+                dictionary: Some(pack_strings(dictionary.values().iter())),
+                values: iter_valid(valid_mask, data).map(|v| *v as u32).collect(),
+            }),
+            ..Default::default()
+        },
+    };
+
+    PbColumn {
+        column_name: column_name.to_string(),
+        semantic_type: semantic_type as _,
+        values: Some(values),
+        null_mask: compute_null_mask(valid_mask),
+    }
+}
+
+/// Returns the row indexes that are set in `valid_mask`.
+fn valid_positions(valid_mask: &BitSet) -> impl Iterator<Item = usize> + '_ {
+    (0..valid_mask.len()).filter(|idx| valid_mask.get(*idx))
+}
+
+/// Returns the values of `data` that are set in `valid_mask`.
+fn iter_valid<'a, T>(valid_mask: &'a BitSet, data: &'a [T]) -> impl Iterator<Item = &'a T> + 'a {
+    valid_positions(valid_mask).map(move |idx| &data[idx])
+}
+
+fn pack_strings<'a>(values: impl Iterator<Item = &'a str>) -> PackedStrings {
+    let mut packed = PackedStrings {
+        values: String::new(),
+        offsets: vec![0],
+    };
+    for v in values {
+        packed.values.push_str(v);
+        packed.offsets.push(packed.values.len() as u32);
+    }
+    packed
+}
+
+/// Converts a valid mask to a null mask, omitting the mask entirely if no
+/// value is null.
+fn compute_null_mask(valid_mask: &BitSet) -> Vec<u8> {
+    if valid_mask.is_all_set() {
+        return vec![];
+    }
+
+    let mut null_mask: Vec<_> = valid_mask.bytes().iter().map(|v| !*v).collect();
+
+    // Clear the bits past the end of the column
+    let overrun = valid_mask.len() & 7;
+    if overrun != 0 {
+        *null_mask.last_mut().unwrap() &= (1 << overrun) - 1;
+    }
+
+    null_mask
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::{decode_database_batch, write_table_batch};
+    use arrow_util::assert_batches_eq;
+    use assert_matches::assert_matches;
+    use mutable_batch_lp::lines_to_batches;
+    use schema::Projection;
+
+    #[test]
+    fn test_encode_decode() {
+        let lp = r#"cpu,tag1=v1,tag2=v2 val=2i,s="foo" 0
+cpu,tag1=v4,tag2=v1 val=2i,b=true 0
+cpu,tag2=v2 val=3i,f=1.5 1
+cpu,tag1=v1,tag2=v2 u=22u 5
+cpu,tag1=v1 s="" 6"#;
+
+        let batch = lines_to_batches(lp, 0).unwrap().remove("cpu").unwrap();
+        let encoded = encode_batch(TableId::new(1), &batch).unwrap();
+
+        let names = encoded
+            .columns
+            .iter()
+            .map(|c| c.column_name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["tag1", "tag2", "b", "f", "s", "u", "val", "time"]);
+
+        let mut decoded = MutableBatch::new();
+        write_table_batch(&mut decoded, &encoded).unwrap();
+
+        let expected = [
+            "+------+-----+-----+------+------+--------------------------------+----+-----+",
+            "| b    | f   | s   | tag1 | tag2 | time                           | u  | val |",
+            "+------+-----+-----+------+------+--------------------------------+----+-----+",
+            "|      |     | foo | v1   | v2   | 1970-01-01T00:00:00Z           |    | 2   |",
+            "| true |     |     | v4   | v1   | 1970-01-01T00:00:00Z           |    | 2   |",
+            "|      | 1.5 |     |      | v2   | 1970-01-01T00:00:00.000000001Z |    | 3   |",
+            "|      |     |     | v1   | v2   | 1970-01-01T00:00:00.000000005Z | 22 |     |",
+            "|      |     |     | v1   |      | 1970-01-01T00:00:00.000000006Z |    |     |",
+            "+------+-----+-----+------+------+--------------------------------+----+-----+",
+        ];
+        assert_batches_eq!(
+            expected,
+            &[batch.clone().try_into_arrow(Projection::All).unwrap()]
+        );
+        assert_batches_eq!(
+            expected,
+            &[decoded.try_into_arrow(Projection::All).unwrap()]
+        );
+
+        // Empty strings are distinct from nulls
+        let s = encoded
+            .columns
+            .iter()
+            .find(|c| c.column_name == "s")
+            .unwrap();
+        assert_eq!(s.null_mask, [0b00001110]);
+        assert_eq!(
+            s.values.as_ref().unwrap().packed_string_values,
+            Some(PackedStrings {
+                values: "foo".to_string(),
+                offsets: vec![0, 3, 3]
+            })
+        );
+    }
+
+    #[test]
+    fn test_encode_decode_database_batch() {
+        let lp = "cpu,host=a usage=1.0 1\nmem,host=b free=2i 2\ncpu,host=c usage=3.0 3";
+        let batches = lines_to_batches(lp, 0).unwrap();
+
+        let tables = [
+            (TableId::new(2), &batches["cpu"]),
+            (TableId::new(1), &batches["mem"]),
+        ];
+        let encoded = encode_database_batch(NamespaceId::new(42), "", tables).unwrap();
+        assert_eq!(encoded.database_id, 42);
+        assert_eq!(
+            encoded
+                .table_batches
+                .iter()
+                .map(|t| (t.table_id, t.row_count))
+                .collect::<Vec<_>>(),
+            [(1, 1), (2, 2)]
+        );
+
+        let decoded = decode_database_batch(&encoded).unwrap();
+        assert_eq!(decoded.len(), 2);
+        for (table_id, batch) in tables {
+            assert_eq!(
+                decoded[&table_id]
+                    .clone()
+                    .try_into_arrow(Projection::All)
+                    .unwrap(),
+                batch.clone().try_into_arrow(Projection::All).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_row_count() {
+        let table_id = TableId::new(1);
+        assert_eq!(row_count(table_id, 42).unwrap(), 42);
+        assert_eq!(row_count(table_id, u32::MAX as usize).unwrap(), u32::MAX);
+        assert_matches!(
+            row_count(table_id, u32::MAX as usize + 1),
+            Err(Error::TooManyRows { rows, .. }) if rows == u32::MAX as usize + 1
+        );
+    }
+}
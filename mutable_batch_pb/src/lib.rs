@@ -0,0 +1,10 @@
+//! Code to convert between the InfluxDB protobuf data protocol (pbdata) and
+//! [`MutableBatch`](mutable_batch::MutableBatch)
+
+#![warn(missing_docs)]
+
+// Workaround for "unused crate" lint false positives.
+use workspace_hack as _;
+
+pub mod decode;
+pub mod encode;
@@ -710,6 +710,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sub_millisecond_interval() {
+        let udf = DateBinWallclockUDF::default();
+        let arr = Arc::new(
+            TimestampNanosecondArray::from(vec![
+                Some(MAYDAY + 123_456),
+                Some(MAYDAY + 129_999),
+                Some(MAYDAY + 130_000),
+            ])
+            .with_timezone("Europe/Paris"),
+        );
+        let args = vec![
+            ColumnarValue::Scalar(ScalarValue::IntervalMonthDayNano(Some(
+                IntervalMonthDayNano::new(0, 0, 10_000),
+            ))),
+            ColumnarValue::Array(arr),
+        ];
+        let arg_fields = arg_to_fields(&args);
+        let result = match udf
+            .invoke_with_args(ScalarFunctionArgs {
+                args,
+                arg_fields,
+                number_rows: 3,
+                return_field: return_field(DataType::Timestamp(
+                    TimeUnit::Nanosecond,
+                    Some(Arc::from("Europe/Paris")),
+                )),
+            })
+            .unwrap()
+        {
+            ColumnarValue::Array(arr) => as_timestamp_nanosecond_array(&arr).unwrap().clone(),
+            _ => panic!("Expected array value"),
+        };
+        assert_eq!(
+            result,
+            TimestampNanosecondArray::from(vec![
+                Some(MAYDAY + 120_000),
+                Some(MAYDAY + 120_000),
+                Some(MAYDAY + 130_000),
+            ])
+            .with_timezone("Europe/Paris")
+        );
+    }
+
     #[test]
     fn array_interval_error() {
         let udf = DateBinWallclockUDF::default();
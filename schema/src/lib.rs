@@ -929,8 +929,11 @@ impl Schema {
     }
 }
 
-/// Gets the influx type for a field
-pub(crate) fn get_influx_type(field: &ArrowField) -> Result<InfluxColumnType, Option<String>> {
+/// Gets the influx type for a field from its metadata.
+///
+/// Returns `Err(None)` if the field has no influx type metadata, or
+/// `Err(Some(metadata))` if the metadata is not a valid influx type.
+pub fn get_influx_type(field: &ArrowField) -> Result<InfluxColumnType, Option<String>> {
     let md = field
         .metadata()
         .get(COLUMN_METADATA_KEY)
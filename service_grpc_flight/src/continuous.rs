@@ -0,0 +1,173 @@
+//! Continuous queries subscribed to with `DoExchange`.
+use std::sync::Arc;
+
+use arrow_flight::FlightData;
+use data_types::TimestampRange;
+use futures::{Stream, StreamExt, future, stream};
+use generated_types::Status;
+use iox_time::TimeProvider;
+
+use crate::{Error, keep_alive::decode_schema, request::ContinuousQuery};
+
+/// Returns the windows of `time` of `query`, each yielded once it, and the
+/// delay of `query`, have passed according to `time_provider`.
+///
+/// The windows are consecutive and never end.
+pub(crate) fn windows(
+    query: &ContinuousQuery,
+    time_provider: Arc<dyn TimeProvider>,
+) -> impl Stream<Item = TimestampRange> + Send + 'static {
+    let start = query.start.unwrap_or_else(|| time_provider.now());
+    let window = query.window;
+    let delay = query.delay;
+
+    stream::unfold(start, move |start| {
+        let time_provider = Arc::clone(&time_provider);
+        async move {
+            let end = start + window;
+            time_provider.sleep_until(end + delay).await;
+
+            let range = TimestampRange::new(start.timestamp_nanos(), end.timestamp_nanos());
+            Some((range, end))
+        }
+    })
+}
+
+/// Merge the `results` of the consecutive evaluations of a continuous
+/// query, each starting with the schema of its results, into a single
+/// stream that only carries the schema once.
+///
+/// The stream ends after the first error, which is also returned if the
+/// schema of the results of an evaluation differs from the first one.
+pub(crate) fn merge_results<S>(results: S) -> impl Stream<Item = Result<FlightData, Status>> + Send
+where
+    S: Stream<Item = Result<FlightData, Status>> + Send,
+{
+    results
+        .scan((None, false), |(schema, failed), data| {
+            if *failed {
+                return future::ready(None);
+            }
+            let data = match data {
+                Ok(data) => match decode_schema(&data) {
+                    None => Some(Ok(data)),
+                    Some(data_schema) => match schema {
+                        None => {
+                            *schema = Some(data_schema);
+                            Some(Ok(data))
+                        }
+                        Some(schema) if *schema == data_schema => None,
+                        Some(_) => Some(Err(Status::from(Error::ContinuousQuerySchemaChanged))),
+                    },
+                },
+                Err(e) => Some(Err(e)),
+            };
+            *failed = matches!(data, Some(Err(_)));
+            future::ready(Some(data))
+        })
+        .filter_map(future::ready)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use arrow::{
+        datatypes::{DataType, Field, Schema},
+        ipc::writer::IpcWriteOptions,
+    };
+    use arrow_flight::SchemaAsIpc;
+    use futures::{FutureExt, TryStreamExt};
+    use generated_types::Code;
+    use iox_time::{MockProvider, Time};
+
+    use crate::request::{IoxGetRequest, RunQuery};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_windows() {
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(25)));
+        let query = ContinuousQuery {
+            request: IoxGetRequest::new("bananas", RunQuery::Sql("SELECT 1".into()), false),
+            window: Duration::from_nanos(10),
+            start: Some(Time::from_timestamp_nanos(0)),
+            delay: Duration::from_nanos(2),
+        };
+        let windows = windows(&query, Arc::clone(&time_provider) as _);
+        tokio::pin!(windows);
+
+        // windows that have passed are yielded immediately
+        assert_eq!(windows.next().await, Some(TimestampRange::new(0, 10)));
+        assert_eq!(windows.next().await, Some(TimestampRange::new(10, 20)));
+
+        // others once their delay has passed too
+        let mut next = windows.next();
+        assert_eq!((&mut next).now_or_never(), None);
+        time_provider.set(Time::from_timestamp_nanos(31));
+        assert_eq!((&mut next).now_or_never(), None);
+        time_provider.set(Time::from_timestamp_nanos(32));
+        assert_eq!(next.await, Some(TimestampRange::new(20, 30)));
+    }
+
+    #[tokio::test]
+    async fn test_windows_start_now() {
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(25)));
+        let query = ContinuousQuery {
+            request: IoxGetRequest::new("bananas", RunQuery::Sql("SELECT 1".into()), false),
+            window: Duration::from_nanos(10),
+            start: None,
+            delay: Duration::ZERO,
+        };
+        let windows = windows(&query, Arc::clone(&time_provider) as _);
+        tokio::pin!(windows);
+
+        let mut next = windows.next();
+        assert_eq!((&mut next).now_or_never(), None);
+        time_provider.set(Time::from_timestamp_nanos(35));
+        assert_eq!(next.await, Some(TimestampRange::new(25, 35)));
+    }
+
+    #[tokio::test]
+    async fn test_merge_results() {
+        let schema = |data_type| {
+            let schema = Schema::new(vec![Field::new("a", data_type, true)]);
+            FlightData::from(SchemaAsIpc::new(&schema, &IpcWriteOptions::default()))
+        };
+        let data = || FlightData::new().with_data_body(b"data".to_vec());
+
+        // the schema is only sent once
+        let results = stream::iter([
+            Ok(schema(DataType::Int64)),
+            Ok(data()),
+            Ok(schema(DataType::Int64)),
+            Ok(data()),
+        ]);
+        let merged = merge_results(results)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(merged, [schema(DataType::Int64), data(), data()]);
+
+        // the stream ends once the schema changes
+        let results = stream::iter([
+            Ok(schema(DataType::Int64)),
+            Ok(data()),
+            Ok(schema(DataType::Utf8)),
+            Ok(data()),
+        ]);
+        let merged = merge_results(results).collect::<Vec<_>>().await;
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[2].as_ref().unwrap_err().code(), Code::Aborted);
+
+        // or after the first error
+        let results = stream::iter([
+            Ok(schema(DataType::Int64)),
+            Err(Status::internal("boom")),
+            Ok(data()),
+        ]);
+        let merged = merge_results(results).collect::<Vec<_>>().await;
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[1].as_ref().unwrap_err().code(), Code::Internal);
+    }
+}
@@ -0,0 +1,520 @@
+//! Ingestion of Arrow record batches sent to `DoPut` with a
+//! [`DescriptorType::Path`](arrow_flight::flight_descriptor::DescriptorType::Path)
+//! descriptor naming the target table, or with a FlightSQL
+//! `CommandStatementIngest`.
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use arrow::{
+    compute::cast,
+    datatypes::{DataType, Field},
+    record_batch::RecordBatch,
+};
+use arrow_flight::{FlightData, decode::FlightRecordBatchStream, error::FlightError};
+use async_trait::async_trait;
+use datafusion::error::DataFusionError;
+use futures::{Stream, TryStreamExt};
+use generated_types::Status;
+use mutable_batch::MutableBatch;
+use schema::{
+    InfluxColumnType, InfluxFieldType, Schema, TIME_COLUMN_NAME, builder::SchemaBuilder,
+    get_influx_type,
+};
+use snafu::{OptionExt, ResultExt};
+
+use crate::{
+    Error, IncompatibleIngestColumnSnafu, IngestColumnCastSnafu, IngestRecordBatchSnafu,
+    IngestStreamSnafu, InvalidIngestBatchSnafu, InvalidIngestPathSnafu, InvalidIngestSchemaSnafu,
+    UnsupportedIngestColumnSnafu, WriteSnafu,
+};
+
+/// The size of the data, in bytes, of the rows of an ingested stream buffered
+/// before they are written.
+const INGEST_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+/// A destination for the rows ingested by `DoPut` and FlightSQL
+/// `CommandStatementIngest` requests, implemented by the host server.
+#[async_trait]
+pub trait IngestSink: Debug + Send + Sync {
+    /// Write `batches`, keyed by table name, to the namespace
+    /// `namespace_name`.
+    async fn write_namespace(
+        &self,
+        namespace_name: &str,
+        batches: HashMap<String, MutableBatch>,
+    ) -> Result<(), DataFusionError>;
+}
+
+/// Returns the table name of the `DoPut` descriptor `path`, which must be a
+/// single, non-empty element.
+pub(crate) fn ingest_table_name(path: &[String]) -> Result<&str, Error> {
+    match path {
+        [table] if !table.is_empty() => Ok(table),
+        _ => InvalidIngestPathSnafu {
+            path: path.to_vec(),
+        }
+        .fail(),
+    }
+}
+
+/// Write the IPC encoded record batches of `stream` to `table` of the
+/// namespace `namespace_name` with `sink`, and return the number of rows
+/// written.
+///
+/// Each record batch is passed through `convert`, and must then carry the
+/// InfluxDB column type metadata described by
+/// [`MutableBatch::from_record_batch`].
+///
+/// The rows are buffered and written in chunks of about
+/// [`INGEST_CHUNK_SIZE`] bytes, so that the size of the stream is not
+/// limited by memory. The chunks written before an error remain written.
+pub(crate) async fn write_ingest_stream<S, F>(
+    sink: &dyn IngestSink,
+    namespace_name: &str,
+    table: &str,
+    stream: S,
+    mut convert: F,
+) -> Result<usize, Error>
+where
+    S: Stream<Item = Result<FlightData, Status>> + Send,
+    F: FnMut(RecordBatch) -> Result<RecordBatch, Error> + Send,
+{
+    let mut batches =
+        FlightRecordBatchStream::new_from_flight_data(stream.map_err(FlightError::from));
+
+    let mut rows = 0;
+    let mut mb = MutableBatch::new();
+    while let Some(batch) = batches.try_next().await.context(IngestStreamSnafu)? {
+        mb.extend_from_record_batch(&convert(batch)?)
+            .context(InvalidIngestBatchSnafu { table })?;
+        if mb.size_data() >= INGEST_CHUNK_SIZE {
+            rows += write_chunk(sink, namespace_name, table, std::mem::take(&mut mb)).await?;
+        }
+    }
+    rows += write_chunk(sink, namespace_name, table, mb).await?;
+
+    Ok(rows)
+}
+
+/// Write the rows of `mb`, if any, to `table` of the namespace
+/// `namespace_name` with `sink`, and return their number.
+async fn write_chunk(
+    sink: &dyn IngestSink,
+    namespace_name: &str,
+    table: &str,
+    mb: MutableBatch,
+) -> Result<usize, Error> {
+    let rows = mb.rows();
+    if rows > 0 {
+        sink.write_namespace(namespace_name, HashMap::from([(table.to_string(), mb)]))
+            .await
+            .context(WriteSnafu { namespace_name })?;
+    }
+    Ok(rows)
+}
+
+/// Returns the InfluxDB column type of the column `field` of a record batch
+/// ingested into `table`.
+///
+/// The `time` column is always the timestamp. The type of any other column is
+/// taken from its InfluxDB column type metadata, if any, or else inferred from
+/// its Arrow type: dictionary encoded strings are tags, and other supported
+/// types are fields. Strings without metadata are tags if the column is a tag
+/// in `table_schema`, the schema of `table` if it exists. The resolved type
+/// must match that of the column in `table_schema`.
+fn ingest_column_type(
+    table: &str,
+    table_schema: Option<&Schema>,
+    field: &Field,
+) -> Result<InfluxColumnType, Error> {
+    let column = field.name();
+    let table_column_type = table_schema.and_then(|s| s.field_type_by_name(column));
+
+    let column_type = if column == TIME_COLUMN_NAME {
+        InfluxColumnType::Timestamp
+    } else {
+        let inferred = infer_column_type(field.data_type());
+        match (get_influx_type(field).ok(), table_column_type, inferred) {
+            (Some(column_type), _, _) => Some(column_type),
+            (
+                None,
+                Some(InfluxColumnType::Tag),
+                Some(InfluxColumnType::Field(InfluxFieldType::String)),
+            ) => Some(InfluxColumnType::Tag),
+            (None, _, inferred) => inferred,
+        }
+        .filter(|t| *t != InfluxColumnType::Timestamp)
+        .context(UnsupportedIngestColumnSnafu {
+            table,
+            column,
+            data_type: field.data_type().clone(),
+        })?
+    };
+
+    if let Some(table_column_type) = table_column_type
+        && table_column_type != column_type
+    {
+        return IncompatibleIngestColumnSnafu {
+            table,
+            column,
+            column_type,
+            table_column_type,
+        }
+        .fail();
+    }
+
+    Ok(column_type)
+}
+
+/// Returns the InfluxDB column type for a column of Arrow type `data_type`
+/// without any InfluxDB column type metadata, if it is supported.
+fn infer_column_type(data_type: &DataType) -> Option<InfluxColumnType> {
+    let field_type = match data_type {
+        DataType::Dictionary(_, value) if value.as_ref() == &DataType::Utf8 => {
+            return Some(InfluxColumnType::Tag);
+        }
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => InfluxFieldType::String,
+        DataType::Float16 | DataType::Float32 | DataType::Float64 => InfluxFieldType::Float,
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => {
+            InfluxFieldType::Integer
+        }
+        DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => {
+            InfluxFieldType::UInteger
+        }
+        DataType::Boolean => InfluxFieldType::Boolean,
+        _ => return None,
+    };
+    Some(InfluxColumnType::Field(field_type))
+}
+
+/// Returns `batch`, sent with a FlightSQL `CommandStatementIngest` to
+/// `table`, with the [`Schema`] of its resolved InfluxDB column types,
+/// casting its columns to the corresponding Arrow types.
+///
+/// Unlike those sent with a path descriptor, such record batches need not
+/// carry the InfluxDB column type metadata, as the type of each column is
+/// resolved by [`ingest_column_type`] against `table_schema`, the schema of
+/// `table` if it exists.
+pub(crate) fn to_influx_batch(
+    table: &str,
+    table_schema: Option<&Schema>,
+    batch: &RecordBatch,
+) -> Result<RecordBatch, Error> {
+    let mut builder = SchemaBuilder::new();
+    for field in batch.schema().fields() {
+        builder.influx_column(
+            field.name(),
+            ingest_column_type(table, table_schema, field)?,
+        );
+    }
+    let schema = builder
+        .build()
+        .context(InvalidIngestSchemaSnafu { table })?;
+
+    let columns = schema
+        .iter()
+        .map(|(_, field)| {
+            let column = batch
+                .column_by_name(field.name())
+                .expect("schema built from the batch columns");
+            cast(column, field.data_type()).context(IngestColumnCastSnafu {
+                table,
+                column: field.name(),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    RecordBatch::try_new(schema.as_arrow(), columns).context(IngestRecordBatchSnafu { table })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use arrow::{
+        array::{
+            ArrayRef, DictionaryArray, Float64Array, Int32Array, StringArray,
+            TimestampMicrosecondArray, TimestampNanosecondArray,
+        },
+        datatypes::Int32Type,
+        record_batch::RecordBatch,
+    };
+    use arrow_flight::{FlightDescriptor, encode::FlightDataEncoderBuilder};
+    use arrow_util::assert_batches_eq;
+    use assert_matches::assert_matches;
+    use async_trait::async_trait;
+    use futures::{StreamExt, stream};
+    use generated_types::Code;
+    use schema::Projection;
+
+    use super::*;
+
+    async fn encode(batches: Vec<RecordBatch>) -> Vec<Result<FlightData, Status>> {
+        FlightDataEncoderBuilder::new()
+            .with_flight_descriptor(Some(FlightDescriptor::new_path(vec!["cpu".to_string()])))
+            .build(stream::iter(batches.into_iter().map(Ok)))
+            .map(|r| r.map_err(Status::from))
+            .collect()
+            .await
+    }
+
+    #[derive(Debug, Default)]
+    struct MockSink {
+        writes: Mutex<Vec<(String, HashMap<String, MutableBatch>)>>,
+    }
+
+    impl MockSink {
+        /// The rows written to `table`, one batch per write.
+        fn batches(&self, table: &str) -> Vec<RecordBatch> {
+            self.writes
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(_, batches)| {
+                    batches[table]
+                        .clone()
+                        .try_into_arrow(Projection::All)
+                        .unwrap()
+                })
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl IngestSink for MockSink {
+        async fn write_namespace(
+            &self,
+            namespace_name: &str,
+            batches: HashMap<String, MutableBatch>,
+        ) -> datafusion::error::Result<()> {
+            self.writes
+                .lock()
+                .unwrap()
+                .push((namespace_name.to_string(), batches));
+            Ok(())
+        }
+    }
+
+    async fn write<S>(sink: &MockSink, stream: S) -> Result<usize, Error>
+    where
+        S: Stream<Item = Result<FlightData, Status>> + Send,
+    {
+        write_ingest_stream(sink, "bananas", "cpu", stream, Ok).await
+    }
+
+    fn batch(hosts: Vec<Option<&str>>, usage: Vec<Option<f64>>, time: Vec<i64>) -> RecordBatch {
+        let schema = SchemaBuilder::new()
+            .tag("host")
+            .influx_field("usage", InfluxFieldType::Float)
+            .timestamp()
+            .build()
+            .unwrap();
+        RecordBatch::try_new(
+            schema.as_arrow(),
+            vec![
+                Arc::new(hosts.into_iter().collect::<DictionaryArray<Int32Type>>()),
+                Arc::new(Float64Array::from(usage)),
+                Arc::new(TimestampNanosecondArray::from(time)),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_ingest_table_name() {
+        assert_eq!(ingest_table_name(&["cpu".to_string()]).unwrap(), "cpu");
+        assert_matches!(ingest_table_name(&[]), Err(Error::InvalidIngestPath { .. }));
+        assert_matches!(
+            ingest_table_name(&["".to_string()]),
+            Err(Error::InvalidIngestPath { .. })
+        );
+        assert_matches!(
+            ingest_table_name(&["db".to_string(), "cpu".to_string()]),
+            Err(Error::InvalidIngestPath { .. })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_ingest_stream() {
+        let data = encode(vec![
+            batch(
+                vec![Some("a"), None],
+                vec![Some(1.0), Some(2.0)],
+                vec![1, 2],
+            ),
+            batch(vec![Some("b")], vec![None], vec![3]),
+        ])
+        .await;
+
+        let sink = MockSink::default();
+        let rows = write(&sink, stream::iter(data)).await.unwrap();
+        assert_eq!(rows, 3);
+        assert_eq!(sink.writes.lock().unwrap()[0].0, "bananas");
+        assert_batches_eq!(
+            [
+                "+------+--------------------------------+-------+",
+                "| host | time                           | usage |",
+                "+------+--------------------------------+-------+",
+                "| a    | 1970-01-01T00:00:00.000000001Z | 1.0   |",
+                "|      | 1970-01-01T00:00:00.000000002Z | 2.0   |",
+                "| b    | 1970-01-01T00:00:00.000000003Z |       |",
+                "+------+--------------------------------+-------+",
+            ],
+            &sink.batches("cpu")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_ingest_stream_chunks() {
+        // more rows than fit in a single chunk
+        let n = INGEST_CHUNK_SIZE / 8;
+        let schema = SchemaBuilder::new()
+            .influx_field("usage", InfluxFieldType::Float)
+            .timestamp()
+            .build()
+            .unwrap();
+        let rb = RecordBatch::try_new(
+            schema.as_arrow(),
+            vec![
+                Arc::new(Float64Array::from_iter_values((0..n).map(|i| i as f64))),
+                Arc::new(TimestampNanosecondArray::from_iter_values(0..n as i64)),
+            ],
+        )
+        .unwrap();
+        let data = encode(vec![rb]).await;
+
+        let sink = MockSink::default();
+        let rows = write(&sink, stream::iter(data)).await.unwrap();
+        assert_eq!(rows, n);
+
+        let batches = sink.batches("cpu");
+        assert!(batches.len() > 1, "expected several writes");
+        assert_eq!(batches.iter().map(RecordBatch::num_rows).sum::<usize>(), n);
+    }
+
+    #[tokio::test]
+    async fn test_write_ingest_stream_without_metadata() {
+        let rb = RecordBatch::try_from_iter([
+            ("host", Arc::new(StringArray::from(vec!["a"])) as ArrayRef),
+            ("time", Arc::new(TimestampNanosecondArray::from(vec![1]))),
+        ])
+        .unwrap();
+        let data = encode(vec![rb]).await;
+
+        let sink = MockSink::default();
+        let err = write(&sink, stream::iter(data)).await.unwrap_err();
+        assert_matches!(err, Error::InvalidIngestBatch { .. });
+        assert_eq!(Status::from(err).code(), Code::InvalidArgument);
+        assert!(sink.writes.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_write_ingest_stream_empty() {
+        let data = encode(vec![]).await;
+
+        let sink = MockSink::default();
+        let rows = write(&sink, stream::iter(data)).await.unwrap();
+        assert_eq!(rows, 0);
+        assert!(sink.writes.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_write_statement_ingest_stream() {
+        // Plain Arrow types, without InfluxDB column type metadata
+        let rb = RecordBatch::try_from_iter([
+            (
+                "region",
+                Arc::new(
+                    vec![Some("west"), None]
+                        .into_iter()
+                        .collect::<DictionaryArray<Int32Type>>(),
+                ) as ArrayRef,
+            ),
+            ("host", Arc::new(StringArray::from(vec!["a", "b"]))),
+            ("count", Arc::new(Int32Array::from(vec![Some(1), None]))),
+            (
+                "time",
+                Arc::new(TimestampMicrosecondArray::from(vec![1, 2])),
+            ),
+        ])
+        .unwrap();
+
+        // host is a tag of the existing table
+        let table_schema = SchemaBuilder::new()
+            .tag("host")
+            .timestamp()
+            .build()
+            .unwrap();
+
+        let data = encode(vec![rb]).await;
+        let sink = MockSink::default();
+        write_ingest_stream(&sink, "bananas", "cpu", stream::iter(data), |batch| {
+            to_influx_batch("cpu", Some(&table_schema), &batch)
+        })
+        .await
+        .unwrap();
+        let mb = sink.writes.lock().unwrap()[0].1["cpu"].clone();
+        assert_eq!(
+            mb_column_types(&mb),
+            [
+                ("count", InfluxColumnType::Field(InfluxFieldType::Integer)),
+                ("host", InfluxColumnType::Tag),
+                ("region", InfluxColumnType::Tag),
+                ("time", InfluxColumnType::Timestamp),
+            ]
+        );
+        assert_batches_eq!(
+            [
+                "+-------+------+--------+-----------------------------+",
+                "| count | host | region | time                        |",
+                "+-------+------+--------+-----------------------------+",
+                "| 1     | a    | west   | 1970-01-01T00:00:00.000001Z |",
+                "|       | b    |        | 1970-01-01T00:00:00.000002Z |",
+                "+-------+------+--------+-----------------------------+",
+            ],
+            &[mb.try_into_arrow(Projection::All).unwrap()]
+        );
+    }
+
+    fn mb_column_types(mb: &MutableBatch) -> Vec<(&str, InfluxColumnType)> {
+        let schema = mb.schema(Projection::All).unwrap();
+        mb.column_names()
+            .into_iter()
+            .map(|name| (name, schema.field_type_by_name(name).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_ingest_column_type() {
+        let table_schema = SchemaBuilder::new()
+            .tag("host")
+            .influx_field("usage", InfluxFieldType::Float)
+            .timestamp()
+            .build()
+            .unwrap();
+        let column_type = |field: Field| ingest_column_type("cpu", Some(&table_schema), &field);
+
+        // New columns are inferred from their Arrow type
+        assert_eq!(
+            column_type(Field::new("msg", DataType::Utf8, true)).unwrap(),
+            InfluxColumnType::Field(InfluxFieldType::String)
+        );
+        assert_matches!(
+            column_type(Field::new("t", DataType::Date32, true)),
+            Err(Error::UnsupportedIngestColumn { .. })
+        );
+
+        // Existing columns must have a compatible type
+        assert_eq!(
+            column_type(Field::new("usage", DataType::Float32, true)).unwrap(),
+            InfluxColumnType::Field(InfluxFieldType::Float)
+        );
+        let err = column_type(Field::new("host", DataType::Float64, true)).unwrap_err();
+        assert_matches!(err, Error::IncompatibleIngestColumn { .. });
+        assert_eq!(Status::from(err).code(), Code::InvalidArgument);
+        assert_matches!(
+            column_type(Field::new("usage", DataType::Int64, true)),
+            Err(Error::IncompatibleIngestColumn { .. })
+        );
+    }
+}
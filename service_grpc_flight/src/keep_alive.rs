@@ -229,7 +229,7 @@ where
 }
 
 /// Decode [`Schema`] from response data stream.
-fn decode_schema(data: &FlightData) -> Option<Schema> {
+pub(crate) fn decode_schema(data: &FlightData) -> Option<Schema> {
     let message = arrow::ipc::root_as_message(&data.data_header[..]).ok()?;
 
     if arrow::ipc::MessageHeader::Schema != message.header_type() {
@@ -2,30 +2,48 @@
 //! FlightSQL, based on Arrow Flight and gRPC. See [`FlightService`]
 //! for full detail.
 
+pub use ingest::IngestSink;
 use keep_alive::KeepAliveStream;
+use namespace_resolver::QueryDatabaseNamespaceResolver;
 use planner::Planner;
+pub use poll::PolledQueryConfig;
+use poll::{PolledQueries, PolledQuery};
+use split::EndpointSplit;
 use tower_trailer::{HeaderMap, Trailers};
 // Workaround for "unused crate" lint false positives.
 use workspace_hack as _;
 
+mod continuous;
+mod ingest;
 mod keep_alive;
+mod namespace_resolver;
 mod planner;
+mod poll;
 mod request;
+mod split;
 
-use arrow::error::ArrowError;
+use arrow::{
+    datatypes::{DataType, SchemaRef},
+    error::ArrowError,
+    ipc::writer::IpcWriteOptions,
+};
 use arrow_flight::{
-    Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightEndpoint, FlightInfo,
-    HandshakeRequest, HandshakeResponse, PollInfo, PutResult, SchemaResult, Ticket,
+    Action, ActionType, CancelFlightInfoRequest, CancelFlightInfoResult, CancelStatus, Criteria,
+    Empty, FlightData, FlightDescriptor, FlightEndpoint, FlightInfo, HandshakeRequest,
+    HandshakeResponse, PollInfo, PutResult, SchemaAsIpc, SchemaResult, Ticket,
     encode::FlightDataEncoderBuilder,
     error::FlightError,
     flight_descriptor::DescriptorType,
     flight_service_server::{FlightService as Flight, FlightServiceServer as FlightServer},
+    sql::{CommandStatementIngest, DoPutUpdateResult},
 };
 use authz::{Authorizer, extract_token};
-use data_types::NamespaceNameError;
-use datafusion::{error::DataFusionError, physical_plan::ExecutionPlan};
+use data_types::{NamespaceName, NamespaceNameError, TimestampRange};
+use datafusion::{
+    common::utils::quote_identifier, error::DataFusionError, physical_plan::ExecutionPlan,
+};
 use error_reporting::DisplaySourceChain;
-use flightsql::FlightSQLCommand;
+use flightsql::{FlightSQLCommand, FlightSQLPlanner};
 use futures::{Stream, StreamExt, TryStreamExt, ready, stream::BoxStream};
 use generated_types::{
     Code, Request, Response, Status, Streaming,
@@ -37,12 +55,23 @@ use iox_query::{
     exec::IOxSessionContext,
     query_log::{PermitAndToken, QueryCompletedToken, QueryLogEntry, StatePermit, StatePlanned},
 };
-use iox_query::{exec::QueryConfig, query_log::QueryLogEntryState};
+use iox_query::{
+    exec::{QueryConfig, QueryLanguage},
+    query_log::QueryLogEntryState,
+};
+use iox_query_influxql::frontend::{
+    namespace::{IntoDatabase, QualifiedDatabase, find_databases, find_into_database},
+    planner::InfluxQLQueryPlanner,
+    select_into::WriteSink,
+};
+use iox_time::{SystemProvider, TimeProvider};
 use prost::Message;
-use request::{IoxGetRequest, RunQuery};
+use request::{ContinuousQuery, IoxGetRequest, RunQuery};
+use schema::{InfluxColumnType, Schema};
 use service_common::{datafusion_error_to_tonic_code, flight_error_to_tonic_code};
-use snafu::{OptionExt, ResultExt, Snafu};
+use snafu::{OptionExt, ResultExt, Snafu, ensure};
 use std::{
+    collections::BTreeSet,
     fmt::Debug,
     pin::Pin,
     str::FromStr,
@@ -75,6 +104,10 @@ const IOX_FLIGHT_PARTITION_LIMIT_HEADER: &str = "x-influxdata-partition-limit";
 /// Header that contains a query-specific parquet file limit.
 const IOX_FLIGHT_PARQUET_FILE_LIMIT_HEADER: &str = "x-influxdata-parquet-file-limit";
 
+/// Header that requests the results of a `GetFlightInfo` request to be split
+/// into several endpoints, see [`EndpointSplit`].
+const IOX_FLIGHT_ENDPOINT_SPLIT_HEADER: &str = "x-influxdata-endpoint-split";
+
 /// Header that specifies the language which the provided query string should be interpreted as;
 /// optionally sent as part of the GetFlightInfo request
 const IOX_FLIGHT_QUERY_LANGUAGE: &str = "x-influxdata-query-language";
@@ -130,6 +163,15 @@ pub enum Error {
     #[snafu(display("Invalid ticket. Error: {}", source))]
     InvalidTicket { source: request::Error },
 
+    #[snafu(display("Invalid '{}' header: {}", IOX_FLIGHT_ENDPOINT_SPLIT_HEADER, msg))]
+    InvalidEndpointSplit { msg: String },
+
+    #[snafu(display("Invalid continuous query: {}", source))]
+    InvalidContinuousQuery { source: request::Error },
+
+    #[snafu(display("Schema of the results of continuous query changed, resubscribe to continue"))]
+    ContinuousQuerySchemaChanged,
+
     #[snafu(display("Internal creating encoding ticket: {}", source))]
     InternalCreatingTicket { source: request::Error },
 
@@ -193,9 +235,84 @@ pub enum Error {
     #[snafu(display("No FlightDescriptor in Flight SQL request"))]
     NoFlightDescriptor,
 
+    #[snafu(display("Invalid DoPut path {:?}: expected a single table name", path))]
+    InvalidIngestPath { path: Vec<String> },
+
+    #[snafu(display("Error while decoding DoPut stream: {}", source))]
+    IngestStream { source: FlightError },
+
+    #[snafu(display("Invalid record batch for table '{}': {}", table, source))]
+    InvalidIngestBatch {
+        table: String,
+        source: mutable_batch::Error,
+    },
+
+    #[snafu(display(
+        "Column '{}' of table '{}' has unsupported type {}",
+        column,
+        table,
+        data_type
+    ))]
+    UnsupportedIngestColumn {
+        table: String,
+        column: String,
+        data_type: DataType,
+    },
+
+    #[snafu(display(
+        "Column '{}' has type {} but has type {} in table '{}'",
+        column,
+        column_type,
+        table_column_type,
+        table
+    ))]
+    IncompatibleIngestColumn {
+        table: String,
+        column: String,
+        column_type: InfluxColumnType,
+        table_column_type: InfluxColumnType,
+    },
+
+    #[snafu(display("Cannot convert column '{}' of table '{}': {}", column, table, source))]
+    IngestColumnCast {
+        table: String,
+        column: String,
+        source: ArrowError,
+    },
+
+    #[snafu(display("Invalid schema for table '{}': {}", table, source))]
+    InvalidIngestSchema {
+        table: String,
+        source: schema::builder::Error,
+    },
+
+    #[snafu(display("Invalid record batch for table '{}': {}", table, source))]
+    IngestRecordBatch { table: String, source: ArrowError },
+
+    #[snafu(display("Invalid schema of existing table '{}': {}", table, source))]
+    IngestTableSchema {
+        table: String,
+        source: schema::Error,
+    },
+
+    #[snafu(display("Error writing to namespace {}: {}", namespace_name, source))]
+    Write {
+        namespace_name: String,
+        source: DataFusionError,
+    },
+
     #[snafu(display("Invalid protobuf: {}", source))]
     Deserialization { source: prost::DecodeError },
 
+    #[snafu(display("Polled query '{}' not found, or its results expired", id))]
+    PolledQueryNotFound { id: String },
+
+    #[snafu(display(
+        "Results of polled query exceed the limit of {} bytes, use DoGet instead",
+        limit
+    ))]
+    PolledQueryMemoryLimit { limit: usize },
+
     #[snafu(display("Unsupported message type: {}", description))]
     UnsupportedMessageType { description: String },
 
@@ -228,10 +345,14 @@ impl From<Error> for Status {
         match err.inner() {
             Error::DatabaseNotFound { .. }
             | Error::InvalidTicket { .. }
+            | Error::InvalidContinuousQuery { .. }
+            | Error::ContinuousQuerySchemaChanged
+            | Error::InvalidEndpointSplit { .. }
             | Error::InvalidHandshake { .. }
             | Error::Unauthenticated
             | Error::PermissionDenied
             | Error::InvalidDatabaseName { .. }
+            | Error::PolledQueryNotFound { .. }
             | Error::Query { .. } => {
                 info!(e=%err, %namespace, %query, msg);
             }
@@ -248,7 +369,18 @@ impl From<Error> for Status {
             | Error::FlightSQL { .. }
             | Error::StreamingFlightSql { .. }
             | Error::AuthzVerification { .. }
-            | Error::NoFlightDescriptor => {
+            | Error::NoFlightDescriptor
+            | Error::InvalidIngestPath { .. }
+            | Error::IngestStream { .. }
+            | Error::InvalidIngestBatch { .. }
+            | Error::UnsupportedIngestColumn { .. }
+            | Error::IncompatibleIngestColumn { .. }
+            | Error::IngestColumnCast { .. }
+            | Error::InvalidIngestSchema { .. }
+            | Error::IngestRecordBatch { .. }
+            | Error::IngestTableSchema { .. }
+            | Error::PolledQueryMemoryLimit { .. }
+            | Error::Write { .. } => {
                 warn!(e=%err, %namespace, %query, msg);
             }
         };
@@ -266,18 +398,30 @@ impl Error {
         let msg = err.to_string();
 
         let code = match err.into_inner() {
-            Self::DatabaseNotFound { .. } => Code::NotFound,
+            Self::DatabaseNotFound { .. } | Self::PolledQueryNotFound { .. } => Code::NotFound,
+            Self::PolledQueryMemoryLimit { .. } => Code::ResourceExhausted,
+            Self::ContinuousQuerySchemaChanged => Code::Aborted,
             Self::InvalidTicket { .. }
+            | Self::InvalidContinuousQuery { .. }
+            | Self::InvalidEndpointSplit { .. }
             | Self::InvalidHandshake { .. }
             | Self::Deserialization { .. }
             | Self::TooManyFlightSQLDatabases { .. }
             | Self::NoFlightSQLDatabase
             | Self::NoFlightDescriptor
             | Self::InvalidDatabaseHeader { .. }
-            | Self::InvalidDatabaseName { .. } => Code::InvalidArgument,
+            | Self::InvalidDatabaseName { .. }
+            | Self::InvalidIngestPath { .. }
+            | Self::InvalidIngestBatch { .. }
+            | Self::UnsupportedIngestColumn { .. }
+            | Self::IncompatibleIngestColumn { .. }
+            | Self::IngestColumnCast { .. }
+            | Self::InvalidIngestSchema { .. }
+            | Self::IngestRecordBatch { .. } => Code::InvalidArgument,
             Self::Database { source }
             | Self::Planning { source, .. }
-            | Self::Query { source, .. } => datafusion_error_to_tonic_code(&source),
+            | Self::Query { source, .. }
+            | Self::Write { source, .. } => datafusion_error_to_tonic_code(&source),
             Self::UnsupportedMessageType { .. } => Code::Unimplemented,
             Self::FlightSQL { source } => match source {
                 flightsql::Error::InvalidHandle { .. }
@@ -287,7 +431,11 @@ impl Error {
                 | flightsql::Error::NoFlightData { .. }
                 | flightsql::Error::Protocol { .. }
                 | flightsql::Error::UnknownParameterType { .. }
-                | flightsql::Error::UnsupportedMessageType { .. } => Code::InvalidArgument,
+                | flightsql::Error::UnsupportedMessageType { .. }
+                | flightsql::Error::EmptyIngestTable => Code::InvalidArgument,
+                flightsql::Error::Unsupported { .. } => Code::Unimplemented,
+                flightsql::Error::IngestTableExists { .. } => Code::AlreadyExists,
+                flightsql::Error::IngestTableNotFound { .. } => Code::NotFound,
                 flightsql::Error::Flight { source } => {
                     // only use status code, NOT the message because it does NOT contain the entire chain
                     flight_error_to_tonic_code(&source)
@@ -299,13 +447,14 @@ impl Error {
                 }
                 flightsql::Error::DataFusion { source } => datafusion_error_to_tonic_code(&source),
             },
-            Self::StreamingFlightSql { source } => {
+            Self::StreamingFlightSql { source } | Self::IngestStream { source } => {
                 // only use status code, NOT the message because it does NOT contain the entire chain
                 flight_error_to_tonic_code(&source)
             }
             Self::InternalCreatingTicket { .. }
             | Self::Optimize { .. }
-            | Self::EncodeSchema { .. } => Code::Internal,
+            | Self::EncodeSchema { .. }
+            | Self::IngestTableSchema { .. } => Code::Internal,
             Self::AuthzVerification { .. } => Code::Unavailable,
             Self::Unauthenticated => Code::Unauthenticated,
             Self::PermissionDenied => Code::PermissionDenied,
@@ -319,6 +468,9 @@ impl Error {
         match self {
             Self::Database { .. }
             | Self::InvalidTicket { .. }
+            | Self::InvalidContinuousQuery { .. }
+            | Self::ContinuousQuerySchemaChanged
+            | Self::InvalidEndpointSplit { .. }
             | Self::InternalCreatingTicket { .. }
             | Self::InvalidHandshake {}
             | Self::TooManyFlightSQLDatabases { .. }
@@ -334,8 +486,20 @@ impl Error {
             | Self::UnsupportedMessageType { .. }
             | Self::Unauthenticated
             | Self::PermissionDenied
-            | Self::AuthzVerification { .. } => "<unknown>",
+            | Self::AuthzVerification { .. }
+            | Self::InvalidIngestPath { .. }
+            | Self::IngestStream { .. }
+            | Self::InvalidIngestBatch { .. }
+            | Self::UnsupportedIngestColumn { .. }
+            | Self::IncompatibleIngestColumn { .. }
+            | Self::IngestColumnCast { .. }
+            | Self::InvalidIngestSchema { .. }
+            | Self::IngestRecordBatch { .. }
+            | Self::IngestTableSchema { .. }
+            | Self::PolledQueryNotFound { .. }
+            | Self::PolledQueryMemoryLimit { .. } => "<unknown>",
             Self::DatabaseNotFound { namespace_name } => namespace_name,
+            Self::Write { namespace_name, .. } => namespace_name,
             Self::Query { namespace_name, .. } => namespace_name,
             Self::Planning { namespace_name, .. } => namespace_name,
         }
@@ -346,6 +510,9 @@ impl Error {
         match self {
             Self::Database { .. }
             | Self::InvalidTicket { .. }
+            | Self::InvalidContinuousQuery { .. }
+            | Self::ContinuousQuerySchemaChanged
+            | Self::InvalidEndpointSplit { .. }
             | Self::InternalCreatingTicket { .. }
             | Self::InvalidHandshake {}
             | Self::TooManyFlightSQLDatabases { .. }
@@ -362,7 +529,19 @@ impl Error {
             | Self::Unauthenticated
             | Self::PermissionDenied
             | Self::AuthzVerification { .. }
-            | Self::DatabaseNotFound { .. } => "NONE",
+            | Self::DatabaseNotFound { .. }
+            | Self::InvalidIngestPath { .. }
+            | Self::IngestStream { .. }
+            | Self::InvalidIngestBatch { .. }
+            | Self::UnsupportedIngestColumn { .. }
+            | Self::IncompatibleIngestColumn { .. }
+            | Self::IngestColumnCast { .. }
+            | Self::InvalidIngestSchema { .. }
+            | Self::IngestRecordBatch { .. }
+            | Self::IngestTableSchema { .. }
+            | Self::PolledQueryNotFound { .. }
+            | Self::PolledQueryMemoryLimit { .. }
+            | Self::Write { .. } => "NONE",
             Self::Query { query, .. } => query,
             Self::Planning { query, .. } => query,
         }
@@ -448,6 +627,58 @@ type TonicStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send + 'sta
 ///     3 ┃◀ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ┃
 /// ```
 ///
+/// # Listing tables
+///
+/// `ListFlights` returns a [`FlightInfo`] for each table of the database
+/// named by the request headers. Each has a path descriptor naming the
+/// table, the schema of the table, and a `Ticket` for a query returning all
+/// of its rows.
+///
+/// # Polling long-running queries
+///
+/// Rather than holding a `DoGet` stream open for the whole duration of a
+/// query, a client may submit a FlightSQL command with `PollFlightInfo`. The
+/// query runs in the background, buffering its results in memory, and the
+/// returned `PollInfo` carries a descriptor to poll it with again. Once the
+/// query completes, the `PollInfo` has an endpoint with a `Ticket` to fetch
+/// the results with `DoGet`.
+///
+/// The headers of each `PollFlightInfo` response describe the progress of
+/// the query, like the trailers of a `DoGet` response. The results are
+/// discarded once they expire, or when the query is cancelled with the
+/// `CancelFlightInfo` action. Queries whose results exceed the memory
+/// available for buffering fail.
+///
+/// # Fetching results over several streams
+///
+/// A `GetFlightInfo` request for a SQL `CommandStatementQuery` may carry an
+/// `x-influxdata-endpoint-split: time-range:<n>` header to split the results
+/// into up to `n` endpoints, which clients may fetch concurrently. Each
+/// endpoint's `Ticket` runs the query restricted to a range of `time`, the
+/// ranges evenly dividing the interval of `time` selected by the `WHERE`
+/// clause.
+///
+/// Only queries that filter and project the rows of a single table, with
+/// `time` bounded on both sides, are split. Other queries return a single
+/// endpoint, as without the header.
+///
+/// # Continuous queries
+///
+/// A client subscribes to a continuous query by calling `DoExchange` with a
+/// first message whose [`FlightDescriptor`] command is a
+/// [`ContinuousQuery`](proto::ContinuousQuery), wrapped as an "Any" message.
+/// It holds the query, as in a `DoGet` ticket, and the length of the windows
+/// of `time` to evaluate it over.
+///
+/// Whenever a window has passed, the query is evaluated over the rows whose
+/// `time` lies within that window only, and its results are streamed back.
+/// Rows ingested after their window was evaluated are not returned, unless
+/// the subscription delays evaluations to account for them. The request is
+/// authorized again before each evaluation. The exchange runs until the
+/// client cancels it, or an evaluation fails, which includes the
+/// authorization being revoked and the schema of the results changing.
+/// Queries that fill gaps in time series are rejected.
+///
 /// # FlightSQL
 ///
 /// IOx also supports [Arrow FlightSQL]. In addition to `DoGet`,
@@ -615,19 +846,430 @@ type TonicStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send + 'sta
 struct FlightService {
     server: Arc<dyn QueryDatabase>,
     authz: Option<Arc<dyn Authorizer>>,
+    write_sink: Option<Arc<dyn WriteSink>>,
+    ingest_sink: Option<Arc<dyn IngestSink>>,
+    polled_queries: PolledQueries,
+    /// Clock deciding when to evaluate the windows of continuous queries.
+    time_provider: Arc<dyn TimeProvider>,
 }
 
+/// Create the Flight service.
+///
+/// InfluxQL `SELECT ... INTO` queries write their points to `write_sink`,
+/// and `DoPut` requests with a path descriptor and FlightSQL
+/// `CommandStatementIngest` requests write their rows to `ingest_sink`. Each
+/// is not supported when its sink is `None`.
+///
+/// The results of queries submitted with `PollFlightInfo` are retained and
+/// limited as configured by `polled_query_config`. Must be called within a
+/// tokio runtime, which expired results are purged on in the background.
 pub fn make_server(
     server: Arc<dyn QueryDatabase>,
     authz: Option<Arc<dyn Authorizer>>,
+    write_sink: Option<Arc<dyn WriteSink>>,
+    ingest_sink: Option<Arc<dyn IngestSink>>,
+    polled_query_config: PolledQueryConfig,
 ) -> FlightServer<impl Flight> {
-    FlightServer::new(FlightService { server, authz })
+    let polled_queries = PolledQueries::from(polled_query_config);
+    polled_queries.spawn_purge_task(poll::PURGE_INTERVAL);
+
+    FlightServer::new(FlightService {
+        server,
+        authz,
+        write_sink,
+        ingest_sink,
+        polled_queries,
+        time_provider: Arc::new(SystemProvider::new()),
+    })
+}
+
+/// The type of the Arrow Flight action cancelling a query submitted with
+/// `PollFlightInfo`.
+const CANCEL_FLIGHT_INFO_ACTION: &str = "CancelFlightInfo";
+
+/// The actions supported by `DoAction`, as returned by `ListActions`.
+const SUPPORTED_ACTIONS: [(&str, &str); 3] = [
+    (
+        CANCEL_FLIGHT_INFO_ACTION,
+        "Explicitly cancel a running FlightInfo.\n\
+         Request Message: CancelFlightInfoRequest\n\
+         Response Message: CancelFlightInfoResult",
+    ),
+    (
+        "CreatePreparedStatement",
+        "Creates a reusable prepared statement resource on the server.\n\
+         Request Message: ActionCreatePreparedStatementRequest\n\
+         Response Message: ActionCreatePreparedStatementResult",
+    ),
+    (
+        "ClosePreparedStatement",
+        "Closes a reusable prepared statement resource on the server.\n\
+         Request Message: ActionClosePreparedStatementRequest\n\
+         Response Message: N/A",
+    ),
+];
+
+/// A FlightSQL command planned by [`FlightService::plan_flight_descriptor`].
+struct PlannedFlightDescriptor {
+    namespace_name: String,
+    flight_descriptor: FlightDescriptor,
+    /// The schema of the results of the command.
+    schema: SchemaRef,
+    /// The query to run for the command in `DoGet`.
+    run_query: RunQuery,
+    /// The time ranges to split the results into, one endpoint each, if
+    /// requested by the `x-influxdata-endpoint-split` header and supported
+    /// by the query.
+    time_ranges: Option<Vec<TimestampRange>>,
+    is_debug: bool,
+    /// The permissions the request was authorized with.
+    perms: Vec<authz::Permission>,
+    /// The subject of the authorization of the request.
+    auth_id: Option<String>,
 }
 
 impl FlightService {
+    /// Authorizes and plans the FlightSQL command of the
+    /// [`FlightDescriptor`] of `request`, for the `GetFlightInfo` and
+    /// `GetSchema` methods named by `method`.
+    async fn plan_flight_descriptor(
+        &self,
+        request: Request<FlightDescriptor>,
+        method: &'static str,
+    ) -> Result<PlannedFlightDescriptor> {
+        let external_span_ctx: Option<RequestLogContext> = request.extensions().get().cloned();
+        let span_ctx: Option<SpanContext> = request.extensions().get().cloned();
+        let trace = external_span_ctx.format_jaeger();
+        let metadata = request.metadata();
+
+        let is_debug = has_debug_header(metadata);
+
+        let namespace_name = get_flightsql_namespace(metadata)?;
+        let authz_token = get_flight_authz(metadata);
+        let query_lang = parse_header_str(metadata, IOX_FLIGHT_QUERY_LANGUAGE);
+        let split = get_endpoint_split(metadata)?;
+        let flight_descriptor = request.into_inner();
+
+        // extract the FlightSQL message
+        let cmd = cmd_from_descriptor(flight_descriptor.clone())?;
+        info!(%namespace_name, %cmd, %trace, "{method} request");
+
+        let perms = match (&cmd, &query_lang) {
+            (FlightSQLCommand::CommandStatementQuery(cmd), Some(QueryLanguage::InfluxQL)) => {
+                influxql_permissions(&namespace_name, &cmd.query)?
+            }
+            _ => flightsql_permissions(&namespace_name, &cmd),
+        };
+        let authz = self.authz.authorize(authz_token.clone(), &perms).await?;
+
+        let db = self
+            .server
+            .namespace(
+                &namespace_name,
+                span_ctx.child_span("get_namespace"),
+                is_debug,
+            )
+            .await
+            .context(DatabaseSnafu)?
+            .context(DatabaseNotFoundSnafu {
+                namespace_name: &namespace_name,
+            })?;
+
+        let resolver = Arc::new(
+            QueryDatabaseNamespaceResolver::new(
+                Arc::clone(&self.server),
+                span_ctx.clone(),
+                is_debug,
+            )
+            .with_authorizer(self.authz.clone(), authz_token),
+        );
+        let ctx = db.new_query_context(span_ctx, None);
+        let ns_name = namespace_name.clone();
+        let planner = Planner::new(&ctx)
+            .with_namespace_resolver(resolver)
+            .with_write_sink(self.write_sink.clone());
+        let cmd_captured = cmd.clone();
+
+        // invariant: schema and run_query must both correspond to the same language. This is not
+        // really something that we can encode in the type system, as that would 1. require
+        // changing the wire format that these tickets and such as encoded/decoded as, and 2. mess
+        // with the concept of having unified types for processing something that is either an
+        // InfluxQL query plan or a SQL query plan. So we just process them at the same place and
+        // hope we don't mess that up in the future
+        let (schema, run_query, time_ranges) = ctx
+            // Run planner on a separate threadpool, rather than the IO pool that is servicing this request
+            .run(async move {
+                planner
+                    .flight_sql_get_flight_info_schema(&ns_name, cmd_captured, query_lang, split)
+                    .await
+            })
+            .await
+            .context(PlanningSnafu {
+                namespace_name: &namespace_name,
+                query: format!("{cmd:?}"),
+            })
+            .inspect_err(|e| info!(%namespace_name, %cmd, %trace, %e, "Error running {method}"))
+            .inspect(|_| debug!(%namespace_name, %cmd, %trace, "Completed {method} request"))?;
+
+        Ok(PlannedFlightDescriptor {
+            namespace_name,
+            flight_descriptor,
+            schema,
+            run_query,
+            time_ranges,
+            is_debug,
+            perms,
+            auth_id: authz.into_subject(),
+        })
+    }
+
+    /// Returns the polled query `id`, if the request with `authz_token` is
+    /// made by the subject that submitted the query, and still has all the
+    /// permissions the query was authorized with.
+    async fn polled_query(
+        &self,
+        id: &str,
+        authz_token: Option<Vec<u8>>,
+    ) -> Result<Arc<PolledQuery>> {
+        let query = self
+            .polled_queries
+            .get(id)
+            .context(PolledQueryNotFoundSnafu { id })?;
+
+        let authz = self
+            .authz
+            .authorize(authz_token, query.permissions())
+            .await?;
+        ensure!(authz.subject() == query.auth_id(), PermissionDeniedSnafu);
+
+        Ok(query)
+    }
+
+    /// Returns the response to a `PollFlightInfo` request for `query`.
+    ///
+    /// The response headers describe the progress of the query, using the
+    /// same counters as the trailers of a `DoGet` response.
+    fn poll_info_response(query: &PolledQuery) -> Result<Response<PollInfo>, Status> {
+        let poll_info = query.poll_info()?;
+
+        let mut headers = HeaderMap::new();
+        QueryResponseMetadata {
+            log_entry: Some(Arc::clone(query.log_entry())),
+        }
+        .write_trailers(&mut headers);
+
+        let mut response = Response::new(poll_info);
+        *response.metadata_mut() = MetadataMap::from_headers(headers);
+        Ok(response)
+    }
+
+    /// Implementation of the `CancelFlightInfo` action, which cancels the
+    /// polled query described by the `CancelFlightInfoRequest` in `body`,
+    /// discarding its results.
+    async fn cancel_flight_info(
+        &self,
+        authz_token: Option<Vec<u8>>,
+        body: bytes::Bytes,
+    ) -> Result<arrow_flight::Result> {
+        let request = CancelFlightInfoRequest::decode(body).context(DeserializationSnafu)?;
+
+        // only polled queries run independently of a request
+        let status = match request.info.as_ref().and_then(poll::id_from_flight_info) {
+            Some(id) => {
+                self.polled_query(id, authz_token).await?;
+                self.polled_queries.remove(id);
+                CancelStatus::Cancelled
+            }
+            None => CancelStatus::NotCancellable,
+        };
+
+        let result = CancelFlightInfoResult {
+            status: status.into(),
+        };
+        Ok(arrow_flight::Result {
+            body: result.encode_to_vec().into(),
+        })
+    }
+
+    /// Implementation of the `DoExchange` method, which evaluates the
+    /// continuous `query` for each of its windows, and streams back the
+    /// results.
+    ///
+    /// Each evaluation runs like a `DoGet` request whose ticket is restricted
+    /// to the window, once the request is authorized again. The schema of the
+    /// results is only sent once, and the stream ends with an error if it
+    /// changes.
+    async fn run_continuous_query(
+        &self,
+        query: ContinuousQuery,
+        authz_token: Option<Vec<u8>>,
+        span_ctx: Option<SpanContext>,
+        external_span_ctx: Option<RequestLogContext>,
+        query_config: Option<QueryConfig>,
+    ) -> Result<TonicStream<FlightData>> {
+        query
+            .check_start(self.time_provider.now())
+            .context(InvalidContinuousQuerySnafu)?;
+
+        let perms = get_request_permissions(&query.request)?;
+        let auth_id = self
+            .authz
+            .authorize(authz_token.clone(), &perms)
+            .await?
+            .into_subject();
+
+        info!(
+            namespace_name=%query.request.database(),
+            query=%query.request.query(),
+            window=?query.window,
+            trace=external_span_ctx.format_jaeger().as_str(),
+            "DoExchange continuous query",
+        );
+
+        let server = Arc::clone(&self.server);
+        let authz = self.authz.clone();
+        let write_sink = self.write_sink.clone();
+        let windows = continuous::windows(&query, Arc::clone(&self.time_provider));
+        let request = query.request;
+
+        let results = windows
+            .then(move |range| {
+                let server = Arc::clone(&server);
+                let authz = authz.clone();
+                let authz_token = authz_token.clone();
+                let perms = perms.clone();
+                let write_sink = write_sink.clone();
+                let span_ctx = span_ctx.clone();
+                let external_span_ctx = external_span_ctx.clone();
+                let request = request.clone().with_time_range(range);
+                let auth_id = auth_id.clone();
+
+                async move {
+                    // the subscription outlives the authorization of the
+                    // request, which may be revoked in the meantime
+                    authz
+                        .authorize(authz_token.clone(), &perms)
+                        .await
+                        .map_err(Error::from)
+                        .and_then(|authz| {
+                            ensure!(authz.subject() == auth_id.as_deref(), PermissionDeniedSnafu);
+                            Ok(())
+                        })?;
+
+                    let mut log_entry = None;
+                    Self::run_do_get(
+                        server,
+                        authz,
+                        authz_token,
+                        write_sink,
+                        span_ctx,
+                        external_span_ctx,
+                        request,
+                        &mut log_entry,
+                        query_config.as_ref(),
+                        auth_id,
+                    )
+                    .await
+                }
+            })
+            .flat_map(|response| match response {
+                Ok(stream) => stream,
+                Err(e) => futures::stream::once(async { Err(e) }).boxed(),
+            });
+        let results = continuous::merge_results(results);
+
+        // windows may be far apart, so keep the stream alive in between
+        Ok(KeepAliveStream::new(results, DO_GET_KEEP_ALIVE_INTERVAL).boxed())
+    }
+
+    /// Implementation of the `DoPut` method for a [`DescriptorType::Path`]
+    /// descriptor, which writes the record batches of `stream` to the table
+    /// named by `path` in `namespace_name`.
+    async fn run_do_put_ingest<S>(
+        &self,
+        namespace_name: String,
+        authz_token: Option<Vec<u8>>,
+        path: &[String],
+        stream: S,
+    ) -> Result<PutResult>
+    where
+        S: Stream<Item = Result<FlightData, Status>> + Send,
+    {
+        let table = ingest::ingest_table_name(path)?;
+
+        let perms = [authz::Permission::ResourceAction(
+            authz::Resource::Database(authz::Target::ResourceName(namespace_name.clone())),
+            authz::Action::Write,
+        )];
+        self.authz.authorize(authz_token, &perms).await?;
+
+        let sink = self
+            .ingest_sink
+            .as_ref()
+            .ok_or_else(|| Error::unsupported_message_type("DoPut with Path"))?;
+
+        ingest::write_ingest_stream(sink.as_ref(), &namespace_name, table, stream, Ok).await?;
+
+        Ok(PutResult {
+            app_metadata: Default::default(),
+        })
+    }
+
+    /// Implementation of the `DoPut` method for a FlightSQL
+    /// [`CommandStatementIngest`], which appends the record batches of
+    /// `stream` to the table named by `cmd`, and returns the number of rows
+    /// written in a [`DoPutUpdateResult`].
+    async fn run_do_put_statement_ingest<S>(
+        &self,
+        namespace_name: &str,
+        cmd: &CommandStatementIngest,
+        ctx: &IOxSessionContext,
+        stream: S,
+    ) -> Result<PutResult>
+    where
+        S: Stream<Item = Result<FlightData, Status>> + Send + 'static,
+    {
+        let sink =
+            Arc::clone(self.ingest_sink.as_ref().ok_or_else(|| {
+                Error::unsupported_message_type("DoPut with CommandStatementIngest")
+            })?);
+
+        let table = cmd.table.clone();
+        let table_schema = FlightSQLPlanner::get_ingest_table_schema(cmd, ctx)
+            .await
+            .context(FlightSQLSnafu)?
+            .map(Schema::try_from)
+            .transpose()
+            .context(IngestTableSchemaSnafu { table: &table })?;
+
+        // Decode and convert the record batches on a separate threadpool,
+        // rather than the IO pool that is servicing this request
+        let ns_name = namespace_name.to_string();
+        let rows = ctx
+            .run(async move {
+                let rows =
+                    ingest::write_ingest_stream(sink.as_ref(), &ns_name, &table, stream, |batch| {
+                        ingest::to_influx_batch(&table, table_schema.as_ref(), &batch)
+                    })
+                    .await;
+                Ok(rows)
+            })
+            .await
+            .context(WriteSnafu { namespace_name })??;
+        let record_count = rows as i64;
+
+        Ok(PutResult {
+            app_metadata: DoPutUpdateResult { record_count }.encode_to_vec().into(),
+        })
+    }
+
     /// Implementation of the `DoGet` method
+    #[expect(clippy::too_many_arguments)]
     async fn run_do_get(
         server: Arc<dyn QueryDatabase>,
+        authz: Option<Arc<dyn Authorizer>>,
+        authz_token: Option<Vec<u8>>,
+        write_sink: Option<Arc<dyn WriteSink>>,
         span_ctx: Option<SpanContext>,
         external_span_ctx: Option<RequestLogContext>,
         request: IoxGetRequest,
@@ -640,6 +1282,7 @@ impl FlightService {
             query,
             params,
             is_debug,
+            time_range,
         } = request;
         let namespace: Arc<str> = database.into();
         let namespace_name = Arc::clone(&namespace);
@@ -675,8 +1318,14 @@ impl FlightService {
             "DoGet request",
         );
 
+        let resolver = Arc::new(
+            QueryDatabaseNamespaceResolver::new(Arc::clone(&server), span_ctx.clone(), is_debug)
+                .with_authorizer(authz, authz_token),
+        );
         let ctx = db.new_query_context(span_ctx, query_config);
-        let planner = Planner::new(&ctx);
+        let planner = Planner::new(&ctx)
+            .with_namespace_resolver(resolver)
+            .with_write_sink(write_sink);
         let query = Arc::new(query);
 
         let q = Arc::clone(&query);
@@ -685,12 +1334,29 @@ impl FlightService {
         // Run planner on a separate threadpool, rather than the IO pool that is servicing this request
         let physical_plan_res = ctx
             .run(async move {
-                match q.as_ref() {
-                    RunQuery::FlightSQL(msg) => {
+                match (q.as_ref(), time_range) {
+                    (RunQuery::Sql(sql_query), Some(range)) => {
+                        planner.sql_in_time_range(sql_query, params, range).await
+                    }
+                    (
+                        RunQuery::FlightSQL(FlightSQLCommand::CommandStatementQuery(cmd)),
+                        Some(range),
+                    ) => planner.sql_in_time_range(&cmd.query, params, range).await,
+                    (RunQuery::InfluxQL(sql_query), Some(range)) => {
+                        planner
+                            .influxql_in_time_range(sql_query, params, range)
+                            .await
+                    }
+                    (RunQuery::FlightSQL(msg), Some(_)) => Err(DataFusionError::NotImplemented(
+                        format!("time range for FlightSQL command {msg}"),
+                    )),
+                    (RunQuery::FlightSQL(msg), None) => {
                         planner.flight_sql_do_get(&ns, db, msg.clone()).await
                     }
-                    RunQuery::Sql(sql_query) => planner.sql(sql_query, params).await,
-                    RunQuery::InfluxQL(sql_query) => planner.influxql(sql_query, params).await,
+                    (RunQuery::Sql(sql_query), None) => planner.sql(sql_query, params).await,
+                    (RunQuery::InfluxQL(sql_query), None) => {
+                        planner.influxql(sql_query, params).await
+                    }
                 }
             })
             .await
@@ -750,11 +1416,21 @@ impl Flight for FlightService {
     type ListActionsStream = TonicStream<ActionType>;
     type DoExchangeStream = TonicStream<FlightData>;
 
+    /// Handles `GetSchema` RPC requests, returning the schema of the
+    /// results of the FlightSQL command of the [`FlightDescriptor`] as
+    /// planned by `GetFlightInfo`.
     async fn get_schema(
         &self,
-        _request: Request<FlightDescriptor>,
+        request: Request<FlightDescriptor>,
     ) -> Result<Response<SchemaResult>, Status> {
-        Err(Status::unimplemented("Not yet implemented: get_schema"))
+        let PlannedFlightDescriptor { schema, .. } =
+            self.plan_flight_descriptor(request, "GetSchema").await?;
+
+        let options = IpcWriteOptions::default();
+        let schema = SchemaResult::try_from(SchemaAsIpc::new(schema.as_ref(), &options))
+            .context(EncodeSchemaSnafu)?;
+
+        Ok(Response::new(schema))
     }
 
     async fn do_get(
@@ -776,6 +1452,14 @@ impl Flight for FlightService {
         let query_config = get_query_config(metadata);
         let ticket = request.into_inner();
 
+        // the results of a query submitted with `PollFlightInfo` are buffered
+        if let Some(id) = poll::id_from_ticket(&ticket) {
+            let query = self.polled_query(id, authz_token).await?;
+            let data = query.results().context(PolledQueryNotFoundSnafu { id })?;
+            let stream = futures::stream::iter(data.into_iter().map(Ok));
+            return Ok(Response::new(stream.boxed()));
+        }
+
         // attempt to decode ticket
         let request = IoxGetRequest::try_decode(ticket)
             .context(InvalidTicketSnafu)
@@ -787,18 +1471,10 @@ impl Flight for FlightService {
             ext.set(request.query().variant())
         }
 
-        let perms = match request.query() {
-            RunQuery::FlightSQL(cmd) => flightsql_permissions(request.database(), cmd),
-            RunQuery::Sql(_) | RunQuery::InfluxQL(_) => vec![authz::Permission::ResourceAction(
-                authz::Resource::Database(authz::Target::ResourceName(
-                    request.database().to_string(),
-                )),
-                authz::Action::Read,
-            )],
-        };
+        let perms = get_request_permissions(&request)?;
         let authz = self
             .authz
-            .authorize(authz_token, &perms)
+            .authorize(authz_token.clone(), &perms)
             .await
             .map_err(Error::from)?;
 
@@ -816,6 +1492,9 @@ impl Flight for FlightService {
         let mut log_entry = None;
         let response = Self::run_do_get(
             server,
+            self.authz.clone(),
+            authz_token,
+            self.write_sink.clone(),
             span_ctx,
             external_span_ctx,
             request,
@@ -893,38 +1572,25 @@ impl Flight for FlightService {
         Ok(response)
     }
 
+    /// Handles `ListFlights` RPC requests, returning a [`FlightInfo`] for
+    /// each table of the namespace, as described on [`FlightService`]. The
+    /// [`Criteria`] are ignored.
     async fn list_flights(
         &self,
-        _request: Request<Criteria>,
+        request: Request<Criteria>,
     ) -> Result<Response<Self::ListFlightsStream>, Status> {
-        Err(Status::unimplemented("Not yet implemented: list_flights"))
-    }
-
-    /// Handles `GetFlightInfo` RPC requests. The [`FlightDescriptor`]
-    /// is treated containing an FlightSQL command, encoded as a binary
-    /// ProtoBuf message.
-    ///
-    /// see [`FlightService`] for more details.
-    async fn get_flight_info(
-        &self,
-        request: Request<FlightDescriptor>,
-    ) -> Result<Response<FlightInfo>, Status> {
         let external_span_ctx: Option<RequestLogContext> = request.extensions().get().cloned();
         let span_ctx: Option<SpanContext> = request.extensions().get().cloned();
         let trace = external_span_ctx.format_jaeger();
-        let metadata = request.metadata();
-
-        let is_debug = has_debug_header(metadata);
+        let is_debug = has_debug_header(request.metadata());
 
-        let namespace_name = get_flightsql_namespace(metadata)?;
-        let authz_token = get_flight_authz(metadata);
-        let query_lang = parse_header_str(metadata, IOX_FLIGHT_QUERY_LANGUAGE);
-        let flight_descriptor = request.into_inner();
+        let namespace_name = get_flightsql_namespace(request.metadata())?;
+        let authz_token = get_flight_authz(request.metadata());
 
-        // extract the FlightSQL message
-        let cmd = cmd_from_descriptor(flight_descriptor.clone())?;
-        info!(%namespace_name, %cmd, %trace, "GetFlightInfo request");
+        info!(%namespace_name, %trace, "ListFlights request");
 
+        // listing tables reveals the same information as a FlightSQL `GetTables` command
+        let cmd = FlightSQLCommand::CommandGetTables(Default::default());
         let perms = flightsql_permissions(&namespace_name, &cmd);
         self.authz
             .authorize(authz_token, &perms)
@@ -945,44 +1611,74 @@ impl Flight for FlightService {
             })?;
 
         let ctx = db.new_query_context(span_ctx, None);
-        let ns_name = namespace_name.clone();
         let planner = Planner::new(&ctx);
-        let cmd_captured = cmd.clone();
-
-        // invariant: schema and run_query must both correspond to the same language. This is not
-        // really something that we can encode in the type system, as that would 1. require
-        // changing the wire format that these tickets and such as encoded/decoded as, and 2. mess
-        // with the concept of having unified types for processing something that is either an
-        // InfluxQL query plan or a SQL query plan. So we just process them at the same place and
-        // hope we don't mess that up in the future
-        let (schema, run_query) = ctx
-            // Run planner on a separate threadpool, rather than the IO pool that is servicing this request
-            .run(async move {
-                planner
-                    .flight_sql_get_flight_info_schema(&ns_name, cmd_captured, query_lang)
-                    .await
-            })
+        // Run planner on a separate threadpool, rather than the IO pool that is servicing this request
+        let tables = ctx
+            .run(async move { planner.table_schemas().await })
             .await
             .context(PlanningSnafu {
                 namespace_name: &namespace_name,
-                query: format!("{cmd:?}"),
+                query: "ListFlights",
+            })?;
+
+        let flights = tables
+            .into_iter()
+            .map(|(table_name, schema)| {
+                let query = format!("SELECT * FROM {}", quote_identifier(&table_name));
+                let ticket = IoxGetRequest::new(&namespace_name, RunQuery::Sql(query), is_debug)
+                    .try_encode()
+                    .context(InternalCreatingTicketSnafu)?;
+
+                FlightInfo::new()
+                    .with_endpoint(FlightEndpoint::new().with_ticket(ticket))
+                    .with_descriptor(FlightDescriptor::new_path(vec![table_name]))
+                    .try_with_schema(schema.as_ref())
+                    .context(EncodeSchemaSnafu)
             })
-            .inspect_err(
-                |e| info!(%namespace_name, %cmd, %trace, %e, "Error running GetFlightInfo"),
-            )
-            .inspect(
-                |_| debug!(%namespace_name, %cmd, %trace, "Completed GetFlightInfo request"),
-            )?;
+            .collect::<Result<Vec<_>>>()?;
+
+        let stream = futures::stream::iter(flights.into_iter().map(Ok));
+        Ok(Response::new(stream.boxed()))
+    }
 
-        // Form the response ticket (that the client will pass back to DoGet)
-        let ticket = IoxGetRequest::new(&namespace_name, run_query, is_debug)
-            .try_encode()
-            .context(InternalCreatingTicketSnafu)?;
+    /// Handles `GetFlightInfo` RPC requests. The [`FlightDescriptor`]
+    /// is treated containing an FlightSQL command, encoded as a binary
+    /// ProtoBuf message.
+    ///
+    /// see [`FlightService`] for more details.
+    async fn get_flight_info(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let PlannedFlightDescriptor {
+            namespace_name,
+            flight_descriptor,
+            schema,
+            run_query,
+            time_ranges,
+            is_debug,
+            ..
+        } = self
+            .plan_flight_descriptor(request, "GetFlightInfo")
+            .await?;
+
+        // Form the response tickets (that the client will pass back to DoGet)
+        let request = IoxGetRequest::new(&namespace_name, run_query, is_debug);
+        let requests = match time_ranges {
+            Some(ranges) => ranges
+                .into_iter()
+                .map(|range| request.clone().with_time_range(range))
+                .collect(),
+            None => vec![request],
+        };
 
-        let endpoint = FlightEndpoint::new().with_ticket(ticket);
+        let mut flight_info = FlightInfo::new();
+        for request in requests {
+            let ticket = request.try_encode().context(InternalCreatingTicketSnafu)?;
+            flight_info = flight_info.with_endpoint(FlightEndpoint::new().with_ticket(ticket));
+        }
 
-        let flight_info = FlightInfo::new()
-            .with_endpoint(endpoint)
+        let flight_info = flight_info
             // return descriptor we were passed
             .with_descriptor(flight_descriptor)
             .try_with_schema(schema.as_ref())
@@ -991,11 +1687,62 @@ impl Flight for FlightService {
         Ok(Response::new(flight_info))
     }
 
+    /// Handles `PollFlightInfo` RPC requests, as described on
+    /// [`FlightService`].
+    ///
+    /// A FlightSQL command starts a new query, which is planned as by
+    /// `GetFlightInfo`. The descriptor returned for a running query polls
+    /// it again.
     async fn poll_flight_info(
         &self,
-        _request: Request<FlightDescriptor>,
+        request: Request<FlightDescriptor>,
     ) -> Result<Response<PollInfo>, Status> {
-        Err(Status::unimplemented("Not yet implemented"))
+        let authz_token = get_flight_authz(request.metadata());
+
+        if let Some(id) = poll::id_from_descriptor(request.get_ref()) {
+            let query = self.polled_query(id, authz_token).await?;
+            return Self::poll_info_response(&query);
+        }
+
+        let external_span_ctx: Option<RequestLogContext> = request.extensions().get().cloned();
+        let span_ctx: Option<SpanContext> = request.extensions().get().cloned();
+        let query_config = get_query_config(request.metadata());
+
+        let PlannedFlightDescriptor {
+            namespace_name,
+            schema,
+            run_query,
+            is_debug,
+            perms,
+            auth_id,
+            ..
+        } = self
+            .plan_flight_descriptor(request, "PollFlightInfo")
+            .await?;
+
+        let mut log_entry = None;
+        let stream = Self::run_do_get(
+            Arc::clone(&self.server),
+            self.authz.clone(),
+            authz_token,
+            self.write_sink.clone(),
+            span_ctx,
+            external_span_ctx,
+            IoxGetRequest::new(&namespace_name, run_query, is_debug),
+            &mut log_entry,
+            query_config.as_ref(),
+            auth_id.clone(),
+        )
+        .await?;
+        let log_entry = log_entry.expect("query is logged before execution");
+
+        // the query log entry ID identifies the query in subsequent requests
+        let id = log_entry.state().id.to_string();
+        let query = self
+            .polled_queries
+            .start(id, auth_id, perms, schema, log_entry, stream);
+
+        Self::poll_info_response(&query)
     }
 
     async fn do_put(
@@ -1021,11 +1768,25 @@ impl Flight for FlightService {
             .transpose()?
             .and_then(|data| data.flight_descriptor)
             .context(NoFlightDescriptorSnafu)?;
+        // a path descriptor names the table to write the record batches of the stream to
+        if flight_descriptor.r#type() == DescriptorType::Path {
+            info!(%namespace_name, path=?flight_descriptor.path, %trace, "DoPut ingest request");
+
+            let result = self
+                .run_do_put_ingest(namespace_name, authz_token, &flight_descriptor.path, stream)
+                .await?;
+            return Ok(Response::new(futures::stream::iter([Ok(result)]).boxed()));
+        }
+
         // extract the FlightSQL message
         let cmd = FlightSQLCommand::try_decode(flight_descriptor.cmd).context(FlightSQLSnafu)?;
 
         info!(%namespace_name, %cmd, %trace, "DoPut request");
 
+        // reject commands that are never supported before authorizing them,
+        // so that the response does not depend on the permissions of the token
+        FlightSQLPlanner::check_supported(&cmd).map_err(Error::from)?;
+
         let perms = flightsql_permissions(&namespace_name, &cmd);
         self.authz
             .authorize(authz_token, &perms)
@@ -1046,6 +1807,15 @@ impl Flight for FlightService {
             })?;
 
         let ctx = db.new_query_context(span_ctx, None);
+
+        // an ingest command writes the record batches of the stream to a table
+        if let FlightSQLCommand::CommandStatementIngest(ingest) = &cmd {
+            let result = self
+                .run_do_put_statement_ingest(&namespace_name, ingest, &ctx, stream)
+                .await?;
+            return Ok(Response::new(futures::stream::iter([Ok(result)]).boxed()));
+        }
+
         let planner = Planner::new(&ctx);
         let name_captured = namespace_name.clone();
         let cmd_captured = cmd.clone();
@@ -1072,6 +1842,14 @@ impl Flight for FlightService {
         &self,
         request: Request<Action>,
     ) -> Result<Response<Self::DoActionStream>, Status> {
+        if request.get_ref().r#type == CANCEL_FLIGHT_INFO_ACTION {
+            let authz_token = get_flight_authz(request.metadata());
+            let result = self
+                .cancel_flight_info(authz_token, request.into_inner().body)
+                .await?;
+            return Ok(Response::new(futures::stream::iter([Ok(result)]).boxed()));
+        }
+
         let external_span_ctx: Option<RequestLogContext> = request.extensions().get().cloned();
         let span_ctx: Option<SpanContext> = request.extensions().get().cloned();
         let trace = external_span_ctx.format_jaeger();
@@ -1132,18 +1910,83 @@ impl Flight for FlightService {
         Ok(Response::new(stream.boxed()))
     }
 
+    /// Handles `ListActions` RPC requests, returning the actions supported
+    /// by `DoAction`.
+    ///
+    /// The request is authorized in the same way as `ListFlights`, requiring
+    /// permission to read the schema of the namespace.
     async fn list_actions(
         &self,
-        _request: Request<Empty>,
+        request: Request<Empty>,
     ) -> Result<Response<Self::ListActionsStream>, Status> {
-        Err(Status::unimplemented("Not yet implemented: list_actions"))
+        let external_span_ctx: Option<RequestLogContext> = request.extensions().get().cloned();
+        let trace = external_span_ctx.format_jaeger();
+
+        let namespace_name = get_flightsql_namespace(request.metadata())?;
+        let authz_token = get_flight_authz(request.metadata());
+
+        info!(%namespace_name, %trace, "ListActions request");
+
+        let cmd = FlightSQLCommand::CommandGetTables(Default::default());
+        let perms = flightsql_permissions(&namespace_name, &cmd);
+        self.authz
+            .authorize(authz_token, &perms)
+            .await
+            .map_err(Error::from)?;
+
+        let actions = SUPPORTED_ACTIONS.map(|(r#type, description)| {
+            Ok(ActionType {
+                r#type: r#type.to_string(),
+                description: description.to_string(),
+            })
+        });
+
+        Ok(Response::new(futures::stream::iter(actions).boxed()))
     }
 
+    /// Handles `DoExchange` RPC requests, subscribing to the continuous
+    /// query sent as the command of the [`FlightDescriptor`] of the first
+    /// message, as described on [`FlightService`].
+    ///
+    /// Any further messages sent by the client are ignored.
     async fn do_exchange(
         &self,
-        _request: Request<Streaming<FlightData>>,
+        request: Request<Streaming<FlightData>>,
     ) -> Result<Response<Self::DoExchangeStream>, Status> {
-        Err(Status::unimplemented("Not yet implemented: do_exchange"))
+        let external_span_ctx: Option<RequestLogContext> = request.extensions().get().cloned();
+        let span_ctx: Option<SpanContext> = request.extensions().get().cloned();
+        let metadata = request.metadata();
+        let authz_token = get_flight_authz(metadata);
+        let debug_header = has_debug_header(metadata);
+        let query_config = get_query_config(metadata);
+
+        let mut messages = request.into_inner();
+        let cmd = messages
+            .message()
+            .await?
+            .and_then(|data| data.flight_descriptor)
+            .filter(|descriptor| descriptor.r#type() == DescriptorType::Cmd)
+            .map(|descriptor| descriptor.cmd)
+            .ok_or_else(|| request::Error::InvalidContent {
+                msg: "the first message must have a command FlightDescriptor".to_string(),
+            });
+        let mut query = cmd
+            .and_then(ContinuousQuery::try_decode)
+            .context(InvalidContinuousQuerySnafu)
+            .inspect_err(|e| info!(%e, "Error decoding Flight API continuous query"))?;
+        query.request = query.request.add_debug_header(debug_header);
+
+        let stream = self
+            .run_continuous_query(
+                query,
+                authz_token,
+                span_ctx,
+                external_span_ctx,
+                query_config,
+            )
+            .await?;
+
+        Ok(Response::new(stream))
     }
 }
 
@@ -1205,6 +2048,18 @@ fn get_flight_authz(metadata: &MetadataMap) -> Option<Vec<u8>> {
     extract_token(metadata.get("authorization"))
 }
 
+/// The permissions required to run the query of a `DoGet` ticket.
+fn get_request_permissions(request: &IoxGetRequest) -> Result<Vec<authz::Permission>> {
+    match request.query() {
+        RunQuery::FlightSQL(cmd) => Ok(flightsql_permissions(request.database(), cmd)),
+        RunQuery::Sql(_) => Ok(vec![authz::Permission::ResourceAction(
+            authz::Resource::Database(authz::Target::ResourceName(request.database().to_string())),
+            authz::Action::Read,
+        )]),
+        RunQuery::InfluxQL(query) => influxql_permissions(request.database(), query),
+    }
+}
+
 fn flightsql_permissions(namespace_name: &str, cmd: &FlightSQLCommand) -> Vec<authz::Permission> {
     let resource =
         authz::Resource::Database(authz::Target::ResourceName(namespace_name.to_string()));
@@ -1223,10 +2078,77 @@ fn flightsql_permissions(namespace_name: &str, cmd: &FlightSQLCommand) -> Vec<au
         FlightSQLCommand::CommandGetXdbcTypeInfo(_) => authz::Action::ReadSchema,
         FlightSQLCommand::ActionCreatePreparedStatementRequest(_) => authz::Action::Read,
         FlightSQLCommand::ActionClosePreparedStatementRequest(_) => authz::Action::Read,
+        FlightSQLCommand::CommandStatementIngest(_) => authz::Action::Write,
+        // rejected before authorization, see `FlightSQLPlanner::check_supported`
+        FlightSQLCommand::CommandStatementUpdate(_) => authz::Action::Write,
     };
     vec![authz::Permission::ResourceAction(resource, action)]
 }
 
+/// Returns the permissions required to run the InfluxQL `query` against
+/// `namespace_name`.
+///
+/// Reading a database referenced by a qualified measurement name, such as
+/// `"telegraf"."autogen"."cpu"`, or by the `ON` clause of a metadata statement,
+/// such as `SHOW TAG KEYS ON "telegraf"`, requires the same permission as reading
+/// `namespace_name`, so a query may only read from multiple databases when the
+/// caller is authorized to read all of them.
+///
+/// A `SELECT ... INTO` statement also requires permission to write to the
+/// namespace of the `INTO` clause.
+///
+/// Returns an error if a database and retention policy do not map to a valid
+/// namespace name, or if the `INTO` clause does not specify a namespace.
+fn influxql_permissions(namespace_name: &str, query: &str) -> Result<Vec<authz::Permission>> {
+    // Statements that fail to parse are rejected by the planner.
+    let statement = InfluxQLQueryPlanner::query_to_statement(query).ok();
+    let qualified = statement
+        .as_ref()
+        .and_then(|statement| find_databases(statement).ok())
+        .unwrap_or_default();
+    let into = statement
+        .as_ref()
+        .map(find_into_database)
+        .transpose()
+        .context(QuerySnafu {
+            namespace_name,
+            query,
+        })?
+        .flatten();
+
+    let mut namespaces = BTreeSet::from([namespace_name.to_string()]);
+    for q in &qualified {
+        namespaces.insert(qualified_namespace_name(q)?);
+    }
+
+    let into = into
+        .map(|into| match into {
+            IntoDatabase::Session => Ok(namespace_name.to_string()),
+            IntoDatabase::Qualified(q) => qualified_namespace_name(&q),
+        })
+        .transpose()?;
+
+    Ok(namespaces
+        .into_iter()
+        .map(|namespace_name| (namespace_name, authz::Action::Read))
+        .chain(into.map(|namespace_name| (namespace_name, authz::Action::Write)))
+        .map(|(namespace_name, action)| {
+            authz::Permission::ResourceAction(
+                authz::Resource::Database(authz::Target::ResourceName(namespace_name)),
+                action,
+            )
+        })
+        .collect())
+}
+
+/// Returns the name of the namespace that the database and retention policy of
+/// `q` map to.
+fn qualified_namespace_name(q: &QualifiedDatabase) -> Result<String> {
+    NamespaceName::from_database_and_retention_policy(&q.database, q.retention_policy.as_deref())
+        .map(String::from)
+        .context(InvalidDatabaseNameSnafu)
+}
+
 /// Check if request has IOx debug header set.
 fn has_debug_header(metadata: &MetadataMap) -> bool {
     metadata
@@ -1244,6 +2166,19 @@ fn parse_header_str<C: FromStr>(metadata: &MetadataMap, header_str: &'static str
         .and_then(|s| s.parse().ok())
 }
 
+/// Extract the [`EndpointSplit`] requested by the request metadata, if any.
+fn get_endpoint_split(metadata: &MetadataMap) -> Result<Option<EndpointSplit>> {
+    let Some(value) = metadata.get(IOX_FLIGHT_ENDPOINT_SPLIT_HEADER) else {
+        return Ok(None);
+    };
+    value
+        .to_str()
+        .map_err(|e| e.to_string())
+        .and_then(str::parse)
+        .map(Some)
+        .map_err(|msg| Error::InvalidEndpointSplit { msg })
+}
+
 /// Extract the desired per-query configuration from the request metadata.
 fn get_query_config(metadata: &MetadataMap) -> Option<QueryConfig> {
     let mut config = None;
@@ -1486,12 +2421,21 @@ impl QueryResponseMetadata {
 
 #[cfg(test)]
 mod tests {
-    use arrow_flight::sql::ProstMessageExt;
+    use arrow::{
+        array::{ArrayRef, Float64Array, StringArray, TimestampNanosecondArray},
+        record_batch::RecordBatch,
+    };
+    use arrow_flight::sql::{
+        ProstMessageExt, TableDefinitionOptions, TableExistsOption, TableNotExistOption,
+    };
+    use assert_matches::assert_matches;
     use async_trait::async_trait;
     use authz::{Authorization, Permission};
     use futures::Future;
     use generated_types::metadata::{MetadataKey, MetadataValue};
-    use iox_query::test::TestDatabaseStore;
+    use iox_query::QueryNamespace;
+    use iox_query::test::{TestChunk, TestDatabaseStore};
+    use iox_time::{MockProvider, Time};
     use metric::{Attributes, Metric, U64Gauge};
     use test_helpers::maybe_start_logging;
     use tokio::pin;
@@ -1525,6 +2469,10 @@ mod tests {
         let service = FlightService {
             server: Arc::clone(&test_storage) as _,
             authz: Option::<Arc<dyn Authorizer>>::None,
+            write_sink: None,
+            ingest_sink: None,
+            polled_queries: Default::default(),
+            time_provider: Arc::new(SystemProvider::new()),
         };
         let ticket = Ticket {
             ticket: br#"{"namespace_name": "my_db", "sql_query": "SELECT 1;"}"#
@@ -1686,6 +2634,10 @@ mod tests {
                         Some("GGOD user".to_owned()),
                         perms.to_vec(),
                     )),
+                    b"OTHER" => Ok(Authorization::new(
+                        Some("other user".to_owned()),
+                        perms.to_vec(),
+                    )),
                     b"BAD" => Err(authz::Error::Forbidden {
                         authorization: Authorization::new(Some("BAD user".to_owned()), vec![]),
                     }),
@@ -1698,6 +2650,83 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_influxql_permissions() {
+        fn read(namespace_name: &str) -> Permission {
+            Permission::ResourceAction(
+                authz::Resource::Database(authz::Target::ResourceName(namespace_name.to_string())),
+                authz::Action::Read,
+            )
+        }
+
+        fn write(namespace_name: &str) -> Permission {
+            Permission::ResourceAction(
+                authz::Resource::Database(authz::Target::ResourceName(namespace_name.to_string())),
+                authz::Action::Write,
+            )
+        }
+
+        assert_eq!(
+            influxql_permissions("bananas", "SELECT * FROM cpu").unwrap(),
+            vec![read("bananas")]
+        );
+
+        // Requires reading each database of a qualified measurement name
+        assert_eq!(
+            influxql_permissions(
+                "bananas",
+                r#"SELECT * FROM cpu, "telegraf"."autogen"."cpu", (SELECT * FROM db.rp.disk)"#
+            )
+            .unwrap(),
+            vec![read("bananas"), read("db/rp"), read("telegraf")]
+        );
+
+        // Requires reading the database of the ON clause
+        assert_eq!(
+            influxql_permissions("bananas", "SHOW TAG KEYS ON telegraf").unwrap(),
+            vec![read("bananas"), read("telegraf")]
+        );
+        assert_eq!(
+            influxql_permissions("bananas", "SHOW MEASUREMENTS ON db.rp").unwrap(),
+            vec![read("bananas"), read("db/rp")]
+        );
+
+        // The current database is only included once
+        assert_eq!(
+            influxql_permissions("bananas", "SELECT * FROM bananas..cpu").unwrap(),
+            vec![read("bananas")]
+        );
+
+        // Requires writing the database of the INTO clause
+        assert_eq!(
+            influxql_permissions("bananas", "SELECT mean(usage) INTO cpu_1h FROM cpu").unwrap(),
+            vec![read("bananas"), write("bananas")]
+        );
+        assert_eq!(
+            influxql_permissions("bananas", "SELECT * INTO db.rp.:MEASUREMENT FROM /.*/").unwrap(),
+            vec![read("bananas"), write("db/rp")]
+        );
+
+        // Databases that do not map to a valid namespace name are rejected
+        let err = influxql_permissions("bananas", r#"SELECT * FROM "bad db"..cpu"#).unwrap_err();
+        assert_matches!(err, Error::InvalidDatabaseName { .. });
+        let err =
+            influxql_permissions("bananas", r#"SELECT * INTO "bad db"..cpu FROM cpu"#).unwrap_err();
+        assert_matches!(err, Error::InvalidDatabaseName { .. });
+
+        // The retention policy of the INTO clause requires the database
+        let err =
+            influxql_permissions("bananas", "SELECT * INTO one_year.cpu FROM cpu").unwrap_err();
+        assert_matches!(err, Error::Query { .. });
+        assert_eq!(err.into_status().code(), tonic::Code::InvalidArgument);
+
+        // Invalid statements are rejected when planning
+        assert_eq!(
+            influxql_permissions("bananas", "SELECT").unwrap(),
+            vec![read("bananas")]
+        );
+    }
+
     #[tokio::test]
     async fn do_get_authz() {
         maybe_start_logging();
@@ -1708,6 +2737,10 @@ mod tests {
         let svc = FlightService {
             server: Arc::clone(&test_storage) as _,
             authz: Some(Arc::new(MockAuthorizer {})),
+            write_sink: None,
+            ingest_sink: None,
+            polled_queries: Default::default(),
+            time_provider: Arc::new(SystemProvider::new()),
         };
 
         async fn assert_code(svc: &FlightService, want: Code, request: Request<Ticket>) {
@@ -1781,6 +2814,193 @@ mod tests {
         assert_code(&svc, Code::Unavailable, flightsql_request("Bearer UGLY")).await;
     }
 
+    #[derive(Debug, Default)]
+    struct MockSink {
+        namespace_writes: Mutex<Vec<(String, Vec<String>, usize)>>,
+    }
+
+    #[async_trait]
+    impl IngestSink for MockSink {
+        async fn write_namespace(
+            &self,
+            namespace_name: &str,
+            batches: std::collections::HashMap<String, mutable_batch::MutableBatch>,
+        ) -> datafusion::error::Result<()> {
+            self.namespace_writes.lock().unwrap().push((
+                namespace_name.to_string(),
+                batches.keys().cloned().collect(),
+                batches.values().map(|b| b.rows()).sum(),
+            ));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn do_put_ingest() {
+        let test_storage = Arc::new(TestDatabaseStore::default());
+        let sink = Arc::new(MockSink::default());
+
+        let svc = FlightService {
+            server: Arc::clone(&test_storage) as _,
+            authz: Some(Arc::new(MockAuthorizer {})),
+            write_sink: None,
+            ingest_sink: Some(Arc::clone(&sink) as _),
+            polled_queries: Default::default(),
+            time_provider: Arc::new(SystemProvider::new()),
+        };
+
+        async fn flight_data() -> Vec<Result<FlightData, Status>> {
+            let batch =
+                mutable_batch_lp::lines_to_batches("cpu,host=a usage=1.0 1\ncpu usage=2.0 2", 0)
+                    .unwrap()
+                    .remove("cpu")
+                    .unwrap()
+                    .try_into_arrow(schema::Projection::All)
+                    .unwrap();
+            FlightDataEncoderBuilder::new()
+                .build(futures::stream::iter([Ok(batch)]))
+                .map_err(Status::from)
+                .collect()
+                .await
+        }
+
+        async fn assert_code(
+            svc: &FlightService,
+            want: Code,
+            path: &[&str],
+            authorization: Option<&str>,
+        ) {
+            let path = path.iter().map(ToString::to_string).collect::<Vec<_>>();
+            let got = match svc
+                .run_do_put_ingest(
+                    "bananas".to_string(),
+                    authorization.map(|t| t.as_bytes().to_vec()),
+                    &path,
+                    futures::stream::iter(flight_data().await),
+                )
+                .await
+            {
+                Ok(_) => Code::Ok,
+                Err(e) => Status::from(e).code(),
+            };
+            assert_eq!(want, got);
+        }
+
+        assert_code(&svc, Code::Unauthenticated, &["cpu"], None).await;
+        assert_code(&svc, Code::PermissionDenied, &["cpu"], Some("BAD")).await;
+        assert_code(&svc, Code::InvalidArgument, &["db", "cpu"], Some("GOOD")).await;
+        assert!(sink.namespace_writes.lock().unwrap().is_empty());
+
+        assert_code(&svc, Code::Ok, &["cpu"], Some("GOOD")).await;
+        assert_eq!(
+            *sink.namespace_writes.lock().unwrap(),
+            [("bananas".to_string(), vec!["cpu".to_string()], 2)]
+        );
+
+        // Writes are not supported without a sink
+        let svc = FlightService {
+            ingest_sink: None,
+            ..svc
+        };
+        assert_code(&svc, Code::Unimplemented, &["cpu"], Some("GOOD")).await;
+    }
+
+    #[tokio::test]
+    async fn do_put_statement_ingest() {
+        let test_storage = Arc::new(TestDatabaseStore::default());
+        let db = test_storage.db_or_create("bananas").await;
+        let ctx = db.new_query_context(None, None);
+        let sink = Arc::new(MockSink::default());
+
+        let svc = FlightService {
+            server: Arc::clone(&test_storage) as _,
+            authz: None,
+            write_sink: None,
+            ingest_sink: Some(Arc::clone(&sink) as _),
+            polled_queries: Default::default(),
+            time_provider: Arc::new(SystemProvider::new()),
+        };
+
+        // A plain Arrow record batch, without InfluxDB column type metadata
+        async fn flight_data() -> Vec<Result<FlightData, Status>> {
+            let batch = RecordBatch::try_from_iter([
+                (
+                    "host",
+                    Arc::new(StringArray::from(vec!["a", "b"])) as ArrayRef,
+                ),
+                ("usage", Arc::new(Float64Array::from(vec![1.0, 2.0]))),
+                ("time", Arc::new(TimestampNanosecondArray::from(vec![1, 2]))),
+            ])
+            .unwrap();
+            FlightDataEncoderBuilder::new()
+                .build(futures::stream::iter([Ok(batch)]))
+                .map_err(Status::from)
+                .collect()
+                .await
+        }
+
+        async fn ingest(
+            svc: &FlightService,
+            ctx: &IOxSessionContext,
+            cmd: CommandStatementIngest,
+        ) -> Result<PutResult, Code> {
+            svc.run_do_put_statement_ingest(
+                "bananas",
+                &cmd,
+                ctx,
+                futures::stream::iter(flight_data().await),
+            )
+            .await
+            .map_err(|e| Status::from(e).code())
+        }
+
+        let cmd = |if_not_exist: TableNotExistOption| CommandStatementIngest {
+            table_definition_options: Some(TableDefinitionOptions {
+                if_not_exist: if_not_exist.into(),
+                if_exists: TableExistsOption::Append.into(),
+            }),
+            table: "cpu".to_string(),
+            ..Default::default()
+        };
+
+        let result = ingest(&svc, &ctx, cmd(TableNotExistOption::Create))
+            .await
+            .unwrap();
+        let result = DoPutUpdateResult::decode(result.app_metadata).unwrap();
+        assert_eq!(result.record_count, 2);
+        assert_eq!(
+            *sink.namespace_writes.lock().unwrap(),
+            [("bananas".to_string(), vec!["cpu".to_string()], 2)]
+        );
+
+        // The test database has no tables
+        assert_eq!(
+            ingest(&svc, &ctx, cmd(TableNotExistOption::Fail))
+                .await
+                .unwrap_err(),
+            Code::NotFound
+        );
+
+        let temporary = CommandStatementIngest {
+            temporary: true,
+            ..cmd(TableNotExistOption::Create)
+        };
+        assert_eq!(
+            ingest(&svc, &ctx, temporary).await.unwrap_err(),
+            Code::Unimplemented
+        );
+
+        let other_schema = CommandStatementIngest {
+            schema: Some("system".to_string()),
+            ..cmd(TableNotExistOption::Create)
+        };
+        assert_eq!(
+            ingest(&svc, &ctx, other_schema).await.unwrap_err(),
+            Code::Unimplemented
+        );
+        assert_eq!(sink.namespace_writes.lock().unwrap().len(), 1);
+    }
+
     #[tokio::test]
     async fn get_flight_info_authz() {
         let test_storage = Arc::new(TestDatabaseStore::default());
@@ -1789,6 +3009,10 @@ mod tests {
         let svc = FlightService {
             server: Arc::clone(&test_storage) as _,
             authz: Some(Arc::new(MockAuthorizer {})),
+            write_sink: None,
+            ingest_sink: None,
+            polled_queries: Default::default(),
+            time_provider: Arc::new(SystemProvider::new()),
         };
 
         async fn assert_code(svc: &FlightService, want: Code, request: Request<FlightDescriptor>) {
@@ -1820,4 +3044,509 @@ mod tests {
         assert_code(&svc, Code::PermissionDenied, request("Bearer BAD")).await;
         assert_code(&svc, Code::Unavailable, request("Bearer UGLY")).await;
     }
+
+    #[tokio::test]
+    async fn get_schema() {
+        let test_storage = Arc::new(TestDatabaseStore::default());
+        test_storage.db_or_create("bananas").await;
+
+        let svc = FlightService {
+            server: Arc::clone(&test_storage) as _,
+            authz: None,
+            write_sink: None,
+            ingest_sink: None,
+            polled_queries: Default::default(),
+            time_provider: Arc::new(SystemProvider::new()),
+        };
+
+        let cmd = arrow_flight::sql::CommandGetCatalogs {};
+        let mut req = Request::new(FlightDescriptor::new_cmd(cmd.as_any().encode_to_vec()));
+        req.metadata_mut().insert(
+            MetadataKey::from_static("database"),
+            MetadataValue::from_static("bananas"),
+        );
+
+        let result = svc.get_schema(req).await.unwrap().into_inner();
+        let schema = arrow::datatypes::Schema::try_from(&result).unwrap();
+        let names = schema
+            .fields()
+            .iter()
+            .map(|f| f.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["catalog_name"]);
+
+        // The database header is required
+        let req = Request::new(FlightDescriptor::new_cmd(cmd.as_any().encode_to_vec()));
+        assert_eq!(
+            svc.get_schema(req).await.unwrap_err().code(),
+            Code::InvalidArgument
+        );
+    }
+
+    #[tokio::test]
+    async fn continuous_query() {
+        let test_storage = Arc::new(TestDatabaseStore::default());
+        let db = test_storage.db_or_create("bananas").await;
+        db.add_chunk(
+            "1970-01-01",
+            Arc::new(
+                TestChunk::new("cpu")
+                    .with_tag_column("host")
+                    .with_time_column()
+                    .with_one_row_of_data(),
+            ),
+        );
+
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(2_000)));
+        let svc = FlightService {
+            server: Arc::clone(&test_storage) as _,
+            authz: Some(Arc::new(MockAuthorizer {})),
+            write_sink: None,
+            ingest_sink: None,
+            polled_queries: Default::default(),
+            time_provider: Arc::clone(&time_provider) as _,
+        };
+
+        let query = ContinuousQuery {
+            request: IoxGetRequest::new(
+                "bananas",
+                RunQuery::Sql("SELECT host, time FROM cpu".into()),
+                false,
+            ),
+            window: Duration::from_nanos(1_000),
+            start: Some(Time::from_timestamp_nanos(0)),
+            delay: Duration::ZERO,
+        };
+
+        let err = svc
+            .run_continuous_query(query.clone(), Some(b"BAD".to_vec()), None, None, None)
+            .await
+            .err()
+            .unwrap();
+        assert_matches!(err, Error::PermissionDenied);
+
+        // the first window may not start too far in the past
+        let err = svc
+            .run_continuous_query(
+                ContinuousQuery {
+                    window: Duration::from_nanos(10),
+                    ..query.clone()
+                },
+                Some(b"GOOD".to_vec()),
+                None,
+                None,
+                None,
+            )
+            .await
+            .err()
+            .unwrap();
+        assert_matches!(err, Error::InvalidContinuousQuery { .. });
+
+        let stream = svc
+            .run_continuous_query(query, Some(b"GOOD".to_vec()), None, None, None)
+            .await
+            .unwrap();
+        let mut batches = arrow_flight::decode::FlightRecordBatchStream::new_from_flight_data(
+            stream.map_err(arrow_flight::error::FlightError::from),
+        );
+
+        // the only row, at time 1000, is returned by the window [1000, 2000)
+        let batch = loop {
+            let batch = batches.try_next().await.unwrap().unwrap();
+            if batch.num_rows() > 0 {
+                break batch;
+            }
+        };
+        arrow_util::assert_batches_eq!(
+            [
+                "+------+----------------------------+",
+                "| host | time                       |",
+                "+------+----------------------------+",
+                "| MA   | 1970-01-01T00:00:00.000001 |",
+                "+------+----------------------------+",
+            ],
+            &[batch]
+        );
+    }
+
+    #[tokio::test]
+    async fn get_flight_info_endpoint_split() {
+        let test_storage = Arc::new(TestDatabaseStore::default());
+        let db = test_storage.db_or_create("bananas").await;
+        db.add_chunk(
+            "1970-01-01",
+            Arc::new(
+                TestChunk::new("cpu")
+                    .with_tag_column("host")
+                    .with_time_column(),
+            ),
+        );
+
+        let svc = FlightService {
+            server: Arc::clone(&test_storage) as _,
+            authz: None,
+            write_sink: None,
+            ingest_sink: None,
+            polled_queries: Default::default(),
+            time_provider: Arc::new(SystemProvider::new()),
+        };
+
+        async fn time_ranges(
+            svc: &FlightService,
+            query: &str,
+            split: &str,
+        ) -> Result<Vec<Option<TimestampRange>>, tonic::Status> {
+            let cmd = arrow_flight::sql::CommandStatementQuery {
+                query: query.to_string(),
+                transaction_id: None,
+            };
+            let mut req = Request::new(FlightDescriptor::new_cmd(cmd.as_any().encode_to_vec()));
+            req.metadata_mut().insert(
+                MetadataKey::from_static("database"),
+                MetadataValue::from_static("bananas"),
+            );
+            req.metadata_mut().insert(
+                MetadataKey::from_static(IOX_FLIGHT_ENDPOINT_SPLIT_HEADER),
+                MetadataValue::try_from(split).unwrap(),
+            );
+
+            let flight_info = svc.get_flight_info(req).await?.into_inner();
+            Ok(flight_info
+                .endpoint
+                .into_iter()
+                .map(|endpoint| {
+                    let ticket = endpoint.ticket.unwrap();
+                    IoxGetRequest::try_decode(ticket).unwrap().time_range
+                })
+                .collect())
+        }
+
+        let query = "SELECT host FROM cpu \
+                     WHERE time >= '1970-01-01T00:00:00Z' AND time < '1970-01-01T00:00:00.000000100Z'";
+        assert_eq!(
+            time_ranges(&svc, query, "time-range:2").await.unwrap(),
+            [
+                Some(TimestampRange::new(i64::MIN, 50)),
+                Some(TimestampRange::new(50, i64::MAX)),
+            ]
+        );
+
+        // queries that cannot be split return a single endpoint
+        let query = "SELECT host FROM cpu \
+                     WHERE time >= '1970-01-01T00:00:00Z' AND time < '1970-01-01T00:00:00.000000100Z' \
+                     ORDER BY time";
+        assert_eq!(
+            time_ranges(&svc, query, "time-range:2").await.unwrap(),
+            [None]
+        );
+
+        let query = "SELECT host FROM cpu WHERE time >= '1970-01-01T00:00:00Z'";
+        assert_eq!(
+            time_ranges(&svc, query, "time-range:2").await.unwrap(),
+            [None]
+        );
+
+        // invalid or excessive splits are rejected
+        for split in [
+            "time-range:many".to_string(),
+            format!("time-range:{}", split::MAX_TIME_RANGES + 1),
+            format!("time-range:{}", usize::MAX),
+        ] {
+            let status = time_ranges(&svc, query, &split).await.unwrap_err();
+            assert_eq!(status.code(), tonic::Code::InvalidArgument, "{split}");
+        }
+    }
+
+    #[tokio::test]
+    async fn list_flights() {
+        let test_storage = Arc::new(TestDatabaseStore::default());
+        let db = test_storage.db_or_create("bananas").await;
+        db.add_chunk(
+            "1970-01-01",
+            Arc::new(TestChunk::new("mem").with_time_column()),
+        )
+        .add_chunk(
+            "1970-01-01",
+            Arc::new(
+                TestChunk::new("cpu")
+                    .with_tag_column("host")
+                    .with_time_column(),
+            ),
+        );
+
+        let svc = FlightService {
+            server: Arc::clone(&test_storage) as _,
+            authz: Some(Arc::new(MockAuthorizer {})),
+            write_sink: None,
+            ingest_sink: None,
+            polled_queries: Default::default(),
+            time_provider: Arc::new(SystemProvider::new()),
+        };
+
+        fn request(authorization: &'static str) -> Request<Criteria> {
+            let mut req = Request::new(Criteria::default());
+            req.metadata_mut().insert(
+                MetadataKey::from_static("database"),
+                MetadataValue::from_static("bananas"),
+            );
+            if !authorization.is_empty() {
+                req.metadata_mut().insert(
+                    MetadataKey::from_static("authorization"),
+                    MetadataValue::from_static(authorization),
+                );
+            }
+            req
+        }
+
+        async fn assert_code(svc: &FlightService, want: Code, request: Request<Criteria>) {
+            let got = match svc.list_flights(request).await {
+                Ok(_) => Code::Ok,
+                Err(e) => e.code(),
+            };
+            assert_eq!(want, got);
+        }
+
+        assert_code(&svc, Code::Unauthenticated, request("")).await;
+        assert_code(&svc, Code::PermissionDenied, request("Bearer BAD")).await;
+        assert_code(&svc, Code::Unavailable, request("Bearer UGLY")).await;
+
+        let flights = svc
+            .list_flights(request("Bearer GOOD"))
+            .await
+            .unwrap()
+            .into_inner()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        let paths = flights
+            .iter()
+            .map(|f| f.flight_descriptor.as_ref().unwrap().path.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(paths, [vec!["cpu".to_string()], vec!["mem".to_string()]]);
+
+        let schema = flights[0].clone().try_decode_schema().unwrap();
+        assert!(schema.field_with_name("host").is_ok());
+
+        // Each flight has a ticket reading the whole table
+        let ticket = flights[0].endpoint[0].ticket.clone().unwrap();
+        let request = IoxGetRequest::try_decode(ticket).unwrap();
+        assert_eq!(request.database(), "bananas");
+        assert_matches!(
+            request.query(),
+            RunQuery::Sql(query) if query == "SELECT * FROM cpu"
+        );
+    }
+
+    #[tokio::test]
+    async fn list_actions() {
+        let svc = FlightService {
+            server: Arc::new(TestDatabaseStore::default()),
+            authz: Some(Arc::new(MockAuthorizer {})),
+            write_sink: None,
+            ingest_sink: None,
+            polled_queries: Default::default(),
+            time_provider: Arc::new(SystemProvider::new()),
+        };
+
+        fn request(authorization: &'static str) -> Request<Empty> {
+            let mut req = Request::new(Empty {});
+            req.metadata_mut().insert(
+                MetadataKey::from_static("database"),
+                MetadataValue::from_static("bananas"),
+            );
+            if !authorization.is_empty() {
+                req.metadata_mut().insert(
+                    MetadataKey::from_static("authorization"),
+                    MetadataValue::from_static(authorization),
+                );
+            }
+            req
+        }
+
+        async fn assert_code(svc: &FlightService, want: Code, request: Request<Empty>) {
+            let got = match svc.list_actions(request).await {
+                Ok(_) => Code::Ok,
+                Err(e) => e.code(),
+            };
+            assert_eq!(want, got);
+        }
+
+        assert_code(&svc, Code::Unauthenticated, request("")).await;
+        assert_code(&svc, Code::PermissionDenied, request("Bearer BAD")).await;
+        assert_code(&svc, Code::Unavailable, request("Bearer UGLY")).await;
+
+        let actions = svc
+            .list_actions(request("Bearer GOOD"))
+            .await
+            .unwrap()
+            .into_inner()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        let types = actions
+            .iter()
+            .map(|a| a.r#type.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            [
+                "CancelFlightInfo",
+                "CreatePreparedStatement",
+                "ClosePreparedStatement"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn poll_flight_info() {
+        let test_storage = Arc::new(TestDatabaseStore::default());
+        test_storage.db_or_create("bananas").await;
+
+        let svc = FlightService {
+            server: Arc::clone(&test_storage) as _,
+            authz: Some(Arc::new(MockAuthorizer {})),
+            write_sink: None,
+            ingest_sink: None,
+            polled_queries: Default::default(),
+            time_provider: Arc::new(SystemProvider::new()),
+        };
+
+        fn authorize<T>(mut req: Request<T>, authorization: &'static str) -> Request<T> {
+            req.metadata_mut().insert(
+                MetadataKey::from_static("database"),
+                MetadataValue::from_static("bananas"),
+            );
+            req.metadata_mut().insert(
+                MetadataKey::from_static("authorization"),
+                MetadataValue::from_static(authorization),
+            );
+            req
+        }
+
+        async fn poll(svc: &FlightService, descriptor: FlightDescriptor) -> PollInfo {
+            svc.poll_flight_info(authorize(Request::new(descriptor), "Bearer GOOD"))
+                .await
+                .unwrap()
+                .into_inner()
+        }
+
+        let cmd = arrow_flight::sql::CommandGetCatalogs {};
+        let mut poll_info = poll(
+            &svc,
+            FlightDescriptor::new_cmd(cmd.as_any().encode_to_vec()),
+        )
+        .await;
+
+        // poll until the query completes
+        for _ in 0..100 {
+            let Some(descriptor) = poll_info.flight_descriptor.clone() else {
+                break;
+            };
+            assert!(poll_info.info.as_ref().unwrap().endpoint.is_empty());
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            poll_info = poll(&svc, descriptor).await;
+        }
+        assert_eq!(poll_info.flight_descriptor, None);
+        assert_eq!(poll_info.progress, None);
+        assert!(poll_info.expiration_time.is_some());
+
+        let info = poll_info.info.unwrap();
+        let schema = info.clone().try_decode_schema().unwrap();
+        assert!(schema.field_with_name("catalog_name").is_ok());
+
+        // fetch the buffered results
+        let ticket = info.endpoint[0].ticket.clone().unwrap();
+        let fetch =
+            |authorization| svc.do_get(authorize(Request::new(ticket.clone()), authorization));
+        assert_eq!(
+            fetch("Bearer BAD").await.unwrap_err().code(),
+            Code::PermissionDenied
+        );
+        // only the subject that submitted the query may fetch its results
+        assert_eq!(
+            fetch("Bearer OTHER").await.unwrap_err().code(),
+            Code::PermissionDenied
+        );
+        let data = fetch("Bearer GOOD")
+            .await
+            .unwrap()
+            .into_inner()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert!(!data.is_empty());
+
+        // cancelling discards the results
+        let cancel = |authorization| {
+            let body = CancelFlightInfoRequest {
+                info: Some(info.clone()),
+            };
+            let action = Action {
+                r#type: CANCEL_FLIGHT_INFO_ACTION.to_string(),
+                body: body.encode_to_vec().into(),
+            };
+            svc.do_action(authorize(Request::new(action), authorization))
+        };
+        assert_eq!(
+            cancel("Bearer BAD").await.unwrap_err().code(),
+            Code::PermissionDenied
+        );
+        assert_eq!(
+            cancel("Bearer OTHER").await.unwrap_err().code(),
+            Code::PermissionDenied
+        );
+        let mut results = cancel("Bearer GOOD").await.unwrap().into_inner();
+        let result =
+            CancelFlightInfoResult::decode(results.next().await.unwrap().unwrap().body).unwrap();
+        assert_eq!(result.status(), CancelStatus::Cancelled);
+
+        assert_eq!(
+            fetch("Bearer GOOD").await.unwrap_err().code(),
+            Code::NotFound
+        );
+        assert_eq!(
+            cancel("Bearer GOOD").await.unwrap_err().code(),
+            Code::NotFound
+        );
+    }
+
+    #[tokio::test]
+    async fn poll_flight_info_memory_limit() {
+        let test_storage = Arc::new(TestDatabaseStore::default());
+        test_storage.db_or_create("bananas").await;
+
+        let svc = FlightService {
+            server: Arc::clone(&test_storage) as _,
+            authz: None,
+            write_sink: None,
+            ingest_sink: None,
+            polled_queries: PolledQueries::new(Duration::from_secs(60), 0),
+            time_provider: Arc::new(SystemProvider::new()),
+        };
+
+        let cmd = arrow_flight::sql::CommandGetCatalogs {};
+        let mut req = Request::new(FlightDescriptor::new_cmd(cmd.as_any().encode_to_vec()));
+        req.metadata_mut().insert(
+            MetadataKey::from_static("database"),
+            MetadataValue::from_static("bananas"),
+        );
+        let mut poll_info = svc.poll_flight_info(req).await.unwrap().into_inner();
+
+        let mut code = Code::Ok;
+        for _ in 0..100 {
+            let Some(descriptor) = poll_info.flight_descriptor.clone() else {
+                break;
+            };
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            match svc.poll_flight_info(Request::new(descriptor)).await {
+                Ok(response) => poll_info = response.into_inner(),
+                Err(e) => {
+                    code = e.code();
+                    break;
+                }
+            }
+        }
+        assert_eq!(code, Code::ResourceExhausted);
+    }
 }
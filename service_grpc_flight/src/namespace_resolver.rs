@@ -0,0 +1,190 @@
+//! Resolution of qualified InfluxQL measurement names to IOx namespaces.
+use std::{collections::BTreeSet, sync::Arc};
+
+use async_trait::async_trait;
+use authz::{Action, Authorizer, Permission, Resource, Target};
+use data_types::NamespaceName;
+use datafusion::{
+    catalog::SchemaProvider,
+    error::{DataFusionError, Result},
+};
+use iox_query::QueryDatabase;
+use iox_query_influxql::frontend::namespace::NamespaceResolver;
+use trace::{ctx::SpanContext, span::SpanExt};
+
+/// Resolves the database and retention policy of qualified InfluxQL measurement
+/// names, such as `"telegraf"."autogen"."cpu"`, to the namespaces of a
+/// [`QueryDatabase`].
+///
+/// The database and retention policy map to a namespace using the same rules as
+/// the V1 write API. Callers must have authorized reads of each namespace before
+/// planning the query.
+///
+/// The databases listed by `SHOW DATABASES` are limited to the namespaces the
+/// caller may read, when configured with
+/// [`QueryDatabaseNamespaceResolver::with_authorizer`].
+#[derive(Debug)]
+pub(crate) struct QueryDatabaseNamespaceResolver {
+    server: Arc<dyn QueryDatabase>,
+    span_ctx: Option<SpanContext>,
+    include_debug_info_tables: bool,
+    authz: Option<Arc<dyn Authorizer>>,
+    authz_token: Option<Vec<u8>>,
+}
+
+impl QueryDatabaseNamespaceResolver {
+    pub(crate) fn new(
+        server: Arc<dyn QueryDatabase>,
+        span_ctx: Option<SpanContext>,
+        include_debug_info_tables: bool,
+    ) -> Self {
+        Self {
+            server,
+            span_ctx,
+            include_debug_info_tables,
+            authz: None,
+            authz_token: None,
+        }
+    }
+
+    /// Limit the databases listed by `SHOW DATABASES` to the namespaces that
+    /// `authz_token` is authorized to read.
+    pub(crate) fn with_authorizer(
+        mut self,
+        authz: Option<Arc<dyn Authorizer>>,
+        authz_token: Option<Vec<u8>>,
+    ) -> Self {
+        self.authz = authz;
+        self.authz_token = authz_token;
+        self
+    }
+
+    /// Returns `true` if the caller may read the namespace `namespace_name`.
+    async fn is_readable(&self, namespace_name: &str) -> bool {
+        let perms = [Permission::ResourceAction(
+            Resource::Database(Target::ResourceName(namespace_name.to_owned())),
+            Action::Read,
+        )];
+        self.authz
+            .authorize(self.authz_token.clone(), &perms)
+            .await
+            .is_ok()
+    }
+}
+
+#[async_trait]
+impl NamespaceResolver for QueryDatabaseNamespaceResolver {
+    async fn resolve(
+        &self,
+        database: &str,
+        retention_policy: Option<&str>,
+    ) -> Result<Option<Arc<dyn SchemaProvider>>> {
+        let namespace_name =
+            NamespaceName::from_database_and_retention_policy(database, retention_policy)
+                .map_err(|e| DataFusionError::Plan(format!("invalid database name: {e}")))?;
+
+        let Some(namespace) = self
+            .server
+            .namespace(
+                &namespace_name,
+                self.span_ctx.child_span("get_namespace"),
+                self.include_debug_info_tables,
+            )
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let ctx = namespace.new_query_context(self.span_ctx.clone(), None);
+        let session_cfg = ctx.inner().copied_config();
+        let cfg = session_cfg.options();
+        Ok(ctx
+            .inner()
+            .catalog(&cfg.catalog.default_catalog)
+            .and_then(|catalog| catalog.schema(&cfg.catalog.default_schema)))
+    }
+
+    async fn database_names(&self) -> Result<Vec<String>> {
+        let mut databases = BTreeSet::new();
+        for namespace_name in self.server.namespace_names().await? {
+            if !self.is_readable(&namespace_name).await {
+                continue;
+            }
+
+            // Namespaces of a retention policy other than the default are
+            // named `<database>/<retention policy>`.
+            let database = match namespace_name.split_once('/') {
+                Some((database, _)) => database.to_owned(),
+                None => namespace_name,
+            };
+            databases.insert(database);
+        }
+
+        Ok(databases.into_iter().collect())
+    }
+
+    async fn retention_policy_names(&self, database: &str) -> Result<Vec<String>> {
+        let mut retention_policies = BTreeSet::new();
+        for namespace_name in self.server.namespace_names().await? {
+            let retention_policy = match namespace_name.split_once('/') {
+                Some((db, rp)) if db == database => rp.to_owned(),
+                None if namespace_name == database => "autogen".to_owned(),
+                _ => continue,
+            };
+            if self.is_readable(&namespace_name).await {
+                retention_policies.insert(retention_policy);
+            }
+        }
+
+        Ok(retention_policies.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iox_query::test::TestDatabaseStore;
+
+    #[tokio::test]
+    async fn test_database_names() {
+        let server = Arc::new(TestDatabaseStore::default());
+        server.db_or_create("telegraf").await;
+        server.db_or_create("telegraf/one_week").await;
+        server.db_or_create("metrics").await;
+
+        let resolver = QueryDatabaseNamespaceResolver::new(server, None, false);
+        assert_eq!(
+            resolver.database_names().await.unwrap(),
+            vec!["metrics".to_owned(), "telegraf".to_owned()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retention_policy_names() {
+        let server = Arc::new(TestDatabaseStore::default());
+        server.db_or_create("telegraf").await;
+        server.db_or_create("telegraf/one_week").await;
+        server.db_or_create("telegraf2/one_year").await;
+        server.db_or_create("metrics/one_month").await;
+
+        let resolver = QueryDatabaseNamespaceResolver::new(server, None, false);
+        assert_eq!(
+            resolver.retention_policy_names("telegraf").await.unwrap(),
+            vec!["autogen".to_owned(), "one_week".to_owned()]
+        );
+
+        // A database without a namespace for the autogen retention policy
+        assert_eq!(
+            resolver.retention_policy_names("metrics").await.unwrap(),
+            vec!["one_month".to_owned()]
+        );
+
+        assert!(
+            resolver
+                .retention_policy_names("my_db")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
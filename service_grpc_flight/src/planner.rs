@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use arrow_flight::FlightData;
 use bytes::Bytes;
+use data_types::TimestampRange;
 pub(crate) use datafusion::error::{DataFusionError as Error, Result};
 use datafusion::{
     arrow::datatypes::SchemaRef, error::DataFusionError, physical_plan::ExecutionPlan,
@@ -15,10 +16,15 @@ use iox_query::{
     exec::{IOxSessionContext, QueryLanguage},
     frontend::sql::SqlQueryPlanner,
 };
-use iox_query_influxql::frontend::planner::InfluxQLQueryPlanner;
+use iox_query_influxql::frontend::{
+    namespace::NamespaceResolver, planner::InfluxQLQueryPlanner, select_into::WriteSink,
+};
 use iox_query_params::StatementParams;
 
-use crate::request::RunQuery;
+use crate::{
+    request::RunQuery,
+    split::{self, EndpointSplit},
+};
 
 /// Query planner that plans queries on a separate threadpool.
 ///
@@ -29,6 +35,12 @@ use crate::request::RunQuery;
 pub(crate) struct Planner {
     /// Executors (whose threadpool to use)
     ctx: IOxSessionContext,
+
+    /// Resolves qualified measurement names of InfluxQL queries.
+    namespace_resolver: Option<Arc<dyn NamespaceResolver>>,
+
+    /// Receives the points written by InfluxQL `SELECT ... INTO` queries.
+    write_sink: Option<Arc<dyn WriteSink>>,
 }
 
 impl Planner {
@@ -36,9 +48,24 @@ impl Planner {
     pub(crate) fn new(ctx: &IOxSessionContext) -> Self {
         Self {
             ctx: ctx.child_ctx("flight_planner"),
+            namespace_resolver: None,
+            write_sink: None,
         }
     }
 
+    /// Resolve qualified measurement names of InfluxQL queries, such as
+    /// `"telegraf"."autogen"."cpu"`, using `resolver`.
+    pub(crate) fn with_namespace_resolver(mut self, resolver: Arc<dyn NamespaceResolver>) -> Self {
+        self.namespace_resolver = Some(resolver);
+        self
+    }
+
+    /// Write the points of InfluxQL `SELECT ... INTO` queries to `sink`, if any.
+    pub(crate) fn with_write_sink(mut self, sink: Option<Arc<dyn WriteSink>>) -> Self {
+        self.write_sink = sink;
+        self
+    }
+
     /// Plan a SQL query against the data in a namespace, and return a
     /// DataFusion physical execution plan.
     pub(crate) async fn sql(
@@ -53,6 +80,22 @@ impl Planner {
         planner.query(query, params, &ctx).await
     }
 
+    /// Plan a SQL query against the rows of a namespace whose `time` lies
+    /// within `range`, and return a DataFusion physical execution plan.
+    pub(crate) async fn sql_in_time_range(
+        &self,
+        query: impl AsRef<str> + Send,
+        params: StatementParams,
+        range: TimestampRange,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let query = query.as_ref();
+        let ctx = self.ctx.child_ctx("planner_sql_in_time_range");
+
+        let plan = ctx.sql_to_logical_plan_with_params(query, params).await?;
+        let plan = split::restrict_time_range(plan, range)?;
+        ctx.create_physical_plan(&plan).await
+    }
+
     /// Plan an InfluxQL query against the data in `database`, and return a
     /// DataFusion physical execution plan.
     pub(crate) async fn influxql(
@@ -63,7 +106,36 @@ impl Planner {
         let query = query.as_ref();
         let ctx = self.ctx.child_ctx("planner_influxql");
 
-        InfluxQLQueryPlanner::query(query, params, &ctx).await
+        InfluxQLQueryPlanner::query(
+            query,
+            params,
+            self.namespace_resolver.as_deref(),
+            self.write_sink.clone(),
+            &ctx,
+        )
+        .await
+    }
+
+    /// Plan an InfluxQL query against the rows of `database` whose `time`
+    /// lies within `range`, and return a DataFusion physical execution plan.
+    pub(crate) async fn influxql_in_time_range(
+        &self,
+        query: impl AsRef<str> + Send,
+        params: impl Into<StatementParams> + Send,
+        range: TimestampRange,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let query = query.as_ref();
+        let ctx = self.ctx.child_ctx("planner_influxql_in_time_range");
+
+        InfluxQLQueryPlanner::query_with_rewrite(
+            query,
+            params,
+            self.namespace_resolver.as_deref(),
+            self.write_sink.clone(),
+            &ctx,
+            |plan| split::restrict_time_range(plan, range),
+        )
+        .await
     }
 
     /// Creates a plan for a `DoGet` FlightSQL message, as described on
@@ -121,27 +193,48 @@ impl Planner {
     ///
     /// If query_lang is  Some(InfluxQL), the query is treated as
     /// InfluxQL. Otherwise, the query is treated as SQL.
+    ///
+    /// If `endpoint_split` is `Some`, also returns the time ranges to split the
+    /// results of a SQL query into, as described on [`split::time_ranges`],
+    /// computed from the same logical plan as the schema. The time ranges are
+    /// [`None`] if the query cannot be split.
     pub(crate) async fn flight_sql_get_flight_info_schema(
         &self,
         namespace_name: impl Into<String> + Send,
         cmd: FlightSQLCommand,
         query_lang: Option<QueryLanguage>,
-    ) -> Result<(SchemaRef, RunQuery)> {
+        endpoint_split: Option<EndpointSplit>,
+    ) -> Result<(SchemaRef, RunQuery, Option<Vec<TimestampRange>>)> {
         let namespace_name = namespace_name.into();
         let ctx = self.ctx.child_ctx("planner_flight_sql_get_flight_info");
         let query_lang = query_lang.unwrap_or(QueryLanguage::Sql);
 
-        match (cmd, query_lang) {
+        match (cmd, query_lang, endpoint_split) {
             // We only want to handle queries with an InfluxQL header like they're actually
             // InfluxQL if they come with a CommandStatementQuery cmd, because that's the only
             // variant that could reasonably be processed as InfluxQL and we're not certain that
             // flightsql clients have the capability to switch this header on and off per-request,
             // so instead of just failing on influxql commands that aren't of this variant, we want
             // to let commands kinda do what you'd expect.
-            (FlightSQLCommand::CommandStatementQuery(cmd), QueryLanguage::InfluxQL) => self
+            (FlightSQLCommand::CommandStatementQuery(cmd), QueryLanguage::InfluxQL, _) => self
                 .influxql_query_to_schema(&cmd.query)
                 .await
-                .map(|schema| (schema, RunQuery::InfluxQL(cmd.query))),
+                .map(|schema| (schema, RunQuery::InfluxQL(cmd.query), None)),
+
+            (
+                FlightSQLCommand::CommandStatementQuery(cmd),
+                QueryLanguage::Sql,
+                Some(EndpointSplit::TimeRange(n)),
+            ) => {
+                let plan = ctx.sql_to_logical_plan(&cmd.query).await?;
+                let schema = FlightSQLPlanner::get_schema_for_plan(&plan);
+                let time_ranges = split::time_ranges(&plan, n)?;
+                Ok((
+                    schema,
+                    RunQuery::FlightSQL(FlightSQLCommand::CommandStatementQuery(cmd)),
+                    time_ranges,
+                ))
+            }
 
             // this seems kinda unnecessary (as it's basically a wildcard but verbose) but we want
             // to make sure nobody misses this in the future if another language is added to this
@@ -151,22 +244,52 @@ impl Planner {
             // we've already verified that, up above, we matched the special case(s) where we want to
             // actually process InfluxQL, and here we just want to fallback to normal sql
             // processing.
-            (cmd, QueryLanguage::Sql | QueryLanguage::InfluxQL) => {
+            (cmd, QueryLanguage::Sql | QueryLanguage::InfluxQL, _) => {
                 FlightSQLPlanner::get_schema(namespace_name, &cmd, &ctx)
                     .await
                     .map_err(DataFusionError::from)
-                    .map(|schema| (schema, RunQuery::FlightSQL(cmd)))
+                    .map(|schema| (schema, RunQuery::FlightSQL(cmd), None))
+            }
+        }
+    }
+
+    /// Returns the name and schema of each table of the namespace, ordered
+    /// by name, on a separate threadpool.
+    pub(crate) async fn table_schemas(&self) -> Result<Vec<(String, SchemaRef)>> {
+        let ctx = self.ctx.child_ctx("planner_table_schemas");
+        let state = ctx.inner().state();
+        let options = &state.config_options().catalog;
+
+        let Some(schema) = ctx
+            .inner()
+            .catalog(&options.default_catalog)
+            .and_then(|catalog| catalog.schema(&options.default_schema))
+        else {
+            return Ok(vec![]);
+        };
+
+        let mut tables = vec![];
+        for table_name in schema.table_names() {
+            if let Some(table) = schema.table(&table_name).await? {
+                tables.push((table_name, table.schema()));
             }
         }
+        tables.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        Ok(tables)
     }
 
     async fn influxql_query_to_schema(&self, query: &str) -> Result<SchemaRef> {
         let ctx = self.ctx.child_ctx("planner_influxql_query_to_schema");
 
         let statement = InfluxQLQueryPlanner::query_to_statement(query)?;
-        let logical_plan =
-            InfluxQLQueryPlanner::statement_to_plan(statement, StatementParams::new(), &ctx)
-                .await?;
+        let logical_plan = InfluxQLQueryPlanner::statement_to_plan(
+            statement,
+            StatementParams::new(),
+            self.namespace_resolver.as_deref(),
+            &ctx,
+        )
+        .await?;
 
         Ok(FlightSQLPlanner::get_schema_for_plan(&logical_plan))
     }
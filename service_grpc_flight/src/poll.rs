@@ -0,0 +1,514 @@
+//! Asynchronous execution of queries submitted with `PollFlightInfo`.
+//!
+//! A polled query runs in the background, buffering its encoded results in
+//! memory until they are fetched with `DoGet`. Clients therefore only hold a
+//! stream open for as long as it takes to transfer the results, rather than
+//! for the whole execution of the query.
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant, SystemTime},
+};
+
+use arrow::datatypes::SchemaRef;
+use arrow_flight::{
+    FlightData, FlightDescriptor, FlightEndpoint, FlightInfo, PollInfo, Ticket,
+    flight_descriptor::DescriptorType,
+};
+use authz::Permission;
+use futures::{Stream, StreamExt};
+use generated_types::Status;
+use iox_query::query_log::QueryLogEntry;
+use snafu::ResultExt;
+use tokio::{
+    task::JoinHandle,
+    time::{MissedTickBehavior, interval},
+};
+
+use crate::{EncodeSchemaSnafu, Error};
+
+/// The first element of the path of the [`FlightDescriptor`] identifying a
+/// polled query, followed by its ID.
+const POLL_DESCRIPTOR_PATH: &str = "iox-polled-query";
+
+/// The prefix of the [`Ticket`] for the results of a polled query, followed by
+/// its ID.
+const POLL_TICKET_PREFIX: &[u8] = b"iox-polled-query/";
+
+/// Default duration for which the results of a polled query are retained
+/// after it finishes.
+const DEFAULT_POLLED_QUERY_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Default limit of the total size of the buffered results of all polled
+/// queries.
+const DEFAULT_POLLED_QUERY_MEMORY_LIMIT: usize = 1024 * 1024 * 1024;
+
+/// Interval at which the polled queries whose results expired are removed,
+/// releasing their memory even if no further queries are polled.
+pub(crate) const PURGE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Configuration of the queries submitted with `PollFlightInfo`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PolledQueryConfig {
+    /// Duration for which the results of a query are retained after it
+    /// finishes.
+    pub ttl: Duration,
+
+    /// Limit of the total size, in bytes, of the buffered results of all
+    /// queries. Queries whose results would exceed it fail.
+    pub memory_limit: usize,
+}
+
+impl Default for PolledQueryConfig {
+    fn default() -> Self {
+        Self {
+            ttl: DEFAULT_POLLED_QUERY_TTL,
+            memory_limit: DEFAULT_POLLED_QUERY_MEMORY_LIMIT,
+        }
+    }
+}
+
+/// Returns the [`FlightDescriptor`] identifying the polled query `id`.
+pub(crate) fn descriptor(id: &str) -> FlightDescriptor {
+    FlightDescriptor::new_path(vec![POLL_DESCRIPTOR_PATH.to_string(), id.to_string()])
+}
+
+/// Returns the ID of the polled query identified by `descriptor`, if any.
+pub(crate) fn id_from_descriptor(descriptor: &FlightDescriptor) -> Option<&str> {
+    if descriptor.r#type() != DescriptorType::Path {
+        return None;
+    }
+    match descriptor.path.as_slice() {
+        [path, id] if path == POLL_DESCRIPTOR_PATH => Some(id),
+        _ => None,
+    }
+}
+
+/// Returns the [`Ticket`] for the results of the polled query `id`.
+pub(crate) fn ticket(id: &str) -> Ticket {
+    Ticket::new([POLL_TICKET_PREFIX, id.as_bytes()].concat())
+}
+
+/// Returns the ID of the polled query whose results `ticket` is for, if any.
+pub(crate) fn id_from_ticket(ticket: &Ticket) -> Option<&str> {
+    let id = ticket.ticket.strip_prefix(POLL_TICKET_PREFIX)?;
+    std::str::from_utf8(id).ok()
+}
+
+/// Returns the ID of the polled query described by `info`, if any.
+pub(crate) fn id_from_flight_info(info: &FlightInfo) -> Option<&str> {
+    info.flight_descriptor
+        .as_ref()
+        .and_then(id_from_descriptor)
+        .or_else(|| {
+            info.endpoint
+                .iter()
+                .filter_map(|endpoint| endpoint.ticket.as_ref())
+                .find_map(id_from_ticket)
+        })
+}
+
+/// The polled queries of a Flight service, and their buffered results.
+#[derive(Debug)]
+pub(crate) struct PolledQueries {
+    /// Duration for which the results of a query are retained after it
+    /// finishes.
+    ttl: Duration,
+
+    /// Limit of the total size of the buffered results of all queries.
+    memory_limit: usize,
+
+    /// Total size of the buffered results of all queries.
+    memory_used: Arc<AtomicUsize>,
+
+    queries: Arc<Mutex<HashMap<String, Arc<PolledQuery>>>>,
+}
+
+impl Default for PolledQueries {
+    fn default() -> Self {
+        Self::from(PolledQueryConfig::default())
+    }
+}
+
+impl From<PolledQueryConfig> for PolledQueries {
+    fn from(config: PolledQueryConfig) -> Self {
+        Self::new(config.ttl, config.memory_limit)
+    }
+}
+
+impl PolledQueries {
+    /// Create a new set of polled queries, retaining the results of each
+    /// query for `ttl` after it finishes, and buffering at most
+    /// `memory_limit` bytes of results in total.
+    pub(crate) fn new(ttl: Duration, memory_limit: usize) -> Self {
+        Self {
+            ttl,
+            memory_limit,
+            memory_used: Default::default(),
+            queries: Default::default(),
+        }
+    }
+
+    /// Start buffering the results `stream` of the query `id` in the
+    /// background.
+    ///
+    /// The query was submitted by `auth_id` with the `permissions` it
+    /// requires, see [`PolledQuery::permissions`].
+    pub(crate) fn start<S>(
+        &self,
+        id: String,
+        auth_id: Option<String>,
+        permissions: Vec<Permission>,
+        schema: SchemaRef,
+        log_entry: Arc<QueryLogEntry>,
+        stream: S,
+    ) -> Arc<PolledQuery>
+    where
+        S: Stream<Item = Result<FlightData, Status>> + Send + 'static,
+    {
+        let state = Arc::new(Mutex::new(QueryState::Running));
+        let reservation = MemoryReservation {
+            used: Arc::clone(&self.memory_used),
+            limit: self.memory_limit,
+            size: 0,
+        };
+        let task = tokio::spawn(buffer_results(
+            stream,
+            reservation,
+            self.ttl,
+            Arc::clone(&state),
+        ));
+
+        let query = Arc::new(PolledQuery {
+            id: id.clone(),
+            auth_id,
+            permissions,
+            schema,
+            log_entry,
+            state,
+            task,
+        });
+
+        let mut queries = self.queries.lock().expect("not poisoned");
+        remove_expired(&mut queries);
+        queries.insert(id, Arc::clone(&query));
+        query
+    }
+
+    /// Returns the query `id`, unless it does not exist or its results
+    /// expired.
+    pub(crate) fn get(&self, id: &str) -> Option<Arc<PolledQuery>> {
+        let mut queries = self.queries.lock().expect("not poisoned");
+        remove_expired(&mut queries);
+        queries.get(id).map(Arc::clone)
+    }
+
+    /// Spawn a task removing the queries whose results expired every
+    /// `period`, until `self` is dropped.
+    ///
+    /// Expired queries are otherwise only removed when queries are started
+    /// or looked up.
+    pub(crate) fn spawn_purge_task(&self, period: Duration) -> JoinHandle<()> {
+        let queries = Arc::downgrade(&self.queries);
+        tokio::spawn(async move {
+            let mut ticker = interval(period);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                let Some(queries) = queries.upgrade() else {
+                    return;
+                };
+                remove_expired(&mut queries.lock().expect("not poisoned"));
+            }
+        })
+    }
+
+    /// Remove the query `id`, stopping it if it is running and discarding
+    /// its results.
+    pub(crate) fn remove(&self, id: &str) -> Option<Arc<PolledQuery>> {
+        let query = self.queries.lock().expect("not poisoned").remove(id)?;
+        query.task.abort();
+        Some(query)
+    }
+}
+
+/// Remove the queries of `queries` whose results expired.
+fn remove_expired(queries: &mut HashMap<String, Arc<PolledQuery>>) {
+    let now = Instant::now();
+    queries.retain(|_, query| !query.is_expired(now));
+}
+
+/// A query submitted with `PollFlightInfo`.
+#[derive(Debug)]
+pub(crate) struct PolledQuery {
+    id: String,
+
+    /// The subject of the authorization the query was submitted with.
+    auth_id: Option<String>,
+
+    /// The permissions the query was authorized with.
+    permissions: Vec<Permission>,
+
+    /// The schema of the results of the query.
+    schema: SchemaRef,
+
+    /// The query log entry, describing the progress of the query.
+    log_entry: Arc<QueryLogEntry>,
+
+    state: Arc<Mutex<QueryState>>,
+
+    /// The task buffering the results of the query.
+    task: JoinHandle<()>,
+}
+
+impl PolledQuery {
+    /// The subject of the authorization the query was submitted with.
+    pub(crate) fn auth_id(&self) -> Option<&str> {
+        self.auth_id.as_deref()
+    }
+
+    /// The permissions the query was authorized with, which include those
+    /// to read any database other than its namespace it reads from, e.g.
+    /// with an InfluxQL `ON` clause.
+    pub(crate) fn permissions(&self) -> &[Permission] {
+        &self.permissions
+    }
+
+    /// The query log entry of the query.
+    pub(crate) fn log_entry(&self) -> &Arc<QueryLogEntry> {
+        &self.log_entry
+    }
+
+    /// Returns the [`PollInfo`] describing the current state of the query, or
+    /// the error it failed with.
+    ///
+    /// While the query is running, the [`PollInfo`] has no endpoints, and
+    /// carries the descriptor to poll the query again. Once it completes,
+    /// the [`PollInfo`] has an endpoint with a [`Ticket`] for the results,
+    /// which are retained until its expiration time.
+    ///
+    /// The `progress` of the [`PollInfo`] is never set, as the fraction of
+    /// the work done by a running query is not known. The response headers
+    /// instead describe the progress of the query, using the counters of its
+    /// query log entry.
+    pub(crate) fn poll_info(&self) -> Result<PollInfo, Status> {
+        let info = FlightInfo::new()
+            .with_descriptor(descriptor(&self.id))
+            .try_with_schema(self.schema.as_ref())
+            .context(EncodeSchemaSnafu)?;
+
+        match &*self.state.lock().expect("not poisoned") {
+            QueryState::Running => Ok(PollInfo {
+                info: Some(info),
+                flight_descriptor: Some(descriptor(&self.id)),
+                progress: None,
+                expiration_time: None,
+            }),
+            QueryState::Completed { expires_at, .. } => {
+                let expiration_time = expiration_time(*expires_at);
+                let endpoint = FlightEndpoint {
+                    expiration_time: Some(expiration_time),
+                    ..FlightEndpoint::new().with_ticket(ticket(&self.id))
+                };
+
+                Ok(PollInfo {
+                    info: Some(info.with_endpoint(endpoint)),
+                    flight_descriptor: None,
+                    progress: None,
+                    expiration_time: Some(expiration_time),
+                })
+            }
+            QueryState::Failed { status, .. } => Err(status.clone()),
+        }
+    }
+
+    /// Returns the buffered results of the query, if it completed.
+    pub(crate) fn results(&self) -> Option<Vec<FlightData>> {
+        match &*self.state.lock().expect("not poisoned") {
+            QueryState::Completed { data, .. } => Some(data.clone()),
+            QueryState::Running | QueryState::Failed { .. } => None,
+        }
+    }
+
+    fn is_expired(&self, now: Instant) -> bool {
+        match &*self.state.lock().expect("not poisoned") {
+            QueryState::Running => false,
+            QueryState::Completed { expires_at, .. } | QueryState::Failed { expires_at, .. } => {
+                *expires_at <= now
+            }
+        }
+    }
+}
+
+impl Drop for PolledQuery {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[derive(Debug)]
+enum QueryState {
+    Running,
+    Completed {
+        data: Vec<FlightData>,
+        /// The memory used by `data`, released when the results are dropped.
+        _reservation: MemoryReservation,
+        expires_at: Instant,
+    },
+    Failed {
+        status: Status,
+        expires_at: Instant,
+    },
+}
+
+/// Returns the wall clock time of `instant`.
+fn expiration_time(instant: Instant) -> prost_types::Timestamp {
+    let remaining = instant.saturating_duration_since(Instant::now());
+    (SystemTime::now() + remaining).into()
+}
+
+/// Buffer the results `stream` of a query, updating `state` when it
+/// finishes.
+///
+/// The query fails if its results exceed the memory available to
+/// `reservation`.
+async fn buffer_results<S>(
+    stream: S,
+    mut reservation: MemoryReservation,
+    ttl: Duration,
+    state: Arc<Mutex<QueryState>>,
+) where
+    S: Stream<Item = Result<FlightData, Status>> + Send,
+{
+    let mut stream = std::pin::pin!(stream);
+    let mut data = vec![];
+
+    let result = loop {
+        match stream.next().await {
+            None => break Ok(()),
+            Some(Ok(flight_data)) => {
+                let size = flight_data.data_header.len()
+                    + flight_data.data_body.len()
+                    + flight_data.app_metadata.len();
+                if !reservation.try_grow(size) {
+                    let limit = reservation.limit;
+                    break Err(Status::from(Error::PolledQueryMemoryLimit { limit }));
+                }
+                data.push(flight_data);
+            }
+            Some(Err(status)) => break Err(status),
+        }
+    };
+
+    let expires_at = Instant::now() + ttl;
+    *state.lock().expect("not poisoned") = match result {
+        Ok(()) => QueryState::Completed {
+            data,
+            _reservation: reservation,
+            expires_at,
+        },
+        Err(status) => QueryState::Failed { status, expires_at },
+    };
+}
+
+/// Memory used by the buffered results of a query, counted towards the
+/// total of all polled queries until dropped.
+#[derive(Debug)]
+struct MemoryReservation {
+    used: Arc<AtomicUsize>,
+    limit: usize,
+    size: usize,
+}
+
+impl MemoryReservation {
+    /// Grow the reservation by `size` bytes, returning false if that would
+    /// exceed the limit.
+    fn try_grow(&mut self, size: usize) -> bool {
+        let limit = self.limit;
+        let grown = self
+            .used
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |used| {
+                used.checked_add(size).filter(|&used| used <= limit)
+            })
+            .is_ok();
+        if grown {
+            self.size += size;
+        }
+        grown
+    }
+}
+
+impl Drop for MemoryReservation {
+    fn drop(&mut self) {
+        self.used.fetch_sub(self.size, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids() {
+        let id = "5f1b2c3d";
+        assert_eq!(id_from_descriptor(&descriptor(id)), Some(id));
+        assert_eq!(id_from_ticket(&ticket(id)), Some(id));
+
+        assert_eq!(
+            id_from_descriptor(&FlightDescriptor::new_path(vec![id.to_string()])),
+            None
+        );
+        assert_eq!(
+            id_from_descriptor(&FlightDescriptor::new_cmd(id.as_bytes().to_vec())),
+            None
+        );
+        assert_eq!(id_from_ticket(&Ticket::new(id.as_bytes().to_vec())), None);
+
+        let info = FlightInfo::new().with_endpoint(FlightEndpoint::new().with_ticket(ticket(id)));
+        assert_eq!(id_from_flight_info(&info), Some(id));
+        let info = FlightInfo::new().with_descriptor(descriptor(id));
+        assert_eq!(id_from_flight_info(&info), Some(id));
+        assert_eq!(id_from_flight_info(&FlightInfo::new()), None);
+    }
+
+    #[tokio::test]
+    async fn test_purge_task_stops() {
+        let queries = PolledQueries::default();
+        let task = queries.spawn_purge_task(Duration::from_millis(1));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!task.is_finished());
+
+        drop(queries);
+        tokio::time::timeout(Duration::from_secs(10), task)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_memory_reservation() {
+        let used = Arc::new(AtomicUsize::new(0));
+        let reservation = || MemoryReservation {
+            used: Arc::clone(&used),
+            limit: 10,
+            size: 0,
+        };
+
+        let mut a = reservation();
+        let mut b = reservation();
+        assert!(a.try_grow(6));
+        assert!(!b.try_grow(5));
+        assert!(b.try_grow(4));
+        assert!(!a.try_grow(1));
+        assert_eq!(used.load(Ordering::SeqCst), 10);
+
+        drop(a);
+        assert_eq!(used.load(Ordering::SeqCst), 4);
+        assert!(b.try_grow(6));
+        drop(b);
+        assert_eq!(used.load(Ordering::SeqCst), 0);
+    }
+}
@@ -3,18 +3,22 @@
 use arrow_flight::Ticket;
 use bytes::Bytes;
 
+use data_types::TimestampRange;
 use flightsql::FlightSQLCommand;
 use generated_types::google::protobuf::Any;
+use generated_types::influxdata::iox::predicate::v1 as predicate_proto;
 use generated_types::influxdata::iox::querier::v1 as proto;
 use generated_types::influxdata::iox::querier::v1::read_info::QueryType;
 
 use iox_query_params::StatementParams;
+use iox_time::Time;
 use prost::Message;
 use serde::Deserialize;
 use tracing::trace;
 
 use snafu::{ResultExt, Snafu};
 use std::fmt::{Debug, Display, Formatter};
+use std::time::Duration;
 use trace_http::query_variant::QueryVariant;
 
 #[derive(Debug, Snafu)]
@@ -125,6 +129,8 @@ pub(crate) struct IoxGetRequest {
     pub(crate) query: RunQuery,
     pub(crate) params: StatementParams,
     pub(crate) is_debug: bool,
+    /// Only return rows whose `time` lies within this range.
+    pub(crate) time_range: Option<TimestampRange>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -170,9 +176,16 @@ impl IoxGetRequest {
             query,
             params: StatementParams::default(),
             is_debug,
+            time_range: None,
         }
     }
 
+    /// Only return rows whose `time` lies within `range`.
+    pub(crate) fn with_time_range(mut self, range: TimestampRange) -> Self {
+        self.time_range = Some(range);
+        self
+    }
+
     /// Merges result of the gRPC debug header into the is_debug field of this request using boolean or logic
     pub(crate) fn add_debug_header(mut self, debug_header: bool) -> Self {
         self.is_debug |= debug_header;
@@ -213,9 +226,14 @@ impl IoxGetRequest {
             query,
             params,
             is_debug,
+            time_range,
         } = self;
 
         let params: Vec<proto::QueryParam> = params.into();
+        let time_range = time_range.map(|range| predicate_proto::TimestampRange {
+            start: range.start(),
+            end: range.end(),
+        });
 
         let read_info = match query {
             RunQuery::Sql(sql_query) => proto::ReadInfo {
@@ -225,6 +243,7 @@ impl IoxGetRequest {
                 flightsql_command: vec![],
                 params,
                 is_debug,
+                time_range,
             },
             RunQuery::InfluxQL(influxql) => proto::ReadInfo {
                 database,
@@ -234,6 +253,7 @@ impl IoxGetRequest {
                 flightsql_command: vec![],
                 params,
                 is_debug,
+                time_range,
             },
             RunQuery::FlightSQL(flightsql_command) => proto::ReadInfo {
                 database,
@@ -245,6 +265,7 @@ impl IoxGetRequest {
                     .into(),
                 params,
                 is_debug,
+                time_range,
             },
         };
 
@@ -303,6 +324,7 @@ impl IoxGetRequest {
             query,
             params,
             is_debug,
+            time_range: None,
         })
     }
 
@@ -322,7 +344,11 @@ impl IoxGetRequest {
     /// See comments on [`IoxGetRequest`] for details of this format
     fn decode_protobuf(ticket: Bytes) -> Result<Self, Error> {
         let read_info = proto::ReadInfo::decode(ticket).context(DecodeProtobufSnafu)?;
+        Self::try_from_read_info(read_info)
+    }
 
+    /// Convert a decoded [`ReadInfo`](proto::ReadInfo) message.
+    fn try_from_read_info(read_info: proto::ReadInfo) -> Result<Self, Error> {
         let query_type = read_info.query_type();
         let proto::ReadInfo {
             database,
@@ -331,6 +357,7 @@ impl IoxGetRequest {
             flightsql_command,
             is_debug,
             params,
+            time_range,
         } = read_info;
 
         Ok(Self {
@@ -368,6 +395,7 @@ impl IoxGetRequest {
             },
             params: params.try_into().context(DecodeParamsSnafu)?,
             is_debug,
+            time_range: time_range.map(|range| TimestampRange::new(range.start, range.end)),
         })
     }
 
@@ -379,6 +407,132 @@ impl IoxGetRequest {
         &self.query
     }
 }
+/// A continuous query subscribed to with `DoExchange`, as described on
+/// [`FlightService`](crate::FlightService).
+///
+/// Sent as a [ContinuousQuery](proto::ContinuousQuery) wrapped as an "Any"
+/// message, as the command of a `FlightDescriptor`.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct ContinuousQuery {
+    /// The query to evaluate for each window.
+    pub(crate) request: IoxGetRequest,
+    /// The length of each window.
+    pub(crate) window: Duration,
+    /// The start of the first window, if not the time of the subscription.
+    pub(crate) start: Option<Time>,
+    /// How long to wait after the end of a window before evaluating it.
+    pub(crate) delay: Duration,
+}
+
+impl ContinuousQuery {
+    const CONTINUOUS_QUERY_TYPE_URL: &'static str =
+        "type.googleapis.com/influxdata.iox.querier.v1.ContinuousQuery";
+
+    /// The minimum length of the windows of a continuous query.
+    pub(crate) const MIN_WINDOW: Duration = Duration::from_secs(1);
+
+    /// The maximum number of windows of a continuous query that may have
+    /// passed when it is received, all of which are evaluated immediately.
+    pub(crate) const MAX_PAST_WINDOWS: u32 = 100;
+
+    /// Decode a continuous query from the command of a `FlightDescriptor`.
+    pub(crate) fn try_decode(cmd: Bytes) -> Result<Self> {
+        let any = Any::decode(cmd).context(DecodeProtobufSnafu)?;
+        if any.type_url != Self::CONTINUOUS_QUERY_TYPE_URL {
+            return InvalidContentSnafu {
+                msg: format!("expected a ContinuousQuery, got {}", any.type_url),
+            }
+            .fail();
+        }
+
+        let proto::ContinuousQuery {
+            read_info,
+            window_ns,
+            start_ns,
+            delay_ns,
+        } = proto::ContinuousQuery::decode(any.value).context(DecodeProtobufSnafu)?;
+
+        let read_info = read_info.ok_or_else(|| Error::InvalidContent {
+            msg: "ContinuousQuery has no read_info".to_string(),
+        })?;
+        let window = u64::try_from(window_ns)
+            .ok()
+            .map(Duration::from_nanos)
+            .filter(|window| *window >= Self::MIN_WINDOW)
+            .ok_or_else(|| Error::InvalidContent {
+                msg: format!(
+                    "ContinuousQuery window_ns must be at least {}, got {window_ns}",
+                    Self::MIN_WINDOW.as_nanos()
+                ),
+            })?;
+        let delay = u64::try_from(delay_ns).map_err(|_| Error::InvalidContent {
+            msg: format!("ContinuousQuery delay_ns must not be negative, got {delay_ns}"),
+        })?;
+
+        Ok(Self {
+            request: IoxGetRequest {
+                time_range: None,
+                ..IoxGetRequest::try_from_read_info(read_info)?
+            },
+            window,
+            start: start_ns.map(Time::from_timestamp_nanos),
+            delay: Duration::from_nanos(delay),
+        })
+    }
+
+    /// Check that the first window of the query does not start more than
+    /// [`Self::MAX_PAST_WINDOWS`] windows before `now`.
+    pub(crate) fn check_start(&self, now: Time) -> Result<()> {
+        let Some(start) = self.start else {
+            return Ok(());
+        };
+        let earliest = self
+            .window
+            .checked_mul(Self::MAX_PAST_WINDOWS)
+            .and_then(|past| now.checked_sub(past));
+        match earliest {
+            Some(earliest) if start < earliest => InvalidContentSnafu {
+                msg: format!(
+                    "ContinuousQuery start_ns must be at most {} windows in the past, \
+                     got {}",
+                    Self::MAX_PAST_WINDOWS,
+                    start.timestamp_nanos()
+                ),
+            }
+            .fail(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Encode the continuous query as the command of a `FlightDescriptor`.
+    #[cfg(test)]
+    pub(crate) fn try_encode(self) -> Result<Bytes> {
+        let Self {
+            request,
+            window,
+            start,
+            delay,
+        } = self;
+
+        let ticket = request.try_encode()?;
+        let read_info = Any::decode(ticket.ticket)
+            .and_then(|any| proto::ReadInfo::decode(any.value))
+            .context(DecodeProtobufSnafu)?;
+
+        let continuous_query = proto::ContinuousQuery {
+            read_info: Some(read_info),
+            window_ns: window.as_nanos() as i64,
+            start_ns: start.map(|start| start.timestamp_nanos()),
+            delay_ns: delay.as_nanos() as i64,
+        };
+        let any = Any {
+            type_url: Self::CONTINUOUS_QUERY_TYPE_URL.to_string(),
+            value: continuous_query.encode_to_vec().into(),
+        };
+        Ok(any.encode_to_vec().into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -432,6 +586,7 @@ mod tests {
                         query: RunQuery::Sql(String::from(query)),
                         params: params.into(),
                         is_debug: false,
+                        time_range: None,
                     },
                 }
             }
@@ -458,6 +613,7 @@ mod tests {
                         query: RunQuery::InfluxQL(String::from(query)),
                         params: params.into(),
                         is_debug: false,
+                        time_range: None,
                     },
                 }
             }
@@ -713,6 +869,7 @@ mod tests {
             flightsql_command: vec![],
             params: vec![],
             is_debug: false,
+            time_range: None,
         });
 
         // Reverts to default (unspecified) for invalid query_type enumeration, and thus SQL
@@ -730,6 +887,7 @@ mod tests {
             flightsql_command: vec![],
             params: vec![],
             is_debug: false,
+            time_range: None,
         });
 
         let ri = IoxGetRequest::try_decode(ticket).unwrap();
@@ -746,6 +904,7 @@ mod tests {
             flightsql_command: vec![],
             params: vec![],
             is_debug: false,
+            time_range: None,
         });
 
         let ri = IoxGetRequest::try_decode(ticket).unwrap();
@@ -762,6 +921,7 @@ mod tests {
             flightsql_command: vec![],
             params: vec![],
             is_debug: false,
+            time_range: None,
         });
 
         // Reverts to default (unspecified) for invalid query_type enumeration, and thus SQL
@@ -780,6 +940,7 @@ mod tests {
             flightsql_command: vec![1, 2, 3],
             params: vec![],
             is_debug: false,
+            time_range: None,
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -796,6 +957,7 @@ mod tests {
             flightsql_command: vec![1, 2, 3],
             params: vec![],
             is_debug: false,
+            time_range: None,
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -812,6 +974,7 @@ mod tests {
             flightsql_command: vec![1, 2, 3],
             params: vec![],
             is_debug: false,
+            time_range: None,
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -838,6 +1001,7 @@ mod tests {
             flightsql_command: vec![],
             params: vec![],
             is_debug: false,
+            time_range: None,
         });
 
         // Reverts to default (unspecified) for invalid query_type enumeration, and thus SQL
@@ -855,6 +1019,7 @@ mod tests {
             flightsql_command: vec![],
             params: vec![],
             is_debug: false,
+            time_range: None,
         });
 
         let ri = IoxGetRequest::try_decode(ticket).unwrap();
@@ -871,6 +1036,7 @@ mod tests {
             flightsql_command: vec![],
             params: vec![],
             is_debug: false,
+            time_range: None,
         });
 
         let ri = IoxGetRequest::try_decode(ticket).unwrap();
@@ -887,6 +1053,7 @@ mod tests {
             flightsql_command: vec![],
             params: vec![],
             is_debug: false,
+            time_range: None,
         });
 
         // Reverts to default (unspecified) for invalid query_type enumeration, and thus SQL
@@ -905,6 +1072,7 @@ mod tests {
             flightsql_command: vec![1, 2, 3],
             params: vec![],
             is_debug: false,
+            time_range: None,
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -921,6 +1089,7 @@ mod tests {
             flightsql_command: vec![1, 2, 3],
             params: vec![],
             is_debug: false,
+            time_range: None,
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -937,6 +1106,7 @@ mod tests {
             flightsql_command: vec![1, 2, 3],
             params: vec![],
             is_debug: false,
+            time_range: None,
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -960,6 +1130,7 @@ mod tests {
             query: RunQuery::Sql("select * from bar".into()),
             params: StatementParams::default(),
             is_debug: false,
+            time_range: None,
         };
 
         let ticket = request.clone().try_encode().expect("encoding failed");
@@ -976,6 +1147,7 @@ mod tests {
             query: RunQuery::Sql("select * from bar".into()),
             params: StatementParams::default(),
             is_debug: true,
+            time_range: None,
         };
 
         let ticket = request.clone().try_encode().expect("encoding failed");
@@ -992,6 +1164,7 @@ mod tests {
             query: RunQuery::InfluxQL("select * from bar".into()),
             params: StatementParams::default(),
             is_debug: false,
+            time_range: None,
         };
 
         let ticket = request.clone().try_encode().expect("encoding failed");
@@ -1013,6 +1186,7 @@ mod tests {
             query: RunQuery::FlightSQL(cmd),
             params: StatementParams::default(),
             is_debug: false,
+            time_range: None,
         };
 
         let ticket = request.clone().try_encode().expect("encoding failed");
@@ -1022,6 +1196,99 @@ mod tests {
         assert_eq!(request, roundtripped)
     }
 
+    #[test]
+    fn round_trip_sql_time_range() {
+        let request = IoxGetRequest::new(
+            "foo_blarg",
+            RunQuery::Sql("select * from bar".into()),
+            false,
+        )
+        .with_time_range(TimestampRange::new(100, 200));
+
+        let ticket = request.clone().try_encode().expect("encoding failed");
+
+        let roundtripped = IoxGetRequest::try_decode(ticket).expect("decode failed");
+
+        assert_eq!(request, roundtripped);
+        assert_eq!(roundtripped.time_range, Some(TimestampRange::new(100, 200)));
+    }
+
+    #[test]
+    fn round_trip_continuous_query() {
+        let query = ContinuousQuery {
+            request: IoxGetRequest::new(
+                "foo_blarg",
+                RunQuery::InfluxQL("select * from bar".into()),
+                false,
+            ),
+            window: Duration::from_secs(10),
+            start: Some(Time::from_timestamp_nanos(1_000)),
+            delay: Duration::from_secs(1),
+        };
+
+        let cmd = query.clone().try_encode().expect("encoding failed");
+
+        let roundtripped = ContinuousQuery::try_decode(cmd).expect("decode failed");
+
+        assert_eq!(query, roundtripped);
+    }
+
+    #[test]
+    fn continuous_query_decoding_invalid() {
+        let query = ContinuousQuery {
+            request: IoxGetRequest::new("foo_blarg", RunQuery::Sql("select 1".into()), false),
+            window: Duration::ZERO,
+            start: None,
+            delay: Duration::ZERO,
+        };
+        let cmd = query.try_encode().expect("encoding failed");
+        let e = ContinuousQuery::try_decode(cmd).unwrap_err();
+        assert_matches!(e, Error::InvalidContent { .. });
+
+        // windows must not be shorter than the minimum
+        let query = ContinuousQuery {
+            request: IoxGetRequest::new("foo_blarg", RunQuery::Sql("select 1".into()), false),
+            window: ContinuousQuery::MIN_WINDOW - Duration::from_nanos(1),
+            start: None,
+            delay: Duration::ZERO,
+        };
+        let cmd = query.try_encode().expect("encoding failed");
+        let e = ContinuousQuery::try_decode(cmd).unwrap_err();
+        assert_matches!(e, Error::InvalidContent { .. });
+
+        // a ticket is not a continuous query
+        let ticket = IoxGetRequest::new("foo_blarg", RunQuery::Sql("select 1".into()), false)
+            .try_encode()
+            .expect("encoding failed");
+        let e = ContinuousQuery::try_decode(ticket.ticket).unwrap_err();
+        assert_matches!(e, Error::InvalidContent { .. });
+    }
+
+    #[test]
+    fn continuous_query_check_start() {
+        let query = |start| ContinuousQuery {
+            request: IoxGetRequest::new("foo_blarg", RunQuery::Sql("select 1".into()), false),
+            window: Duration::from_secs(10),
+            start,
+            delay: Duration::ZERO,
+        };
+        let now = Time::from_timestamp_nanos(0) + Duration::from_secs(2_000);
+
+        query(None).check_start(now).unwrap();
+        query(Some(now)).check_start(now).unwrap();
+        query(Some(now + Duration::from_secs(3_600)))
+            .check_start(now)
+            .unwrap();
+        query(Some(Time::from_timestamp_nanos(1_000_000_000_000)))
+            .check_start(now)
+            .unwrap();
+
+        let e = query(Some(Time::from_timestamp_nanos(999_999_999_999)))
+            .check_start(now)
+            .unwrap_err();
+        assert_matches!(e, Error::InvalidContent { .. });
+    }
+
     fn make_any_wrapped_proto_ticket(read_info: &proto::ReadInfo) -> Ticket {
         let any = Any {
             type_url: IoxGetRequest::READ_INFO_TYPE_URL.to_string(),
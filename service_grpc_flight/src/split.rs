@@ -0,0 +1,456 @@
+//! Splitting of SQL queries into independent time ranges, so that clients
+//! can fetch the results of a `GetFlightInfo` request over several
+//! concurrent `DoGet` streams.
+use std::{str::FromStr, sync::Arc};
+
+use data_types::{MIN_NANO_TIME, TimestampRange};
+use datafusion::{
+    arrow::datatypes::{DataType, TimeUnit},
+    common::{
+        Column, DFSchema, ScalarValue, not_impl_err,
+        tree_node::{Transformed, TreeNode, TreeNodeRecursion},
+    },
+    error::Result,
+    logical_expr::{
+        BinaryExpr, Expr, Filter, LogicalPlan, Operator, TableScan, cast, lit,
+        utils::{conjunction, split_conjunction},
+    },
+    optimizer::simplify_expressions::{ExprSimplifier, SimplifyContext},
+    physical_expr::execution_props::ExecutionProps,
+};
+use schema::TIME_COLUMN_NAME;
+
+/// The maximum number of endpoints the results of a query may be split
+/// into.
+pub(crate) const MAX_TIME_RANGES: usize = 64;
+
+/// How to split the results of a query into several Flight endpoints.
+///
+/// Parsed from the `x-influxdata-endpoint-split` header of `GetFlightInfo`
+/// requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EndpointSplit {
+    /// Split the query into at most `n` ranges of `time`, formatted as
+    /// `time-range:<n>`. `n` may not exceed [`MAX_TIME_RANGES`].
+    TimeRange(usize),
+}
+
+impl FromStr for EndpointSplit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().split_once(':') {
+            Some(("time-range", n)) => match n.trim().parse() {
+                Ok(n) if n <= MAX_TIME_RANGES => Ok(Self::TimeRange(n)),
+                Ok(n) => Err(format!(
+                    "number of time ranges {n} exceeds the maximum of {MAX_TIME_RANGES}"
+                )),
+                Err(e) => Err(format!("invalid number of time ranges '{n}': {e}")),
+            },
+            _ => Err(format!("unknown endpoint split '{s}'")),
+        }
+    }
+}
+
+/// Returns the time ranges to split the SQL query `plan` into.
+///
+/// Running the query restricted to each range (see [`restrict_time_range`])
+/// returns disjoint sets of rows whose union is the result of the whole
+/// query. Returns [`None`] if the query cannot be split that way, which is
+/// the case unless it only filters and projects the rows of a single table,
+/// or if its `WHERE` clause does not bound `time` on both sides.
+///
+/// The bounded interval is cut into at most `n` (and never more than
+/// [`MAX_TIME_RANGES`]) ranges of equal length. The
+/// first and last ranges extend to the beginning and the end of time, so
+/// the ranges cover all rows even if the bounds evaluate differently once
+/// the query runs, e.g. because they depend on `now()`.
+pub(crate) fn time_ranges(plan: &LogicalPlan, n: usize) -> Result<Option<Vec<TimestampRange>>> {
+    let n = n.min(MAX_TIME_RANGES);
+    if n < 2 || !is_splittable(plan)? {
+        return Ok(None);
+    }
+    let Some((start, end)) = time_bounds(plan)? else {
+        return Ok(None);
+    };
+
+    let span = i128::from(end) - i128::from(start);
+    if span < 2 {
+        return Ok(None);
+    }
+    let n = usize::try_from(span).map_or(n, |span| n.min(span)) as i128;
+
+    let mut ranges = Vec::with_capacity(n as usize);
+    let mut lower = i64::MIN;
+    for i in 1..n {
+        let cut = (i128::from(start) + span * i / n) as i64;
+        ranges.push(TimestampRange::new(lower, cut));
+        lower = cut;
+    }
+    ranges.push(TimestampRange::new(lower, i64::MAX));
+
+    Ok(Some(ranges))
+}
+
+/// Restrict every table scan of `plan` to the rows whose `time` lies within
+/// `range`.
+///
+/// Plans that cannot be split (see [`time_ranges`]) are rejected, as the
+/// tickets of `DoGet` requests are supplied by clients and may apply a time
+/// range to any query. For example, a query that fills gaps in time series
+/// would fill them over the range of its `WHERE` clause, which the
+/// restriction does not change, and so return rows outside of `range`.
+pub(crate) fn restrict_time_range(plan: LogicalPlan, range: TimestampRange) -> Result<LogicalPlan> {
+    if !is_splittable(&plan)? {
+        return not_impl_err!("query cannot be restricted to a time range");
+    }
+
+    plan.transform_up(|node| {
+        let LogicalPlan::TableScan(scan) = node else {
+            return Ok(Transformed::no(node));
+        };
+        let Some(tz) = time_column_timezone(&scan) else {
+            return Ok(Transformed::no(LogicalPlan::TableScan(scan)));
+        };
+
+        let time = Expr::Column(Column::new(Some(scan.table_name.clone()), TIME_COLUMN_NAME));
+        let timestamp = |v| lit(ScalarValue::TimestampNanosecond(Some(v), tz.clone()));
+
+        let mut predicates = vec![];
+        if range.start() > MIN_NANO_TIME {
+            predicates.push(time.clone().gt_eq(timestamp(range.start())));
+        }
+        if range.end() < i64::MAX {
+            predicates.push(time.lt(timestamp(range.end())));
+        }
+        let Some(predicate) = conjunction(predicates) else {
+            return Ok(Transformed::no(LogicalPlan::TableScan(scan)));
+        };
+
+        let filter = Filter::try_new(predicate, Arc::new(LogicalPlan::TableScan(scan)))?;
+        Ok(Transformed::yes(LogicalPlan::Filter(filter)))
+    })
+    .map(|transformed| transformed.data)
+}
+
+/// Returns true if `plan` only filters and projects the rows of a single
+/// table with a `time` column, so that its results can be computed
+/// independently for any partition of the rows of that table.
+fn is_splittable(plan: &LogicalPlan) -> Result<bool> {
+    let mut scans = 0;
+    let mut splittable = true;
+
+    plan.apply(|node| {
+        match node {
+            LogicalPlan::Projection(_) | LogicalPlan::Filter(_) | LogicalPlan::SubqueryAlias(_) => {
+            }
+            LogicalPlan::TableScan(scan) if time_column_timezone(scan).is_some() => scans += 1,
+            _ => {
+                splittable = false;
+                return Ok(TreeNodeRecursion::Stop);
+            }
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })?;
+
+    Ok(splittable && scans == 1)
+}
+
+/// Returns the interval `[start, end)` of `time` selected by the filters
+/// applied directly to the table scanned by `plan`, if bounded on both
+/// sides.
+fn time_bounds(plan: &LogicalPlan) -> Result<Option<(i64, i64)>> {
+    let props = ExecutionProps::new();
+    let simplifier =
+        ExprSimplifier::new(SimplifyContext::new(&props).with_schema(Arc::new(DFSchema::empty())));
+
+    let mut start = None;
+    let mut end = None;
+    plan.apply(|node| {
+        let LogicalPlan::Filter(filter) = node else {
+            return Ok(TreeNodeRecursion::Continue);
+        };
+        if !is_table_scan(&filter.input) {
+            return Ok(TreeNodeRecursion::Continue);
+        }
+
+        for expr in split_conjunction(&filter.predicate) {
+            let Some((op, v)) = time_comparison(expr, &simplifier) else {
+                continue;
+            };
+            let (lower, upper) = match op {
+                Operator::Eq => (Some(v), Some(v.saturating_add(1))),
+                Operator::Gt => (Some(v.saturating_add(1)), None),
+                Operator::GtEq => (Some(v), None),
+                Operator::Lt => (None, Some(v)),
+                Operator::LtEq => (None, Some(v.saturating_add(1))),
+                _ => (None, None),
+            };
+            if let Some(lower) = lower {
+                start = Some(start.map_or(lower, |start: i64| start.max(lower)));
+            }
+            if let Some(upper) = upper {
+                end = Some(end.map_or(upper, |end: i64| end.min(upper)));
+            }
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })?;
+
+    Ok(start.zip(end))
+}
+
+/// Returns true if `plan` is a table scan, possibly aliased.
+fn is_table_scan(plan: &LogicalPlan) -> bool {
+    match plan {
+        LogicalPlan::TableScan(_) => true,
+        LogicalPlan::SubqueryAlias(alias) => {
+            matches!(alias.input.as_ref(), LogicalPlan::TableScan(_))
+        }
+        _ => false,
+    }
+}
+
+/// If `expr` compares the `time` column with a constant, returns the
+/// comparison operator, with `time` on its left-hand side, and the constant
+/// as nanoseconds since the epoch.
+fn time_comparison(
+    expr: &Expr,
+    simplifier: &ExprSimplifier<SimplifyContext<'_>>,
+) -> Option<(Operator, i64)> {
+    let Expr::BinaryExpr(BinaryExpr { left, op, right }) = expr else {
+        return None;
+    };
+    let (op, value) = match (left.as_ref(), right.as_ref()) {
+        (Expr::Column(column), value) if column.name == TIME_COLUMN_NAME => (*op, value),
+        (value, Expr::Column(column)) if column.name == TIME_COLUMN_NAME => (op.swap()?, value),
+        _ => return None,
+    };
+    if !value.column_refs().is_empty() {
+        return None;
+    }
+
+    match simplifier.simplify(cast(
+        value.clone(),
+        DataType::Timestamp(TimeUnit::Nanosecond, None),
+    )) {
+        Ok(Expr::Literal(ScalarValue::TimestampNanosecond(Some(v), _), _)) => Some((op, v)),
+        _ => None,
+    }
+}
+
+/// Returns the timezone of the nanosecond `time` column of the table read by
+/// `scan`, or [`None`] if it has no such column.
+fn time_column_timezone(scan: &TableScan) -> Option<Option<Arc<str>>> {
+    let schema = scan.source.schema();
+    let (_, field) = schema.column_with_name(TIME_COLUMN_NAME)?;
+    match field.data_type() {
+        DataType::Timestamp(TimeUnit::Nanosecond, tz) => Some(tz.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::{
+        arrow::datatypes::{Field, Schema},
+        error::DataFusionError,
+        execution::FunctionRegistry,
+        logical_expr::{LogicalPlanBuilder, col, lit_timestamp_nano, logical_plan::table_scan},
+    };
+    use query_functions::gapfill::DATE_BIN_GAPFILL_UDF_NAME;
+
+    use super::*;
+
+    fn cpu() -> LogicalPlanBuilder {
+        let schema = Schema::new(vec![
+            Field::new("host", DataType::Utf8, true),
+            Field::new(
+                TIME_COLUMN_NAME,
+                DataType::Timestamp(TimeUnit::Nanosecond, None),
+                false,
+            ),
+        ]);
+        table_scan(Some("cpu"), &schema, None).unwrap()
+    }
+
+    #[test]
+    fn test_parse_endpoint_split() {
+        assert_eq!(
+            "time-range:4".parse::<EndpointSplit>(),
+            Ok(EndpointSplit::TimeRange(4))
+        );
+        assert_eq!(
+            "time-range:64".parse::<EndpointSplit>(),
+            Ok(EndpointSplit::TimeRange(MAX_TIME_RANGES))
+        );
+        assert!("time-range:many".parse::<EndpointSplit>().is_err());
+        assert!("time-range:65".parse::<EndpointSplit>().is_err());
+        assert!(
+            format!("time-range:{}", usize::MAX)
+                .parse::<EndpointSplit>()
+                .is_err()
+        );
+        assert!("time-range:-1".parse::<EndpointSplit>().is_err());
+        assert!("partition:4".parse::<EndpointSplit>().is_err());
+    }
+
+    #[test]
+    fn test_time_ranges() {
+        let plan = cpu()
+            .filter(
+                col("time")
+                    .gt_eq(lit_timestamp_nano(0))
+                    .and(lit_timestamp_nano(100).gt(col("time"))),
+            )
+            .unwrap()
+            .project(vec![col("host")])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            time_ranges(&plan, 4).unwrap(),
+            Some(vec![
+                TimestampRange::new(i64::MIN, 25),
+                TimestampRange::new(25, 50),
+                TimestampRange::new(50, 75),
+                TimestampRange::new(75, i64::MAX),
+            ])
+        );
+        assert_eq!(time_ranges(&plan, 1).unwrap(), None);
+
+        // no more ranges than timestamps
+        let plan = cpu()
+            .filter(
+                col("time")
+                    .gt(lit_timestamp_nano(0))
+                    .and(col("time").lt_eq(lit_timestamp_nano(2))),
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            time_ranges(&plan, 4).unwrap(),
+            Some(vec![
+                TimestampRange::new(i64::MIN, 2),
+                TimestampRange::new(2, i64::MAX),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_time_ranges_huge_n() {
+        let plan = cpu()
+            .filter(
+                col("time")
+                    .gt_eq(lit_timestamp_nano(i64::MIN + 2))
+                    .and(col("time").lt(lit_timestamp_nano(i64::MAX - 1))),
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ranges = time_ranges(&plan, usize::MAX).unwrap().unwrap();
+        assert_eq!(ranges.len(), MAX_TIME_RANGES);
+        assert_eq!(ranges[0].start(), MIN_NANO_TIME);
+        assert_eq!(ranges[MAX_TIME_RANGES - 1].end(), i64::MAX);
+        for pair in ranges.windows(2) {
+            assert!(pair[0].end() < pair[1].end());
+            assert_eq!(pair[0].end(), pair[1].start());
+        }
+    }
+
+    #[test]
+    fn test_time_ranges_not_splittable() {
+        // unbounded
+        let plan = cpu()
+            .filter(col("time").gt_eq(lit_timestamp_nano(0)))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(time_ranges(&plan, 4).unwrap(), None);
+
+        // results depend on all rows
+        let plan = cpu()
+            .filter(
+                col("time")
+                    .gt_eq(lit_timestamp_nano(0))
+                    .and(col("time").lt(lit_timestamp_nano(100))),
+            )
+            .unwrap()
+            .limit(0, Some(10))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(time_ranges(&plan, 4).unwrap(), None);
+    }
+
+    #[test]
+    fn test_restrict_time_range() {
+        let time = || Expr::Column(Column::new(Some("cpu"), TIME_COLUMN_NAME));
+        let plan = cpu().project(vec![col("host")]).unwrap().build().unwrap();
+
+        let restricted = restrict_time_range(plan.clone(), TimestampRange::new(25, 50)).unwrap();
+        let LogicalPlan::Projection(projection) = &restricted else {
+            panic!("expected projection, got {restricted}");
+        };
+        let LogicalPlan::Filter(filter) = projection.input.as_ref() else {
+            panic!("expected filter, got {}", projection.input);
+        };
+        assert_eq!(
+            filter.predicate,
+            time()
+                .gt_eq(lit(ScalarValue::TimestampNanosecond(Some(25), None)))
+                .and(time().lt(lit(ScalarValue::TimestampNanosecond(Some(50), None))))
+        );
+
+        // the first range has no lower bound
+        let restricted =
+            restrict_time_range(plan.clone(), TimestampRange::new(i64::MIN, 25)).unwrap();
+        let LogicalPlan::Projection(projection) = &restricted else {
+            panic!("expected projection, got {restricted}");
+        };
+        let LogicalPlan::Filter(filter) = projection.input.as_ref() else {
+            panic!("expected filter, got {}", projection.input);
+        };
+        assert_eq!(
+            filter.predicate,
+            time().lt(lit(ScalarValue::TimestampNanosecond(Some(25), None)))
+        );
+
+        // unbounded ranges leave the plan unchanged
+        let restricted =
+            restrict_time_range(plan.clone(), TimestampRange::new(i64::MIN, i64::MAX)).unwrap();
+        assert_eq!(restricted, plan);
+    }
+
+    #[test]
+    fn test_restrict_time_range_gap_fill() {
+        let date_bin_gapfill = query_functions::registry()
+            .udf(DATE_BIN_GAPFILL_UDF_NAME)
+            .unwrap();
+        let plan = cpu()
+            .aggregate(
+                vec![date_bin_gapfill.call(vec![
+                    lit(ScalarValue::new_interval_mdn(0, 0, 10)),
+                    col("time"),
+                ])],
+                Vec::<Expr>::new(),
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let err = restrict_time_range(plan, TimestampRange::new(0, 10)).unwrap_err();
+        assert!(matches!(err, DataFusionError::NotImplemented(_)), "{err}");
+    }
+
+    #[test]
+    fn test_restrict_time_range_not_splittable() {
+        // results depend on all rows
+        let plan = cpu().limit(0, Some(10)).unwrap().build().unwrap();
+
+        let err = restrict_time_range(plan, TimestampRange::new(0, 10)).unwrap_err();
+        assert!(matches!(err, DataFusionError::NotImplemented(_)), "{err}");
+    }
+}